@@ -11,6 +11,7 @@ use std::rc::Rc;
 use std::sync::Arc;
 
 pub mod fetcher;
+pub mod indicator;
 pub mod result;
 mod state;
 mod time_range;
@@ -18,6 +19,7 @@ mod time_range;
 pub struct AssetAnalyzer {
     mutable_state: Rc<RefCell<AnalysisState>>,
     platform: Arc<dyn FinancialPlatform>,
+    one_year: bool,
 }
 
 impl AssetAnalyzer {
@@ -25,9 +27,15 @@ impl AssetAnalyzer {
         Self {
             mutable_state: Rc::new(RefCell::new(Default::default())),
             platform: platform.clone(),
+            one_year: true,
         }
     }
 
+    /// Whether to fetch the last year of daily bars the indicators need.
+    pub fn with_one_year(self, one_year: bool) -> Self {
+        Self { one_year, ..self }
+    }
+
     pub async fn analyze_bars(
         &self,
         assets: &Vec<AssetSymbol>,
@@ -37,10 +45,11 @@ impl AssetAnalyzer {
     }
 
     async fn refresh_bars_if_needed(&self, assets: &Vec<AssetSymbol>) -> Result<(), GreedError> {
-        let should_fetch = self.mutable_state.borrow().should_fetch(Local::now());
+        let now = self.platform.now().with_timezone(&Local);
+        let should_fetch = self.mutable_state.borrow().should_fetch(now);
         if should_fetch {
             let fetch_result = self.fetch_bars(assets).await?;
-            *self.mutable_state.borrow_mut() = AnalysisState::new(fetch_result, Local::now());
+            *self.mutable_state.borrow_mut() = AnalysisState::new(fetch_result, now);
         }
         Ok(())
     }
@@ -49,7 +58,7 @@ impl AssetAnalyzer {
         &self,
         assets: &Vec<AssetSymbol>,
     ) -> Result<HashMap<AssetSymbol, BarsResult>, GreedError> {
-        let fetcher = BarsFetcher::new(self.platform.clone());
+        let fetcher = BarsFetcher::new(self.platform.clone()).with_one_year(self.one_year);
         let mut results_by_symbol = HashMap::new();
         for symbol in assets {
             let results = fetcher.fetch(symbol).await?;
@@ -11,16 +11,24 @@ use std::sync::Arc;
 pub struct BarsFetcher {
     platform: Arc<dyn FinancialPlatform>,
     time_ranges: FetcherTimeRanges,
+    one_year: bool,
 }
 
 impl BarsFetcher {
     pub fn new(platform: Arc<dyn FinancialPlatform>) -> Self {
+        let time_ranges = FetcherTimeRanges::new(platform.now());
         BarsFetcher {
             platform,
-            time_ranges: FetcherTimeRanges::new(),
+            time_ranges,
+            one_year: true,
         }
     }
 
+    /// Whether to fetch the last year of daily bars, which is left empty otherwise.
+    pub fn with_one_year(self, one_year: bool) -> Self {
+        Self { one_year, ..self }
+    }
+
     pub async fn fetch(&self, symbol: &AssetSymbol) -> Result<BarsResult, GreedError> {
         let bars_result = BarsResult {
             symbol: symbol.clone(),
@@ -28,12 +36,22 @@ impl BarsFetcher {
             seven_day: self.fetch_seven_day(symbol.clone()).await?,
             thirty_day: self.fetch_thirty_day(symbol.clone()).await?,
             thirty_day_hourly: self.fetch_thirty_day_hourly(symbol.clone()).await?,
+            one_year: if self.one_year {
+                self.fetch_one_year(symbol.clone()).await?
+            } else {
+                Bars::default()
+            },
         };
         Ok(bars_result)
     }
 
     async fn fetch_last_trading_day(&self, symbol: AssetSymbol) -> Result<Bars, GreedError> {
-        let time_range = self.time_ranges.last_trading_day_range();
+        // Crypto trades every day, so its last trading day is simply yesterday.
+        let time_range = if symbol.is_crypto() {
+            self.time_ranges.last_x_days(1)
+        } else {
+            self.time_ranges.last_trading_day_range()
+        };
         self.platform
             .bars(BarRequest {
                 symbol,
@@ -71,6 +89,19 @@ impl BarsFetcher {
             .await
     }
 
+    async fn fetch_one_year(&self, symbol: AssetSymbol) -> Result<Bars, GreedError> {
+        let time_range = self.time_ranges.last_x_days(365);
+        self.platform
+            .bars(BarRequest {
+                symbol,
+                start: time_range.start,
+                end: time_range.end,
+                timeframe: TimeFrame::OneDay,
+                ..Default::default()
+            })
+            .await
+    }
+
     async fn fetch_thirty_day_hourly(&self, symbol: AssetSymbol) -> Result<Bars, GreedError> {
         let time_range = self.time_ranges.last_x_days(30);
         let bars = self
@@ -0,0 +1,104 @@
+use crate::analysis::indicator::bollinger::{BollingerBands, BollingerPeriod};
+use crate::analysis::indicator::macd::{Macd, MacdPeriods};
+use crate::platform::bars::Bars;
+
+pub mod atr;
+pub mod bollinger;
+pub mod deviation;
+pub mod macd;
+pub mod moving_average;
+pub mod rate_of_change;
+pub mod rsi;
+pub mod vwap;
+
+/// Technical indicators calculated from the bar closes (or the full bars where the indicator
+/// needs highs, lows or volume). Each returns `None` when there aren't enough bars for the
+/// requested period.
+impl Bars {
+    pub fn closes(&self) -> Vec<f64> {
+        self.bars.iter().map(|b| b.close).collect()
+    }
+
+    pub fn sma(&self, period: usize) -> Option<f64> {
+        moving_average::sma(&self.closes(), period)
+    }
+
+    pub fn ema(&self, period: usize) -> Option<f64> {
+        moving_average::ema(&self.closes(), period)
+    }
+
+    pub fn rsi(&self, period: usize) -> Option<f64> {
+        rsi::rsi(&self.closes(), period)
+    }
+
+    pub fn macd(&self, periods: MacdPeriods) -> Option<Macd> {
+        macd::macd(&self.closes(), periods)
+    }
+
+    pub fn bollinger_bands(&self, period: BollingerPeriod) -> Option<BollingerBands> {
+        bollinger::bollinger_bands(&self.closes(), period)
+    }
+
+    pub fn atr(&self, period: usize) -> Option<f64> {
+        atr::atr(&self.bars, period)
+    }
+
+    pub fn vwap(&self, period: usize) -> Option<f64> {
+        vwap::vwap(&self.bars, period)
+    }
+
+    pub fn std_dev(&self, period: usize) -> Option<f64> {
+        deviation::std_dev(&self.closes(), period)
+    }
+
+    pub fn rate_of_change(&self, period: usize) -> Option<f64> {
+        rate_of_change::rate_of_change(&self.closes(), period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::platform::bar::Bar;
+    use crate::platform::bars::Bars;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn closes() {
+        assert_eq!(bars().closes(), vec![1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn indicators_use_closes() {
+        let bars = bars();
+        assert_relative_eq!(bars.sma(2).unwrap(), 3.5);
+        assert_relative_eq!(bars.rate_of_change(3).unwrap(), 300.0);
+        assert_relative_eq!(bars.std_dev(2).unwrap(), 0.5);
+        assert_eq!(bars.rsi(3).unwrap(), 100.0);
+    }
+
+    #[test]
+    fn indicators_empty() {
+        let bars = Bars::default();
+        assert_eq!(bars.sma(1), None);
+        assert_eq!(bars.ema(1), None);
+        assert_eq!(bars.rsi(14), None);
+        assert_eq!(bars.macd(Default::default()), None);
+        assert_eq!(bars.bollinger_bands(Default::default()), None);
+        assert_eq!(bars.atr(14), None);
+        assert_eq!(bars.vwap(1), None);
+    }
+
+    fn bars() -> Bars {
+        Bars::with_bars(
+            (1..=4)
+                .map(|i| Bar {
+                    close: i as f64,
+                    high: i as f64,
+                    low: i as f64,
+                    volume: 100,
+                    ..Default::default()
+                })
+                .collect(),
+        )
+    }
+}
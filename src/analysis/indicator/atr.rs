@@ -0,0 +1,64 @@
+use crate::platform::bar::Bar;
+
+/// The average true range using Wilder's smoothing. Needs at least `period + 1` bars since each
+/// true range depends on the previous close.
+pub fn atr(bars: &[Bar], period: usize) -> Option<f64> {
+    if period == 0 || bars.len() <= period {
+        return None;
+    }
+    let true_ranges = bars
+        .windows(2)
+        .map(|w| true_range(&w[1], w[0].close))
+        .collect::<Vec<_>>();
+    let (first, rest) = true_ranges.split_at(period);
+    let periods = period as f64;
+    let seed = first.iter().sum::<f64>() / periods;
+    Some(
+        rest.iter()
+            .fold(seed, |atr, tr| (atr * (periods - 1.0) + tr) / periods),
+    )
+}
+
+fn true_range(bar: &Bar, previous_close: f64) -> f64 {
+    let high_low = bar.high - bar.low;
+    let high_close = (bar.high - previous_close).abs();
+    let low_close = (bar.low - previous_close).abs();
+    high_low.max(high_close).max(low_close)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn atr_known_values() {
+        let bars = vec![
+            bar(10.0, 8.0, 9.0),
+            // True range of 3, the high is 3 above the previous close.
+            bar(12.0, 10.0, 11.0),
+            // True range of 4, the low is 4 below the previous close.
+            bar(10.0, 7.0, 8.0),
+            // True range of 2, the high and low are both inside the previous close.
+            bar(9.0, 7.0, 8.5),
+        ];
+        // The first average is (3 + 4) / 2 = 3.5, then (3.5 + 2) / 2.
+        assert_relative_eq!(atr(&bars, 2).unwrap(), 2.75);
+    }
+
+    #[test]
+    fn atr_short_or_empty() {
+        assert_eq!(atr(&[bar(10.0, 8.0, 9.0)], 1), None);
+        assert_eq!(atr(&[], 14), None);
+        assert_eq!(atr(&vec![bar(10.0, 8.0, 9.0); 3], 0), None);
+    }
+
+    fn bar(high: f64, low: f64, close: f64) -> Bar {
+        Bar {
+            high,
+            low,
+            close,
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use crate::analysis::indicator::deviation::std_dev;
+use crate::analysis::indicator::moving_average::sma;
+
+/// The moving average period and band width for Bollinger Bands.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BollingerPeriod {
+    pub period: usize,
+    /// How many standard deviations the bands sit from the middle.
+    pub std_devs: f64,
+}
+
+impl Default for BollingerPeriod {
+    fn default() -> Self {
+        Self {
+            period: 20,
+            std_devs: 2.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BollingerBands {
+    pub lower: f64,
+    pub middle: f64,
+    pub upper: f64,
+}
+
+/// Bands around the simple moving average of the last `period` values.
+pub fn bollinger_bands(values: &[f64], period: BollingerPeriod) -> Option<BollingerBands> {
+    let middle = sma(values, period.period)?;
+    let width = std_dev(values, period.period)? * period.std_devs;
+    Some(BollingerBands {
+        lower: middle - width,
+        middle,
+        upper: middle + width,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn bollinger_bands_known_values() {
+        let values = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        let period = BollingerPeriod {
+            period: 8,
+            std_devs: 2.0,
+        };
+        let bands = bollinger_bands(&values, period).unwrap();
+        assert_relative_eq!(bands.lower, 1.0);
+        assert_relative_eq!(bands.middle, 5.0);
+        assert_relative_eq!(bands.upper, 9.0);
+    }
+
+    #[test]
+    fn bollinger_bands_short_or_empty() {
+        assert_eq!(bollinger_bands(&[1.0; 19], Default::default()), None);
+        assert_eq!(bollinger_bands(&[], Default::default()), None);
+    }
+}
@@ -0,0 +1,35 @@
+/// The population standard deviation of the last `period` values.
+pub fn std_dev(values: &[f64], period: usize) -> Option<f64> {
+    if period == 0 || values.len() < period {
+        return None;
+    }
+    let window = &values[values.len() - period..];
+    let mean = window.iter().sum::<f64>() / period as f64;
+    let variance = window.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / period as f64;
+    Some(variance.sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn std_dev_known_values() {
+        let values = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        assert_relative_eq!(std_dev(&values, 8).unwrap(), 2.0);
+    }
+
+    #[test]
+    fn std_dev_uses_last_period() {
+        let values = [100.0, 1.0, 1.0, 1.0];
+        assert_relative_eq!(std_dev(&values, 3).unwrap(), 0.0);
+    }
+
+    #[test]
+    fn std_dev_short_or_empty() {
+        assert_eq!(std_dev(&[1.0], 2), None);
+        assert_eq!(std_dev(&[], 2), None);
+        assert_eq!(std_dev(&[1.0], 0), None);
+    }
+}
@@ -0,0 +1,101 @@
+use crate::analysis::indicator::moving_average::ema_series;
+
+/// The fast, slow and signal periods for MACD.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MacdPeriods {
+    pub fast: usize,
+    pub slow: usize,
+    pub signal: usize,
+}
+
+impl Default for MacdPeriods {
+    fn default() -> Self {
+        Self {
+            fast: 12,
+            slow: 26,
+            signal: 9,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Macd {
+    /// The fast EMA minus the slow EMA.
+    pub macd: f64,
+    /// The EMA of the MACD line.
+    pub signal: f64,
+    /// The MACD line minus the signal line.
+    pub histogram: f64,
+}
+
+/// The latest MACD values. Needs at least `slow + signal - 1` values.
+pub fn macd(values: &[f64], periods: MacdPeriods) -> Option<Macd> {
+    if periods.fast == 0 || periods.fast >= periods.slow {
+        return None;
+    }
+    let fast = ema_series(values, periods.fast);
+    let slow = ema_series(values, periods.slow);
+    if slow.is_empty() {
+        return None;
+    }
+    // Both series end at the latest value, the fast series simply starts earlier.
+    let offset = fast.len() - slow.len();
+    let macd_line = slow
+        .iter()
+        .zip(&fast[offset..])
+        .map(|(slow, fast)| fast - slow)
+        .collect::<Vec<_>>();
+    let signal = *ema_series(&macd_line, periods.signal).last()?;
+    let macd = *macd_line.last()?;
+    Some(Macd {
+        macd,
+        signal,
+        histogram: macd - signal,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn macd_known_values() {
+        let values = (1..=10).map(|v| (v * v) as f64).collect::<Vec<_>>();
+        let periods = MacdPeriods {
+            fast: 3,
+            slow: 5,
+            signal: 3,
+        };
+        let result = macd(&values, periods).unwrap();
+        assert_relative_eq!(result.macd, 13.5163, epsilon = 1e-3);
+        assert_relative_eq!(result.signal, 11.9210, epsilon = 1e-3);
+        assert_relative_eq!(result.histogram, result.macd - result.signal);
+    }
+
+    #[test]
+    fn macd_flat() {
+        let values = vec![10.0; 40];
+        let result = macd(&values, Default::default()).unwrap();
+        assert_relative_eq!(result.macd, 0.0);
+        assert_relative_eq!(result.signal, 0.0);
+    }
+
+    #[test]
+    fn macd_short_or_empty() {
+        let periods = MacdPeriods::default();
+        assert_eq!(macd(&vec![10.0; 33], periods), None);
+        assert!(macd(&vec![10.0; 34], periods).is_some());
+        assert_eq!(macd(&[], periods), None);
+    }
+
+    #[test]
+    fn macd_invalid_periods() {
+        let periods = MacdPeriods {
+            fast: 26,
+            slow: 12,
+            signal: 9,
+        };
+        assert_eq!(macd(&vec![10.0; 40], periods), None);
+    }
+}
@@ -0,0 +1,73 @@
+/// The simple moving average of the last `period` values.
+pub fn sma(values: &[f64], period: usize) -> Option<f64> {
+    if period == 0 || values.len() < period {
+        return None;
+    }
+    let window = &values[values.len() - period..];
+    Some(window.iter().sum::<f64>() / period as f64)
+}
+
+/// The exponential moving average at every value from index `period - 1` onward. The average is
+/// seeded with the simple average of the first `period` values.
+pub fn ema_series(values: &[f64], period: usize) -> Vec<f64> {
+    if period == 0 || values.len() < period {
+        return Vec::new();
+    }
+    let smoothing = 2.0 / (period as f64 + 1.0);
+    let seed = values[..period].iter().sum::<f64>() / period as f64;
+    let mut series = Vec::with_capacity(values.len() - period + 1);
+    series.push(seed);
+    for value in &values[period..] {
+        let previous = series[series.len() - 1];
+        series.push(previous + (value - previous) * smoothing);
+    }
+    series
+}
+
+/// The latest exponential moving average over `period` values.
+pub fn ema(values: &[f64], period: usize) -> Option<f64> {
+    ema_series(values, period).last().copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    const CLOSES: [f64; 12] = [
+        22.27, 22.19, 22.08, 22.17, 22.18, 22.13, 22.23, 22.43, 22.24, 22.29, 22.15, 22.39,
+    ];
+
+    #[test]
+    fn sma_last_period() {
+        assert_relative_eq!(sma(&[1.0, 2.0, 3.0, 4.0, 5.0], 3).unwrap(), 4.0);
+    }
+
+    #[test]
+    fn sma_short_or_empty() {
+        assert_eq!(sma(&[1.0, 2.0], 3), None);
+        assert_eq!(sma(&[], 3), None);
+        assert_eq!(sma(&[1.0], 0), None);
+    }
+
+    #[test]
+    fn ema_series_known_values() {
+        let series = ema_series(&CLOSES, 10);
+        assert_eq!(series.len(), 3);
+        assert_relative_eq!(series[0], 22.221, epsilon = 1e-3);
+        assert_relative_eq!(series[1], 22.2081, epsilon = 1e-3);
+        assert_relative_eq!(series[2], 22.2412, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn ema_latest() {
+        assert_relative_eq!(ema(&CLOSES, 10).unwrap(), 22.2412, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn ema_short_or_empty() {
+        assert!(ema_series(&CLOSES, 13).is_empty());
+        assert_eq!(ema(&[], 10), None);
+        assert_eq!(ema(&CLOSES, 0), None);
+    }
+}
@@ -0,0 +1,37 @@
+/// The percent change between the latest value and the value `period` values before it.
+pub fn rate_of_change(values: &[f64], period: usize) -> Option<f64> {
+    if period == 0 || values.len() <= period {
+        return None;
+    }
+    let latest = values[values.len() - 1];
+    let base = values[values.len() - 1 - period];
+    if base == 0.0 {
+        return None;
+    }
+    Some((latest - base) / base * 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn rate_of_change_known_values() {
+        let values = [50.0, 100.0, 90.0, 110.0];
+        assert_relative_eq!(rate_of_change(&values, 2).unwrap(), 10.0);
+        assert_relative_eq!(rate_of_change(&values, 3).unwrap(), 120.0);
+    }
+
+    #[test]
+    fn rate_of_change_short_or_empty() {
+        assert_eq!(rate_of_change(&[1.0, 2.0], 2), None);
+        assert_eq!(rate_of_change(&[], 1), None);
+        assert_eq!(rate_of_change(&[1.0, 2.0], 0), None);
+    }
+
+    #[test]
+    fn rate_of_change_zero_base() {
+        assert_eq!(rate_of_change(&[0.0, 2.0], 1), None);
+    }
+}
@@ -0,0 +1,55 @@
+/// The relative strength index using Wilder's smoothing. Needs at least `period + 1` values.
+pub fn rsi(values: &[f64], period: usize) -> Option<f64> {
+    if period == 0 || values.len() <= period {
+        return None;
+    }
+    let changes = values.windows(2).map(|w| w[1] - w[0]).collect::<Vec<_>>();
+    let (first, rest) = changes.split_at(period);
+    let mut average_gain = first.iter().map(|c| c.max(0.0)).sum::<f64>() / period as f64;
+    let mut average_loss = first.iter().map(|c| (-c).max(0.0)).sum::<f64>() / period as f64;
+    let periods = period as f64;
+    for change in rest {
+        average_gain = (average_gain * (periods - 1.0) + change.max(0.0)) / periods;
+        average_loss = (average_loss * (periods - 1.0) + (-change).max(0.0)) / periods;
+    }
+    if average_loss == 0.0 {
+        // No losses at all is as strong as the index gets, a flat series is neutral.
+        return Some(if average_gain == 0.0 { 50.0 } else { 100.0 });
+    }
+    let relative_strength = average_gain / average_loss;
+    Some(100.0 - 100.0 / (1.0 + relative_strength))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    const CLOSES: [f64; 16] = [
+        44.34, 44.09, 44.15, 43.61, 44.33, 44.83, 45.10, 45.42, 45.84, 46.08, 45.89, 46.03, 45.61,
+        46.28, 46.28, 46.00,
+    ];
+
+    #[test]
+    fn rsi_known_values() {
+        assert_relative_eq!(rsi(&CLOSES[..15], 14).unwrap(), 70.46, epsilon = 1e-2);
+        assert_relative_eq!(rsi(&CLOSES, 14).unwrap(), 66.25, epsilon = 1e-2);
+    }
+
+    #[test]
+    fn rsi_only_gains() {
+        assert_eq!(rsi(&[1.0, 2.0, 3.0], 2), Some(100.0));
+    }
+
+    #[test]
+    fn rsi_flat() {
+        assert_eq!(rsi(&[1.0, 1.0, 1.0], 2), Some(50.0));
+    }
+
+    #[test]
+    fn rsi_short_or_empty() {
+        assert_eq!(rsi(&CLOSES[..14], 14), None);
+        assert_eq!(rsi(&[], 14), None);
+        assert_eq!(rsi(&CLOSES, 0), None);
+    }
+}
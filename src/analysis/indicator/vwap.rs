@@ -0,0 +1,54 @@
+use crate::platform::bar::Bar;
+
+/// The volume weighted average of each bar's typical price, (high + low + close) / 3, over the
+/// last `period` bars.
+pub fn vwap(bars: &[Bar], period: usize) -> Option<f64> {
+    if period == 0 || bars.len() < period {
+        return None;
+    }
+    let window = &bars[bars.len() - period..];
+    let volume = window.iter().map(|b| b.volume as f64).sum::<f64>();
+    if volume == 0.0 {
+        return None;
+    }
+    let weighted = window
+        .iter()
+        .map(|b| (b.high + b.low + b.close) / 3.0 * b.volume as f64)
+        .sum::<f64>();
+    Some(weighted / volume)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn vwap_known_values() {
+        let bars = vec![bar(100.0, 1000), bar(10.0, 100), bar(20.0, 300)];
+        // (10 * 100 + 20 * 300) / 400
+        assert_relative_eq!(vwap(&bars, 2).unwrap(), 17.5);
+    }
+
+    #[test]
+    fn vwap_no_volume() {
+        assert_eq!(vwap(&[bar(10.0, 0)], 1), None);
+    }
+
+    #[test]
+    fn vwap_short_or_empty() {
+        assert_eq!(vwap(&[bar(10.0, 100)], 2), None);
+        assert_eq!(vwap(&[], 1), None);
+        assert_eq!(vwap(&[bar(10.0, 100)], 0), None);
+    }
+
+    fn bar(price: f64, volume: usize) -> Bar {
+        Bar {
+            high: price,
+            low: price,
+            close: price,
+            volume,
+            ..Default::default()
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use crate::analysis::indicator::bollinger::BollingerPeriod;
+use crate::analysis::indicator::macd::MacdPeriods;
 use crate::asset::AssetSymbol;
 use crate::platform::bars::Bars;
 use std::fmt::{Display, Formatter};
@@ -9,6 +11,8 @@ pub struct BarsResult {
     pub seven_day: Bars,
     pub thirty_day: Bars,
     pub thirty_day_hourly: Bars,
+    /// Daily bars for the last year, used by the technical indicators.
+    pub one_year: Bars,
 }
 
 impl BarsResult {
@@ -20,8 +24,49 @@ impl BarsResult {
             seven_day: Bars::fixture(symbol.clone(), 200.0),
             thirty_day: Bars::fixture(symbol.clone(), 100.0),
             thirty_day_hourly: Bars::fixture(symbol.clone(), 400.0),
+            one_year: Bars::fixture(symbol.clone(), 500.0),
         }
     }
+
+    /// Daily indicators over the last year, omitting any without enough bars.
+    fn indicators_summary(&self) -> String {
+        let bars = &self.one_year;
+        let mut lines = Vec::new();
+        if let Some(rsi) = bars.rsi(14) {
+            lines.push(format!("RSI(14): {:.2}", rsi));
+        }
+        if let Some(sma) = bars.sma(50) {
+            lines.push(format!("SMA(50): {:.2}", sma));
+        }
+        if let Some(sma) = bars.sma(200) {
+            lines.push(format!("SMA(200): {:.2}", sma));
+        }
+        if let Some(ema) = bars.ema(20) {
+            lines.push(format!("EMA(20): {:.2}", ema));
+        }
+        if let Some(macd) = bars.macd(MacdPeriods::default()) {
+            lines.push(format!(
+                "MACD(12, 26, 9): {:.2}, signal: {:.2}, histogram: {:.2}",
+                macd.macd, macd.signal, macd.histogram
+            ));
+        }
+        if let Some(bands) = bars.bollinger_bands(BollingerPeriod::default()) {
+            lines.push(format!(
+                "Bollinger(20, 2): {:.2} - {:.2} - {:.2}",
+                bands.lower, bands.middle, bands.upper
+            ));
+        }
+        if let Some(atr) = bars.atr(14) {
+            lines.push(format!("ATR(14): {:.2}", atr));
+        }
+        if let Some(vwap) = bars.vwap(20) {
+            lines.push(format!("VWAP(20): {:.2}", vwap));
+        }
+        if let Some(roc) = bars.rate_of_change(20) {
+            lines.push(format!("ROC(20): {:.2}%", roc));
+        }
+        lines.iter().map(|l| format!("{}\n", l)).collect()
+    }
 }
 
 impl Display for BarsResult {
@@ -35,6 +80,7 @@ impl Display for BarsResult {
             30 day median: {:.2}\n\
             Median Positive Change: {:.2}%\n\
             Median Negative Change: {:.2}%\n\
+            {}\
             ----------
         ",
             self.symbol,
@@ -47,6 +93,7 @@ impl Display for BarsResult {
             self.thirty_day_hourly
                 .negative_percent_median()
                 .unwrap_or_default(),
+            self.indicators_summary(),
         )
     }
 }
@@ -8,11 +8,11 @@ pub struct FetcherTimeRanges {
 }
 
 impl FetcherTimeRanges {
-    pub fn new() -> Self {
-        Self { now: Utc::now() }
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self { now }
     }
 
-    /// Yesterday from 00:00:00 to 23:59:00
+    /// The previous trading day from 00:00:00 to 23:59:00
     pub fn last_trading_day_range(&self) -> Range<DateTime<Utc>> {
         let now_date = self.now.date_naive();
         let last_trading_date = now_date.previous_trading_day();
@@ -35,6 +35,7 @@ impl FetcherTimeRanges {
 }
 
 #[cfg(test)]
+#[allow(clippy::zero_prefixed_literal)]
 mod tests {
     use super::*;
     use crate::date::DateTimeFixture;
@@ -55,6 +56,17 @@ mod tests {
         assert_eq!(expected, ranges.last_trading_day_range())
     }
 
+    #[test]
+    fn last_trading_day_range_after_holiday() {
+        let ranges = FetcherTimeRanges::new(Utc.with_ymd_and_hms(2024, 7, 5, 15, 0, 0).unwrap());
+        let expected_start = Utc.with_ymd_and_hms(2024, 7, 3, 0, 0, 0).unwrap();
+        let expected_end = Utc.with_ymd_and_hms(2024, 7, 3, 23, 59, 0).unwrap();
+        assert_eq!(
+            expected_start..expected_end,
+            ranges.last_trading_day_range()
+        )
+    }
+
     #[test]
     fn last_x_days() {
         let ranges = create_ranges();
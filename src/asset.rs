@@ -1,9 +1,14 @@
+use crate::float::{CRYPTO_QUANTITY_PRECISION, QUANTITY_PRECISION};
 use serde::de::{Error, Visitor};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
-/// A symbol representing an asset (for example a stock such as VTI).
+/// Quote currencies Alpaca drops the slash from in crypto position symbols (BTCUSD for BTC/USD).
+const CRYPTO_QUOTE_CURRENCIES: [&str; 4] = ["USDT", "USDC", "USD", "BTC"];
+
+/// A symbol representing an asset (for example a stock such as VTI, or a crypto pair such as
+/// BTC/USD).
 #[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
 pub struct AssetSymbol {
     pub symbol: String,
@@ -15,6 +20,38 @@ impl AssetSymbol {
             symbol: symbol.to_string(),
         }
     }
+
+    /// Creates a crypto pair symbol, restoring the slash when the broker reports the pair without
+    /// it (for example BTCUSD).
+    pub fn crypto(symbol: &str) -> Self {
+        if symbol.contains('/') {
+            return Self::new(symbol);
+        }
+        let upper = symbol.to_uppercase();
+        CRYPTO_QUOTE_CURRENCIES
+            .iter()
+            .find_map(|quote| {
+                upper
+                    .strip_suffix(quote)
+                    .filter(|base| !base.is_empty())
+                    .map(|base| Self::new(&format!("{base}/{quote}")))
+            })
+            .unwrap_or_else(|| Self::new(symbol))
+    }
+
+    /// Crypto symbols are pairs such as BTC/USD, everything else is an equity.
+    pub fn is_crypto(&self) -> bool {
+        self.symbol.contains('/')
+    }
+
+    /// The number of decimal places an order quantity for this asset is floored to.
+    pub fn quantity_precision(&self) -> i32 {
+        if self.is_crypto() {
+            CRYPTO_QUANTITY_PRECISION
+        } else {
+            QUANTITY_PRECISION
+        }
+    }
 }
 
 impl FromStr for AssetSymbol {
@@ -117,6 +154,31 @@ mod test {
         assert_eq!(asset, expected);
     }
 
+    #[test]
+    fn crypto_with_slash() {
+        let asset = AssetSymbol::crypto("BTC/USD");
+        assert_eq!(asset, AssetSymbol::new("BTC/USD"));
+    }
+
+    #[test]
+    fn crypto_without_slash() {
+        assert_eq!(AssetSymbol::crypto("BTCUSD"), AssetSymbol::new("BTC/USD"));
+        assert_eq!(AssetSymbol::crypto("ETHUSDT"), AssetSymbol::new("ETH/USDT"));
+        assert_eq!(AssetSymbol::crypto("ETHBTC"), AssetSymbol::new("ETH/BTC"));
+    }
+
+    #[test]
+    fn is_crypto() {
+        assert!(AssetSymbol::new("BTC/USD").is_crypto());
+        assert!(!AssetSymbol::new("VTI").is_crypto());
+    }
+
+    #[test]
+    fn quantity_precision() {
+        assert_eq!(AssetSymbol::new("BTC/USD").quantity_precision(), 8);
+        assert_eq!(AssetSymbol::new("VTI").quantity_precision(), 7);
+    }
+
     #[derive(Debug, Deserialize, PartialEq, Serialize)]
     struct TestAssetContainer {
         asset: AssetSymbol,
@@ -0,0 +1,238 @@
+use crate::asset::AssetSymbol;
+use crate::backtest::platform::BacktestPlatform;
+use crate::backtest::report::{BacktestReport, EquityPoint};
+use crate::config::strategy::StrategyProperties;
+use crate::config::tactic::TacticConfig;
+use crate::config::Config;
+use crate::date::NaiveDateTimeConvert;
+use crate::error::GreedError;
+use crate::platform::args::PlatformArgs;
+use crate::platform::bar::history::BarHistory;
+use crate::platform::risk_guard::RiskGuardPlatform;
+use crate::platform::FinancialPlatform;
+use crate::strategy::reader::{read_all_tactics, read_config_from_path};
+use crate::tactic::TacticRunner;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use itertools::Itertools;
+use log::warn;
+use std::ops::Range;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+mod platform;
+pub mod report;
+
+/// The number of days of history needed before the first tick so the analysis has its thirty
+/// days of bars.
+const HISTORY_LEAD_DAYS: i64 = 31;
+
+/// The lead for rules which read a year of daily bars, such as long moving averages, RSI, bands
+/// and drawdown highs.
+const ONE_YEAR_HISTORY_LEAD_DAYS: i64 = 366;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct BacktestArgs {
+    pub config_path: PathBuf,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub cash: f64,
+    /// A directory of `<SYMBOL>.csv` bar files. When missing bars are fetched from the platform.
+    pub bars_path: Option<PathBuf>,
+    pub is_simulated: bool,
+}
+
+pub struct Backtester {
+    config_assets: Vec<AssetSymbol>,
+    platform: Arc<BacktestPlatform>,
+    range: Range<DateTime<Utc>>,
+    starting_cash: f64,
+    tactic_runners: Vec<TacticRunner>,
+}
+
+impl Backtester {
+    pub async fn from_args(args: &BacktestArgs) -> Result<Self, GreedError> {
+        if args.start > args.end {
+            return Err(GreedError::new("backtest start must not be after the end"));
+        }
+        let config = read_config_from_path(&args.config_path).await?;
+        let range = Self::date_range(args.start, args.end);
+
+        let tactic_runner_configs = read_all_tactics(&args.config_path, &config).await?;
+        let config_assets = tactic_runner_configs
+            .iter()
+            .flat_map(|(tactic, _)| tactic.assets())
+            .unique()
+            .collect::<Vec<_>>();
+
+        let lead_days = Self::history_lead_days(&tactic_runner_configs);
+        let history = Self::load_history(&config, args, &config_assets, &range, lead_days).await?;
+        let platform = Arc::new(BacktestPlatform::new(history, args.cash, range.start));
+        let mut dyn_platform: Arc<dyn FinancialPlatform> = platform.clone();
+        if !config.risk.is_empty() {
+            dyn_platform = Arc::new(RiskGuardPlatform::new(dyn_platform, config.risk.clone()));
+        }
+        let tactic_runners = tactic_runner_configs
+            .into_iter()
+            .map(|(tactic, properties)| TacticRunner::new(tactic, dyn_platform.clone(), properties))
+            .collect();
+
+        Ok(Self {
+            config_assets,
+            platform,
+            range,
+            starting_cash: args.cash,
+            tactic_runners,
+        })
+    }
+
+    async fn load_history(
+        config: &Config,
+        args: &BacktestArgs,
+        assets: &[AssetSymbol],
+        range: &Range<DateTime<Utc>>,
+        lead_days: i64,
+    ) -> Result<BarHistory, GreedError> {
+        match &args.bars_path {
+            Some(path) => BarHistory::from_directory(path, assets),
+            None => {
+                let platform_args = PlatformArgs {
+                    is_simulated: args.is_simulated,
+                };
+                let platform = crate::platform::for_type(&config.platform, platform_args)?;
+                let fetch_range = (range.start - Duration::days(lead_days))..range.end;
+                BarHistory::fetch(platform.as_ref(), assets, fetch_range).await
+            }
+        }
+    }
+
+    /// How many days of bars to fetch before the start, so the first tick sees the full lookback
+    /// of the configured rules.
+    fn history_lead_days(tactics: &[(TacticConfig, StrategyProperties)]) -> i64 {
+        if tactics
+            .iter()
+            .any(|(tactic, _)| tactic.should_fetch_one_year())
+        {
+            ONE_YEAR_HISTORY_LEAD_DAYS
+        } else {
+            HISTORY_LEAD_DAYS
+        }
+    }
+
+    fn date_range(start: NaiveDate, end: NaiveDate) -> Range<DateTime<Utc>> {
+        let start = start.and_hms_opt(0, 0, 0).expect("start was invalid");
+        let end = end.and_hms_opt(23, 59, 59).expect("end was invalid");
+        (start..end).to_utc()
+    }
+
+    /// Steps the virtual clock through every bar in the range, running each tactic at every step.
+    pub async fn run(&self) -> Result<BacktestReport, GreedError> {
+        let mut equity_curve = Vec::new();
+        for time in self.platform.history_timestamps(&self.range) {
+            self.platform.advance_to(time);
+            for runner in &self.tactic_runners {
+                let _ = runner
+                    .run(&self.config_assets)
+                    .await
+                    .inspect_err(|e| warn!("{e}"));
+            }
+            let account = self.platform.account().await?;
+            equity_curve.push(EquityPoint {
+                time,
+                equity: account.equity,
+            });
+        }
+
+        Ok(BacktestReport {
+            starting_cash: self.starting_cash,
+            trades: self.platform.trades(),
+            equity_curve,
+            account: self.platform.account().await?,
+            positions: self.platform.positions().await?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture;
+    use crate::platform::order::side::OrderSide;
+    use approx::assert_relative_eq;
+
+    #[tokio::test]
+    async fn run_with_local_bars() {
+        let backtester = Backtester::from_args(&args(date(1), date(5)))
+            .await
+            .expect("backtester should be created");
+        let report = backtester.run().await.expect("backtest should run");
+
+        let sides = report.trades.iter().map(|t| t.side).collect::<Vec<_>>();
+        assert_eq!(sides, vec![OrderSide::Buy, OrderSide::Sell]);
+        assert_eq!(report.equity_curve.len(), 5);
+        assert_relative_eq!(report.realized_gain(), 78.947, max_relative = 0.001);
+        assert_relative_eq!(report.account.equity, 1078.947, max_relative = 0.001);
+        assert!(report.positions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn history_lead_days() {
+        let config_path = fixture::path("config_backtest.toml");
+        let config = fixture::config("config_backtest.toml").await;
+        let tactics = read_all_tactics(&config_path, &config).await.unwrap();
+        assert_eq!(Backtester::history_lead_days(&tactics), HISTORY_LEAD_DAYS);
+
+        let one_year = TacticConfig {
+            buy: toml::from_str(
+                "for = { stock = \"VTI\" }\nwhen = { drawdown_from_high_percent = 10 }\ndo = { buy_percent = 10 }",
+            )
+            .unwrap(),
+            ..Default::default()
+        };
+        let tactics = vec![(one_year, StrategyProperties::default())];
+        assert_eq!(
+            Backtester::history_lead_days(&tactics),
+            ONE_YEAR_HISTORY_LEAD_DAYS
+        );
+    }
+
+    #[tokio::test]
+    async fn run_with_risk_limits() {
+        let args = BacktestArgs {
+            config_path: fixture::path("config_backtest_risk.toml"),
+            ..args(date(1), date(5))
+        };
+        let backtester = Backtester::from_args(&args)
+            .await
+            .expect("backtester should be created");
+        let report = backtester.run().await.expect("backtest should run");
+
+        // Every buy and sell is capped at $100, so the sell leaves part of the position behind.
+        assert_eq!(report.trades.len(), 3);
+        assert!(report
+            .trades
+            .iter()
+            .all(|t| t.quantity * t.price <= 100.0 + f64::EPSILON));
+        assert_eq!(report.positions.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn from_args_start_after_end() {
+        let result = Backtester::from_args(&args(date(5), date(1))).await;
+        assert!(result.is_err())
+    }
+
+    fn args(start: NaiveDate, end: NaiveDate) -> BacktestArgs {
+        BacktestArgs {
+            config_path: fixture::path("config_backtest.toml"),
+            start,
+            end,
+            cash: 1000.0,
+            bars_path: Some(fixture::path("bars")),
+            is_simulated: false,
+        }
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 1, day).unwrap()
+    }
+}
@@ -0,0 +1,299 @@
+use crate::asset::AssetSymbol;
+use crate::error::GreedError;
+use crate::platform::account::Account;
+use crate::platform::bar::bar_request::BarRequest;
+use crate::platform::bar::history::BarHistory;
+use crate::platform::bars::Bars;
+use crate::platform::id::Id;
+use crate::platform::order::Order;
+use crate::platform::position::Position;
+use crate::platform::quote::Quote;
+use crate::platform::request::replace::ReplaceRequest;
+use crate::platform::request::OrderRequest;
+use crate::platform::simulated::book::OrderBook;
+use crate::platform::simulated::trade::Trade;
+use crate::platform::FinancialPlatform;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::ops::Range;
+use std::sync::Mutex;
+
+/// A financial platform which fills orders against historical bars as a virtual clock is
+/// advanced.
+pub struct BacktestPlatform {
+    history: BarHistory,
+    state: Mutex<BacktestState>,
+}
+
+#[derive(Debug, Default)]
+struct BacktestState {
+    now: DateTime<Utc>,
+    book: OrderBook,
+}
+
+impl BacktestPlatform {
+    pub fn new(history: BarHistory, cash: f64, start: DateTime<Utc>) -> Self {
+        Self {
+            history,
+            state: Mutex::new(BacktestState {
+                now: start,
+                book: OrderBook::new(cash),
+            }),
+        }
+    }
+
+    /// Moves the virtual clock forward to `now`, filling any open orders the new prices satisfy.
+    pub fn advance_to(&self, now: DateTime<Utc>) {
+        let mut state = self.state.lock().unwrap();
+        state.now = now;
+        state.book.settle(|s| self.history.price_at(s, now), now);
+    }
+
+    pub fn history_timestamps(&self, range: &Range<DateTime<Utc>>) -> Vec<DateTime<Utc>> {
+        self.history.timestamps(range)
+    }
+
+    pub fn trades(&self) -> Vec<Trade> {
+        self.state.lock().unwrap().book.trades().to_vec()
+    }
+}
+
+#[async_trait]
+impl FinancialPlatform for BacktestPlatform {
+    async fn account(&self) -> Result<Account, GreedError> {
+        let state = self.state.lock().unwrap();
+        Ok(state.book.account(|s| self.history.price_at(s, state.now)))
+    }
+
+    async fn bars(&self, bars_request: BarRequest) -> Result<Bars, GreedError> {
+        let now = self.now();
+        let mut bars = self.history.bars_between(
+            &bars_request.symbol,
+            bars_request.start,
+            bars_request.end,
+            now,
+        );
+        if let Some(limit) = bars_request.limit {
+            bars.truncate(limit);
+        }
+        Ok(Bars {
+            symbol: bars_request.symbol,
+            bars,
+        })
+    }
+
+    async fn latest_quotes(&self, symbols: &[AssetSymbol]) -> Result<Vec<Quote>, GreedError> {
+        let now = self.now();
+        let quotes = symbols
+            .iter()
+            .filter_map(|symbol| {
+                self.history.latest_bar(symbol, now).map(|bar| Quote {
+                    time: bar.timestamp,
+                    ask_price: bar.close,
+                    bid_price: bar.close,
+                    symbol: symbol.clone(),
+                    ..Default::default()
+                })
+            })
+            .collect();
+        Ok(quotes)
+    }
+
+    async fn place_order(&self, order_request: OrderRequest) -> Result<Order, GreedError> {
+        let mut state = self.state.lock().unwrap();
+        let now = state.now;
+        let price = self.history.price_at(&order_request.symbol, now);
+        state.book.submit(order_request, price, now)
+    }
+
+    async fn positions(&self) -> Result<Vec<Position>, GreedError> {
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .book
+            .positions(|s| self.history.price_at(s, state.now)))
+    }
+
+    async fn open_orders(&self) -> Result<Vec<Order>, GreedError> {
+        Ok(self.state.lock().unwrap().book.open_orders())
+    }
+
+    async fn recent_orders(&self) -> Result<Vec<Order>, GreedError> {
+        Ok(self.state.lock().unwrap().book.recent_orders())
+    }
+
+    async fn order_history(&self, range: Range<DateTime<Utc>>) -> Result<Vec<Order>, GreedError> {
+        Ok(self.state.lock().unwrap().book.order_history(&range))
+    }
+
+    async fn cancel_order(&self, id: &Id) -> Result<(), GreedError> {
+        let mut state = self.state.lock().unwrap();
+        let now = state.now;
+        state.book.cancel(id, now)?;
+        Ok(())
+    }
+
+    async fn replace_order(&self, id: &Id, request: ReplaceRequest) -> Result<Order, GreedError> {
+        let mut state = self.state.lock().unwrap();
+        let now = state.now;
+        state.book.replace(id, &request, now)
+    }
+
+    async fn close_position(&self, symbol: &AssetSymbol) -> Result<Order, GreedError> {
+        let mut state = self.state.lock().unwrap();
+        let now = state.now;
+        let price = self.history.price_at(symbol, now);
+        state.book.close(symbol, price, now)
+    }
+
+    fn now(&self) -> DateTime<Utc> {
+        self.state.lock().unwrap().now
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::order::amount::Amount;
+    use crate::platform::order::order_type::OrderType;
+    use crate::platform::order::status::Status;
+    use approx::assert_relative_eq;
+    use chrono::TimeZone;
+    use num_decimal::Num;
+    use std::collections::HashMap;
+
+    #[tokio::test]
+    async fn account_initial_cash() {
+        let platform = platform();
+        let account = platform.account().await.unwrap();
+        assert_eq!(account.cash, 1000.0);
+        assert_eq!(account.equity, 1000.0);
+    }
+
+    #[tokio::test]
+    async fn place_order_buy_notional() {
+        let platform = platform();
+        let order = platform
+            .place_order(buy(Amount::Notional(500.0)))
+            .await
+            .unwrap();
+        assert_eq!(order.status, Status::Filled);
+
+        let positions = platform.positions().await.unwrap();
+        assert_eq!(positions.len(), 1);
+        assert_relative_eq!(positions[0].quantity, 50.0);
+
+        platform.advance_to(date(2));
+        let account = platform.account().await.unwrap();
+        assert_relative_eq!(account.cash, 500.0);
+        assert_relative_eq!(account.equity, 1500.0);
+    }
+
+    #[tokio::test]
+    async fn place_order_sell_realizes_gain() {
+        let platform = platform();
+        platform
+            .place_order(buy(Amount::Quantity(10.0)))
+            .await
+            .unwrap();
+        platform.advance_to(date(2));
+        platform
+            .place_order(OrderRequest::market_order_sell(
+                AssetSymbol::new("VTI"),
+                Amount::Quantity(20.0),
+            ))
+            .await
+            .unwrap();
+
+        let trades = platform.trades();
+        assert_eq!(trades.len(), 2);
+        assert_relative_eq!(trades[1].quantity, 10.0);
+        assert_relative_eq!(trades[1].realized_gain, 100.0);
+        assert!(platform.positions().await.unwrap().is_empty());
+        assert_relative_eq!(platform.account().await.unwrap().cash, 1100.0);
+    }
+
+    #[tokio::test]
+    async fn place_order_insufficient_cash() {
+        let platform = platform();
+        let result = platform.place_order(buy(Amount::Notional(5000.0))).await;
+        assert!(result.is_err())
+    }
+
+    #[tokio::test]
+    async fn advance_to_fills_limit_order() {
+        let platform = platform();
+        let request = OrderRequest {
+            order_type: OrderType::Limit,
+            limit_price: Some(Num::from(15)),
+            ..OrderRequest::market_order_sell(AssetSymbol::new("VTI"), Amount::Quantity(5.0))
+        };
+        platform
+            .place_order(buy(Amount::Quantity(10.0)))
+            .await
+            .unwrap();
+        platform.place_order(request).await.unwrap();
+        assert_eq!(platform.open_orders().await.unwrap().len(), 1);
+
+        platform.advance_to(date(2));
+
+        assert!(platform.open_orders().await.unwrap().is_empty());
+        let trades = platform.trades();
+        assert_eq!(trades.len(), 2);
+        assert_relative_eq!(trades[1].realized_gain, 50.0);
+    }
+
+    #[tokio::test]
+    async fn cancel_all_orders() {
+        let platform = platform();
+        let request = OrderRequest {
+            order_type: OrderType::Limit,
+            limit_price: Some(Num::from(5)),
+            ..buy(Amount::Quantity(10.0))
+        };
+        let order = platform.place_order(request).await.unwrap();
+
+        let canceled = platform.cancel_all_orders().await.unwrap();
+
+        assert_eq!(canceled, vec![order]);
+        assert!(platform.open_orders().await.unwrap().is_empty());
+        platform.advance_to(date(2));
+        assert!(platform.trades().is_empty());
+    }
+
+    #[tokio::test]
+    async fn latest_quotes_uses_clock() {
+        let platform = platform();
+        platform.advance_to(date(2));
+        let quotes = platform
+            .latest_quotes(&[AssetSymbol::new("VTI")])
+            .await
+            .unwrap();
+        assert_eq!(quotes[0].ask_price, 20.0);
+    }
+
+    fn platform() -> BacktestPlatform {
+        let history = BarHistory::new(HashMap::from([(
+            AssetSymbol::new("VTI"),
+            vec![bar(1, 10.0), bar(2, 20.0)],
+        )]));
+        BacktestPlatform::new(history, 1000.0, date(1))
+    }
+
+    fn buy(amount: Amount) -> OrderRequest {
+        OrderRequest::market_order_buy(AssetSymbol::new("VTI"), amount)
+    }
+
+    fn bar(day: u32, close: f64) -> crate::platform::bar::Bar {
+        crate::platform::bar::Bar {
+            timestamp: date(day),
+            close,
+            ..Default::default()
+        }
+    }
+
+    fn date(day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, day, 15, 0, 0)
+            .earliest()
+            .expect("failed to create test date")
+    }
+}
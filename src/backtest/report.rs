@@ -0,0 +1,160 @@
+use crate::error::GreedError;
+use crate::platform::account::Account;
+use crate::platform::position::Position;
+use crate::platform::simulated::trade::Trade;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+/// The portfolio equity at a point in time.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct EquityPoint {
+    pub time: DateTime<Utc>,
+    pub equity: f64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BacktestReport {
+    pub starting_cash: f64,
+    pub trades: Vec<Trade>,
+    pub equity_curve: Vec<EquityPoint>,
+    pub account: Account,
+    pub positions: Vec<Position>,
+}
+
+impl BacktestReport {
+    pub fn realized_gain(&self) -> f64 {
+        self.trades.iter().map(|t| t.realized_gain).sum()
+    }
+
+    pub fn unrealized_gain(&self) -> f64 {
+        self.positions
+            .iter()
+            .filter_map(|p| p.unrealized_gain_total)
+            .sum()
+    }
+
+    pub fn total_return_percent(&self) -> f64 {
+        if self.starting_cash <= 0.0 {
+            return 0.0;
+        }
+        ((self.account.equity - self.starting_cash) / self.starting_cash) * 100.0
+    }
+
+    /// The largest peak to trough decline of the equity curve, as a percent of the peak.
+    pub fn max_drawdown_percent(&self) -> f64 {
+        let mut peak = self.starting_cash;
+        let mut max_drawdown = 0.0_f64;
+        for point in &self.equity_curve {
+            peak = peak.max(point.equity);
+            if peak > 0.0 {
+                let drawdown = ((peak - point.equity) / peak) * 100.0;
+                max_drawdown = max_drawdown.max(drawdown);
+            }
+        }
+        max_drawdown
+    }
+
+    pub fn write_equity_curve<P: AsRef<Path>>(&self, path: P) -> Result<(), GreedError> {
+        let mut writer = csv::Writer::from_path(path)?;
+        for point in &self.equity_curve {
+            writer.serialize(point)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+impl Display for BacktestReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Trades:")?;
+        for trade in &self.trades {
+            writeln!(f, "-- {}", trade)?;
+        }
+        writeln!(f, "Final Portfolio:")?;
+        writeln!(f, "-- {}", self.account)?;
+        for position in &self.positions {
+            writeln!(f, "-- {}", position)?;
+        }
+        writeln!(f, "Results:")?;
+        writeln!(f, "-- starting cash: {:.2}", self.starting_cash)?;
+        writeln!(f, "-- final equity: {:.2}", self.account.equity)?;
+        writeln!(f, "-- total return: {:.2}%", self.total_return_percent())?;
+        writeln!(f, "-- realized gain: {:.2}", self.realized_gain())?;
+        writeln!(f, "-- unrealized gain: {:.2}", self.unrealized_gain())?;
+        write!(f, "-- max drawdown: {:.2}%", self.max_drawdown_percent())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn max_drawdown_percent() {
+        let report = BacktestReport {
+            starting_cash: 100.0,
+            equity_curve: equity_curve(&[110.0, 88.0, 120.0, 90.0, 130.0]),
+            ..Default::default()
+        };
+        assert_relative_eq!(report.max_drawdown_percent(), 25.0)
+    }
+
+    #[test]
+    fn max_drawdown_percent_no_decline() {
+        let report = BacktestReport {
+            starting_cash: 100.0,
+            equity_curve: equity_curve(&[100.0, 110.0, 120.0]),
+            ..Default::default()
+        };
+        assert_eq!(report.max_drawdown_percent(), 0.0)
+    }
+
+    #[test]
+    fn realized_and_unrealized_gain() {
+        let report = BacktestReport {
+            trades: vec![
+                Trade {
+                    realized_gain: 10.0,
+                    ..Default::default()
+                },
+                Trade {
+                    realized_gain: -4.0,
+                    ..Default::default()
+                },
+            ],
+            positions: vec![Position {
+                unrealized_gain_total: Some(3.0),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        assert_eq!(report.realized_gain(), 6.0);
+        assert_eq!(report.unrealized_gain(), 3.0);
+    }
+
+    #[test]
+    fn total_return_percent() {
+        let report = BacktestReport {
+            starting_cash: 100.0,
+            account: Account {
+                equity: 150.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(report.total_return_percent(), 50.0)
+    }
+
+    fn equity_curve(values: &[f64]) -> Vec<EquityPoint> {
+        values
+            .iter()
+            .map(|equity| EquityPoint {
+                equity: *equity,
+                ..Default::default()
+            })
+            .collect()
+    }
+}
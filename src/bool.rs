@@ -21,6 +21,7 @@ impl BooleanWhen for bool {
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use super::*;
 
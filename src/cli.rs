@@ -1,14 +1,24 @@
 pub mod analyze;
+pub mod backtest;
+mod cancel;
+mod close;
+mod explain;
 pub mod init;
 mod orders;
 pub mod quote;
+mod report;
 pub mod run;
 mod status;
 
 use crate::cli::analyze::AnalyzeArgs;
+use crate::cli::backtest::BacktestCommandArgs;
+use crate::cli::cancel::CancelArgs;
+use crate::cli::close::CloseArgs;
+use crate::cli::explain::ExplainArgs;
 use crate::cli::init::InitArgs;
 use crate::cli::orders::OrdersArgs;
 use crate::cli::quote::QuoteArgs;
+use crate::cli::report::ReportCommandArgs;
 use crate::cli::run::RunCommandArgs;
 use clap::{Args, Parser, Subcommand};
 
@@ -23,12 +33,22 @@ pub struct Cli {
 pub enum Command {
     #[command(about = "Analyze stocks")]
     Analyze(AnalyzeArgs),
+    #[command(about = "Replay a config against historical bars")]
+    Backtest(BacktestCommandArgs),
+    #[command(about = "Cancel an open order, or all open orders with --all")]
+    Cancel(CancelArgs),
+    #[command(about = "Sell an entire position")]
+    Close(CloseArgs),
+    #[command(about = "Evaluate a config once and explain why each rule acted or skipped")]
+    Explain(ExplainArgs),
     #[command(about = "Generate a starter config file")]
     Init(InitArgs),
     #[command(about = "Fetch recent orders")]
     Orders(OrdersArgs),
     #[command(about = "Fetch quote")]
     Quote(QuoteArgs),
+    #[command(about = "Report profit and loss per strategy and tactic")]
+    Report(ReportCommandArgs),
     #[command(about = "Run the main greed loop")]
     Run(RunCommandArgs),
     #[command(about = "Get your current creed status")]
@@ -0,0 +1,69 @@
+use chrono::NaiveDate;
+use clap::Args;
+use greed::backtest::BacktestArgs;
+use std::path::PathBuf;
+
+#[derive(Args, Debug)]
+pub struct BacktestCommandArgs {
+    /// Path to the greed configuration toml or csv file
+    #[arg(value_name = "PATH")]
+    pub config_path: PathBuf,
+    /// The first day to replay (YYYY-MM-DD).
+    #[arg(long)]
+    pub start: NaiveDate,
+    /// The last day to replay (YYYY-MM-DD).
+    #[arg(long)]
+    pub end: NaiveDate,
+    /// The cash the simulated account starts with.
+    #[arg(short = 'c', long, default_value_t = 10000.0)]
+    pub cash: f64,
+    /// Directory of <SYMBOL>.csv bar files to replay. Bars are fetched from the platform if omitted.
+    #[arg(short = 'b', long)]
+    pub bars: Option<PathBuf>,
+    /// Write the equity curve to this csv file.
+    #[arg(short = 'e', long)]
+    pub equity_curve: Option<PathBuf>,
+    /// Fetch bars using the simulated financial platform instead of a live account.
+    #[arg(short = 's', long)]
+    pub is_simulated: bool,
+}
+
+impl From<&BacktestCommandArgs> for BacktestArgs {
+    fn from(value: &BacktestCommandArgs) -> Self {
+        Self {
+            config_path: value.config_path.clone(),
+            start: value.start,
+            end: value.end,
+            cash: value.cash,
+            bars_path: value.bars.clone(),
+            is_simulated: value.is_simulated,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn backtest_args_from() {
+        let command_args = BacktestCommandArgs {
+            config_path: PathBuf::from("path"),
+            start: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            end: NaiveDate::from_ymd_opt(2024, 2, 1).unwrap(),
+            cash: 500.0,
+            bars: Some(PathBuf::from("bars")),
+            equity_curve: None,
+            is_simulated: true,
+        };
+        let expected = BacktestArgs {
+            config_path: PathBuf::from("path"),
+            start: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            end: NaiveDate::from_ymd_opt(2024, 2, 1).unwrap(),
+            cash: 500.0,
+            bars_path: Some(PathBuf::from("bars")),
+            is_simulated: true,
+        };
+        assert_eq!(BacktestArgs::from(&command_args), expected)
+    }
+}
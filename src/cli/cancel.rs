@@ -0,0 +1,64 @@
+use clap::Args;
+use greed::config::platform::PlatformType;
+use greed::platform::args::PlatformArgs;
+use greed::platform::id::Id;
+use uuid::Uuid;
+
+#[derive(Args, Debug)]
+pub struct CancelArgs {
+    /// The id of the order to cancel, as shown by `greed orders`.
+    #[arg(value_name = "ORDER_ID", required_unless_present = "all")]
+    pub order_id: Option<Uuid>,
+    /// Cancel every open order.
+    #[arg(short = 'a', long, conflicts_with = "order_id")]
+    pub all: bool,
+    /// Indicates if we should use a simulated financial platform instead of a live account.
+    #[arg(short = 's', long)]
+    pub is_simulated: bool,
+    #[arg(short = 'p', long, default_value = "alpaca")]
+    pub platform_type: PlatformType,
+}
+
+impl CancelArgs {
+    pub fn order_id(&self) -> Option<Id> {
+        self.order_id.map(Id::Uuid)
+    }
+}
+
+impl From<&CancelArgs> for PlatformArgs {
+    fn from(value: &CancelArgs) -> Self {
+        PlatformArgs {
+            is_simulated: value.is_simulated,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from() {
+        let cancel_args = CancelArgs {
+            order_id: None,
+            all: true,
+            is_simulated: true,
+            platform_type: Default::default(),
+        };
+        let platform_args = PlatformArgs::from(&cancel_args);
+        let expected = PlatformArgs { is_simulated: true };
+        assert_eq!(platform_args, expected)
+    }
+
+    #[test]
+    fn order_id() {
+        let uuid = Uuid::from_u128(1);
+        let cancel_args = CancelArgs {
+            order_id: Some(uuid),
+            all: false,
+            is_simulated: false,
+            platform_type: Default::default(),
+        };
+        assert_eq!(cancel_args.order_id(), Some(Id::Uuid(uuid)))
+    }
+}
@@ -0,0 +1,41 @@
+use clap::Args;
+use greed::asset::AssetSymbol;
+use greed::config::platform::PlatformType;
+use greed::platform::args::PlatformArgs;
+
+#[derive(Args, Debug)]
+pub struct CloseArgs {
+    /// The asset symbol of the position to sell.
+    #[arg(value_name = "SYMBOL")]
+    pub symbol: AssetSymbol,
+    /// Indicates if we should use a simulated financial platform instead of a live account.
+    #[arg(short = 's', long)]
+    pub is_simulated: bool,
+    #[arg(short = 'p', long, default_value = "alpaca")]
+    pub platform_type: PlatformType,
+}
+
+impl From<&CloseArgs> for PlatformArgs {
+    fn from(value: &CloseArgs) -> Self {
+        PlatformArgs {
+            is_simulated: value.is_simulated,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from() {
+        let close_args = CloseArgs {
+            symbol: AssetSymbol::new("VTI"),
+            is_simulated: true,
+            platform_type: Default::default(),
+        };
+        let platform_args = PlatformArgs::from(&close_args);
+        let expected = PlatformArgs { is_simulated: true };
+        assert_eq!(platform_args, expected)
+    }
+}
@@ -0,0 +1,37 @@
+use clap::Args;
+use greed::platform::args::PlatformArgs;
+use std::path::PathBuf;
+
+#[derive(Args, Debug)]
+pub struct ExplainArgs {
+    /// Path to the greed configuration toml or csv file
+    #[arg(value_name = "PATH")]
+    pub config_path: PathBuf,
+    /// Indicates if we should use a simulated financial platform instead of a live account.
+    #[arg(short = 's', long)]
+    pub is_simulated: bool,
+}
+
+impl From<&ExplainArgs> for PlatformArgs {
+    fn from(value: &ExplainArgs) -> Self {
+        PlatformArgs {
+            is_simulated: value.is_simulated,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from() {
+        let explain_args = ExplainArgs {
+            config_path: PathBuf::from("greed.toml"),
+            is_simulated: true,
+        };
+        let platform_args = PlatformArgs::from(&explain_args);
+        let expected = PlatformArgs { is_simulated: true };
+        assert_eq!(platform_args, expected)
+    }
+}
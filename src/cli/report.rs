@@ -0,0 +1,58 @@
+use chrono::NaiveDate;
+use clap::Args;
+use greed::report::{ReportArgs, ReportFormat};
+use std::path::PathBuf;
+
+#[derive(Args, Debug)]
+pub struct ReportCommandArgs {
+    /// Path to the greed configuration toml or csv file
+    #[arg(value_name = "PATH")]
+    pub config_path: PathBuf,
+    /// Only include orders created on or after this day (YYYY-MM-DD). Defaults to the first
+    /// journal entry.
+    #[arg(long)]
+    pub start: Option<NaiveDate>,
+    /// Only include orders created on or before this day (YYYY-MM-DD). Defaults to now.
+    #[arg(long)]
+    pub end: Option<NaiveDate>,
+    /// How to print the report.
+    #[arg(short = 'f', long, default_value = "table")]
+    pub format: ReportFormat,
+    /// Indicates if we should use a simulated financial platform instead of a live account.
+    #[arg(short = 's', long)]
+    pub is_simulated: bool,
+}
+
+impl From<&ReportCommandArgs> for ReportArgs {
+    fn from(value: &ReportCommandArgs) -> Self {
+        Self {
+            config_path: value.config_path.clone(),
+            start: value.start,
+            end: value.end,
+            is_simulated: value.is_simulated,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_args_from() {
+        let command_args = ReportCommandArgs {
+            config_path: PathBuf::from("greed.toml"),
+            start: NaiveDate::from_ymd_opt(2024, 1, 1),
+            end: None,
+            format: ReportFormat::Csv,
+            is_simulated: true,
+        };
+        let expected = ReportArgs {
+            config_path: PathBuf::from("greed.toml"),
+            start: NaiveDate::from_ymd_opt(2024, 1, 1),
+            end: None,
+            is_simulated: true,
+        };
+        assert_eq!(ReportArgs::from(&command_args), expected)
+    }
+}
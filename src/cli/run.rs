@@ -10,6 +10,15 @@ pub struct RunCommandArgs {
     /// Use a simulated financial platform instead of a live account.
     #[arg(short = 's', long)]
     is_simulated: bool,
+    /// Record every platform call and response to a JSONL cassette file.
+    #[arg(long = "record", value_name = "FILE", conflicts_with = "replay_path")]
+    record_path: Option<PathBuf>,
+    /// Replay platform responses from a cassette file recorded with --record.
+    #[arg(long = "replay", value_name = "FILE")]
+    replay_path: Option<PathBuf>,
+    /// Evaluate every rule against the account but log orders instead of placing them.
+    #[arg(long = "dry-run")]
+    is_dry_run: bool,
 }
 
 impl From<RunCommandArgs> for GreedRunnerArgs {
@@ -17,6 +26,9 @@ impl From<RunCommandArgs> for GreedRunnerArgs {
         Self {
             config_path: value.config_path.clone(),
             is_simulated: value.is_simulated,
+            record_path: value.record_path,
+            replay_path: value.replay_path,
+            is_dry_run: value.is_dry_run,
         }
     }
 }
@@ -32,11 +44,17 @@ mod test {
         let command_args = RunCommandArgs {
             config_path: PathBuf::from("path"),
             is_simulated: true,
+            record_path: Some(PathBuf::from("record.jsonl")),
+            replay_path: None,
+            is_dry_run: true,
         };
         let runner_args: GreedRunnerArgs = command_args.into();
         let expected = GreedRunnerArgs {
             config_path: PathBuf::from("path"),
             is_simulated: true,
+            record_path: Some(PathBuf::from("record.jsonl")),
+            replay_path: None,
+            is_dry_run: true,
         };
         assert_eq!(runner_args, expected)
     }
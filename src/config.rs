@@ -1,15 +1,17 @@
 use crate::config::platform::PlatformType;
 use crate::config::reader::read_config;
+use crate::config::risk::RiskConfig;
 use crate::config::strategy::StrategyConfig;
 use crate::config::tactic::TacticConfig;
 use crate::error::GreedError;
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 pub mod agent;
 pub mod platform;
 pub mod quote_fetcher_config;
 pub mod reader;
+pub mod risk;
 pub mod simple;
 pub mod strategy;
 pub mod tactic;
@@ -24,6 +26,11 @@ pub struct Config {
     pub tactics: Vec<TacticConfig>,
     #[serde(default = "default_interval")]
     pub interval: u64,
+    /// A JSONL file, relative to the config, where placed orders and their fills are journaled.
+    #[serde(default)]
+    pub journal: Option<String>,
+    #[serde(default)]
+    pub risk: RiskConfig,
 }
 
 fn default_interval() -> u64 {
@@ -34,6 +41,13 @@ impl Config {
     pub async fn from_path<P: AsRef<Path>>(path: P) -> Result<Config, GreedError> {
         read_config(path).await
     }
+
+    /// Resolves the journal path relative to the directory containing the config.
+    pub fn journal_path(&self, config_path: &Path) -> Option<PathBuf> {
+        let journal = self.journal.as_ref()?;
+        let directory = config_path.parent().unwrap_or(Path::new(""));
+        Some(directory.join(journal))
+    }
 }
 
 #[cfg(test)]
@@ -57,6 +71,8 @@ mod test {
             strategies: vec![],
             tactics: vec![],
             interval: 0,
+            journal: None,
+            risk: Default::default(),
         };
 
         assert_eq!(default, expected)
@@ -70,6 +86,8 @@ mod test {
             strategies: vec![],
             tactics: vec![],
             interval: 60,
+            journal: None,
+            risk: Default::default(),
         };
         assert_eq!(expected, config)
     }
@@ -90,7 +108,10 @@ mod test {
                         below_median_percent: 5.0,
                         median_period: Default::default(),
                     },
-                    do_config: DoConfig::Buy { buy_percent: 10.0 },
+                    do_config: DoConfig::Buy {
+                        buy_percent: 10.0,
+                        order: Default::default(),
+                    },
                 },
                 sell: RuleConfig {
                     for_config: ForConfig::Stock {
@@ -99,10 +120,16 @@ mod test {
                     when_config: WhenConfig::GainAbove {
                         gain_above_percent: 5.0,
                     },
-                    do_config: DoConfig::SellAll { sell_all: true },
+                    do_config: DoConfig::SellAll {
+                        sell_all: true,
+                        order: Default::default(),
+                    },
                 },
+                exit: Default::default(),
             }],
             interval: 300,
+            journal: None,
+            risk: Default::default(),
         };
         assert_eq!(expected, config)
     }
@@ -124,7 +151,10 @@ mod test {
                             below_median_percent: 5.0,
                             median_period: Default::default(),
                         },
-                        do_config: DoConfig::Buy { buy_percent: 10.0 },
+                        do_config: DoConfig::Buy {
+                            buy_percent: 10.0,
+                            order: Default::default(),
+                        },
                     },
                     sell: RuleConfig {
                         for_config: ForConfig::Stock {
@@ -133,8 +163,12 @@ mod test {
                         when_config: WhenConfig::GainAbove {
                             gain_above_percent: 5.0,
                         },
-                        do_config: DoConfig::SellAll { sell_all: true },
+                        do_config: DoConfig::SellAll {
+                            sell_all: true,
+                            order: Default::default(),
+                        },
                     },
+                    exit: Default::default(),
                 },
                 TacticConfig {
                     name: "Chaos".to_string(),
@@ -146,7 +180,10 @@ mod test {
                             below_median_percent: 2.0,
                             median_period: MedianPeriod::Week,
                         },
-                        do_config: DoConfig::Buy { buy_percent: 5.0 },
+                        do_config: DoConfig::Buy {
+                            buy_percent: 5.0,
+                            order: Default::default(),
+                        },
                     },
                     sell: RuleConfig {
                         for_config: ForConfig::Stock {
@@ -155,11 +192,17 @@ mod test {
                         when_config: WhenConfig::GainAbove {
                             gain_above_percent: 3.0,
                         },
-                        do_config: DoConfig::SellAll { sell_all: true },
+                        do_config: DoConfig::SellAll {
+                            sell_all: true,
+                            order: Default::default(),
+                        },
                     },
+                    exit: Default::default(),
                 },
             ],
             interval: 300,
+            journal: None,
+            risk: Default::default(),
         };
         assert_eq!(expected, config)
     }
@@ -202,7 +245,10 @@ mod test {
                         below_median_percent: 5.0,
                         median_period: Default::default(),
                     },
-                    do_config: DoConfig::Buy { buy_percent: 10.0 },
+                    do_config: DoConfig::Buy {
+                        buy_percent: 10.0,
+                        order: Default::default(),
+                    },
                 },
                 sell: RuleConfig {
                     for_config: ForConfig::Stock {
@@ -211,11 +257,33 @@ mod test {
                     when_config: WhenConfig::GainAbove {
                         gain_above_percent: 5.0,
                     },
-                    do_config: DoConfig::SellAll { sell_all: true },
+                    do_config: DoConfig::SellAll {
+                        sell_all: true,
+                        order: Default::default(),
+                    },
                 },
+                exit: Default::default(),
             }],
             interval: 5,
+            journal: None,
+            risk: Default::default(),
         };
         assert_eq!(config, expected)
     }
+
+    #[tokio::test]
+    async fn deserialize_journal_config() {
+        let config = fixture::config("config_journal.toml").await;
+        assert_eq!(config.journal, Some("journal.jsonl".to_string()));
+        assert_eq!(
+            config.journal_path(&fixture::path("config_journal.toml")),
+            Some(fixture::path("journal.jsonl"))
+        );
+    }
+
+    #[test]
+    fn journal_path_none() {
+        let config = Config::default();
+        assert_eq!(config.journal_path(&fixture::path("config.toml")), None);
+    }
 }
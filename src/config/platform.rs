@@ -6,6 +6,8 @@ use serde::{Deserialize, Serialize};
 pub enum PlatformType {
     #[default]
     Alpaca,
+    /// A local brokerage backed by an in-process order book.
+    Simulated,
 }
 
 #[cfg(test)]
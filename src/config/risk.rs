@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+/// Limits every order must pass before it reaches the platform. Each limit is off unless set.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct RiskConfig {
+    /// The most a single order may be worth, in dollars.
+    pub max_order_notional: Option<f64>,
+    /// The largest a single position may grow to, as a percent of equity.
+    pub max_position_percent: Option<f64>,
+    /// The most orders, buys or sells, which may be placed per trading day.
+    pub max_orders_per_day: Option<u32>,
+    /// Cash, in dollars, which buys may never spend.
+    pub min_cash_reserve: Option<f64>,
+    /// Stop buying for the rest of the day once equity has fallen this percent since the
+    /// previous close.
+    pub daily_loss_limit_percent: Option<f64>,
+}
+
+impl RiskConfig {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize() {
+        let toml = r#"
+max_order_notional = 500.0
+max_position_percent = 20.0
+max_orders_per_day = 10
+min_cash_reserve = 1000.0
+daily_loss_limit_percent = 3.0
+"#;
+        let config: RiskConfig = toml::from_str(toml).unwrap();
+        let expected = RiskConfig {
+            max_order_notional: Some(500.0),
+            max_position_percent: Some(20.0),
+            max_orders_per_day: Some(10),
+            min_cash_reserve: Some(1000.0),
+            daily_loss_limit_percent: Some(3.0),
+        };
+        assert_eq!(config, expected);
+        assert!(!config.is_empty())
+    }
+
+    #[test]
+    fn is_empty() {
+        assert!(RiskConfig::default().is_empty())
+    }
+}
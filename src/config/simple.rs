@@ -26,6 +26,8 @@ impl From<SimpleConfig> for Config {
             strategies: vec![],
             tactics: value.tactics.into_iter().map(Into::into).collect(),
             interval: 5,
+            journal: None,
+            risk: Default::default(),
         }
     }
 }
@@ -79,6 +81,8 @@ mod test {
             strategies: vec![],
             tactics: vec![],
             interval: 5,
+            journal: None,
+            risk: Default::default(),
         };
         assert_eq!(expected, Config::from(simple_config))
     }
@@ -107,6 +111,8 @@ mod test {
                 simple_tactic_2.clone().into(),
             ],
             interval: 5,
+            journal: None,
+            risk: Default::default(),
         };
         assert_eq!(
             expected,
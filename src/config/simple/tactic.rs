@@ -39,6 +39,7 @@ impl From<SimpleTacticConfig> for TacticConfig {
             name: value.asset.symbol.clone(),
             buy: buy_rules(&value),
             sell: sell_rules(&value),
+            exit: Default::default(),
         }
     }
 }
@@ -55,6 +56,7 @@ fn buy_rules(simple_config: &SimpleTacticConfig) -> RuleConfig {
             },
             do_config: DoConfig::Buy {
                 buy_percent: simple_config.amount,
+                order: Default::default(),
             },
         }
     } else {
@@ -71,7 +73,10 @@ fn sell_rules(simple_config: &SimpleTacticConfig) -> RuleConfig {
             when_config: WhenConfig::GainAbove {
                 gain_above_percent: sell,
             },
-            do_config: DoConfig::SellAll { sell_all: true },
+            do_config: DoConfig::SellAll {
+                sell_all: true,
+                order: Default::default(),
+            },
         }
     } else {
         RuleConfig::default()
@@ -92,6 +97,7 @@ mod tests {
             name: "VTI".into(),
             buy: RuleConfig::default(),
             sell: RuleConfig::default(),
+            exit: Default::default(),
         };
         assert_eq!(expected, TacticConfig::from(simple_config))
     }
@@ -115,7 +121,10 @@ mod tests {
                     below_median_percent: 0.1,
                     median_period: Default::default(),
                 },
-                do_config: DoConfig::Buy { buy_percent: 0.5 },
+                do_config: DoConfig::Buy {
+                    buy_percent: 0.5,
+                    order: Default::default(),
+                },
             },
             sell: RuleConfig {
                 for_config: ForConfig::Stock {
@@ -124,8 +133,12 @@ mod tests {
                 when_config: WhenConfig::GainAbove {
                     gain_above_percent: 0.2,
                 },
-                do_config: DoConfig::SellAll { sell_all: true },
+                do_config: DoConfig::SellAll {
+                    sell_all: true,
+                    order: Default::default(),
+                },
             },
+            exit: Default::default(),
         };
         assert_eq!(expected, TacticConfig::from(simple_config))
     }
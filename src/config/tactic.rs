@@ -1,11 +1,17 @@
 pub mod r#do;
+pub mod exit;
 pub mod r#for;
 pub mod median;
+pub mod moving_average;
+pub mod order;
+pub mod oscillator;
 pub mod rule;
+pub mod schedule;
 pub mod when;
 
 use crate::asset::AssetSymbol;
 use crate::config::quote_fetcher_config::QuoteFetcherConfig;
+use crate::config::tactic::exit::ExitConfig;
 use crate::config::tactic::rule::RuleConfig;
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
@@ -18,6 +24,8 @@ pub struct TacticConfig {
     pub buy: RuleConfig,
     #[serde(default)]
     pub sell: RuleConfig,
+    #[serde(default)]
+    pub exit: ExitConfig,
 }
 
 impl TacticConfig {
@@ -30,6 +38,26 @@ impl TacticConfig {
             .unique()
             .collect::<Vec<_>>()
     }
+
+    pub fn should_fetch_order_history(&self) -> bool {
+        self.buy.should_fetch_order_history() || self.sell.should_fetch_order_history()
+    }
+
+    pub fn should_fetch_market_day(&self) -> bool {
+        self.buy.should_fetch_market_day() || self.sell.should_fetch_market_day()
+    }
+
+    pub fn should_fetch_asset_info(&self) -> bool {
+        self.buy.should_fetch_asset_info() || self.sell.should_fetch_asset_info()
+    }
+
+    pub fn should_fetch_bars(&self) -> bool {
+        self.buy.should_fetch_bars() || self.sell.should_fetch_bars()
+    }
+
+    pub fn should_fetch_one_year(&self) -> bool {
+        self.buy.should_fetch_one_year() || self.sell.should_fetch_one_year()
+    }
 }
 
 impl QuoteFetcherConfig for TacticConfig {
@@ -92,7 +120,10 @@ mod tests {
     fn should_fetch_quotes_buy_requires_quotes() {
         let tactic = TacticConfig {
             buy: RuleConfig {
-                do_config: DoConfig::Buy { buy_percent: 0.5 },
+                do_config: DoConfig::Buy {
+                    buy_percent: 0.5,
+                    order: Default::default(),
+                },
                 ..Default::default()
             },
             ..Default::default()
@@ -119,12 +150,18 @@ mod tests {
     fn should_fetch_quotes_neither_requires_quotes() {
         let tactic = TacticConfig {
             buy: RuleConfig {
-                do_config: DoConfig::SellAll { sell_all: true },
+                do_config: DoConfig::SellAll {
+                    sell_all: true,
+                    order: Default::default(),
+                },
                 ..Default::default()
             },
             sell: RuleConfig {
                 when_config: WhenConfig::Always { always: true },
-                do_config: DoConfig::SellAll { sell_all: true },
+                do_config: DoConfig::SellAll {
+                    sell_all: true,
+                    order: Default::default(),
+                },
                 ..Default::default()
             },
             ..Default::default()
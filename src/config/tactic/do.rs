@@ -1,20 +1,114 @@
 use crate::config::quote_fetcher_config::QuoteFetcherConfig;
+use crate::config::tactic::order::OrderConfig;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum DoConfig {
-    Buy { buy_percent: f64 },
-    Nothing { nothing: bool },
-    SellAll { sell_all: bool },
+    Buy {
+        buy_percent: f64,
+        #[serde(default, skip_serializing_if = "OrderConfig::is_market")]
+        order: OrderConfig,
+    },
+    CoverAll {
+        cover_all: bool,
+        #[serde(default, skip_serializing_if = "OrderConfig::is_market")]
+        order: OrderConfig,
+    },
+    InvestNotional {
+        invest_notional: f64,
+        #[serde(default, skip_serializing_if = "OrderConfig::is_market")]
+        order: OrderConfig,
+    },
+    Nothing {
+        nothing: bool,
+    },
+    Rebalance {
+        rebalance: bool,
+        #[serde(default = "default_drift_percent")]
+        drift_percent: f64,
+        #[serde(default = "default_min_trade")]
+        min_trade: f64,
+    },
+    SellAll {
+        sell_all: bool,
+        #[serde(default, skip_serializing_if = "OrderConfig::is_market")]
+        order: OrderConfig,
+    },
+    SellNotional {
+        sell_notional: f64,
+        #[serde(default, skip_serializing_if = "OrderConfig::is_market")]
+        order: OrderConfig,
+    },
+    SellPercent {
+        sell_percent: f64,
+        #[serde(default, skip_serializing_if = "OrderConfig::is_market")]
+        order: OrderConfig,
+    },
+    ShortPercent {
+        short_percent: f64,
+        #[serde(default, skip_serializing_if = "OrderConfig::is_market")]
+        order: OrderConfig,
+    },
+    TrimToPercent {
+        trim_to_percent: f64,
+        #[serde(default, skip_serializing_if = "OrderConfig::is_market")]
+        order: OrderConfig,
+    },
+}
+
+impl DoConfig {
+    /// The kind of order placed, `None` for actions which don't take one.
+    pub fn order(&self) -> Option<&OrderConfig> {
+        match self {
+            DoConfig::Buy { order, .. } => Some(order),
+            DoConfig::CoverAll { order, .. } => Some(order),
+            DoConfig::InvestNotional { order, .. } => Some(order),
+            DoConfig::Nothing { .. } => None,
+            DoConfig::Rebalance { .. } => None,
+            DoConfig::SellAll { order, .. } => Some(order),
+            DoConfig::SellNotional { order, .. } => Some(order),
+            DoConfig::SellPercent { order, .. } => Some(order),
+            DoConfig::ShortPercent { order, .. } => Some(order),
+            DoConfig::TrimToPercent { order, .. } => Some(order),
+        }
+    }
+
+    /// Whether the rule needs the broker's asset info, to know which assets can be borrowed for
+    /// shorting or bought in fractions.
+    pub fn should_fetch_asset_info(&self) -> bool {
+        if self.order().is_some_and(OrderConfig::is_for_whole_shares) {
+            return true;
+        }
+        matches!(
+            self,
+            DoConfig::Buy { .. }
+                | DoConfig::InvestNotional { .. }
+                | DoConfig::Rebalance { .. }
+                | DoConfig::ShortPercent { .. }
+        )
+    }
 }
 
 impl QuoteFetcherConfig for DoConfig {
     fn should_fetch_quotes(&self) -> bool {
+        if self.order().is_some_and(|order| !order.is_market()) {
+            return true;
+        }
         match self {
             DoConfig::Buy { .. } => true,
+            DoConfig::CoverAll { .. } => false,
+            // Assets which only trade in whole shares are priced off the quote.
+            DoConfig::InvestNotional { .. } => true,
             &DoConfig::Nothing { .. } => false,
+            // Buys of assets which only trade in whole shares are priced off the quote.
+            DoConfig::Rebalance { .. } => true,
             DoConfig::SellAll { .. } => false,
+            DoConfig::SellNotional { .. } => false,
+            DoConfig::SellPercent { .. } => false,
+            // Shorts are placed for whole shares, so they're priced off the quote.
+            DoConfig::ShortPercent { .. } => true,
+            DoConfig::TrimToPercent { .. } => false,
         }
     }
 }
@@ -25,6 +119,16 @@ impl Default for DoConfig {
     }
 }
 
+/// How far, in percentage points of the portfolio, an asset may drift before it's rebalanced.
+pub fn default_drift_percent() -> f64 {
+    5.0
+}
+
+/// The smallest rebalancing trade worth placing, in dollars.
+pub fn default_min_trade() -> f64 {
+    1.0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -36,10 +140,40 @@ mod tests {
 
     #[test]
     fn should_fetch_quotes_buy() {
-        let config = DoConfig::Buy { buy_percent: 0.5 };
+        let config = DoConfig::Buy {
+            buy_percent: 0.5,
+            order: Default::default(),
+        };
         assert!(config.should_fetch_quotes());
     }
 
+    #[test]
+    fn should_fetch_asset_info_buy() {
+        let config = DoConfig::Buy {
+            buy_percent: 0.5,
+            order: Default::default(),
+        };
+        assert!(config.should_fetch_asset_info());
+    }
+
+    #[test]
+    fn should_fetch_asset_info_trailing_stop() {
+        let config = DoConfig::SellAll {
+            sell_all: true,
+            order: OrderConfig::TrailingStop { trail_percent: 5.0 },
+        };
+        assert!(config.should_fetch_asset_info());
+    }
+
+    #[test]
+    fn should_fetch_asset_info_invest_notional() {
+        let config = DoConfig::InvestNotional {
+            invest_notional: 200.0,
+            order: Default::default(),
+        };
+        assert!(config.should_fetch_asset_info());
+    }
+
     #[test]
     fn should_fetch_quotes_nothin() {
         let config = DoConfig::Nothing { nothing: true };
@@ -48,7 +182,157 @@ mod tests {
 
     #[test]
     fn should_fetch_quotes_sell_all() {
-        let config = DoConfig::SellAll { sell_all: true };
+        let config = DoConfig::SellAll {
+            sell_all: true,
+            order: Default::default(),
+        };
         assert!(!config.should_fetch_quotes());
     }
+
+    #[test]
+    fn should_fetch_quotes_partial_sells() {
+        assert!(!DoConfig::SellNotional {
+            sell_notional: 500.0,
+            order: Default::default()
+        }
+        .should_fetch_quotes());
+        assert!(!DoConfig::SellPercent {
+            sell_percent: 25.0,
+            order: Default::default()
+        }
+        .should_fetch_quotes());
+        assert!(!DoConfig::TrimToPercent {
+            trim_to_percent: 10.0,
+            order: Default::default()
+        }
+        .should_fetch_quotes());
+    }
+
+    #[test]
+    fn deserialize_partial_sells() {
+        let sell_percent: DoConfig = toml::from_str("sell_percent = 25").unwrap();
+        let sell_notional: DoConfig = toml::from_str("sell_notional = 500").unwrap();
+        let trim: DoConfig = toml::from_str("trim_to_percent = 10").unwrap();
+        assert_eq!(
+            DoConfig::SellPercent {
+                sell_percent: 25.0,
+                order: Default::default()
+            },
+            sell_percent
+        );
+        assert_eq!(
+            DoConfig::SellNotional {
+                sell_notional: 500.0,
+                order: Default::default()
+            },
+            sell_notional
+        );
+        assert_eq!(
+            DoConfig::TrimToPercent {
+                trim_to_percent: 10.0,
+                order: Default::default()
+            },
+            trim
+        );
+    }
+
+    #[test]
+    fn deserialize_invest_notional() {
+        let config: DoConfig = toml::from_str("invest_notional = 200").unwrap();
+        assert_eq!(
+            DoConfig::InvestNotional {
+                invest_notional: 200.0,
+                order: Default::default()
+            },
+            config
+        );
+        assert!(config.should_fetch_quotes());
+    }
+
+    #[test]
+    fn should_fetch_quotes_rebalance() {
+        let config = DoConfig::Rebalance {
+            rebalance: true,
+            drift_percent: default_drift_percent(),
+            min_trade: default_min_trade(),
+        };
+        assert!(config.should_fetch_quotes());
+        assert!(config.should_fetch_asset_info());
+    }
+
+    #[test]
+    fn deserialize_rebalance() {
+        let config: DoConfig = toml::from_str("rebalance = true").unwrap();
+        let expected = DoConfig::Rebalance {
+            rebalance: true,
+            drift_percent: 5.0,
+            min_trade: 1.0,
+        };
+        assert_eq!(expected, config);
+
+        let config: DoConfig =
+            toml::from_str("rebalance = true\ndrift_percent = 2.5\nmin_trade = 50").unwrap();
+        let expected = DoConfig::Rebalance {
+            rebalance: true,
+            drift_percent: 2.5,
+            min_trade: 50.0,
+        };
+        assert_eq!(expected, config);
+    }
+
+    #[test]
+    fn deserialize_order() {
+        let config: DoConfig =
+            toml::from_str("buy_percent = 10\norder = { type = \"limit\", offset_percent = -0.5 }")
+                .unwrap();
+        let expected = DoConfig::Buy {
+            buy_percent: 10.0,
+            order: OrderConfig::Limit {
+                offset_percent: -0.5,
+            },
+        };
+        assert_eq!(expected, config);
+        assert_eq!(
+            Some(&OrderConfig::Market),
+            DoConfig::SellAll {
+                sell_all: true,
+                order: Default::default()
+            }
+            .order()
+        );
+        assert_eq!(None, DoConfig::default().order());
+    }
+
+    #[test]
+    fn deserialize_short_and_cover() {
+        let short: DoConfig = toml::from_str("short_percent = 5").unwrap();
+        let cover: DoConfig = toml::from_str("cover_all = true").unwrap();
+        assert_eq!(
+            DoConfig::ShortPercent {
+                short_percent: 5.0,
+                order: Default::default()
+            },
+            short
+        );
+        assert_eq!(
+            DoConfig::CoverAll {
+                cover_all: true,
+                order: Default::default()
+            },
+            cover
+        );
+        assert!(short.should_fetch_quotes());
+        assert!(short.should_fetch_asset_info());
+        assert!(!cover.should_fetch_quotes());
+        assert!(!cover.should_fetch_asset_info());
+    }
+
+    #[test]
+    fn should_fetch_quotes_order() {
+        let config = DoConfig::SellPercent {
+            sell_percent: 100.0,
+            order: OrderConfig::TrailingStop { trail_percent: 5.0 },
+        };
+        assert!(config.should_fetch_quotes());
+    }
 }
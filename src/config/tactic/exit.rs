@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+/// Exits the broker manages for the tactic's positions between runs.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct ExitConfig {
+    /// Keeps a trailing stop sell at this percent covering the shares the tactic bought.
+    pub trailing_stop_percent: Option<f64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize() {
+        let config: ExitConfig = toml::from_str("trailing_stop_percent = 7").unwrap();
+        let expected = ExitConfig {
+            trailing_stop_percent: Some(7.0),
+        };
+        assert_eq!(expected, config);
+    }
+
+    #[test]
+    fn default() {
+        let config: ExitConfig = toml::from_str("").unwrap();
+        assert_eq!(ExitConfig::default(), config);
+    }
+}
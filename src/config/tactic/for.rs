@@ -1,14 +1,18 @@
 use crate::asset::AssetSymbol;
 use crate::config::quote_fetcher_config::QuoteFetcherConfig;
+use itertools::Itertools;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum ForConfig {
     AllOtherPositions { all_other_positions: bool },
     AnyOf { any_of: Vec<AssetSymbol> },
+    Crypto { crypto: AssetSymbol },
     Nothing { nothing: bool },
     Stock { stock: AssetSymbol },
+    Weights { weights: HashMap<AssetSymbol, f64> },
 }
 
 impl ForConfig {
@@ -16,8 +20,14 @@ impl ForConfig {
         match self {
             ForConfig::AllOtherPositions { .. } => vec![],
             ForConfig::AnyOf { any_of } => any_of.clone(),
+            ForConfig::Crypto { crypto } => vec![AssetSymbol::crypto(&crypto.symbol)],
             ForConfig::Nothing { .. } => vec![],
             ForConfig::Stock { stock } => vec![stock.clone()],
+            ForConfig::Weights { weights } => weights
+                .keys()
+                .cloned()
+                .sorted_by(|a, b| a.symbol.cmp(&b.symbol))
+                .collect(),
         }
     }
 }
@@ -49,6 +59,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn assets_crypto() {
+        let crypto = ForConfig::Crypto {
+            crypto: AssetSymbol::new("BTCUSD"),
+        };
+        assert_eq!(vec![AssetSymbol::new("BTC/USD")], crypto.assets())
+    }
+
     #[test]
     fn assets_nothing() {
         assert_eq!(
@@ -69,6 +87,25 @@ mod tests {
         )
     }
 
+    #[test]
+    fn assets_weights() {
+        let weights = ForConfig::Weights {
+            weights: HashMap::from([
+                (AssetSymbol::new("VXUS"), 30.0),
+                (AssetSymbol::new("BND"), 10.0),
+                (AssetSymbol::new("VTI"), 60.0),
+            ]),
+        };
+        assert_eq!(
+            vec![
+                AssetSymbol::new("BND"),
+                AssetSymbol::new("VTI"),
+                AssetSymbol::new("VXUS")
+            ],
+            weights.assets()
+        )
+    }
+
     #[test]
     fn default() {
         assert_eq!(ForConfig::Nothing { nothing: true }, Default::default())
@@ -103,4 +140,27 @@ mod tests {
         };
         assert!(!stock.should_fetch_quotes());
     }
+
+    #[test]
+    fn deserialize_weights() {
+        let config: ForConfig =
+            toml::from_str("weights = { VTI = 60, VXUS = 30, BND = 10 }").unwrap();
+        let expected = ForConfig::Weights {
+            weights: HashMap::from([
+                (AssetSymbol::new("VTI"), 60.0),
+                (AssetSymbol::new("VXUS"), 30.0),
+                (AssetSymbol::new("BND"), 10.0),
+            ]),
+        };
+        assert_eq!(expected, config)
+    }
+
+    #[test]
+    fn deserialize_crypto() {
+        let config: ForConfig = toml::from_str("crypto = \"BTC/USD\"").unwrap();
+        let expected = ForConfig::Crypto {
+            crypto: AssetSymbol::new("BTC/USD"),
+        };
+        assert_eq!(expected, config)
+    }
 }
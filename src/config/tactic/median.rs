@@ -1,3 +1,4 @@
+use crate::lowercase_enum_display;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
@@ -9,6 +10,8 @@ pub enum MedianPeriod {
     Month,
 }
 
+lowercase_enum_display!(MedianPeriod);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,63 @@
+use crate::lowercase_enum_display;
+use crate::platform::bars::Bars;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MovingAverage {
+    #[default]
+    Sma,
+    Ema,
+}
+
+lowercase_enum_display!(MovingAverage);
+
+impl MovingAverage {
+    pub fn calculate(&self, bars: &Bars, period: usize) -> Option<f64> {
+        match self {
+            MovingAverage::Sma => bars.sma(period),
+            MovingAverage::Ema => bars.ema(period),
+        }
+    }
+}
+
+pub fn default_fast_period() -> usize {
+    50
+}
+
+pub fn default_slow_period() -> usize {
+    200
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::bar::Bar;
+
+    #[test]
+    fn default() {
+        assert_eq!(MovingAverage::Sma, Default::default())
+    }
+
+    #[test]
+    fn default_periods() {
+        assert_eq!(50, default_fast_period());
+        assert_eq!(200, default_slow_period());
+    }
+
+    #[test]
+    fn calculate() {
+        let bars = Bars::with_bars(
+            [1.0, 2.0, 3.0]
+                .into_iter()
+                .map(|close| Bar {
+                    close,
+                    ..Default::default()
+                })
+                .collect(),
+        );
+        assert_eq!(MovingAverage::Sma.calculate(&bars, 2), Some(2.5));
+        assert_eq!(MovingAverage::Ema.calculate(&bars, 2), Some(2.5));
+        assert_eq!(MovingAverage::Sma.calculate(&bars, 4), None);
+    }
+}
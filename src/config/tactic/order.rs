@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+
+/// The kind of order a `do` rule places. Prices are set relative to the quote: the ask for buys
+/// and the bid for sells.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OrderConfig {
+    #[default]
+    Market,
+    /// A limit order priced `offset_percent` away from the quote, negative to buy below it.
+    Limit {
+        #[serde(default)]
+        offset_percent: f64,
+    },
+    /// A stop order triggered `offset_percent` away from the quote.
+    Stop { offset_percent: f64 },
+    /// A trailing stop order which follows the price at `trail_percent`.
+    TrailingStop { trail_percent: f64 },
+    /// A market buy with take profit and stop loss exits, set as percents above and below the
+    /// quote.
+    Bracket {
+        take_profit_percent: f64,
+        stop_loss_percent: f64,
+    },
+}
+
+impl OrderConfig {
+    pub fn is_market(&self) -> bool {
+        matches!(self, OrderConfig::Market)
+    }
+
+    /// Whether the order is rounded to whole shares unless the asset allows fractions.
+    pub fn is_for_whole_shares(&self) -> bool {
+        matches!(
+            self,
+            OrderConfig::TrailingStop { .. } | OrderConfig::Bracket { .. }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct Container {
+        order: OrderConfig,
+    }
+
+    #[test]
+    fn default() {
+        assert_eq!(OrderConfig::Market, Default::default())
+    }
+
+    #[test]
+    fn deserialize() {
+        let parse = |toml: &str| toml::from_str::<Container>(toml).unwrap().order;
+        assert_eq!(
+            OrderConfig::Limit {
+                offset_percent: -0.5
+            },
+            parse(r#"order = { type = "limit", offset_percent = -0.5 }"#)
+        );
+        assert_eq!(
+            OrderConfig::Limit {
+                offset_percent: 0.0
+            },
+            parse(r#"order = { type = "limit" }"#)
+        );
+        assert_eq!(
+            OrderConfig::TrailingStop { trail_percent: 5.0 },
+            parse(r#"order = { type = "trailing_stop", trail_percent = 5 }"#)
+        );
+        assert_eq!(
+            OrderConfig::Bracket {
+                take_profit_percent: 10.0,
+                stop_loss_percent: 5.0
+            },
+            parse(
+                r#"order = { type = "bracket", take_profit_percent = 10, stop_loss_percent = 5 }"#
+            )
+        );
+    }
+
+    #[test]
+    fn is_market() {
+        assert!(OrderConfig::Market.is_market());
+        assert!(!OrderConfig::Stop {
+            offset_percent: -2.0
+        }
+        .is_market());
+    }
+}
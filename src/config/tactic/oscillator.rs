@@ -0,0 +1,23 @@
+pub fn default_rsi_period() -> usize {
+    14
+}
+
+pub fn default_band_period() -> usize {
+    20
+}
+
+pub fn default_band_stddev() -> f64 {
+    2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults() {
+        assert_eq!(14, default_rsi_period());
+        assert_eq!(20, default_band_period());
+        assert_eq!(2.0, default_band_stddev());
+    }
+}
@@ -19,6 +19,26 @@ impl RuleConfig {
     pub fn assets(&self) -> Vec<AssetSymbol> {
         self.for_config.assets()
     }
+
+    pub fn should_fetch_order_history(&self) -> bool {
+        self.when_config.should_fetch_order_history()
+    }
+
+    pub fn should_fetch_market_day(&self) -> bool {
+        self.when_config.should_fetch_market_day()
+    }
+
+    pub fn should_fetch_bars(&self) -> bool {
+        self.when_config.should_fetch_bars()
+    }
+
+    pub fn should_fetch_one_year(&self) -> bool {
+        self.when_config.should_fetch_one_year()
+    }
+
+    pub fn should_fetch_asset_info(&self) -> bool {
+        self.do_config.should_fetch_asset_info()
+    }
 }
 
 impl QuoteFetcherConfig for RuleConfig {
@@ -61,7 +81,10 @@ mod tests {
     #[test]
     fn should_fetch_quotes_when_buy() {
         let config = RuleConfig {
-            do_config: DoConfig::Buy { buy_percent: 0.5 },
+            do_config: DoConfig::Buy {
+                buy_percent: 0.5,
+                order: Default::default(),
+            },
             ..Default::default()
         };
         assert!(config.should_fetch_quotes());
@@ -70,7 +93,10 @@ mod tests {
     #[test]
     fn should_fetch_quotes_when_sell_all() {
         let config = RuleConfig {
-            do_config: DoConfig::SellAll { sell_all: true },
+            do_config: DoConfig::SellAll {
+                sell_all: true,
+                order: Default::default(),
+            },
             ..Default::default()
         };
         assert!(!config.should_fetch_quotes());
@@ -80,7 +106,10 @@ mod tests {
     fn should_fetch_quotes_when_always() {
         let config = RuleConfig {
             when_config: WhenConfig::Always { always: true },
-            do_config: DoConfig::SellAll { sell_all: true },
+            do_config: DoConfig::SellAll {
+                sell_all: true,
+                order: Default::default(),
+            },
             ..Default::default()
         };
         assert!(!config.should_fetch_quotes());
@@ -0,0 +1,22 @@
+use crate::lowercase_enum_display;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SchedulePeriod {
+    Day,
+    Week,
+    Month,
+}
+
+lowercase_enum_display!(SchedulePeriod);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!("week", SchedulePeriod::Week.to_string())
+    }
+}
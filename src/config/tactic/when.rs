@@ -1,12 +1,36 @@
 use crate::config::quote_fetcher_config::QuoteFetcherConfig;
 use crate::config::tactic::median::MedianPeriod;
+use crate::config::tactic::moving_average::{
+    default_fast_period, default_slow_period, MovingAverage,
+};
+use crate::config::tactic::oscillator::{
+    default_band_period, default_band_stddev, default_rsi_period,
+};
+use crate::config::tactic::schedule::SchedulePeriod;
+use chrono::{NaiveTime, Weekday};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum WhenConfig {
+    AboveMovingAverage {
+        above_moving_average_days: usize,
+        #[serde(default)]
+        moving_average: MovingAverage,
+    },
+    AboveUpperBand {
+        above_upper_band: bool,
+        #[serde(default = "default_band_period")]
+        band_period: usize,
+        #[serde(default = "default_band_stddev")]
+        band_stddev: f64,
+    },
     AllOf {
-        all_off: Vec<WhenConfig>,
+        #[serde(alias = "all_off")]
+        all_of: Vec<WhenConfig>,
+    },
+    AnyOf {
+        any_of: Vec<WhenConfig>,
     },
     Always {
         always: bool,
@@ -16,12 +40,157 @@ pub enum WhenConfig {
         #[serde(default)]
         median_period: MedianPeriod,
     },
+    BelowLowerBand {
+        below_lower_band: bool,
+        #[serde(default = "default_band_period")]
+        band_period: usize,
+        #[serde(default = "default_band_stddev")]
+        band_stddev: f64,
+    },
+    BelowMovingAverage {
+        below_moving_average_days: usize,
+        #[serde(default)]
+        moving_average: MovingAverage,
+    },
+    Between {
+        between: (NaiveTime, NaiveTime),
+    },
+    Cooldown {
+        cooldown_hours: f64,
+    },
+    DeathCross {
+        death_cross: bool,
+        #[serde(default = "default_fast_period")]
+        fast_period: usize,
+        #[serde(default = "default_slow_period")]
+        slow_period: usize,
+        #[serde(default)]
+        moving_average: MovingAverage,
+    },
+    DrawdownFromHigh {
+        drawdown_from_high_percent: f64,
+    },
+    Every {
+        every: SchedulePeriod,
+    },
     GainAbove {
         gain_above_percent: f64,
     },
+    GoldenCross {
+        golden_cross: bool,
+        #[serde(default = "default_fast_period")]
+        fast_period: usize,
+        #[serde(default = "default_slow_period")]
+        slow_period: usize,
+        #[serde(default)]
+        moving_average: MovingAverage,
+    },
+    HeldAtLeast {
+        held_at_least_days: f64,
+    },
+    LossBelow {
+        loss_below_percent: f64,
+    },
+    MinutesAfterOpen {
+        minutes_after_open: i64,
+    },
+    MinutesBeforeClose {
+        minutes_before_close: i64,
+    },
     Never {
         never: bool,
     },
+    Not {
+        not: Box<WhenConfig>,
+    },
+    OnDayOfMonth {
+        on_day_of_month: u32,
+    },
+    RsiAbove {
+        rsi_above: f64,
+        #[serde(default = "default_rsi_period")]
+        rsi_period: usize,
+    },
+    RsiBelow {
+        rsi_below: f64,
+        #[serde(default = "default_rsi_period")]
+        rsi_period: usize,
+    },
+    Weekdays {
+        weekdays: Vec<Weekday>,
+    },
+}
+
+impl WhenConfig {
+    /// Whether the rule needs recent orders, such as to know when each position was opened.
+    pub fn should_fetch_order_history(&self) -> bool {
+        match self {
+            WhenConfig::AllOf { all_of } => all_of
+                .iter()
+                .any(|config| config.should_fetch_order_history()),
+            WhenConfig::AnyOf { any_of } => any_of
+                .iter()
+                .any(|config| config.should_fetch_order_history()),
+            WhenConfig::Not { not } => not.should_fetch_order_history(),
+            WhenConfig::Cooldown { .. } => true,
+            WhenConfig::DrawdownFromHigh { .. } => true,
+            WhenConfig::Every { .. } => true,
+            WhenConfig::HeldAtLeast { .. } => true,
+            WhenConfig::OnDayOfMonth { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Whether the rule reads bars, such as to compare the price against a median or indicator.
+    pub fn should_fetch_bars(&self) -> bool {
+        match self {
+            WhenConfig::AllOf { all_of } => all_of.iter().any(|config| config.should_fetch_bars()),
+            WhenConfig::AnyOf { any_of } => any_of.iter().any(|config| config.should_fetch_bars()),
+            WhenConfig::Not { not } => not.should_fetch_bars(),
+            WhenConfig::BelowMedian { .. } => true,
+            _ => self.should_fetch_one_year(),
+        }
+    }
+
+    /// Whether the rule needs the last year of daily bars, which the indicators are computed
+    /// over. The medians only need the last month.
+    pub fn should_fetch_one_year(&self) -> bool {
+        match self {
+            WhenConfig::AllOf { all_of } => {
+                all_of.iter().any(|config| config.should_fetch_one_year())
+            }
+            WhenConfig::AnyOf { any_of } => {
+                any_of.iter().any(|config| config.should_fetch_one_year())
+            }
+            WhenConfig::Not { not } => not.should_fetch_one_year(),
+            WhenConfig::AboveMovingAverage { .. } => true,
+            WhenConfig::AboveUpperBand { .. } => true,
+            WhenConfig::BelowLowerBand { .. } => true,
+            WhenConfig::BelowMovingAverage { .. } => true,
+            WhenConfig::DeathCross { .. } => true,
+            WhenConfig::DrawdownFromHigh { .. } => true,
+            WhenConfig::GoldenCross { .. } => true,
+            WhenConfig::RsiAbove { .. } => true,
+            WhenConfig::RsiBelow { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Whether the rule needs today's trading session.
+    pub fn should_fetch_market_day(&self) -> bool {
+        match self {
+            WhenConfig::AllOf { all_of } => {
+                all_of.iter().any(|config| config.should_fetch_market_day())
+            }
+            WhenConfig::AnyOf { any_of } => {
+                any_of.iter().any(|config| config.should_fetch_market_day())
+            }
+            WhenConfig::Not { not } => not.should_fetch_market_day(),
+            WhenConfig::MinutesAfterOpen { .. } => true,
+            WhenConfig::MinutesBeforeClose { .. } => true,
+            _ => false,
+        }
+    }
 }
 
 impl Default for WhenConfig {
@@ -33,13 +202,35 @@ impl Default for WhenConfig {
 impl QuoteFetcherConfig for WhenConfig {
     fn should_fetch_quotes(&self) -> bool {
         match self {
-            WhenConfig::AllOf { all_off } => {
-                all_off.iter().any(|config| config.should_fetch_quotes())
+            WhenConfig::AboveMovingAverage { .. } => true,
+            WhenConfig::AboveUpperBand { .. } => true,
+            WhenConfig::AllOf { all_of } => {
+                all_of.iter().any(|config| config.should_fetch_quotes())
+            }
+            WhenConfig::AnyOf { any_of } => {
+                any_of.iter().any(|config| config.should_fetch_quotes())
             }
             WhenConfig::Always { .. } => false,
             WhenConfig::BelowMedian { .. } => true,
+            WhenConfig::BelowLowerBand { .. } => true,
+            WhenConfig::Between { .. } => false,
+            WhenConfig::BelowMovingAverage { .. } => true,
+            WhenConfig::Cooldown { .. } => false,
+            WhenConfig::DeathCross { .. } => false,
+            WhenConfig::DrawdownFromHigh { .. } => true,
+            WhenConfig::Every { .. } => false,
             WhenConfig::GainAbove { .. } => false,
+            WhenConfig::GoldenCross { .. } => false,
+            WhenConfig::HeldAtLeast { .. } => false,
+            WhenConfig::LossBelow { .. } => false,
+            WhenConfig::MinutesAfterOpen { .. } => false,
+            WhenConfig::MinutesBeforeClose { .. } => false,
             WhenConfig::Never { .. } => false,
+            WhenConfig::Not { not } => not.should_fetch_quotes(),
+            WhenConfig::OnDayOfMonth { .. } => false,
+            WhenConfig::RsiAbove { .. } => false,
+            WhenConfig::RsiBelow { .. } => false,
+            WhenConfig::Weekdays { .. } => false,
         }
     }
 }
@@ -53,10 +244,152 @@ mod tests {
         assert_eq!(WhenConfig::Always { always: true }, Default::default())
     }
 
+    #[test]
+    fn deserialize_golden_cross_defaults() {
+        let config: WhenConfig = toml::from_str("golden_cross = true").unwrap();
+        let expected = WhenConfig::GoldenCross {
+            golden_cross: true,
+            fast_period: 50,
+            slow_period: 200,
+            moving_average: MovingAverage::Sma,
+        };
+        assert_eq!(expected, config);
+    }
+
+    #[test]
+    fn deserialize_below_moving_average() {
+        let config: WhenConfig =
+            toml::from_str("below_moving_average_days = 20\nmoving_average = \"ema\"").unwrap();
+        let expected = WhenConfig::BelowMovingAverage {
+            below_moving_average_days: 20,
+            moving_average: MovingAverage::Ema,
+        };
+        assert_eq!(expected, config);
+    }
+
+    #[test]
+    fn deserialize_all_of() {
+        let config: WhenConfig =
+            toml::from_str("all_of = [{ always = true }, { never = true }]").unwrap();
+        let expected = WhenConfig::AllOf {
+            all_of: vec![
+                WhenConfig::Always { always: true },
+                WhenConfig::Never { never: true },
+            ],
+        };
+        assert_eq!(expected, config);
+    }
+
+    #[test]
+    fn deserialize_all_off_typo() {
+        let config: WhenConfig = toml::from_str("all_off = [{ always = true }]").unwrap();
+        let expected = WhenConfig::AllOf {
+            all_of: vec![WhenConfig::Always { always: true }],
+        };
+        assert_eq!(expected, config);
+    }
+
+    #[test]
+    fn deserialize_nested_groups() {
+        let toml = r#"
+            all_of = [
+                { gain_above_percent = 5.0 },
+                { not = { any_of = [{ below_median_percent = 2.0 }, { rsi_below = 30 }] } },
+            ]
+        "#;
+        let config: WhenConfig = toml::from_str(toml).unwrap();
+        let expected = WhenConfig::AllOf {
+            all_of: vec![
+                WhenConfig::GainAbove {
+                    gain_above_percent: 5.0,
+                },
+                WhenConfig::Not {
+                    not: Box::new(WhenConfig::AnyOf {
+                        any_of: vec![
+                            WhenConfig::BelowMedian {
+                                below_median_percent: 2.0,
+                                median_period: MedianPeriod::Month,
+                            },
+                            WhenConfig::RsiBelow {
+                                rsi_below: 30.0,
+                                rsi_period: 14,
+                            },
+                        ],
+                    }),
+                },
+            ],
+        };
+        assert_eq!(expected, config);
+    }
+
+    #[test]
+    fn deserialize_between() {
+        let config: WhenConfig = toml::from_str(r#"between = ["10:00", "15:30"]"#).unwrap();
+        let expected = WhenConfig::Between {
+            between: (
+                NaiveTime::from_hms_opt(10, 0, 0).unwrap(),
+                NaiveTime::from_hms_opt(15, 30, 0).unwrap(),
+            ),
+        };
+        assert_eq!(expected, config);
+    }
+
+    #[test]
+    fn deserialize_weekdays() {
+        let config: WhenConfig = toml::from_str(r#"weekdays = ["mon", "wed"]"#).unwrap();
+        let expected = WhenConfig::Weekdays {
+            weekdays: vec![Weekday::Mon, Weekday::Wed],
+        };
+        assert_eq!(expected, config);
+    }
+
+    #[test]
+    fn deserialize_schedules() {
+        let every: WhenConfig = toml::from_str(r#"every = "week""#).unwrap();
+        let on_day: WhenConfig = toml::from_str("on_day_of_month = 1").unwrap();
+        assert_eq!(
+            WhenConfig::Every {
+                every: SchedulePeriod::Week
+            },
+            every
+        );
+        assert_eq!(WhenConfig::OnDayOfMonth { on_day_of_month: 1 }, on_day);
+    }
+
+    #[test]
+    fn deserialize_minutes_after_open() {
+        let config: WhenConfig = toml::from_str("minutes_after_open = 30").unwrap();
+        let expected = WhenConfig::MinutesAfterOpen {
+            minutes_after_open: 30,
+        };
+        assert_eq!(expected, config);
+    }
+
+    #[test]
+    fn deserialize_rsi_below() {
+        let config: WhenConfig = toml::from_str("rsi_below = 30\nrsi_period = 7").unwrap();
+        let expected = WhenConfig::RsiBelow {
+            rsi_below: 30.0,
+            rsi_period: 7,
+        };
+        assert_eq!(expected, config);
+    }
+
+    #[test]
+    fn deserialize_below_lower_band_defaults() {
+        let config: WhenConfig = toml::from_str("below_lower_band = true").unwrap();
+        let expected = WhenConfig::BelowLowerBand {
+            below_lower_band: true,
+            band_period: 20,
+            band_stddev: 2.0,
+        };
+        assert_eq!(expected, config);
+    }
+
     #[test]
     fn should_fetch_quotes_all_of() {
         let all_of = WhenConfig::AllOf {
-            all_off: vec![
+            all_of: vec![
                 WhenConfig::Always { always: true },
                 WhenConfig::BelowMedian {
                     below_median_percent: 10.0,
@@ -70,7 +403,7 @@ mod tests {
     #[test]
     fn should_fetch_quotes_all_of_without_quotes() {
         let all_of = WhenConfig::AllOf {
-            all_off: vec![
+            all_of: vec![
                 WhenConfig::Always { always: true },
                 WhenConfig::GainAbove {
                     gain_above_percent: 10.0,
@@ -81,6 +414,38 @@ mod tests {
         assert!(!all_of.should_fetch_quotes());
     }
 
+    #[test]
+    fn should_fetch_quotes_any_of() {
+        let any_of = WhenConfig::AnyOf {
+            any_of: vec![
+                WhenConfig::Never { never: true },
+                WhenConfig::BelowLowerBand {
+                    below_lower_band: true,
+                    band_period: 20,
+                    band_stddev: 2.0,
+                },
+            ],
+        };
+        assert!(any_of.should_fetch_quotes());
+    }
+
+    #[test]
+    fn should_fetch_quotes_not() {
+        let not = WhenConfig::Not {
+            not: Box::new(WhenConfig::GainAbove {
+                gain_above_percent: 5.0,
+            }),
+        };
+        let not_below_median = WhenConfig::Not {
+            not: Box::new(WhenConfig::BelowMedian {
+                below_median_percent: 5.0,
+                median_period: MedianPeriod::Week,
+            }),
+        };
+        assert!(!not.should_fetch_quotes());
+        assert!(not_below_median.should_fetch_quotes());
+    }
+
     #[test]
     fn should_fetch_quotes_always() {
         let always = WhenConfig::Always { always: true };
@@ -96,6 +461,133 @@ mod tests {
         assert!(below_median.should_fetch_quotes());
     }
 
+    #[test]
+    fn should_fetch_quotes_moving_average() {
+        let above = WhenConfig::AboveMovingAverage {
+            above_moving_average_days: 200,
+            moving_average: MovingAverage::Sma,
+        };
+        let golden_cross = WhenConfig::GoldenCross {
+            golden_cross: true,
+            fast_period: 50,
+            slow_period: 200,
+            moving_average: MovingAverage::Ema,
+        };
+        assert!(above.should_fetch_quotes());
+        // Crossovers only compare averages of the bars.
+        assert!(!golden_cross.should_fetch_quotes());
+        assert!(golden_cross.should_fetch_bars());
+    }
+
+    #[test]
+    fn should_fetch_quotes_oscillators() {
+        let rsi_above = WhenConfig::RsiAbove {
+            rsi_above: 70.0,
+            rsi_period: 14,
+        };
+        let below_lower_band = WhenConfig::BelowLowerBand {
+            below_lower_band: true,
+            band_period: 20,
+            band_stddev: 2.0,
+        };
+        // The RSI is worked out from the bars alone, the bands are compared with the ask.
+        assert!(!rsi_above.should_fetch_quotes());
+        assert!(rsi_above.should_fetch_bars());
+        assert!(below_lower_band.should_fetch_quotes());
+    }
+
+    #[test]
+    fn should_fetch_quotes_stop_loss() {
+        let loss_below = WhenConfig::LossBelow {
+            loss_below_percent: 8.0,
+        };
+        let drawdown = WhenConfig::DrawdownFromHigh {
+            drawdown_from_high_percent: 10.0,
+        };
+        assert!(!loss_below.should_fetch_quotes());
+        assert!(drawdown.should_fetch_quotes());
+    }
+
+    #[test]
+    fn should_fetch_market_day() {
+        let after_open = WhenConfig::MinutesAfterOpen {
+            minutes_after_open: 30,
+        };
+        let not_before_close = WhenConfig::Not {
+            not: Box::new(WhenConfig::MinutesBeforeClose {
+                minutes_before_close: 15,
+            }),
+        };
+        let weekdays = WhenConfig::Weekdays {
+            weekdays: vec![Weekday::Fri],
+        };
+        assert!(after_open.should_fetch_market_day());
+        assert!(not_before_close.should_fetch_market_day());
+        assert!(!weekdays.should_fetch_market_day());
+        assert!(!after_open.should_fetch_quotes());
+    }
+
+    #[test]
+    fn should_fetch_order_history() {
+        let drawdown = WhenConfig::DrawdownFromHigh {
+            drawdown_from_high_percent: 10.0,
+        };
+        let all_of = WhenConfig::AllOf {
+            all_of: vec![WhenConfig::Always { always: true }, drawdown.clone()],
+        };
+        let loss_below = WhenConfig::LossBelow {
+            loss_below_percent: 8.0,
+        };
+        assert!(drawdown.should_fetch_order_history());
+        assert!(WhenConfig::Cooldown {
+            cooldown_hours: 24.0
+        }
+        .should_fetch_order_history());
+        assert!(WhenConfig::HeldAtLeast {
+            held_at_least_days: 30.0
+        }
+        .should_fetch_order_history());
+        assert!(WhenConfig::Every {
+            every: SchedulePeriod::Month
+        }
+        .should_fetch_order_history());
+        assert!(WhenConfig::OnDayOfMonth {
+            on_day_of_month: 15
+        }
+        .should_fetch_order_history());
+        assert!(all_of.should_fetch_order_history());
+        assert!(!loss_below.should_fetch_order_history());
+    }
+
+    #[test]
+    fn should_fetch_bars() {
+        let below_median = WhenConfig::BelowMedian {
+            below_median_percent: 1.0,
+            median_period: Default::default(),
+        };
+        let rsi_below = WhenConfig::RsiBelow {
+            rsi_below: 30.0,
+            rsi_period: 14,
+        };
+        let not = WhenConfig::Not {
+            not: Box::new(rsi_below.clone()),
+        };
+        let any_of = WhenConfig::AnyOf {
+            any_of: vec![WhenConfig::Always { always: true }, below_median.clone()],
+        };
+        let gain_above = WhenConfig::GainAbove {
+            gain_above_percent: 5.0,
+        };
+        assert!(below_median.should_fetch_bars());
+        assert!(!below_median.should_fetch_one_year());
+        assert!(rsi_below.should_fetch_bars());
+        assert!(rsi_below.should_fetch_one_year());
+        assert!(not.should_fetch_one_year());
+        assert!(any_of.should_fetch_bars());
+        assert!(!any_of.should_fetch_one_year());
+        assert!(!gain_above.should_fetch_bars());
+    }
+
     #[test]
     fn should_fetch_quotes_gain_above() {
         let gain_above = WhenConfig::GainAbove {
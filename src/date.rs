@@ -29,6 +29,7 @@ use chrono::Local;
 pub struct DateTimeFixture {}
 
 #[cfg(test)]
+#[allow(clippy::zero_prefixed_literal)]
 impl DateTimeFixture {
     pub fn local() -> DateTime<Local> {
         Local
@@ -1,7 +1,8 @@
+use serde::{Deserialize, Serialize};
 use std::env::VarError;
 use std::fmt::{Debug, Display, Formatter};
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct GreedError {
     message: String,
 }
@@ -45,6 +46,7 @@ macro_rules! greed_error_from {
 }
 
 greed_error_from!(csv::Error);
+greed_error_from!(serde_json::Error);
 greed_error_from!(std::io::Error);
 greed_error_from!(toml::de::Error);
 greed_error_from!(VarError);
@@ -1,3 +1,11 @@
+use crate::asset::AssetSymbol;
+
+/// Decimal places kept on equity order quantities.
+pub const QUANTITY_PRECISION: i32 = 7;
+/// Decimal places kept on crypto order quantities. Alpaca accepts more, but order amounts are
+/// sent as decimals with at most 8 places.
+pub const CRYPTO_QUANTITY_PRECISION: i32 = 8;
+
 pub trait PrecisionFloor {
     fn floor_with(&self, precision: i32) -> Self;
 }
@@ -12,6 +20,8 @@ impl PrecisionFloor for f64 {
 pub trait FloatAmountRounding {
     fn round_for_notional(&self) -> Self;
     fn round_for_quantity(&self) -> Self;
+    /// Floors a quantity to the decimal places the asset trades in.
+    fn round_quantity_for(&self, symbol: &AssetSymbol) -> Self;
 }
 
 impl FloatAmountRounding for f64 {
@@ -20,7 +30,11 @@ impl FloatAmountRounding for f64 {
     }
 
     fn round_for_quantity(&self) -> Self {
-        self.floor_with(7)
+        self.floor_with(QUANTITY_PRECISION)
+    }
+
+    fn round_quantity_for(&self, symbol: &AssetSymbol) -> Self {
+        self.floor_with(symbol.quantity_precision())
     }
 }
 
@@ -64,6 +78,18 @@ mod tests {
         assert_relative_eq!(expected, floored, max_relative = 0.001)
     }
 
+    #[test]
+    fn round_quantity_for_equity() {
+        let quantity = 0.123456789.round_quantity_for(&AssetSymbol::new("VTI"));
+        assert_relative_eq!(0.1234567, quantity, max_relative = 0.000_000_001)
+    }
+
+    #[test]
+    fn round_quantity_for_crypto() {
+        let quantity = 0.123456789.round_quantity_for(&AssetSymbol::new("BTC/USD"));
+        assert_relative_eq!(0.12345678, quantity, max_relative = 0.000_000_001)
+    }
+
     #[test]
     fn percent_of_percent_is_fraction() {
         let num = 10.0;
@@ -0,0 +1,330 @@
+use crate::error::GreedError;
+use crate::jsonl::read_jsonl;
+use crate::platform::id::Id;
+use crate::platform::order::status::Status;
+use crate::platform::order::Order;
+use crate::platform::request::OrderRequest;
+use crate::platform::FinancialPlatform;
+use chrono::{DateTime, Utc};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// The strategy, tactic and rule which produced an order, along with the inputs they acted on.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct OrderSource {
+    pub strategy: String,
+    pub tactic: String,
+    /// The rule set for tactics ("buy" or "sell") or the tool name for agents.
+    pub rule: String,
+    pub inputs: Vec<String>,
+}
+
+/// A single line of the JSONL trade journal.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+#[allow(clippy::large_enum_variant)]
+pub enum JournalEntry {
+    OrderPlaced {
+        time: DateTime<Utc>,
+        source: OrderSource,
+        request: OrderRequest,
+        response: Result<Order, GreedError>,
+    },
+    /// A journaled order changed status, typically because it was filled.
+    OrderUpdated { time: DateTime<Utc>, order: Order },
+}
+
+impl JournalEntry {
+    pub fn time(&self) -> DateTime<Utc> {
+        match self {
+            JournalEntry::OrderPlaced { time, .. } | JournalEntry::OrderUpdated { time, .. } => {
+                *time
+            }
+        }
+    }
+}
+
+/// An append only audit trail tying each order back to the config which caused it.
+pub struct Journal {
+    file: Option<Mutex<File>>,
+    /// Orders placed by this process which may still change, by the status they were last
+    /// journaled with.
+    pending: Mutex<HashMap<Id, Status>>,
+}
+
+impl Journal {
+    /// Opens the journal for appending. Orders journaled by an earlier run which hadn't reached a
+    /// final status are picked up again, so their fills still get journaled after a restart.
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, GreedError> {
+        let pending = if path.as_ref().exists() {
+            Self::pending_orders(&read_journal(&path)?)
+        } else {
+            HashMap::new()
+        };
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            file: Some(Mutex::new(file)),
+            pending: Mutex::new(pending),
+        })
+    }
+
+    /// The orders which were still open as of their last entry, by that entry's status.
+    fn pending_orders(entries: &[JournalEntry]) -> HashMap<Id, Status> {
+        let mut pending = HashMap::new();
+        for entry in entries {
+            let order = match entry {
+                JournalEntry::OrderPlaced {
+                    response: Ok(order),
+                    ..
+                } => order,
+                JournalEntry::OrderUpdated { order, .. } => order,
+                JournalEntry::OrderPlaced { .. } => continue,
+            };
+            if order.status.is_terminal() {
+                pending.remove(&order.id);
+            } else {
+                pending.insert(order.id.clone(), order.status);
+            }
+        }
+        pending
+    }
+
+    /// A journal which doesn't write anything.
+    pub fn disabled() -> Arc<Self> {
+        Arc::new(Self {
+            file: None,
+            pending: Mutex::new(HashMap::new()),
+        })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.file.is_some()
+    }
+
+    pub fn record_order(
+        &self,
+        time: DateTime<Utc>,
+        source: OrderSource,
+        request: OrderRequest,
+        response: &Result<Order, GreedError>,
+    ) {
+        if self.file.is_none() {
+            return;
+        }
+        if let Ok(order) = response {
+            if !order.status.is_terminal() {
+                let mut pending = self.pending.lock().unwrap();
+                pending.insert(order.id.clone(), order.status);
+            }
+        }
+        self.record(JournalEntry::OrderPlaced {
+            time,
+            source,
+            request,
+            response: response.clone(),
+        })
+    }
+
+    /// Journals any status changes to orders which were still open when they were last recorded.
+    pub async fn sync_orders(&self, platform: &dyn FinancialPlatform) {
+        if self.pending.lock().unwrap().is_empty() {
+            return;
+        }
+        match platform.recent_orders().await {
+            Ok(orders) => self.update_orders(platform.now(), &orders),
+            Err(e) => warn!("error fetching orders for the journal: {e}"),
+        }
+    }
+
+    fn update_orders(&self, time: DateTime<Utc>, orders: &[Order]) {
+        let mut changed = Vec::new();
+        {
+            let mut pending = self.pending.lock().unwrap();
+            for order in orders {
+                let Some(status) = pending.get(&order.id) else {
+                    continue;
+                };
+                if *status == order.status {
+                    continue;
+                }
+                if order.status.is_terminal() {
+                    pending.remove(&order.id);
+                } else {
+                    pending.insert(order.id.clone(), order.status);
+                }
+                changed.push(order.clone());
+            }
+        }
+        for order in changed {
+            self.record(JournalEntry::OrderUpdated { time, order })
+        }
+    }
+
+    fn record(&self, entry: JournalEntry) {
+        let Some(file) = &self.file else {
+            return;
+        };
+        // A journal failure shouldn't interrupt trading, so we only warn about it.
+        let result = serde_json::to_string(&entry)
+            .map_err(GreedError::from)
+            .and_then(|json| {
+                let mut file = file.lock().unwrap();
+                writeln!(file, "{json}")?;
+                Ok(())
+            });
+        if let Err(e) = result {
+            warn!("failed to write journal entry: {e}")
+        }
+    }
+}
+
+/// Reads every entry from a JSONL journal, skipping blank lines.
+pub fn read_journal<P: AsRef<Path>>(path: P) -> Result<Vec<JournalEntry>, GreedError> {
+    read_jsonl(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asset::AssetSymbol;
+    use crate::date::DateTimeFixture;
+    use crate::platform::order::amount::Amount;
+    use crate::platform::MockPlatform;
+    use std::env;
+    use std::fs;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn records_orders_and_fills() {
+        let path = journal_path("fills");
+        let journal = Journal::new(&path).unwrap();
+        let placed = Order {
+            id: Id::Uuid(Uuid::from_u128(1)),
+            status: Status::New,
+            ..Order::fixture(AssetSymbol::new("VTI"))
+        };
+        let filled = Order {
+            status: Status::Filled,
+            ..placed.clone()
+        };
+        journal.record_order(
+            DateTimeFixture::utc(),
+            source(),
+            request(),
+            &Ok(placed.clone()),
+        );
+
+        let platform = MockPlatform::new().with_recent_orders(vec![filled.clone()]);
+        journal.sync_orders(&platform).await;
+        journal.sync_orders(&platform).await;
+
+        let entries = read_journal(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(
+            entries[0],
+            JournalEntry::OrderPlaced {
+                time: DateTimeFixture::utc(),
+                source: source(),
+                request: request(),
+                response: Ok(placed),
+            }
+        );
+        assert!(matches!(
+            &entries[1],
+            JournalEntry::OrderUpdated { order, .. } if *order == filled
+        ));
+    }
+
+    #[tokio::test]
+    async fn resumes_pending_orders_after_restart() {
+        let path = journal_path("restart");
+        let placed = Order {
+            id: Id::Uuid(Uuid::from_u128(1)),
+            status: Status::New,
+            ..Order::fixture(AssetSymbol::new("VTI"))
+        };
+        let done = Order {
+            id: Id::Uuid(Uuid::from_u128(2)),
+            status: Status::Filled,
+            ..placed.clone()
+        };
+        {
+            let journal = Journal::new(&path).unwrap();
+            journal.record_order(
+                DateTimeFixture::utc(),
+                source(),
+                request(),
+                &Ok(placed.clone()),
+            );
+            journal.record_order(DateTimeFixture::utc(), source(), request(), &Ok(done));
+        }
+
+        let journal = Journal::new(&path).unwrap();
+        let filled = Order {
+            status: Status::Filled,
+            ..placed.clone()
+        };
+        let platform = MockPlatform::new().with_recent_orders(vec![filled.clone()]);
+        journal.sync_orders(&platform).await;
+
+        let entries = read_journal(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert!(matches!(
+            &entries[2],
+            JournalEntry::OrderUpdated { order, .. } if *order == filled
+        ));
+        assert!(journal.pending.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn records_rejected_orders() {
+        let path = journal_path("rejected");
+        let journal = Journal::new(&path).unwrap();
+        let response = Err(GreedError::new("rejected"));
+        journal.record_order(DateTimeFixture::utc(), source(), request(), &response);
+
+        let entries = read_journal(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            &entries[0],
+            JournalEntry::OrderPlaced {
+                response: Err(_),
+                ..
+            }
+        ));
+        assert!(journal.pending.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn disabled_journal_tracks_nothing() {
+        let journal = Journal::disabled();
+        assert!(!journal.is_enabled());
+        let order = Order::fixture(AssetSymbol::new("VTI"));
+        journal.record_order(DateTimeFixture::utc(), source(), request(), &Ok(order));
+        assert!(journal.pending.lock().unwrap().is_empty());
+    }
+
+    fn source() -> OrderSource {
+        OrderSource {
+            strategy: "strategy".to_string(),
+            tactic: "tactic".to_string(),
+            rule: "buy".to_string(),
+            inputs: vec!["always".to_string()],
+        }
+    }
+
+    fn request() -> OrderRequest {
+        OrderRequest::market_order_buy(AssetSymbol::new("VTI"), Amount::Notional(10.0))
+    }
+
+    fn journal_path(name: &str) -> std::path::PathBuf {
+        env::temp_dir().join(format!("greed_journal_{name}_{}.jsonl", std::process::id()))
+    }
+}
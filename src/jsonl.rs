@@ -0,0 +1,35 @@
+use crate::error::GreedError;
+use serde::de::DeserializeOwned;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// Reads every line of a JSONL file, skipping blank lines.
+pub fn read_jsonl<T: DeserializeOwned, P: AsRef<Path>>(path: P) -> Result<Vec<T>, GreedError> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut entries = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        entries.push(serde_json::from_str(&line)?);
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn read_jsonl_skips_blank_lines() {
+        let path = env::temp_dir().join(format!("greed_jsonl_{}.jsonl", std::process::id()));
+        fs::write(&path, "1\n\n  \n2\n").unwrap();
+        let values: Vec<u32> = read_jsonl(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(values, vec![1, 2]);
+    }
+}
@@ -1,13 +1,21 @@
 use crate::analysis::AssetAnalyzer;
 use crate::asset::AssetSymbol;
+use crate::backtest::{BacktestArgs, Backtester};
 use crate::config::platform::PlatformType;
 use crate::error::GreedError;
 use crate::platform::args::PlatformArgs;
+use crate::platform::id::Id;
+use crate::report::{PerformanceReport, ReportArgs, ReportFormat};
 use crate::run::{GreedRunner, GreedRunnerArgs};
+use crate::strategy::reader::{read_all_tactics, read_config_from_path};
+use crate::tactic::TacticRunner;
+use itertools::Itertools;
+use std::path::{Path, PathBuf};
 
 mod analysis;
 mod assert;
 pub mod asset;
+pub mod backtest;
 mod bool;
 pub mod config;
 mod date;
@@ -15,9 +23,12 @@ mod enum_display;
 pub mod error;
 mod fixture;
 mod float;
+pub mod journal;
+mod jsonl;
 mod num;
 mod pager;
 pub mod platform;
+pub mod report;
 pub mod run;
 mod statistics;
 mod strategy;
@@ -26,8 +37,57 @@ pub mod template;
 mod trading_days;
 
 pub async fn greed_loop(args: GreedRunnerArgs) -> Result<(), GreedError> {
+    // A cassette only holds so many responses, so replays make a single pass.
+    let is_replay = args.replay_path.is_some();
     let runner = GreedRunner::from_args(args).await?;
-    runner.run_loop().await;
+    if is_replay {
+        runner.run_once().await
+    } else {
+        runner.run_loop().await;
+        Ok(())
+    }
+}
+
+pub async fn run_backtest(
+    args: BacktestArgs,
+    equity_curve_path: Option<PathBuf>,
+) -> Result<(), GreedError> {
+    let backtester = Backtester::from_args(&args).await?;
+    let report = backtester.run().await?;
+    println!("{}", report);
+    if let Some(path) = equity_curve_path {
+        report.write_equity_curve(&path)?;
+        println!("Wrote equity curve to {}", path.display());
+    }
+    Ok(())
+}
+
+/// Evaluates every tactic in the config once and prints why each rule acted or skipped.
+/// Orders are never placed.
+pub async fn explain_config(
+    config_path: &Path,
+    platform_args: PlatformArgs,
+) -> Result<(), GreedError> {
+    let config = read_config_from_path(config_path).await?;
+    let platform = platform::for_type(&config.platform, platform_args)?;
+    let tactics = read_all_tactics(config_path, &config).await?;
+    let config_assets = tactics
+        .iter()
+        .flat_map(|(tactic, _)| tactic.assets())
+        .unique()
+        .collect::<Vec<_>>();
+    for (tactic, properties) in tactics {
+        let runner = TacticRunner::new(tactic, platform.clone(), properties);
+        let explanation = runner.explain(&config_assets).await?;
+        println!("{}", explanation);
+    }
+    Ok(())
+}
+
+/// Prints realized and unrealized profit and loss per strategy and tactic.
+pub async fn report_performance(args: ReportArgs, format: &ReportFormat) -> Result<(), GreedError> {
+    let report = PerformanceReport::from_args(&args).await?;
+    println!("{}", report.render(format)?);
     Ok(())
 }
 
@@ -53,11 +113,49 @@ pub async fn fetch_recent_orders(
     let orders = platform.recent_orders().await?;
     println!("Recent Orders:");
     for order in orders {
-        println!("-- {}", order.display_with_time_zone(&chrono::Local))
+        println!(
+            "-- {} ({})",
+            order.display_with_time_zone(&chrono::Local),
+            order.id
+        )
     }
     Ok(())
 }
 
+/// Cancels the order with `order_id`, or every open order when no id is provided.
+pub async fn cancel_orders(
+    platform_args: PlatformArgs,
+    platform_type: &PlatformType,
+    order_id: Option<Id>,
+) -> Result<(), GreedError> {
+    let platform = platform::for_type(platform_type, platform_args)?;
+    match order_id {
+        Some(id) => {
+            platform.cancel_order(&id).await?;
+            println!("Canceled order {id}");
+        }
+        None => {
+            let orders = platform.cancel_all_orders().await?;
+            println!("Canceled Orders:");
+            for order in orders {
+                println!("-- {} ({})", order, order.id)
+            }
+        }
+    }
+    Ok(())
+}
+
+pub async fn close_position(
+    platform_args: PlatformArgs,
+    platform_type: &PlatformType,
+    symbol: &AssetSymbol,
+) -> Result<(), GreedError> {
+    let platform = platform::for_type(platform_type, platform_args)?;
+    let order = platform.close_position(symbol).await?;
+    println!("Closing {symbol}: {} ({})", order, order.id);
+    Ok(())
+}
+
 pub async fn fetch_status(
     platform_args: PlatformArgs,
     platform_type: &PlatformType,
@@ -67,6 +165,8 @@ pub async fn fetch_status(
     // Fetch account info
     let account = platform.account().await?;
     println!("Account Info: {}", account);
+    let clock = platform.clock().await?;
+    println!("Market: {}", clock);
     // Bail if we're not showing full status
     if !show_full_status {
         return Ok(());
@@ -82,7 +182,7 @@ pub async fn fetch_status(
     println!("Open Orders:");
     let orders = platform.open_orders().await?;
     for order in &orders {
-        println!("-- {}", order);
+        println!("-- {} ({})", order, order.id);
     }
     Ok(())
 }
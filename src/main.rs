@@ -5,7 +5,10 @@ use simplelog::{ColorChoice, CombinedLogger, Config, ConfigBuilder, TermLogger,
 use greed::error::GreedError;
 use greed::platform::args::PlatformArgs;
 use greed::template;
-use greed::{analyze_stocks, fetch_quote, fetch_recent_orders, fetch_status, greed_loop};
+use greed::{
+    analyze_stocks, cancel_orders, close_position, explain_config, fetch_quote,
+    fetch_recent_orders, fetch_status, greed_loop, report_performance, run_backtest,
+};
 
 use crate::cli::{Cli, Command};
 
@@ -39,6 +42,30 @@ async fn async_main(log_config: Config) {
             .await
             .expect("stock analysis failed");
         }
+        Command::Backtest(args) => {
+            run_backtest((&args).into(), args.equity_curve.clone())
+                .await
+                .expect("backtest failed");
+        }
+        Command::Cancel(args) => {
+            cancel_orders(
+                PlatformArgs::from(&args),
+                &args.platform_type,
+                args.order_id(),
+            )
+            .await
+            .expect("order cancellation failed");
+        }
+        Command::Close(args) => {
+            close_position(PlatformArgs::from(&args), &args.platform_type, &args.symbol)
+                .await
+                .expect("position close failed");
+        }
+        Command::Explain(args) => {
+            explain_config(&args.config_path, PlatformArgs::from(&args))
+                .await
+                .expect("explain failed");
+        }
         Command::Orders(args) => {
             fetch_recent_orders(PlatformArgs::from(&args), &args.platform_type)
                 .await
@@ -53,6 +80,11 @@ async fn async_main(log_config: Config) {
             .await
             .expect("quote fetch failed");
         }
+        Command::Report(args) => {
+            report_performance((&args).into(), &args.format)
+                .await
+                .expect("report failed");
+        }
         Command::Run(args) => {
             setup_logging(log_config);
             let result = greed_loop(args.into()).await;
@@ -2,8 +2,14 @@ pub mod account;
 mod alpaca;
 pub mod args;
 pub mod asset_class;
+pub mod asset_filter;
+pub mod asset_info;
 pub mod bar;
 pub mod bars;
+pub mod calendar;
+pub mod cassette;
+pub mod clock;
+pub mod dry_run;
 pub mod id;
 #[cfg(test)]
 mod mock;
@@ -13,8 +19,12 @@ pub use mock::MockPlatform;
 pub mod order;
 pub mod position;
 pub mod quote;
+pub mod record;
+pub mod replay;
 pub mod request;
+pub mod risk_guard;
 pub mod side;
+pub mod simulated;
 
 use crate::asset::AssetSymbol;
 use crate::config::platform::PlatformType;
@@ -22,14 +32,24 @@ use crate::error::GreedError;
 use crate::platform::account::Account;
 use crate::platform::alpaca::AlpacaPlatform;
 use crate::platform::args::PlatformArgs;
+use crate::platform::asset_filter::AssetFilter;
+use crate::platform::asset_info::AssetInfo;
 use crate::platform::bar::bar_request::BarRequest;
 use crate::platform::bars::Bars;
+use crate::platform::calendar::MarketDay;
+use crate::platform::clock::MarketClock;
+use crate::platform::id::Id;
 use crate::platform::order::Order;
 use crate::platform::position::Position;
 use crate::platform::quote::Quote;
+use crate::platform::request::replace::ReplaceRequest;
 use crate::platform::request::OrderRequest;
+use crate::platform::simulated::SimulatedPlatform;
+use crate::trading_days;
 use async_trait::async_trait;
-use log::info;
+use chrono::{DateTime, NaiveDate, Utc};
+use log::{info, warn};
+use std::ops::Range;
 use std::sync::Arc;
 
 #[async_trait]
@@ -41,6 +61,64 @@ pub trait FinancialPlatform: Send + Sync {
     async fn positions(&self) -> Result<Vec<Position>, GreedError>;
     async fn open_orders(&self) -> Result<Vec<Order>, GreedError>;
     async fn recent_orders(&self) -> Result<Vec<Order>, GreedError>;
+    async fn cancel_order(&self, id: &Id) -> Result<(), GreedError>;
+    /// Replaces an open order with an amended copy, returning the new order.
+    async fn replace_order(&self, id: &Id, request: ReplaceRequest) -> Result<Order, GreedError>;
+    /// Sells the entire position for `symbol` with a market order.
+    async fn close_position(&self, symbol: &AssetSymbol) -> Result<Order, GreedError>;
+
+    /// Cancels every open order, returning the orders which were canceled. Orders which fail to
+    /// cancel are logged and skipped.
+    async fn cancel_all_orders(&self) -> Result<Vec<Order>, GreedError> {
+        let mut canceled = Vec::new();
+        for order in self.open_orders().await? {
+            match self.cancel_order(&order.id).await {
+                Ok(_) => canceled.push(order),
+                Err(e) => warn!("failed to cancel order {}: {e}", order.id),
+            }
+        }
+        Ok(canceled)
+    }
+
+    /// Orders created within `range`, newest first. Defaults to filtering `recent_orders`, which
+    /// only covers the latest page of orders.
+    async fn order_history(&self, range: Range<DateTime<Utc>>) -> Result<Vec<Order>, GreedError> {
+        let orders = self.recent_orders().await?;
+        Ok(orders
+            .into_iter()
+            .filter(|o| range.contains(&o.created_at))
+            .collect())
+    }
+
+    /// What the broker knows about `symbol` and allows for trading it. Defaults to a tradable,
+    /// fractionable asset which can't be shorted, for platforms which don't simulate borrowing
+    /// shares.
+    async fn asset(&self, symbol: &AssetSymbol) -> Result<AssetInfo, GreedError> {
+        Ok(AssetInfo::unrestricted(symbol))
+    }
+
+    /// The assets the broker lists which match `filter`. Defaults to none, for platforms without
+    /// an asset listing.
+    async fn assets(&self, _filter: AssetFilter) -> Result<Vec<AssetInfo>, GreedError> {
+        Ok(Vec::new())
+    }
+
+    /// Whether the market is open at `now`. Defaults to the bundled NYSE calendar.
+    async fn clock(&self) -> Result<MarketClock, GreedError> {
+        Ok(trading_days::market_clock(self.now()))
+    }
+
+    /// The trading sessions within `range`, excluding the end date. Defaults to the bundled NYSE
+    /// calendar.
+    async fn calendar(&self, range: Range<NaiveDate>) -> Result<Vec<MarketDay>, GreedError> {
+        Ok(trading_days::market_calendar(range))
+    }
+
+    /// The current time as seen by the platform. Simulated platforms may override this to
+    /// drive analysis from a virtual clock.
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
 }
 
 pub fn for_type(
@@ -48,8 +126,9 @@ pub fn for_type(
     args: PlatformArgs,
 ) -> Result<Arc<dyn FinancialPlatform>, GreedError> {
     info!("🏦 Using financial platform - {:?}", platform_type);
-    let platform = match platform_type {
+    let platform: Arc<dyn FinancialPlatform> = match platform_type {
         PlatformType::Alpaca => Arc::new(AlpacaPlatform::new(&args)?),
+        PlatformType::Simulated => Arc::new(SimulatedPlatform::new()?),
     };
     Ok(platform)
 }
@@ -1,7 +1,8 @@
 use crate::platform::id::Id;
+use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
 
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 pub struct Account {
     /// The account's status.
     pub id: Id,
@@ -22,6 +23,9 @@ pub struct Account {
     pub market_value_short: f64,
     /// The sum of `cash`, `market_value_long`, and `market_value_short`.
     pub equity: f64,
+    /// Equity as of the previous trading day's close, zero when the platform doesn't track it.
+    #[serde(default)]
+    pub last_equity: f64,
 }
 
 impl Account {
@@ -1,52 +1,100 @@
 use apca::api::v2::order::CreateReq;
 use apca::api::v2::orders::{ListReq, Status};
-use apca::api::v2::{account, order, orders, positions};
+use apca::api::v2::{account, calendar, clock, order, orders, position, positions};
 use apca::data::v2::bars::ListReq as BarsReq;
 use apca::data::v2::{bars, last_quotes};
 use apca::Client;
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, NaiveDate, Utc};
 use itertools::Itertools;
 use log::info;
+use std::ops::Range;
 
 use crate::asset::AssetSymbol;
 use crate::error::GreedError;
 use crate::pager;
 use crate::platform::account::Account;
+use crate::platform::alpaca::assets_client::AssetsClient;
+use crate::platform::alpaca::crypto_data::CryptoDataClient;
 use crate::platform::alpaca::factory::create_alpaca_client;
+use crate::platform::alpaca::orders_client::OrdersClient;
 use crate::platform::args::PlatformArgs;
+use crate::platform::asset_filter::AssetFilter;
+use crate::platform::asset_info::AssetInfo;
 use crate::platform::bar::bar_request::BarRequest;
 use crate::platform::bar::time_frame::TimeFrame;
 use crate::platform::bar::Bar;
 use crate::platform::bars::Bars;
+use crate::platform::calendar::MarketDay;
+use crate::platform::clock::MarketClock;
+use crate::platform::id::Id;
 use crate::platform::order::Order;
 use crate::platform::position::Position;
 use crate::platform::quote::Quote;
+use crate::platform::request::replace::ReplaceRequest;
 use crate::platform::request::OrderRequest;
 use crate::platform::FinancialPlatform;
 
+mod assets_client;
 mod convert_account;
 mod convert_asset_class;
+mod convert_asset_info;
 mod convert_bar;
+mod convert_calendar;
+mod convert_clock;
 mod convert_order;
 mod convert_position;
 mod convert_quote;
 mod convert_request;
 mod convert_side;
 mod convert_symbol;
+mod crypto_data;
 mod factory;
+mod http_client;
+mod orders_client;
 
 pub struct AlpacaPlatform {
     client: Client,
+    assets: AssetsClient,
+    crypto_data: CryptoDataClient,
+    orders: OrdersClient,
 }
 
 impl AlpacaPlatform {
     pub fn new(runner_args: &PlatformArgs) -> Result<Self, GreedError> {
+        let client = create_alpaca_client(runner_args.is_simulated)?;
+        let assets = AssetsClient::new(client.api_info());
+        let crypto_data = CryptoDataClient::new(client.api_info());
+        let orders = OrdersClient::new(client.api_info());
         Ok(Self {
-            client: create_alpaca_client(runner_args.is_simulated)?,
+            client,
+            assets,
+            crypto_data,
+            orders,
         })
     }
 
+    async fn stock_quotes(&self, symbols: &[AssetSymbol]) -> Result<Vec<Quote>, GreedError> {
+        if symbols.is_empty() {
+            return Ok(Vec::new());
+        }
+        let symbol_strings = symbols.iter().map(|s| &s.symbol).collect::<Vec<_>>();
+        let latest_req = apca::data::v2::last_quotes::GetReqInit {
+            ..Default::default()
+        }
+        .init(symbol_strings);
+
+        let result = self.client.issue::<last_quotes::Get>(&latest_req).await?;
+        Ok(result.into_iter().map(|q| q.into()).collect())
+    }
+
+    async fn crypto_quotes(&self, symbols: &[AssetSymbol]) -> Result<Vec<Quote>, GreedError> {
+        if symbols.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.crypto_data.latest_quotes(symbols).await
+    }
+
     async fn replace_invalid_quotes(&self, quotes: Vec<Quote>) -> Vec<Quote> {
         let mut valid_quotes = Vec::new();
         for quote in quotes {
@@ -91,6 +139,10 @@ impl FinancialPlatform for AlpacaPlatform {
 
     async fn bars(&self, bars_request: BarRequest) -> Result<Bars, GreedError> {
         let symbol = bars_request.symbol.clone();
+        if symbol.is_crypto() {
+            let bars = self.crypto_data.bars(&bars_request).await?;
+            return Ok(Bars { symbol, bars });
+        }
         let alpaca_request: BarsReq = bars_request.into();
         let all_alpaca_bars = pager::fetch_all(|page| async {
             let mut page_request = alpaca_request.clone();
@@ -111,14 +163,10 @@ impl FinancialPlatform for AlpacaPlatform {
     }
 
     async fn latest_quotes(&self, symbols: &[AssetSymbol]) -> Result<Vec<Quote>, GreedError> {
-        let symbol_strings = symbols.iter().map(|s| &s.symbol).collect::<Vec<_>>();
-        let latest_req = apca::data::v2::last_quotes::GetReqInit {
-            ..Default::default()
-        }
-        .init(symbol_strings);
-
-        let result = self.client.issue::<last_quotes::Get>(&latest_req).await?;
-        let quotes: Vec<Quote> = result.into_iter().map(|q| q.into()).collect();
+        let (crypto, stocks): (Vec<_>, Vec<_>) =
+            symbols.iter().cloned().partition(AssetSymbol::is_crypto);
+        let mut quotes = self.stock_quotes(&stocks).await?;
+        quotes.extend(self.crypto_quotes(&crypto).await?);
         let valid_quotes = self.replace_invalid_quotes(quotes).await;
         Ok(valid_quotes)
     }
@@ -154,4 +202,48 @@ impl FinancialPlatform for AlpacaPlatform {
         let orders: Vec<Order> = alpaca_orders.into_iter().map_into().collect();
         Ok(orders)
     }
+
+    async fn order_history(&self, range: Range<DateTime<Utc>>) -> Result<Vec<Order>, GreedError> {
+        self.orders.order_history(range).await
+    }
+
+    async fn cancel_order(&self, id: &Id) -> Result<(), GreedError> {
+        self.client
+            .issue::<order::Delete>(&id.clone().into())
+            .await?;
+        Ok(())
+    }
+
+    async fn replace_order(&self, id: &Id, request: ReplaceRequest) -> Result<Order, GreedError> {
+        let change_req = (id.clone().into(), request.into());
+        let order = self.client.issue::<order::Change>(&change_req).await?;
+        Ok(order.into())
+    }
+
+    async fn close_position(&self, symbol: &AssetSymbol) -> Result<Order, GreedError> {
+        let order = self
+            .client
+            .issue::<position::Delete>(&convert_symbol::into_path(symbol))
+            .await?;
+        Ok(order.into())
+    }
+
+    async fn asset(&self, symbol: &AssetSymbol) -> Result<AssetInfo, GreedError> {
+        self.assets.asset(symbol).await
+    }
+
+    async fn assets(&self, filter: AssetFilter) -> Result<Vec<AssetInfo>, GreedError> {
+        self.assets.assets(filter).await
+    }
+
+    async fn clock(&self) -> Result<MarketClock, GreedError> {
+        let clock = self.client.issue::<clock::Get>(&()).await?;
+        Ok(clock.into())
+    }
+
+    async fn calendar(&self, range: Range<NaiveDate>) -> Result<Vec<MarketDay>, GreedError> {
+        let calendar_req: calendar::ListReq = range.into();
+        let days = self.client.issue::<calendar::List>(&calendar_req).await?;
+        Ok(days.into_iter().map_into().collect())
+    }
 }
@@ -0,0 +1,68 @@
+use crate::asset::AssetSymbol;
+use crate::error::GreedError;
+use crate::platform::alpaca::convert_asset_info::AlpacaAsset;
+use crate::platform::alpaca::convert_symbol::path_symbol;
+use crate::platform::alpaca::http_client::AlpacaHttpClient;
+use crate::platform::asset_class::AssetClass;
+use crate::platform::asset_filter::AssetFilter;
+use crate::platform::asset_info::AssetInfo;
+use apca::ApiInfo;
+
+const ASSETS_PATH: &str = "/v2/assets";
+
+/// Looks up assets through Alpaca's trading API.
+pub struct AssetsClient {
+    http: AlpacaHttpClient,
+}
+
+impl AssetsClient {
+    pub fn new(api_info: &ApiInfo) -> Self {
+        Self {
+            http: AlpacaHttpClient::new(&api_info.api_base_url, api_info),
+        }
+    }
+
+    pub async fn asset(&self, symbol: &AssetSymbol) -> Result<AssetInfo, GreedError> {
+        let url = self
+            .http
+            .url(&format!("{ASSETS_PATH}/{}", path_symbol(symbol)));
+        let asset: AlpacaAsset = self.http.get(url).await?;
+        Ok(asset.into())
+    }
+
+    pub async fn assets(&self, filter: AssetFilter) -> Result<Vec<AssetInfo>, GreedError> {
+        let mut url = self.http.url(ASSETS_PATH);
+        if filter.tradable_only {
+            url.query_pairs_mut().append_pair("status", "active");
+        }
+        if let Some(class) = filter.class.and_then(asset_class) {
+            url.query_pairs_mut().append_pair("asset_class", class);
+        }
+        let assets: Vec<AlpacaAsset> = self.http.get(url).await?;
+        Ok(assets
+            .into_iter()
+            .map(AssetInfo::from)
+            .filter(|asset| filter.matches(asset))
+            .collect())
+    }
+}
+
+fn asset_class(class: AssetClass) -> Option<&'static str> {
+    match class {
+        AssetClass::Crypto => Some("crypto"),
+        AssetClass::UsEquity => Some("us_equity"),
+        AssetClass::Unknown => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn asset_classes() {
+        assert_eq!(asset_class(AssetClass::Crypto), Some("crypto"));
+        assert_eq!(asset_class(AssetClass::UsEquity), Some("us_equity"));
+        assert_eq!(asset_class(AssetClass::Unknown), None);
+    }
+}
@@ -15,6 +15,7 @@ impl From<apca::api::v2::account::Account> for Account {
             market_value_long: value.market_value_long.to_f64().unwrap_or(0.0),
             market_value_short: value.market_value_short.to_f64().unwrap_or(0.0),
             equity: value.equity.to_f64().unwrap_or(0.0),
+            last_equity: value.last_equity.to_f64().unwrap_or(0.0),
         }
     }
 }
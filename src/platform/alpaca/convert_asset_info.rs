@@ -0,0 +1,109 @@
+use crate::platform::alpaca::convert_symbol;
+use crate::platform::asset_info::AssetInfo;
+use apca::api::v2::asset::{Class, Status};
+use serde::Deserialize;
+
+/// An asset as returned by Alpaca's assets endpoints. apca's asset leaves out the name and
+/// minimum order size, and maps the crypto exchange to unknown, so assets are decoded here.
+#[derive(Debug, Deserialize)]
+pub struct AlpacaAsset {
+    class: Class,
+    exchange: String,
+    symbol: String,
+    #[serde(default)]
+    name: String,
+    status: Status,
+    tradable: bool,
+    shortable: bool,
+    easy_to_borrow: bool,
+    fractionable: bool,
+    /// A decimal string, only set for crypto.
+    min_order_size: Option<String>,
+}
+
+impl From<AlpacaAsset> for AssetInfo {
+    fn from(value: AlpacaAsset) -> Self {
+        Self {
+            symbol: convert_symbol::from_alpaca(&value.symbol, value.class),
+            name: value.name,
+            exchange: value.exchange,
+            class: value.class.into(),
+            tradable: value.tradable && value.status == Status::Active,
+            fractionable: value.fractionable,
+            shortable: value.shortable,
+            easy_to_borrow: value.easy_to_borrow,
+            min_order_size: value.min_order_size.and_then(|size| size.parse().ok()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::asset::AssetSymbol;
+    use crate::platform::asset_class::AssetClass;
+    use serde_json::json;
+
+    #[test]
+    fn from_alpaca() {
+        let json = json!({
+            "id": "b0b6dd9d-8b9b-48a9-ba46-b9d54906e415",
+            "class": "us_equity",
+            "exchange": "NYSEARCA",
+            "symbol": "VTI",
+            "name": "VTI Fund",
+            "status": "active",
+            "tradable": true,
+            "marginable": true,
+            "shortable": true,
+            "easy_to_borrow": false,
+            "fractionable": true,
+            "attributes": []
+        });
+        let asset: AlpacaAsset = serde_json::from_value(json).unwrap();
+        let expected = AssetInfo {
+            easy_to_borrow: false,
+            ..AssetInfo::fixture(AssetSymbol::new("VTI"))
+        };
+        assert_eq!(expected, asset.into())
+    }
+
+    #[test]
+    fn from_alpaca_crypto() {
+        let json = json!({
+            "class": "crypto",
+            "exchange": "CRYPTO",
+            "symbol": "BTC/USD",
+            "name": "Bitcoin / US Dollar",
+            "status": "active",
+            "tradable": true,
+            "shortable": false,
+            "easy_to_borrow": false,
+            "fractionable": true,
+            "min_order_size": "0.0001"
+        });
+        let asset: AlpacaAsset = serde_json::from_value(json).unwrap();
+        let info: AssetInfo = asset.into();
+        assert_eq!(info.symbol, AssetSymbol::new("BTC/USD"));
+        assert_eq!(info.class, AssetClass::Crypto);
+        assert_eq!(info.exchange, "CRYPTO");
+        assert_eq!(info.min_order_size, Some(0.0001))
+    }
+
+    #[test]
+    fn from_alpaca_inactive() {
+        let json = json!({
+            "class": "us_equity",
+            "exchange": "NASDAQ",
+            "symbol": "OLD",
+            "status": "inactive",
+            "tradable": true,
+            "shortable": false,
+            "easy_to_borrow": false,
+            "fractionable": false
+        });
+        let asset: AlpacaAsset = serde_json::from_value(json).unwrap();
+        let info: AssetInfo = asset.into();
+        assert!(!info.tradable)
+    }
+}
@@ -0,0 +1,42 @@
+use crate::greed_error_from;
+use crate::platform::calendar::MarketDay;
+use crate::trading_days::eastern::eastern_to_utc;
+use apca::RequestError;
+
+/// Alpaca reports session times in US eastern time.
+impl From<apca::api::v2::calendar::OpenClose> for MarketDay {
+    fn from(value: apca::api::v2::calendar::OpenClose) -> Self {
+        Self {
+            date: value.date,
+            open: eastern_to_utc(value.date, value.open),
+            close: eastern_to_utc(value.date, value.close),
+        }
+    }
+}
+
+greed_error_from!(RequestError<apca::api::v2::calendar::ListError>);
+
+#[cfg(test)]
+mod test {
+    use crate::assert;
+    use crate::platform::calendar::MarketDay;
+    use apca::api::v2::calendar::OpenClose;
+    use chrono::{NaiveDate, NaiveTime, TimeZone, Utc};
+
+    #[test]
+    fn from_alpaca() {
+        let date = NaiveDate::from_ymd_opt(2024, 11, 29).unwrap();
+        let open_close = OpenClose {
+            date,
+            open: NaiveTime::from_hms_opt(9, 30, 0).unwrap(),
+            close: NaiveTime::from_hms_opt(13, 0, 0).unwrap(),
+            _non_exhaustive: (),
+        };
+        let expected = MarketDay {
+            date,
+            open: Utc.with_ymd_and_hms(2024, 11, 29, 14, 30, 0).unwrap(),
+            close: Utc.with_ymd_and_hms(2024, 11, 29, 18, 0, 0).unwrap(),
+        };
+        assert::conversion(open_close, expected)
+    }
+}
@@ -0,0 +1,44 @@
+use crate::greed_error_from;
+use crate::platform::clock::MarketClock;
+use apca::RequestError;
+
+impl From<apca::api::v2::clock::Clock> for MarketClock {
+    fn from(value: apca::api::v2::clock::Clock) -> Self {
+        Self {
+            is_open: value.open,
+            timestamp: value.current,
+            next_open: value.next_open,
+            next_close: value.next_close,
+        }
+    }
+}
+
+greed_error_from!(RequestError<apca::api::v2::clock::GetError>);
+
+#[cfg(test)]
+mod test {
+    use crate::assert;
+    use crate::date::DateTimeFixture;
+    use crate::platform::clock::MarketClock;
+    use apca::api::v2::clock::Clock;
+    use chrono::Duration;
+
+    #[test]
+    fn from_alpaca() {
+        let now = DateTimeFixture::utc();
+        let clock = Clock {
+            open: false,
+            current: now,
+            next_open: now + Duration::hours(1),
+            next_close: now + Duration::hours(8),
+            _non_exhaustive: (),
+        };
+        let expected = MarketClock {
+            is_open: false,
+            timestamp: now,
+            next_open: now + Duration::hours(1),
+            next_close: now + Duration::hours(8),
+        };
+        assert::conversion(clock, expected)
+    }
+}
@@ -1,4 +1,5 @@
 use crate::greed_error_from;
+use crate::platform::alpaca::convert_symbol;
 use crate::platform::id::Id;
 use crate::platform::order::Order;
 use apca::RequestError;
@@ -21,7 +22,7 @@ impl From<apca::api::v2::order::Order> for Order {
             expired_at: value.expired_at,
             canceled_at: value.canceled_at,
             asset_class: value.asset_class.into(),
-            symbol: value.symbol.parse().unwrap(),
+            symbol: convert_symbol::from_alpaca(&value.symbol, value.asset_class),
             amount: value.amount.into(),
             filled_quantity: value.filled_quantity,
             order_type: value.type_.into(),
@@ -34,8 +35,32 @@ impl From<apca::api::v2::order::Order> for Order {
             trail_percent: value.trail_percent,
             average_fill_price: value.average_fill_price,
             extended_hours: value.extended_hours,
+            client_order_id: Some(value.client_order_id).filter(|id| !id.is_empty()),
+        }
+    }
+}
+
+impl From<Id> for apca::api::v2::order::Id {
+    fn from(value: Id) -> Self {
+        match value {
+            Id::Uuid(uuid) => Self(uuid),
         }
     }
 }
 
 greed_error_from!(RequestError<apca::api::v2::orders::ListError>);
+greed_error_from!(RequestError<apca::api::v2::order::ChangeError>);
+greed_error_from!(RequestError<apca::api::v2::order::DeleteError>);
+
+#[cfg(test)]
+mod test {
+    use crate::assert;
+    use crate::platform::id::Id;
+    use uuid::Uuid;
+
+    #[test]
+    fn id_into_alpaca() {
+        let uuid = Uuid::from_u128(1);
+        assert::conversion(Id::Uuid(uuid), apca::api::v2::order::Id(uuid))
+    }
+}
@@ -1,3 +1,4 @@
+use crate::asset::AssetSymbol;
 use crate::float::FloatAmountRounding;
 use crate::num::NumFromFloat;
 use crate::platform::order::amount::Amount;
@@ -29,9 +30,22 @@ impl From<Amount> for apca::api::v2::order::Amount {
     }
 }
 
+impl Amount {
+    /// Converts to an Alpaca amount, flooring quantities to the decimal places the asset trades in.
+    pub fn into_alpaca(self, symbol: &AssetSymbol) -> apca::api::v2::order::Amount {
+        match self {
+            Amount::Quantity(quantity) => apca::api::v2::order::Amount::Quantity {
+                quantity: Num::from_f64(quantity.round_quantity_for(symbol)),
+            },
+            Amount::Notional(_) => self.into(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::assert;
+    use crate::asset::AssetSymbol;
     use crate::platform::order::amount::Amount;
     use num_decimal::Num;
 
@@ -86,4 +100,24 @@ mod test {
             panic!("wrong type was converted")
         }
     }
+
+    #[test]
+    fn into_alpaca_crypto_rounding() {
+        let amount = Amount::Quantity(0.123456789).into_alpaca(&AssetSymbol::new("BTC/USD"));
+        if let apca::api::v2::order::Amount::Quantity { quantity } = amount {
+            assert_eq!(quantity.to_f64(), Some(0.12345678));
+        } else {
+            panic!("wrong type was converted")
+        }
+    }
+
+    #[test]
+    fn into_alpaca_equity_rounding() {
+        let amount = Amount::Quantity(0.123456789).into_alpaca(&AssetSymbol::new("VTI"));
+        if let apca::api::v2::order::Amount::Quantity { quantity } = amount {
+            assert_eq!(quantity.to_f64(), Some(0.1234567));
+        } else {
+            panic!("wrong type was converted")
+        }
+    }
 }
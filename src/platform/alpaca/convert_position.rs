@@ -1,4 +1,5 @@
 use crate::greed_error_from;
+use crate::platform::alpaca::convert_symbol;
 use crate::platform::id::Id;
 use crate::platform::position::Position;
 use apca::RequestError;
@@ -17,7 +18,7 @@ impl From<apca::api::v2::position::Position> for Position {
             quantity: value.quantity.to_f64().unwrap_or(0.0),
             quantity_available: value.quantity_available.to_f64().unwrap_or(0.0),
             side: value.side.into(),
-            symbol: value.symbol.parse().unwrap(),
+            symbol: convert_symbol::from_alpaca(&value.symbol, value.asset_class),
             unrealized_gain_today: value.unrealized_gain_today.and_then(|p| p.to_f64()),
             unrealized_gain_today_percent: value
                 .unrealized_gain_today_percent
@@ -33,3 +34,4 @@ impl From<apca::api::v2::position::Position> for Position {
 }
 
 greed_error_from!(RequestError<apca::api::v2::positions::ListError>);
+greed_error_from!(RequestError<apca::api::v2::position::DeleteError>);
@@ -3,14 +3,15 @@ use crate::platform::request::OrderRequest;
 use apca::api::v2::order::CreateReq;
 use apca::RequestError;
 
+mod convert_replace;
 mod convert_stop_loss;
 mod convert_take_profit;
 
 impl From<OrderRequest> for CreateReq {
     fn from(value: OrderRequest) -> Self {
         CreateReq {
+            amount: value.amount.into_alpaca(&value.symbol),
             symbol: value.symbol.into(),
-            amount: value.amount.into(),
             side: value.side.into(),
             class: value.class.into(),
             type_: value.order_type.into(),
@@ -22,7 +23,7 @@ impl From<OrderRequest> for CreateReq {
             take_profit: value.take_profit.map(|val| val.into()),
             stop_loss: value.stop_loss.map(|val| val.into()),
             extended_hours: value.extended_hours,
-            client_order_id: None,
+            client_order_id: value.client_order_id,
             _non_exhaustive: (),
         }
     }
@@ -59,6 +60,7 @@ mod test {
             take_profit: Some(TakeProfit::Limit(Num::from(6))),
             stop_loss: Some(StopLoss::Stop(Num::from(7))),
             extended_hours: true,
+            client_order_id: Some("greed-1".to_string()),
         };
         let expected = CreateReq {
             symbol: Symbol::Sym("VTI".to_string()),
@@ -76,7 +78,7 @@ mod test {
             take_profit: Some(apca::api::v2::order::TakeProfit::Limit(Num::from(6))),
             stop_loss: Some(apca::api::v2::order::StopLoss::Stop(Num::from(7))),
             extended_hours: true,
-            client_order_id: None,
+            client_order_id: Some("greed-1".to_string()),
             _non_exhaustive: (),
         };
         let alpaca_request: CreateReq = request.into();
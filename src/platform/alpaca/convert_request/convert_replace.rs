@@ -0,0 +1,34 @@
+use crate::num::NumFromFloat;
+use crate::platform::request::replace::ReplaceRequest;
+use apca::api::v2::order::ChangeReq;
+use num_decimal::Num;
+
+impl From<ReplaceRequest> for ChangeReq {
+    fn from(value: ReplaceRequest) -> Self {
+        ChangeReq {
+            quantity: value.quantity.map(Num::from_f64),
+            limit_price: value.limit_price,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::assert;
+
+    #[test]
+    fn into_alpaca() {
+        let request = ReplaceRequest {
+            quantity: Some(2.0),
+            limit_price: Some(Num::from(95)),
+        };
+        let expected = ChangeReq {
+            quantity: Some(Num::from(2)),
+            limit_price: Some(Num::from(95)),
+            ..Default::default()
+        };
+        assert::conversion(request, expected)
+    }
+}
@@ -1,5 +1,5 @@
 use crate::asset::AssetSymbol;
-use apca::api::v2::asset::Symbol;
+use apca::api::v2::asset::{Class, Symbol};
 
 impl From<AssetSymbol> for Symbol {
     fn from(value: AssetSymbol) -> Self {
@@ -7,15 +7,62 @@ impl From<AssetSymbol> for Symbol {
     }
 }
 
+/// Alpaca reports crypto position symbols without the pair's slash (BTCUSD), so crypto symbols
+/// are normalized to the BTC/USD form used everywhere else.
+pub fn from_alpaca(symbol: &str, class: Class) -> AssetSymbol {
+    match class {
+        Class::Crypto => AssetSymbol::crypto(symbol),
+        _ => symbol.into(),
+    }
+}
+
+/// Crypto pairs go into URL paths without their slash, which would otherwise split the path.
+pub fn path_symbol(symbol: &AssetSymbol) -> String {
+    symbol.to_string().replace('/', "")
+}
+
+/// The symbol for endpoints which take it in the URL path, such as closing a position.
+pub fn into_path(symbol: &AssetSymbol) -> Symbol {
+    Symbol::Sym(path_symbol(symbol))
+}
+
 #[cfg(test)]
 mod test {
     use crate::assert;
     use crate::asset::AssetSymbol;
-    use apca::api::v2::asset::Symbol;
+    use apca::api::v2::asset::{Class, Symbol};
 
     #[test]
     fn into_alpaca() {
         let asset_symbol = AssetSymbol::new("VTI");
         assert::conversion(asset_symbol, Symbol::Sym("VTI".to_string()))
     }
+
+    #[test]
+    fn path_symbol_crypto() {
+        assert_eq!(super::path_symbol(&AssetSymbol::new("BTC/USD")), "BTCUSD")
+    }
+
+    #[test]
+    fn path_symbol_equity() {
+        assert_eq!(super::path_symbol(&AssetSymbol::new("vti")), "VTI")
+    }
+
+    #[test]
+    fn into_path_crypto() {
+        let symbol = super::into_path(&AssetSymbol::new("BTC/USD"));
+        assert_eq!(symbol, Symbol::Sym("BTCUSD".to_string()))
+    }
+
+    #[test]
+    fn from_alpaca_crypto() {
+        let symbol = super::from_alpaca("BTCUSD", Class::Crypto);
+        assert_eq!(symbol, AssetSymbol::new("BTC/USD"))
+    }
+
+    #[test]
+    fn from_alpaca_equity() {
+        let symbol = super::from_alpaca("VTI", Class::UsEquity);
+        assert_eq!(symbol, AssetSymbol::new("VTI"))
+    }
 }
@@ -0,0 +1,209 @@
+use crate::asset::AssetSymbol;
+use crate::error::GreedError;
+use crate::pager;
+use crate::platform::alpaca::http_client::AlpacaHttpClient;
+use crate::platform::bar::bar_request::BarRequest;
+use crate::platform::bar::time_frame::TimeFrame;
+use crate::platform::bar::Bar;
+use crate::platform::quote::Quote;
+use apca::ApiInfo;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+const BARS_PATH: &str = "/v1beta3/crypto/us/bars";
+const LATEST_QUOTES_PATH: &str = "/v1beta3/crypto/us/latest/quotes";
+
+/// Fetches bars and quotes from Alpaca's crypto market data endpoints, which apca doesn't cover.
+pub struct CryptoDataClient {
+    http: AlpacaHttpClient,
+}
+
+impl CryptoDataClient {
+    pub fn new(api_info: &ApiInfo) -> Self {
+        Self {
+            http: AlpacaHttpClient::new(&api_info.data_base_url, api_info),
+        }
+    }
+
+    pub async fn bars(&self, request: &BarRequest) -> Result<Vec<Bar>, GreedError> {
+        let symbol = request.symbol.to_string();
+        let crypto_bars = pager::fetch_all(|page: Option<String>| {
+            let symbol = symbol.clone();
+            async move {
+                let mut url = self.http.url(BARS_PATH);
+                url.query_pairs_mut()
+                    .append_pair("symbols", &symbol)
+                    .append_pair("timeframe", time_frame(&request.timeframe))
+                    .append_pair("start", &timestamp(request.start))
+                    .append_pair("end", &timestamp(request.end));
+                if let Some(limit) = request.limit {
+                    url.query_pairs_mut()
+                        .append_pair("limit", &limit.to_string());
+                }
+                if let Some(page) = page {
+                    url.query_pairs_mut().append_pair("page_token", &page);
+                }
+                let mut response: CryptoBarsResponse = self.http.get(url).await?;
+                let bars = response.bars.remove(&symbol).unwrap_or_default();
+                Ok((bars, response.next_page_token))
+            }
+        })
+        .await?;
+        Ok(crypto_bars.into_iter().map(Bar::from).collect())
+    }
+
+    pub async fn latest_quotes(&self, symbols: &[AssetSymbol]) -> Result<Vec<Quote>, GreedError> {
+        let symbols = symbols.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let mut url = self.http.url(LATEST_QUOTES_PATH);
+        url.query_pairs_mut()
+            .append_pair("symbols", &symbols.join(","));
+        let response: CryptoQuotesResponse = self.http.get(url).await?;
+        Ok(response.quotes.into_iter().map(Quote::from).collect())
+    }
+}
+
+fn time_frame(time_frame: &TimeFrame) -> &'static str {
+    match time_frame {
+        TimeFrame::OneMinute => "1Min",
+        TimeFrame::OneHour => "1Hour",
+        TimeFrame::OneDay => "1Day",
+    }
+}
+
+fn timestamp(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[derive(Debug, Deserialize)]
+struct CryptoBarsResponse {
+    bars: HashMap<String, Vec<CryptoBar>>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CryptoBar {
+    t: DateTime<Utc>,
+    o: f64,
+    h: f64,
+    l: f64,
+    c: f64,
+    v: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct CryptoQuotesResponse {
+    quotes: HashMap<String, CryptoQuote>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CryptoQuote {
+    t: DateTime<Utc>,
+    ap: f64,
+    #[serde(rename = "as")]
+    as_: f64,
+    bp: f64,
+    bs: f64,
+}
+
+// Crypto trades in fractions of a coin, so volume and sizes are rounded to whole units.
+impl From<CryptoBar> for Bar {
+    fn from(value: CryptoBar) -> Self {
+        Self {
+            timestamp: value.t,
+            open: value.o,
+            close: value.c,
+            high: value.h,
+            low: value.l,
+            volume: value.v.round() as usize,
+        }
+    }
+}
+
+impl From<(String, CryptoQuote)> for Quote {
+    fn from(value: (String, CryptoQuote)) -> Self {
+        let (symbol, quote) = value;
+        Self {
+            time: quote.t,
+            ask_price: quote.ap,
+            ask_size: quote.as_.round() as u64,
+            bid_price: quote.bp,
+            bid_size: quote.bs.round() as u64,
+            symbol: AssetSymbol::new(&symbol),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    #[test]
+    fn bars_response() {
+        let json = json!({
+            "bars": {
+                "BTC/USD": [
+                    {"t": "2024-01-03T00:00:00Z", "o": 100.0, "h": 110.0, "l": 90.0, "c": 105.0, "v": 2.6, "n": 10, "vw": 101.0}
+                ]
+            },
+            "next_page_token": "token"
+        });
+        let mut response: CryptoBarsResponse = serde_json::from_value(json).unwrap();
+        let bars = response
+            .bars
+            .remove("BTC/USD")
+            .unwrap()
+            .into_iter()
+            .map(Bar::from)
+            .collect::<Vec<_>>();
+        let expected = Bar {
+            timestamp: Utc.with_ymd_and_hms(2024, 1, 3, 0, 0, 0).unwrap(),
+            open: 100.0,
+            close: 105.0,
+            high: 110.0,
+            low: 90.0,
+            volume: 3,
+        };
+        assert_eq!(bars, vec![expected]);
+        assert_eq!(response.next_page_token, Some("token".to_string()))
+    }
+
+    #[test]
+    fn quotes_response() {
+        let json = json!({
+            "quotes": {
+                "BTC/USD": {"t": "2024-01-03T00:00:00Z", "ap": 101.0, "as": 0.4, "bp": 100.0, "bs": 1.6}
+            }
+        });
+        let response: CryptoQuotesResponse = serde_json::from_value(json).unwrap();
+        let quotes = response
+            .quotes
+            .into_iter()
+            .map(Quote::from)
+            .collect::<Vec<_>>();
+        let expected = Quote {
+            time: Utc.with_ymd_and_hms(2024, 1, 3, 0, 0, 0).unwrap(),
+            ask_price: 101.0,
+            ask_size: 0,
+            bid_price: 100.0,
+            bid_size: 2,
+            symbol: AssetSymbol::new("BTC/USD"),
+        };
+        assert_eq!(quotes, vec![expected])
+    }
+
+    #[test]
+    fn time_frames() {
+        assert_eq!(time_frame(&TimeFrame::OneMinute), "1Min");
+        assert_eq!(time_frame(&TimeFrame::OneHour), "1Hour");
+        assert_eq!(time_frame(&TimeFrame::OneDay), "1Day");
+    }
+
+    #[test]
+    fn timestamp_format() {
+        let time = Utc.with_ymd_and_hms(2024, 1, 3, 4, 5, 6).unwrap();
+        assert_eq!(timestamp(time), "2024-01-03T04:05:06Z")
+    }
+}
@@ -0,0 +1,53 @@
+use crate::error::GreedError;
+use crate::greed_error_from;
+use apca::ApiInfo;
+use reqwest::Url;
+use serde::de::DeserializeOwned;
+
+/// An authenticated client for the Alpaca endpoints apca doesn't cover, or covers only in part.
+pub struct AlpacaHttpClient {
+    http: reqwest::Client,
+    base_url: Url,
+    key_id: String,
+    secret: String,
+}
+
+impl AlpacaHttpClient {
+    pub fn new(base_url: &Url, api_info: &ApiInfo) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            base_url: base_url.clone(),
+            key_id: api_info.key_id.clone(),
+            secret: api_info.secret.clone(),
+        }
+    }
+
+    pub fn url(&self, path: &str) -> Url {
+        let mut url = self.base_url.clone();
+        url.set_path(path);
+        url
+    }
+
+    /// Fetches and decodes the JSON at `url`. Error statuses fail with Alpaca's message.
+    pub async fn get<T: DeserializeOwned>(&self, url: Url) -> Result<T, GreedError> {
+        let path = url.path().to_string();
+        let response = self
+            .http
+            .get(url)
+            .header("APCA-API-KEY-ID", &self.key_id)
+            .header("APCA-API-SECRET-KEY", &self.secret)
+            .send()
+            .await?;
+        let status = response.status();
+        let body = response.bytes().await?;
+        if !status.is_success() {
+            let message = String::from_utf8_lossy(&body);
+            return Err(GreedError::new(&format!(
+                "{path} returned {status}: {message}"
+            )));
+        }
+        Ok(serde_json::from_slice(&body)?)
+    }
+}
+
+greed_error_from!(reqwest::Error);
@@ -0,0 +1,157 @@
+use crate::error::GreedError;
+use crate::pager;
+use crate::platform::alpaca::http_client::AlpacaHttpClient;
+use crate::platform::order::Order;
+use apca::ApiInfo;
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use std::collections::HashSet;
+use std::ops::Range;
+
+const ORDERS_PATH: &str = "/v2/orders";
+
+/// The maximum number of orders Alpaca returns from a single order list request.
+const PAGE_LIMIT: usize = 500;
+
+/// Lists orders through Alpaca's trading API. apca's order list request doesn't expose the after
+/// and until filters, which are needed to page through a long history.
+pub struct OrdersClient {
+    http: AlpacaHttpClient,
+}
+
+impl OrdersClient {
+    pub fn new(api_info: &ApiInfo) -> Self {
+        Self {
+            http: AlpacaHttpClient::new(&api_info.api_base_url, api_info),
+        }
+    }
+
+    /// Fetches every order created within `range`, newest first. Each page ends before the oldest
+    /// order of the previous one, until a short page shows the start of the range is covered.
+    pub async fn order_history(
+        &self,
+        range: Range<DateTime<Utc>>,
+    ) -> Result<Vec<Order>, GreedError> {
+        let orders = pager::fetch_all(|until: Option<DateTime<Utc>>| {
+            let until = until.unwrap_or(range.end);
+            async move {
+                let mut url = self.http.url(ORDERS_PATH);
+                url.query_pairs_mut()
+                    .append_pair("status", "all")
+                    .append_pair("direction", "desc")
+                    .append_pair("nested", "true")
+                    .append_pair("limit", &PAGE_LIMIT.to_string())
+                    .append_pair("after", &timestamp(range.start))
+                    .append_pair("until", &timestamp(until));
+                let page: Vec<apca::api::v2::order::Order> = self.http.get(url).await?;
+                let orders = page.into_iter().map(Order::from).collect::<Vec<_>>();
+                let next = next_until(&orders, until);
+                Ok((orders, next))
+            }
+        })
+        .await?;
+        Ok(distinct_in_range(orders, &range))
+    }
+}
+
+/// The cursor for the page after `orders`, or `None` once a page comes back short. Timestamps are
+/// sent to the second, so the cursor is a second past the oldest order to avoid skipping orders
+/// created within that second. A full page which can't move the cursor back ends the history.
+fn next_until(orders: &[Order], until: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    if orders.len() < PAGE_LIMIT {
+        return None;
+    }
+    orders
+        .iter()
+        .map(|o| o.created_at + Duration::seconds(1))
+        .min()
+        .filter(|next| *next < until)
+}
+
+/// Pages overlap on the second of the cursor, so repeats are dropped.
+fn distinct_in_range(orders: Vec<Order>, range: &Range<DateTime<Utc>>) -> Vec<Order> {
+    let mut seen = HashSet::new();
+    orders
+        .into_iter()
+        .filter(|o| range.contains(&o.created_at))
+        .filter(|o| seen.insert(o.id.clone()))
+        .collect()
+}
+
+fn timestamp(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asset::AssetSymbol;
+    use crate::date::DateTimeFixture;
+    use crate::platform::id::Id;
+    use uuid::Uuid;
+
+    #[test]
+    fn next_until_short_page() {
+        let orders = vec![order(1, DateTimeFixture::utc())];
+        assert_eq!(next_until(&orders, DateTimeFixture::utc()), None)
+    }
+
+    #[test]
+    fn next_until_full_page() {
+        let until = DateTimeFixture::utc();
+        let oldest = until - Duration::days(3);
+        let orders = (0..PAGE_LIMIT)
+            .map(|i| {
+                order(
+                    i as u128,
+                    until - Duration::days(3) + Duration::minutes(i as i64),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            next_until(&orders, until),
+            Some(oldest + Duration::seconds(1))
+        )
+    }
+
+    #[test]
+    fn next_until_full_page_at_cursor() {
+        let until = DateTimeFixture::utc();
+        let orders = (0..PAGE_LIMIT)
+            .map(|i| order(i as u128, until))
+            .collect::<Vec<_>>();
+        assert_eq!(next_until(&orders, until), None)
+    }
+
+    #[test]
+    fn distinct_in_range_drops_repeats_and_outliers() {
+        let now = DateTimeFixture::utc();
+        let range = now - Duration::days(1)..now;
+        let orders = vec![
+            order(1, now - Duration::hours(1)),
+            order(1, now - Duration::hours(1)),
+            order(2, now - Duration::days(2)),
+            order(3, now - Duration::hours(2)),
+        ];
+        let ids = distinct_in_range(orders, &range)
+            .into_iter()
+            .map(|o| o.id)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            vec![Id::Uuid(Uuid::from_u128(1)), Id::Uuid(Uuid::from_u128(3))]
+        )
+    }
+
+    #[test]
+    fn timestamp_format() {
+        assert_eq!(timestamp(DateTimeFixture::utc()), "2023-12-04T08:00:00Z")
+    }
+
+    fn order(id: u128, created_at: DateTime<Utc>) -> Order {
+        Order {
+            id: Id::Uuid(Uuid::from_u128(id)),
+            created_at,
+            ..Order::fixture(AssetSymbol::new("VTI"))
+        }
+    }
+}
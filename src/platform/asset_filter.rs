@@ -0,0 +1,58 @@
+use crate::platform::asset_class::AssetClass;
+use crate::platform::asset_info::AssetInfo;
+use serde::{Deserialize, Serialize};
+
+/// Narrows down the assets listed by a platform.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct AssetFilter {
+    /// Only list assets of this class, or every class when `None`.
+    pub class: Option<AssetClass>,
+    /// Only list assets which can currently be traded.
+    pub tradable_only: bool,
+}
+
+impl AssetFilter {
+    pub fn matches(&self, asset: &AssetInfo) -> bool {
+        let class_matches = self.class.is_none_or(|class| class == asset.class);
+        class_matches && (asset.tradable || !self.tradable_only)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asset::AssetSymbol;
+
+    #[test]
+    fn matches_class() {
+        let filter = AssetFilter {
+            class: Some(AssetClass::Crypto),
+            ..Default::default()
+        };
+        assert!(filter.matches(&AssetInfo::unrestricted(&AssetSymbol::new("BTC/USD"))));
+        assert!(!filter.matches(&AssetInfo::unrestricted(&AssetSymbol::new("VTI"))));
+    }
+
+    #[test]
+    fn matches_default() {
+        let untradable = AssetInfo {
+            tradable: false,
+            ..AssetInfo::fixture(AssetSymbol::new("VTI"))
+        };
+        assert!(AssetFilter::default().matches(&untradable))
+    }
+
+    #[test]
+    fn matches_tradable_only() {
+        let filter = AssetFilter {
+            tradable_only: true,
+            ..Default::default()
+        };
+        let untradable = AssetInfo {
+            tradable: false,
+            ..AssetInfo::fixture(AssetSymbol::new("VTI"))
+        };
+        assert!(!filter.matches(&untradable));
+        assert!(filter.matches(&AssetInfo::fixture(AssetSymbol::new("VTI"))));
+    }
+}
@@ -0,0 +1,182 @@
+use crate::asset::AssetSymbol;
+use crate::float::FloatAmountRounding;
+use crate::platform::asset_class::AssetClass;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// What the broker knows about an asset and allows for trading it.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct AssetInfo {
+    pub symbol: AssetSymbol,
+    /// The asset's full name, such as Vanguard Total Stock Market ETF.
+    pub name: String,
+    /// The exchange the asset trades on, such as NYSEARCA.
+    pub exchange: String,
+    pub class: AssetClass,
+    pub tradable: bool,
+    /// Whether the broker accepts orders for fractions of a share, including notional orders.
+    pub fractionable: bool,
+    /// Whether the broker allows the asset to be sold short.
+    pub shortable: bool,
+    /// Whether shares can be borrowed for a short sale without locating them first.
+    pub easy_to_borrow: bool,
+    /// The smallest quantity the broker accepts in an order, when it sets one.
+    pub min_order_size: Option<f64>,
+}
+
+impl AssetInfo {
+    /// A tradable, fractionable asset which can't be shorted, for platforms which don't track
+    /// what their assets allow.
+    pub fn unrestricted(symbol: &AssetSymbol) -> Self {
+        let class = if symbol.is_crypto() {
+            AssetClass::Crypto
+        } else {
+            AssetClass::UsEquity
+        };
+        Self {
+            symbol: symbol.clone(),
+            name: symbol.to_string(),
+            class,
+            tradable: true,
+            fractionable: true,
+            ..Default::default()
+        }
+    }
+
+    /// Whether orders for `symbol` can be for fractions of a unit. Crypto always can, other assets
+    /// only when the broker says they're fractionable.
+    pub fn allows_fractions(symbol: &AssetSymbol, info: Option<&AssetInfo>) -> bool {
+        symbol.is_crypto() || info.is_some_and(|info| info.fractionable)
+    }
+
+    /// Rounds `quantity` down to what an order for `symbol` can be for: whole shares unless the
+    /// asset allows fractions.
+    pub fn order_quantity(symbol: &AssetSymbol, info: Option<&AssetInfo>, quantity: f64) -> f64 {
+        if Self::allows_fractions(symbol, info) {
+            quantity.round_quantity_for(symbol)
+        } else {
+            quantity.floor()
+        }
+    }
+
+    /// Whether a short sale of the asset can be placed right away.
+    pub fn can_short(&self) -> bool {
+        self.tradable && self.shortable && self.easy_to_borrow
+    }
+
+    #[cfg(test)]
+    pub fn fixture(symbol: AssetSymbol) -> Self {
+        Self {
+            name: format!("{symbol} Fund"),
+            exchange: "NYSEARCA".to_string(),
+            class: AssetClass::UsEquity,
+            symbol,
+            tradable: true,
+            fractionable: true,
+            shortable: true,
+            easy_to_borrow: true,
+            min_order_size: None,
+        }
+    }
+}
+
+impl Display for AssetInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let tradable = if self.tradable {
+            "tradable"
+        } else {
+            "not tradable"
+        };
+        let fractional = if self.fractionable {
+            "fractional"
+        } else {
+            "whole shares"
+        };
+        let borrow = if self.can_short() {
+            "available to borrow"
+        } else {
+            "not available to borrow"
+        };
+        write!(
+            f,
+            "{} ({}) - {tradable}, {fractional}, {borrow}",
+            self.symbol, self.name
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_short() {
+        assert!(AssetInfo::fixture(AssetSymbol::new("VTI")).can_short())
+    }
+
+    #[test]
+    fn order_quantity() {
+        let vti = AssetSymbol::new("VTI");
+        let btc = AssetSymbol::new("BTC/USD");
+        let whole_shares = AssetInfo {
+            fractionable: false,
+            ..AssetInfo::fixture(vti.clone())
+        };
+        let fractionable = AssetInfo::fixture(vti.clone());
+        assert_eq!(AssetInfo::order_quantity(&vti, None, 2.5), 2.0);
+        assert_eq!(
+            AssetInfo::order_quantity(&vti, Some(&whole_shares), 2.5),
+            2.0
+        );
+        assert_eq!(
+            AssetInfo::order_quantity(&vti, Some(&fractionable), 2.5),
+            2.5
+        );
+        assert_eq!(AssetInfo::order_quantity(&btc, None, 0.25), 0.25);
+    }
+
+    #[test]
+    fn can_short_hard_to_borrow() {
+        let info = AssetInfo {
+            easy_to_borrow: false,
+            ..AssetInfo::fixture(AssetSymbol::new("VTI"))
+        };
+        assert!(!info.can_short())
+    }
+
+    #[test]
+    fn deserialize_missing_fields() {
+        let info: AssetInfo =
+            serde_json::from_str(r#"{"symbol": "VTI", "tradable": true}"#).unwrap();
+        let expected = AssetInfo {
+            symbol: AssetSymbol::new("VTI"),
+            tradable: true,
+            ..Default::default()
+        };
+        assert_eq!(expected, info)
+    }
+
+    #[test]
+    fn display() {
+        let info = AssetInfo::fixture(AssetSymbol::new("VTI"));
+        assert_eq!(
+            info.to_string(),
+            "VTI (VTI Fund) - tradable, fractional, available to borrow"
+        )
+    }
+
+    #[test]
+    fn unrestricted_crypto() {
+        let info = AssetInfo::unrestricted(&AssetSymbol::new("BTC/USD"));
+        assert_eq!(info.class, AssetClass::Crypto);
+        assert!(info.tradable && info.fractionable && !info.can_short())
+    }
+
+    #[test]
+    fn unrestricted_equity() {
+        let info = AssetInfo::unrestricted(&AssetSymbol::new("VTI"));
+        assert_eq!(info.class, AssetClass::UsEquity);
+        assert!(info.tradable && info.fractionable && !info.can_short())
+    }
+}
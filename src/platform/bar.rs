@@ -1,4 +1,7 @@
+use serde::{Deserialize, Serialize};
+
 pub mod bar_request;
+pub mod history;
 pub mod time_frame;
 
 #[cfg(test)]
@@ -6,7 +9,7 @@ use crate::date::DateTimeFixture;
 use chrono::{DateTime, Utc};
 use std::fmt::{Display, Formatter};
 
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 pub struct Bar {
     pub timestamp: DateTime<Utc>,
     /// The open price.
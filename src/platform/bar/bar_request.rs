@@ -1,8 +1,9 @@
 use crate::asset::AssetSymbol;
 use crate::platform::bar::time_frame::TimeFrame;
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 pub struct BarRequest {
     pub symbol: AssetSymbol,
     pub limit: Option<usize>,
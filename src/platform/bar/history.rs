@@ -0,0 +1,266 @@
+use crate::asset::AssetSymbol;
+use crate::error::GreedError;
+use crate::platform::bar::bar_request::BarRequest;
+use crate::platform::bar::time_frame::TimeFrame;
+use crate::platform::bar::Bar;
+use crate::platform::FinancialPlatform;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+
+/// Historical bars keyed by symbol which simulated platforms and backtests price from.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BarHistory {
+    bars_by_symbol: HashMap<AssetSymbol, Vec<Bar>>,
+}
+
+/// A single row from a bar history csv file.
+#[derive(Debug, Deserialize)]
+struct BarRecord {
+    timestamp: DateTime<Utc>,
+    open: f64,
+    close: f64,
+    high: f64,
+    low: f64,
+    #[serde(default)]
+    volume: usize,
+}
+
+impl From<BarRecord> for Bar {
+    fn from(value: BarRecord) -> Self {
+        Self {
+            timestamp: value.timestamp,
+            open: value.open,
+            close: value.close,
+            high: value.high,
+            low: value.low,
+            volume: value.volume,
+        }
+    }
+}
+
+impl BarHistory {
+    pub fn new(bars_by_symbol: HashMap<AssetSymbol, Vec<Bar>>) -> Self {
+        let bars_by_symbol = bars_by_symbol
+            .into_iter()
+            .map(|(symbol, mut bars)| {
+                bars.sort_by_key(|b| b.timestamp);
+                (symbol, bars)
+            })
+            .collect();
+        Self { bars_by_symbol }
+    }
+
+    /// Loads bars from `<directory>/<SYMBOL>.csv` for each symbol. Each file is expected to have
+    /// a `timestamp,open,close,high,low,volume` header.
+    pub fn from_directory<P: AsRef<Path>>(
+        directory: P,
+        symbols: &[AssetSymbol],
+    ) -> Result<Self, GreedError> {
+        let mut bars_by_symbol = HashMap::new();
+        for symbol in symbols {
+            let path = directory.as_ref().join(format!("{}.csv", symbol));
+            bars_by_symbol.insert(symbol.clone(), Self::read_bars(&path)?);
+        }
+        Ok(Self::new(bars_by_symbol))
+    }
+
+    /// Loads every `<SYMBOL>.csv` file found in the directory.
+    pub fn from_directory_all<P: AsRef<Path>>(directory: P) -> Result<Self, GreedError> {
+        let mut bars_by_symbol = HashMap::new();
+        for entry in fs::read_dir(directory)? {
+            let path = entry?.path();
+            if path.extension().is_none_or(|ext| ext != "csv") {
+                continue;
+            }
+            let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            bars_by_symbol.insert(AssetSymbol::from(stem), Self::read_bars(&path)?);
+        }
+        Ok(Self::new(bars_by_symbol))
+    }
+
+    fn read_bars(path: &Path) -> Result<Vec<Bar>, GreedError> {
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_path(path)?;
+        let bars = reader
+            .deserialize::<BarRecord>()
+            .map(|record| record.map(Bar::from))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(bars)
+    }
+
+    /// Fetches hourly bars for each symbol from the platform once, up front.
+    pub async fn fetch(
+        platform: &dyn FinancialPlatform,
+        symbols: &[AssetSymbol],
+        range: Range<DateTime<Utc>>,
+    ) -> Result<Self, GreedError> {
+        let mut bars_by_symbol = HashMap::new();
+        for symbol in symbols {
+            let bars = platform
+                .bars(BarRequest {
+                    symbol: symbol.clone(),
+                    start: range.start,
+                    end: range.end,
+                    timeframe: TimeFrame::OneHour,
+                    ..Default::default()
+                })
+                .await?;
+            bars_by_symbol.insert(symbol.clone(), bars.bars);
+        }
+        Ok(Self::new(bars_by_symbol))
+    }
+
+    /// Whether any bars were loaded for the symbol.
+    pub fn contains(&self, symbol: &AssetSymbol) -> bool {
+        self.bars_by_symbol.contains_key(symbol)
+    }
+
+    /// The symbols with bars, in alphabetical order.
+    pub fn symbols(&self) -> Vec<AssetSymbol> {
+        let mut symbols = self.bars_by_symbol.keys().cloned().collect::<Vec<_>>();
+        symbols.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        symbols
+    }
+
+    /// All distinct bar timestamps within the range, in chronological order.
+    pub fn timestamps(&self, range: &Range<DateTime<Utc>>) -> Vec<DateTime<Utc>> {
+        let mut timestamps = self
+            .bars_by_symbol
+            .values()
+            .flat_map(|bars| bars.iter().map(|b| b.timestamp))
+            .filter(|t| range.contains(t))
+            .collect::<Vec<_>>();
+        timestamps.sort();
+        timestamps.dedup();
+        timestamps
+    }
+
+    /// Bars for the symbol between start and end, ignoring anything after `now`.
+    pub fn bars_between(
+        &self,
+        symbol: &AssetSymbol,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Vec<Bar> {
+        let end = end.min(now);
+        self.bars_by_symbol
+            .get(symbol)
+            .map(|bars| {
+                bars.iter()
+                    .filter(|b| b.timestamp >= start && b.timestamp <= end)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// The latest bar for the symbol at or before `now`.
+    pub fn latest_bar(&self, symbol: &AssetSymbol, now: DateTime<Utc>) -> Option<&Bar> {
+        self.bars_by_symbol
+            .get(symbol)?
+            .iter()
+            .take_while(|b| b.timestamp <= now)
+            .last()
+    }
+
+    /// The close price of the latest bar for the symbol at or before `now`.
+    pub fn price_at(&self, symbol: &AssetSymbol, now: DateTime<Utc>) -> Option<f64> {
+        self.latest_bar(symbol, now).map(|b| b.close)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture;
+    use chrono::TimeZone;
+
+    #[test]
+    fn from_directory() {
+        let symbol = AssetSymbol::new("VTI");
+        let history =
+            BarHistory::from_directory(fixture::path("bars"), std::slice::from_ref(&symbol))
+                .expect("history should load");
+        let bars = history.bars_between(&symbol, date(1), date(31), date(31));
+        assert_eq!(bars.len(), 5);
+        assert_eq!(
+            bars[0],
+            Bar {
+                timestamp: date(1),
+                open: 100.0,
+                close: 101.0,
+                high: 102.0,
+                low: 99.0,
+                volume: 1000,
+            }
+        )
+    }
+
+    #[test]
+    fn from_directory_missing_file() {
+        let result = BarHistory::from_directory(fixture::path("bars"), &[AssetSymbol::new("NOPE")]);
+        assert!(result.is_err())
+    }
+
+    #[test]
+    fn from_directory_all() {
+        let history =
+            BarHistory::from_directory_all(fixture::path("bars")).expect("history should load");
+        let vti = AssetSymbol::new("VTI");
+        assert_eq!(history.price_at(&vti, date(31)), Some(110.0));
+    }
+
+    #[test]
+    fn timestamps() {
+        let history = history();
+        let timestamps = history.timestamps(&(date(2)..date(4)));
+        assert_eq!(timestamps, vec![date(2), date(3)])
+    }
+
+    #[test]
+    fn bars_between_ignores_future() {
+        let history = history();
+        let bars = history.bars_between(&AssetSymbol::new("VTI"), date(1), date(4), date(2));
+        assert_eq!(bars, vec![bar(1, 10.0), bar(2, 20.0)])
+    }
+
+    #[test]
+    fn price_at() {
+        let history = history();
+        let vti = AssetSymbol::new("VTI");
+        assert_eq!(history.price_at(&vti, date(2)), Some(20.0));
+        assert_eq!(
+            history.price_at(&vti, date(1) - chrono::Duration::days(1)),
+            None
+        );
+    }
+
+    fn history() -> BarHistory {
+        BarHistory::new(HashMap::from([(
+            AssetSymbol::new("VTI"),
+            vec![bar(3, 30.0), bar(1, 10.0), bar(2, 20.0)],
+        )]))
+    }
+
+    fn bar(day: u32, close: f64) -> Bar {
+        Bar {
+            timestamp: date(day),
+            close,
+            ..Default::default()
+        }
+    }
+
+    fn date(day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, day, 15, 0, 0)
+            .earliest()
+            .expect("failed to create test date")
+    }
+}
@@ -1,8 +1,9 @@
 use crate::asset::AssetSymbol;
 use crate::platform::bar::Bar;
 use crate::statistics::median;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 pub struct Bars {
     pub symbol: AssetSymbol,
     pub bars: Vec<Bar>,
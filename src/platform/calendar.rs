@@ -0,0 +1,10 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+/// The trading session for a single day.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct MarketDay {
+    pub date: NaiveDate,
+    pub open: DateTime<Utc>,
+    pub close: DateTime<Utc>,
+}
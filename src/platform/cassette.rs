@@ -0,0 +1,209 @@
+use crate::asset::AssetSymbol;
+use crate::error::GreedError;
+use crate::jsonl::read_jsonl;
+use crate::platform::account::Account;
+use crate::platform::asset_filter::AssetFilter;
+use crate::platform::asset_info::AssetInfo;
+use crate::platform::bar::bar_request::BarRequest;
+use crate::platform::bars::Bars;
+use crate::platform::calendar::MarketDay;
+use crate::platform::clock::MarketClock;
+use crate::platform::id::Id;
+use crate::platform::order::Order;
+use crate::platform::position::Position;
+use crate::platform::quote::Quote;
+use crate::platform::request::replace::ReplaceRequest;
+use crate::platform::request::OrderRequest;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+use std::path::Path;
+
+/// A single platform call and its response, stored as one line of a JSONL cassette.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "call", rename_all = "snake_case")]
+#[allow(clippy::large_enum_variant)]
+pub enum CassetteEntry {
+    Account {
+        time: DateTime<Utc>,
+        response: Result<Account, GreedError>,
+    },
+    Bars {
+        time: DateTime<Utc>,
+        request: BarRequest,
+        response: Result<Bars, GreedError>,
+    },
+    LatestQuotes {
+        time: DateTime<Utc>,
+        symbols: Vec<AssetSymbol>,
+        response: Result<Vec<Quote>, GreedError>,
+    },
+    PlaceOrder {
+        time: DateTime<Utc>,
+        request: OrderRequest,
+        response: Result<Order, GreedError>,
+    },
+    Positions {
+        time: DateTime<Utc>,
+        response: Result<Vec<Position>, GreedError>,
+    },
+    OpenOrders {
+        time: DateTime<Utc>,
+        response: Result<Vec<Order>, GreedError>,
+    },
+    RecentOrders {
+        time: DateTime<Utc>,
+        response: Result<Vec<Order>, GreedError>,
+    },
+    CancelOrder {
+        time: DateTime<Utc>,
+        id: Id,
+        response: Result<(), GreedError>,
+    },
+    ReplaceOrder {
+        time: DateTime<Utc>,
+        id: Id,
+        request: ReplaceRequest,
+        response: Result<Order, GreedError>,
+    },
+    ClosePosition {
+        time: DateTime<Utc>,
+        symbol: AssetSymbol,
+        response: Result<Order, GreedError>,
+    },
+    Asset {
+        time: DateTime<Utc>,
+        symbol: AssetSymbol,
+        response: Result<AssetInfo, GreedError>,
+    },
+    Assets {
+        time: DateTime<Utc>,
+        filter: AssetFilter,
+        response: Result<Vec<AssetInfo>, GreedError>,
+    },
+    Clock {
+        time: DateTime<Utc>,
+        response: Result<MarketClock, GreedError>,
+    },
+    Calendar {
+        time: DateTime<Utc>,
+        range: Range<NaiveDate>,
+        response: Result<Vec<MarketDay>, GreedError>,
+    },
+    OrderHistory {
+        time: DateTime<Utc>,
+        range: Range<DateTime<Utc>>,
+        response: Result<Vec<Order>, GreedError>,
+    },
+}
+
+impl CassetteEntry {
+    pub fn time(&self) -> DateTime<Utc> {
+        match self {
+            CassetteEntry::Account { time, .. }
+            | CassetteEntry::Bars { time, .. }
+            | CassetteEntry::LatestQuotes { time, .. }
+            | CassetteEntry::PlaceOrder { time, .. }
+            | CassetteEntry::Positions { time, .. }
+            | CassetteEntry::OpenOrders { time, .. }
+            | CassetteEntry::RecentOrders { time, .. }
+            | CassetteEntry::CancelOrder { time, .. }
+            | CassetteEntry::ReplaceOrder { time, .. }
+            | CassetteEntry::ClosePosition { time, .. }
+            | CassetteEntry::Asset { time, .. }
+            | CassetteEntry::Assets { time, .. }
+            | CassetteEntry::Clock { time, .. }
+            | CassetteEntry::Calendar { time, .. }
+            | CassetteEntry::OrderHistory { time, .. } => *time,
+        }
+    }
+}
+
+/// Reads every entry from a JSONL cassette, skipping blank lines.
+pub fn read_cassette<P: AsRef<Path>>(path: P) -> Result<Vec<CassetteEntry>, GreedError> {
+    read_jsonl(path)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::date::DateTimeFixture;
+    use crate::platform::order::amount::Amount;
+    use crate::platform::quote::Quote;
+
+    #[test]
+    fn serde_round_trip() {
+        let entries = vec![
+            CassetteEntry::Account {
+                time: DateTimeFixture::utc(),
+                response: Ok(Account::fixture()),
+            },
+            CassetteEntry::LatestQuotes {
+                time: DateTimeFixture::utc(),
+                symbols: vec![AssetSymbol::new("VTI")],
+                response: Ok(vec![Quote::fixture(AssetSymbol::new("VTI"))]),
+            },
+            CassetteEntry::PlaceOrder {
+                time: DateTimeFixture::utc(),
+                request: OrderRequest::market_order_buy(
+                    AssetSymbol::new("VTI"),
+                    Amount::Notional(10.0),
+                ),
+                response: Err(GreedError::new("rejected")),
+            },
+            CassetteEntry::CancelOrder {
+                time: DateTimeFixture::utc(),
+                id: Id::default(),
+                response: Ok(()),
+            },
+            CassetteEntry::ReplaceOrder {
+                time: DateTimeFixture::utc(),
+                id: Id::default(),
+                request: ReplaceRequest {
+                    quantity: Some(2.0),
+                    ..Default::default()
+                },
+                response: Ok(Order::fixture(AssetSymbol::new("VTI"))),
+            },
+            CassetteEntry::Asset {
+                time: DateTimeFixture::utc(),
+                symbol: AssetSymbol::new("VTI"),
+                response: Ok(AssetInfo::fixture(AssetSymbol::new("VTI"))),
+            },
+            CassetteEntry::Assets {
+                time: DateTimeFixture::utc(),
+                filter: AssetFilter {
+                    tradable_only: true,
+                    ..Default::default()
+                },
+                response: Ok(vec![AssetInfo::fixture(AssetSymbol::new("VTI"))]),
+            },
+            CassetteEntry::Calendar {
+                time: DateTimeFixture::utc(),
+                range: NaiveDate::from_ymd_opt(2024, 1, 2).unwrap()
+                    ..NaiveDate::from_ymd_opt(2024, 1, 3).unwrap(),
+                response: Ok(vec![MarketDay::default()]),
+            },
+            CassetteEntry::OrderHistory {
+                time: DateTimeFixture::utc(),
+                range: DateTimeFixture::utc()..DateTimeFixture::utc(),
+                response: Ok(vec![Order::fixture(AssetSymbol::new("VTI"))]),
+            },
+        ];
+        for entry in entries {
+            let json = serde_json::to_string(&entry).expect("entry should serialize");
+            let restored: CassetteEntry =
+                serde_json::from_str(&json).expect("entry should deserialize");
+            assert_eq!(restored, entry)
+        }
+    }
+
+    #[test]
+    fn time() {
+        let entry = CassetteEntry::Positions {
+            time: DateTimeFixture::utc(),
+            response: Ok(Vec::new()),
+        };
+        assert_eq!(entry.time(), DateTimeFixture::utc())
+    }
+}
@@ -0,0 +1,63 @@
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+/// Whether the market is open, along with the bounds of the current or next session.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct MarketClock {
+    pub is_open: bool,
+    /// The time the clock was read.
+    pub timestamp: DateTime<Utc>,
+    pub next_open: DateTime<Utc>,
+    pub next_close: DateTime<Utc>,
+}
+
+impl MarketClock {
+    /// How long until the market opens, `None` when it is already open.
+    pub fn time_until_open(&self) -> Option<Duration> {
+        if self.is_open {
+            return None;
+        }
+        (self.next_open - self.timestamp).to_std().ok()
+    }
+}
+
+impl Display for MarketClock {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let format = "%Y-%m-%d %H:%M";
+        if self.is_open {
+            let close = self.next_close.with_timezone(&Local).format(format);
+            write!(f, "open until {close}")
+        } else {
+            let open = self.next_open.with_timezone(&Local).format(format);
+            write!(f, "closed until {open}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date::DateTimeFixture;
+
+    #[test]
+    fn time_until_open_closed() {
+        let clock = MarketClock {
+            is_open: false,
+            timestamp: DateTimeFixture::utc(),
+            next_open: DateTimeFixture::utc() + chrono::Duration::hours(2),
+            next_close: DateTimeFixture::utc() + chrono::Duration::hours(8),
+        };
+        assert_eq!(clock.time_until_open(), Some(Duration::from_secs(7200)))
+    }
+
+    #[test]
+    fn time_until_open_open() {
+        let clock = MarketClock {
+            is_open: true,
+            ..Default::default()
+        };
+        assert_eq!(clock.time_until_open(), None)
+    }
+}
@@ -0,0 +1,218 @@
+use crate::asset::AssetSymbol;
+use crate::error::GreedError;
+use crate::platform::account::Account;
+use crate::platform::asset_filter::AssetFilter;
+use crate::platform::asset_info::AssetInfo;
+use crate::platform::bar::bar_request::BarRequest;
+use crate::platform::bars::Bars;
+use crate::platform::calendar::MarketDay;
+use crate::platform::clock::MarketClock;
+use crate::platform::id::Id;
+use crate::platform::order::amount::Amount;
+use crate::platform::order::side::OrderSide;
+use crate::platform::order::status::Status;
+use crate::platform::order::Order;
+use crate::platform::position::Position;
+use crate::platform::quote::Quote;
+use crate::platform::request::replace::ReplaceRequest;
+use crate::platform::request::OrderRequest;
+use crate::platform::FinancialPlatform;
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use log::info;
+use std::ops::Range;
+use std::sync::Arc;
+
+/// Wraps a platform so everything is read from it as usual but orders are only logged.
+pub struct DryRunPlatform {
+    platform: Arc<dyn FinancialPlatform>,
+}
+
+impl DryRunPlatform {
+    pub fn new(platform: Arc<dyn FinancialPlatform>) -> Self {
+        Self { platform }
+    }
+}
+
+#[async_trait]
+impl FinancialPlatform for DryRunPlatform {
+    async fn account(&self) -> Result<Account, GreedError> {
+        self.platform.account().await
+    }
+
+    async fn bars(&self, bars_request: BarRequest) -> Result<Bars, GreedError> {
+        self.platform.bars(bars_request).await
+    }
+
+    async fn latest_quotes(&self, symbols: &[AssetSymbol]) -> Result<Vec<Quote>, GreedError> {
+        self.platform.latest_quotes(symbols).await
+    }
+
+    async fn place_order(&self, order_request: OrderRequest) -> Result<Order, GreedError> {
+        info!("🧪 dry run, would place order: {order_request}");
+        let now = self.platform.now();
+        Ok(Order {
+            status: Status::New,
+            created_at: now,
+            submitted_at: Some(now),
+            symbol: order_request.symbol,
+            amount: order_request.amount,
+            order_type: order_request.order_type,
+            class: order_request.class,
+            side: order_request.side,
+            time_in_force: order_request.time_in_force,
+            limit_price: order_request.limit_price,
+            stop_price: order_request.stop_price,
+            trail_price: order_request.trail_price,
+            trail_percent: order_request.trail_percent,
+            extended_hours: order_request.extended_hours,
+            client_order_id: order_request.client_order_id,
+            ..Default::default()
+        })
+    }
+
+    async fn positions(&self) -> Result<Vec<Position>, GreedError> {
+        self.platform.positions().await
+    }
+
+    async fn open_orders(&self) -> Result<Vec<Order>, GreedError> {
+        self.platform.open_orders().await
+    }
+
+    async fn recent_orders(&self) -> Result<Vec<Order>, GreedError> {
+        self.platform.recent_orders().await
+    }
+
+    async fn cancel_order(&self, id: &Id) -> Result<(), GreedError> {
+        info!("🧪 dry run, would cancel order: {id}");
+        Ok(())
+    }
+
+    async fn replace_order(&self, id: &Id, request: ReplaceRequest) -> Result<Order, GreedError> {
+        info!("🧪 dry run, would replace order {id} with: {request}");
+        let order = self
+            .platform
+            .open_orders()
+            .await?
+            .into_iter()
+            .find(|o| &o.id == id)
+            .ok_or(GreedError::new(&format!("no open order {id}")))?;
+        Ok(request.apply_to(&order))
+    }
+
+    async fn close_position(&self, symbol: &AssetSymbol) -> Result<Order, GreedError> {
+        info!("🧪 dry run, would close position: {symbol}");
+        let position = self
+            .platform
+            .positions()
+            .await?
+            .into_iter()
+            .find(|p| &p.symbol == symbol)
+            .ok_or(GreedError::new(&format!(
+                "no position to close for {symbol}"
+            )))?;
+        // Shorts carry a negative quantity and are closed by buying them back.
+        let side = if position.is_short() {
+            OrderSide::Buy
+        } else {
+            OrderSide::Sell
+        };
+        let now = self.platform.now();
+        Ok(Order {
+            status: Status::New,
+            created_at: now,
+            submitted_at: Some(now),
+            symbol: symbol.clone(),
+            amount: Amount::Quantity(position.quantity.abs()),
+            side,
+            ..Default::default()
+        })
+    }
+
+    async fn order_history(&self, range: Range<DateTime<Utc>>) -> Result<Vec<Order>, GreedError> {
+        self.platform.order_history(range).await
+    }
+
+    async fn asset(&self, symbol: &AssetSymbol) -> Result<AssetInfo, GreedError> {
+        self.platform.asset(symbol).await
+    }
+
+    async fn assets(&self, filter: AssetFilter) -> Result<Vec<AssetInfo>, GreedError> {
+        self.platform.assets(filter).await
+    }
+
+    async fn clock(&self) -> Result<MarketClock, GreedError> {
+        self.platform.clock().await
+    }
+
+    async fn calendar(&self, range: Range<NaiveDate>) -> Result<Vec<MarketDay>, GreedError> {
+        self.platform.calendar(range).await
+    }
+
+    fn now(&self) -> DateTime<Utc> {
+        self.platform.now()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::platform::side::Side;
+    use crate::platform::MockPlatform;
+
+    #[tokio::test]
+    async fn place_order_is_not_forwarded() {
+        let mock = Arc::new(MockPlatform::new());
+        let platform = DryRunPlatform::new(mock.clone());
+        let request =
+            OrderRequest::market_order_buy(AssetSymbol::new("VTI"), Amount::Quantity(1.0));
+
+        let order = platform.place_order(request).await.unwrap();
+
+        assert_eq!(order.symbol, AssetSymbol::new("VTI"));
+        assert_eq!(order.amount, Amount::Quantity(1.0));
+        assert_eq!(order.status, Status::New);
+        assert!(mock.placed_orders().is_empty());
+    }
+
+    #[tokio::test]
+    async fn cancel_and_close_are_not_forwarded() {
+        let vti = AssetSymbol::new("VTI");
+        let mock =
+            Arc::new(MockPlatform::new().with_positions(vec![Position::fixture(vti.clone())]));
+        let platform = DryRunPlatform::new(mock.clone());
+
+        platform.cancel_all_orders().await.unwrap();
+        platform.cancel_order(&Id::default()).await.unwrap();
+        let order = platform.close_position(&vti).await.unwrap();
+
+        assert_eq!(order.side, OrderSide::Sell);
+        assert!(mock.canceled_orders().is_empty());
+        assert!(mock.closed_positions().is_empty());
+    }
+
+    #[tokio::test]
+    async fn close_short_buys_to_cover() {
+        let vti = AssetSymbol::new("VTI");
+        let short = Position {
+            quantity: -10.0,
+            side: Side::Short,
+            ..Position::fixture(vti.clone())
+        };
+        let mock = Arc::new(MockPlatform::new().with_positions(vec![short]));
+        let platform = DryRunPlatform::new(mock.clone());
+
+        let order = platform.close_position(&vti).await.unwrap();
+
+        assert_eq!(order.side, OrderSide::Buy);
+        assert_eq!(order.amount, Amount::Quantity(10.0));
+        assert!(mock.closed_positions().is_empty());
+    }
+
+    #[tokio::test]
+    async fn reads_are_forwarded() {
+        let account = Account::fixture();
+        let platform = DryRunPlatform::new(MockPlatform::new().with_account(account.clone()).arc());
+        assert_eq!(platform.account().await, Ok(account));
+    }
+}
@@ -1,6 +1,9 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
 use uuid::Uuid;
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Id {
     Uuid(Uuid),
 }
@@ -10,3 +13,22 @@ impl Default for Id {
         Id::Uuid(Default::default())
     }
 }
+
+impl Display for Id {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Id::Uuid(uuid) => write!(f, "{uuid}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let id = Id::Uuid(Uuid::from_u128(1));
+        assert_eq!(id.to_string(), "00000000-0000-0000-0000-000000000001")
+    }
+}
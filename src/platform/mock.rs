@@ -3,9 +3,11 @@ use crate::error::GreedError;
 use crate::platform::account::Account;
 use crate::platform::bar::bar_request::BarRequest;
 use crate::platform::bars::Bars;
+use crate::platform::id::Id;
 use crate::platform::order::Order;
 use crate::platform::position::Position;
 use crate::platform::quote::Quote;
+use crate::platform::request::replace::ReplaceRequest;
 use crate::platform::request::OrderRequest;
 use crate::platform::FinancialPlatform;
 use async_trait::async_trait;
@@ -19,6 +21,16 @@ pub struct MockPlatform {
     quotes: Vec<Quote>,
     place_order_result: Order,
     placed_orders: Mutex<Vec<OrderRequest>>,
+    canceled_orders: Mutex<Vec<Id>>,
+    closed_positions: Mutex<Vec<AssetSymbol>>,
+    /// Leaves canceled orders open, like a broker which hasn't acknowledged the cancel yet.
+    stuck_cancels: bool,
+}
+
+impl Default for MockPlatform {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl MockPlatform {
@@ -31,6 +43,9 @@ impl MockPlatform {
             quotes: Vec::new(),
             place_order_result: Order::default(),
             placed_orders: Mutex::new(Vec::new()),
+            canceled_orders: Mutex::new(Vec::new()),
+            closed_positions: Mutex::new(Vec::new()),
+            stuck_cancels: false,
         }
     }
 
@@ -64,10 +79,23 @@ impl MockPlatform {
         self
     }
 
+    pub fn with_stuck_cancels(mut self) -> Self {
+        self.stuck_cancels = true;
+        self
+    }
+
     pub fn placed_orders(&self) -> Vec<OrderRequest> {
         self.placed_orders.lock().unwrap().clone()
     }
 
+    pub fn canceled_orders(&self) -> Vec<Id> {
+        self.canceled_orders.lock().unwrap().clone()
+    }
+
+    pub fn closed_positions(&self) -> Vec<AssetSymbol> {
+        self.closed_positions.lock().unwrap().clone()
+    }
+
     pub fn arc(self) -> Arc<dyn FinancialPlatform> {
         Arc::new(self)
     }
@@ -97,10 +125,34 @@ impl FinancialPlatform for MockPlatform {
     }
 
     async fn open_orders(&self) -> Result<Vec<Order>, GreedError> {
-        Ok(self.open_orders.clone())
+        let canceled = self.canceled_orders.lock().unwrap();
+        Ok(self
+            .open_orders
+            .iter()
+            .filter(|o| self.stuck_cancels || !canceled.contains(&o.id))
+            .cloned()
+            .collect())
     }
 
     async fn recent_orders(&self) -> Result<Vec<Order>, GreedError> {
         Ok(self.recent_orders.clone())
     }
+
+    async fn cancel_order(&self, id: &Id) -> Result<(), GreedError> {
+        self.canceled_orders.lock().unwrap().push(id.clone());
+        Ok(())
+    }
+
+    async fn replace_order(&self, id: &Id, request: ReplaceRequest) -> Result<Order, GreedError> {
+        self.open_orders
+            .iter()
+            .find(|o| &o.id == id)
+            .map(|o| request.apply_to(o))
+            .ok_or(GreedError::new(&format!("no open order {id}")))
+    }
+
+    async fn close_position(&self, symbol: &AssetSymbol) -> Result<Order, GreedError> {
+        self.closed_positions.lock().unwrap().push(symbol.clone());
+        Ok(self.place_order_result.clone())
+    }
 }
@@ -3,9 +3,11 @@ use crate::error::GreedError;
 use crate::platform::account::Account;
 use crate::platform::bar::bar_request::BarRequest;
 use crate::platform::bars::Bars;
+use crate::platform::id::Id;
 use crate::platform::order::Order;
 use crate::platform::position::Position;
 use crate::platform::quote::Quote;
+use crate::platform::request::replace::ReplaceRequest;
 use crate::platform::request::OrderRequest;
 use crate::platform::FinancialPlatform;
 use async_trait::async_trait;
@@ -53,6 +55,18 @@ impl FinancialPlatform for NoOpPlatform {
     async fn recent_orders(&self) -> Result<Vec<Order>, GreedError> {
         Ok(Vec::new())
     }
+
+    async fn cancel_order(&self, _id: &Id) -> Result<(), GreedError> {
+        Ok(())
+    }
+
+    async fn replace_order(&self, _id: &Id, _request: ReplaceRequest) -> Result<Order, GreedError> {
+        Ok(Order::default())
+    }
+
+    async fn close_position(&self, _symbol: &AssetSymbol) -> Result<Order, GreedError> {
+        Ok(Order::default())
+    }
 }
 
 #[cfg(test)]
@@ -103,4 +117,27 @@ mod tests {
         let result = platform.open_orders().await;
         assert!(result.unwrap().is_empty());
     }
+
+    #[tokio::test]
+    async fn test_cancel_order() {
+        let platform = NoOpPlatform::new();
+        let result = platform.cancel_order(&Id::default()).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_replace_order() {
+        let platform = NoOpPlatform::new();
+        let result = platform
+            .replace_order(&Id::default(), ReplaceRequest::default())
+            .await;
+        assert_eq!(result.unwrap(), Order::default());
+    }
+
+    #[tokio::test]
+    async fn test_close_position() {
+        let platform = NoOpPlatform::new();
+        let result = platform.close_position(&AssetSymbol::new("VTI")).await;
+        assert_eq!(result.unwrap(), Order::default());
+    }
 }
@@ -1,6 +1,8 @@
+use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Amount {
     Quantity(f64),
     Notional(f64),
@@ -28,6 +30,21 @@ impl Amount {
             Amount::Notional(num) => *num == 0.0,
         }
     }
+
+    /// The number of units or dollars, whichever the amount is in.
+    pub fn value(&self) -> f64 {
+        match self {
+            Amount::Quantity(value) | Amount::Notional(value) => *value,
+        }
+    }
+
+    /// An amount in the same terms holding `value` instead.
+    pub fn with_value(&self, value: f64) -> Self {
+        match self {
+            Amount::Quantity(_) => Amount::Quantity(value),
+            Amount::Notional(_) => Amount::Notional(value),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -75,4 +92,19 @@ mod test {
         let amount = Amount::Quantity(100.0);
         assert!(!amount.is_empty())
     }
+
+    #[test]
+    fn value() {
+        assert_eq!(Amount::Quantity(5.0).value(), 5.0);
+        assert_eq!(Amount::Notional(10.0).value(), 10.0);
+    }
+
+    #[test]
+    fn with_value() {
+        assert_eq!(Amount::Quantity(5.0).with_value(2.0), Amount::Quantity(2.0));
+        assert_eq!(
+            Amount::Notional(10.0).with_value(2.0),
+            Amount::Notional(2.0)
+        );
+    }
 }
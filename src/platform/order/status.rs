@@ -1,6 +1,8 @@
 use crate::lowercase_enum_display;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Status {
     New,
     /// The order has changed.
@@ -51,6 +53,20 @@ pub enum Status {
     Unknown,
 }
 
+impl Status {
+    /// Whether the order is done and will not receive any further updates.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            Status::Replaced
+                | Status::Filled
+                | Status::Canceled
+                | Status::Expired
+                | Status::Rejected
+        )
+    }
+}
+
 lowercase_enum_display!(Status);
 
 #[cfg(test)]
@@ -63,6 +79,14 @@ mod test {
         assert_eq!(status_str, "new")
     }
 
+    #[test]
+    fn is_terminal() {
+        assert!(Status::Filled.is_terminal());
+        assert!(Status::Canceled.is_terminal());
+        assert!(!Status::New.is_terminal());
+        assert!(!Status::PartiallyFilled.is_terminal());
+    }
+
     #[test]
     fn default() {
         let status: Status = Default::default();
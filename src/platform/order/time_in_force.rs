@@ -1,4 +1,8 @@
-#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+use crate::asset::AssetSymbol;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum TimeInForce {
     /// The order is good for the day, and it will be canceled
     /// automatically at the end of Regular Trading Hours if unfilled.
@@ -20,8 +24,21 @@ pub enum TimeInForce {
     UntilMarketClose,
 }
 
+impl TimeInForce {
+    /// The time in force for a plain order of the asset. Crypto trades around the clock, so it has
+    /// no day to be good for and Alpaca rejects day orders for it.
+    pub fn for_symbol(symbol: &AssetSymbol) -> Self {
+        if symbol.is_crypto() {
+            Self::UntilCanceled
+        } else {
+            Self::Day
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use crate::asset::AssetSymbol;
     use crate::platform::order::time_in_force::TimeInForce;
 
     #[test]
@@ -29,4 +46,16 @@ mod test {
         let status: TimeInForce = Default::default();
         assert_eq!(status, TimeInForce::Day)
     }
+
+    #[test]
+    fn for_symbol_crypto() {
+        let time_in_force = TimeInForce::for_symbol(&AssetSymbol::new("BTC/USD"));
+        assert_eq!(time_in_force, TimeInForce::UntilCanceled)
+    }
+
+    #[test]
+    fn for_symbol_equity() {
+        let time_in_force = TimeInForce::for_symbol(&AssetSymbol::new("VTI"));
+        assert_eq!(time_in_force, TimeInForce::Day)
+    }
 }
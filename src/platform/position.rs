@@ -6,8 +6,9 @@ use crate::asset::AssetSymbol;
 use crate::platform::asset_class::AssetClass;
 use crate::platform::id::Id;
 use crate::platform::side::Side;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 pub struct Position {
     pub id: Id,
     /// Asset class of the position
@@ -47,6 +48,43 @@ trait NumFormatter {
 }
 
 impl Position {
+    /// The current price per share, derived from the market value when the price is missing.
+    pub fn price(&self) -> Option<f64> {
+        self.current_price.or_else(|| {
+            let market_value = self.market_value?;
+            // Short positions carry a negative quantity and market value.
+            (self.quantity != 0.0).then(|| market_value / self.quantity)
+        })
+    }
+
+    pub fn is_long(&self) -> bool {
+        self.side == Side::Long
+    }
+
+    pub fn is_short(&self) -> bool {
+        self.side == Side::Short
+    }
+
+    /// The total unrealized gain percent. It's worked out from the price against the average entry
+    /// when both are known, so a short gains as the price falls, otherwise the reported percent is
+    /// used.
+    pub fn gain_percent(&self) -> Option<f64> {
+        let entry = self.average_entry_price;
+        let Some(price) = self.price().filter(|p| *p > 0.0 && entry > 0.0) else {
+            return self.unrealized_gain_total_percent;
+        };
+        let change = (price - entry) / entry * 100.0;
+        match self.side {
+            Side::Long => Some(change),
+            Side::Short => Some(-change),
+        }
+    }
+
+    /// The value of the shares which aren't already covered by open orders.
+    pub fn available_value(&self) -> Option<f64> {
+        self.price().map(|price| price * self.quantity_available)
+    }
+
     #[cfg(test)]
     pub fn fixture(symbol: AssetSymbol) -> Self {
         Self {
@@ -101,6 +139,81 @@ impl Display for Position {
 mod test {
     use crate::asset::AssetSymbol;
     use crate::platform::position::Position;
+    use crate::platform::side::Side;
+
+    #[test]
+    fn price_current_price() {
+        let position = Position {
+            current_price: Some(10.0),
+            market_value: Some(1000.0),
+            ..Position::fixture(AssetSymbol::new("VTI"))
+        };
+        assert_eq!(position.price(), Some(10.0))
+    }
+
+    #[test]
+    fn price_from_market_value() {
+        let position = Position {
+            market_value: Some(1000.0),
+            ..Position::fixture(AssetSymbol::new("VTI"))
+        };
+        assert_eq!(position.price(), Some(20.0))
+    }
+
+    #[test]
+    fn price_short_from_market_value() {
+        let position = Position {
+            market_value: Some(-1000.0),
+            quantity: -50.0,
+            side: Side::Short,
+            ..Position::fixture(AssetSymbol::new("VTI"))
+        };
+        assert_eq!(position.price(), Some(20.0))
+    }
+
+    #[test]
+    fn gain_percent_long() {
+        let position = Position {
+            average_entry_price: 100.0,
+            current_price: Some(110.0),
+            ..Position::fixture(AssetSymbol::new("VTI"))
+        };
+        assert_eq!(position.gain_percent(), Some(10.0))
+    }
+
+    #[test]
+    fn gain_percent_short() {
+        let position = Position {
+            average_entry_price: 100.0,
+            current_price: Some(110.0),
+            side: Side::Short,
+            ..Position::fixture(AssetSymbol::new("VTI"))
+        };
+        assert_eq!(position.gain_percent(), Some(-10.0))
+    }
+
+    #[test]
+    fn gain_percent_reported() {
+        let position = Position {
+            unrealized_gain_total_percent: Some(5.0),
+            ..Position::fixture(AssetSymbol::new("VTI"))
+        };
+        assert_eq!(position.gain_percent(), Some(5.0))
+    }
+
+    #[test]
+    fn available_value() {
+        let position = Position {
+            current_price: Some(10.0),
+            quantity_available: 20.0,
+            ..Position::fixture(AssetSymbol::new("VTI"))
+        };
+        assert_eq!(position.available_value(), Some(200.0));
+        assert_eq!(
+            Position::fixture(AssetSymbol::new("VTI")).available_value(),
+            None
+        );
+    }
 
     #[test]
     fn display_all_optionals_present() {
@@ -3,8 +3,9 @@ use std::fmt::{Display, Formatter};
 use chrono::{DateTime, Utc};
 
 use crate::asset::AssetSymbol;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 pub struct Quote {
     pub time: DateTime<Utc>,
     /// The ask price.
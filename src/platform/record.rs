@@ -0,0 +1,236 @@
+use crate::asset::AssetSymbol;
+use crate::error::GreedError;
+use crate::platform::account::Account;
+use crate::platform::asset_filter::AssetFilter;
+use crate::platform::asset_info::AssetInfo;
+use crate::platform::bar::bar_request::BarRequest;
+use crate::platform::bars::Bars;
+use crate::platform::calendar::MarketDay;
+use crate::platform::cassette::CassetteEntry;
+use crate::platform::clock::MarketClock;
+use crate::platform::id::Id;
+use crate::platform::order::Order;
+use crate::platform::position::Position;
+use crate::platform::quote::Quote;
+use crate::platform::request::replace::ReplaceRequest;
+use crate::platform::request::OrderRequest;
+use crate::platform::FinancialPlatform;
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use log::warn;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// Wraps a platform and appends every call and response to a JSONL cassette which
+/// `ReplayPlatform` can later feed back.
+pub struct RecordingPlatform {
+    platform: Arc<dyn FinancialPlatform>,
+    cassette: Mutex<File>,
+}
+
+impl RecordingPlatform {
+    pub fn new<P: AsRef<Path>>(
+        platform: Arc<dyn FinancialPlatform>,
+        path: P,
+    ) -> Result<Self, GreedError> {
+        let cassette = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            platform,
+            cassette: Mutex::new(cassette),
+        })
+    }
+
+    fn record(&self, entry: CassetteEntry) {
+        // A recording failure shouldn't interrupt trading, so we only warn about it.
+        let result = serde_json::to_string(&entry)
+            .map_err(GreedError::from)
+            .and_then(|json| {
+                let mut cassette = self.cassette.lock().unwrap();
+                writeln!(cassette, "{json}")?;
+                Ok(())
+            });
+        if let Err(e) = result {
+            warn!("failed to record platform call: {e}")
+        }
+    }
+
+    fn recorded<T: Clone>(
+        &self,
+        response: Result<T, GreedError>,
+        entry: impl FnOnce(DateTime<Utc>, Result<T, GreedError>) -> CassetteEntry,
+    ) -> Result<T, GreedError> {
+        let time = self.platform.now();
+        self.record(entry(time, response.clone()));
+        response
+    }
+}
+
+#[async_trait]
+impl FinancialPlatform for RecordingPlatform {
+    async fn account(&self) -> Result<Account, GreedError> {
+        let response = self.platform.account().await;
+        self.recorded(response, |time, response| CassetteEntry::Account {
+            time,
+            response,
+        })
+    }
+
+    async fn bars(&self, bars_request: BarRequest) -> Result<Bars, GreedError> {
+        let response = self.platform.bars(bars_request.clone()).await;
+        self.recorded(response, |time, response| CassetteEntry::Bars {
+            time,
+            request: bars_request,
+            response,
+        })
+    }
+
+    async fn latest_quotes(&self, symbols: &[AssetSymbol]) -> Result<Vec<Quote>, GreedError> {
+        let response = self.platform.latest_quotes(symbols).await;
+        self.recorded(response, |time, response| CassetteEntry::LatestQuotes {
+            time,
+            symbols: symbols.to_vec(),
+            response,
+        })
+    }
+
+    async fn place_order(&self, order_request: OrderRequest) -> Result<Order, GreedError> {
+        let response = self.platform.place_order(order_request.clone()).await;
+        self.recorded(response, |time, response| CassetteEntry::PlaceOrder {
+            time,
+            request: order_request,
+            response,
+        })
+    }
+
+    async fn positions(&self) -> Result<Vec<Position>, GreedError> {
+        let response = self.platform.positions().await;
+        self.recorded(response, |time, response| CassetteEntry::Positions {
+            time,
+            response,
+        })
+    }
+
+    async fn open_orders(&self) -> Result<Vec<Order>, GreedError> {
+        let response = self.platform.open_orders().await;
+        self.recorded(response, |time, response| CassetteEntry::OpenOrders {
+            time,
+            response,
+        })
+    }
+
+    async fn recent_orders(&self) -> Result<Vec<Order>, GreedError> {
+        let response = self.platform.recent_orders().await;
+        self.recorded(response, |time, response| CassetteEntry::RecentOrders {
+            time,
+            response,
+        })
+    }
+
+    async fn cancel_order(&self, id: &Id) -> Result<(), GreedError> {
+        let response = self.platform.cancel_order(id).await;
+        self.recorded(response, |time, response| CassetteEntry::CancelOrder {
+            time,
+            id: id.clone(),
+            response,
+        })
+    }
+
+    async fn replace_order(&self, id: &Id, request: ReplaceRequest) -> Result<Order, GreedError> {
+        let response = self.platform.replace_order(id, request.clone()).await;
+        self.recorded(response, |time, response| CassetteEntry::ReplaceOrder {
+            time,
+            id: id.clone(),
+            request,
+            response,
+        })
+    }
+
+    async fn close_position(&self, symbol: &AssetSymbol) -> Result<Order, GreedError> {
+        let response = self.platform.close_position(symbol).await;
+        self.recorded(response, |time, response| CassetteEntry::ClosePosition {
+            time,
+            symbol: symbol.clone(),
+            response,
+        })
+    }
+
+    async fn asset(&self, symbol: &AssetSymbol) -> Result<AssetInfo, GreedError> {
+        let response = self.platform.asset(symbol).await;
+        self.recorded(response, |time, response| CassetteEntry::Asset {
+            time,
+            symbol: symbol.clone(),
+            response,
+        })
+    }
+
+    async fn assets(&self, filter: AssetFilter) -> Result<Vec<AssetInfo>, GreedError> {
+        let response = self.platform.assets(filter.clone()).await;
+        self.recorded(response, |time, response| CassetteEntry::Assets {
+            time,
+            filter,
+            response,
+        })
+    }
+
+    async fn clock(&self) -> Result<MarketClock, GreedError> {
+        let response = self.platform.clock().await;
+        self.recorded(response, |time, response| CassetteEntry::Clock {
+            time,
+            response,
+        })
+    }
+
+    async fn order_history(&self, range: Range<DateTime<Utc>>) -> Result<Vec<Order>, GreedError> {
+        let response = self.platform.order_history(range.clone()).await;
+        self.recorded(response, |time, response| CassetteEntry::OrderHistory {
+            time,
+            range,
+            response,
+        })
+    }
+
+    async fn calendar(&self, range: Range<NaiveDate>) -> Result<Vec<MarketDay>, GreedError> {
+        let response = self.platform.calendar(range.clone()).await;
+        self.recorded(response, |time, response| CassetteEntry::Calendar {
+            time,
+            range,
+            response,
+        })
+    }
+
+    fn now(&self) -> DateTime<Utc> {
+        self.platform.now()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::platform::cassette::read_cassette;
+    use crate::platform::MockPlatform;
+    use std::env;
+    use std::fs;
+
+    #[tokio::test]
+    async fn records_calls() {
+        let path = env::temp_dir().join(format!("greed_record_{}.jsonl", std::process::id()));
+        let account = Account::fixture();
+        let mock = MockPlatform::new().with_account(account.clone());
+        let platform = RecordingPlatform::new(mock.arc(), &path).unwrap();
+
+        platform.account().await.unwrap();
+        platform.positions().await.unwrap();
+
+        let entries = read_cassette(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(matches!(
+            &entries[0],
+            CassetteEntry::Account { response: Ok(a), .. } if *a == account
+        ));
+        assert!(matches!(&entries[1], CassetteEntry::Positions { .. }));
+    }
+}
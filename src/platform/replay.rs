@@ -0,0 +1,374 @@
+use crate::asset::AssetSymbol;
+use crate::error::GreedError;
+use crate::platform::account::Account;
+use crate::platform::asset_filter::AssetFilter;
+use crate::platform::asset_info::AssetInfo;
+use crate::platform::bar::bar_request::BarRequest;
+use crate::platform::bars::Bars;
+use crate::platform::calendar::MarketDay;
+use crate::platform::cassette::{read_cassette, CassetteEntry};
+use crate::platform::clock::MarketClock;
+use crate::platform::id::Id;
+use crate::platform::order::Order;
+use crate::platform::position::Position;
+use crate::platform::quote::Quote;
+use crate::platform::request::replace::ReplaceRequest;
+use crate::platform::request::OrderRequest;
+use crate::platform::FinancialPlatform;
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use log::warn;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Feeds back the responses stored in a cassette written by `RecordingPlatform`.
+///
+/// Each call consumes the first unused entry of the same kind. Bars and quotes are also matched
+/// by symbol so analysis sees the same data even if calls happen in a different order. Order
+/// history and calendar ranges are built from the clock, which only replays at the recorded call
+/// times, so those are matched by kind alone.
+pub struct ReplayPlatform {
+    state: Mutex<ReplayState>,
+}
+
+struct ReplayState {
+    entries: Vec<CassetteEntry>,
+    now: DateTime<Utc>,
+}
+
+impl ReplayPlatform {
+    pub fn new(entries: Vec<CassetteEntry>) -> Self {
+        let now = entries.first().map(|e| e.time()).unwrap_or_else(Utc::now);
+        Self {
+            state: Mutex::new(ReplayState { entries, now }),
+        }
+    }
+
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, GreedError> {
+        Ok(Self::new(read_cassette(path)?))
+    }
+
+    fn take<F>(&self, call: &str, matches: F) -> Result<CassetteEntry, GreedError>
+    where
+        F: Fn(&CassetteEntry) -> bool,
+    {
+        let mut state = self.state.lock().unwrap();
+        let index = state
+            .entries
+            .iter()
+            .position(matches)
+            .ok_or(GreedError::new(&format!(
+                "no recorded response left for {call}"
+            )))?;
+        let entry = state.entries.remove(index);
+        state.now = entry.time();
+        Ok(entry)
+    }
+}
+
+#[async_trait]
+impl FinancialPlatform for ReplayPlatform {
+    async fn account(&self) -> Result<Account, GreedError> {
+        match self.take("account", |e| matches!(e, CassetteEntry::Account { .. }))? {
+            CassetteEntry::Account { response, .. } => response,
+            _ => unreachable!(),
+        }
+    }
+
+    async fn bars(&self, bars_request: BarRequest) -> Result<Bars, GreedError> {
+        let entry = self.take("bars", |e| {
+            matches!(e, CassetteEntry::Bars { request, .. }
+                if request.symbol == bars_request.symbol
+                    && request.timeframe == bars_request.timeframe)
+        })?;
+        match entry {
+            CassetteEntry::Bars { response, .. } => response,
+            _ => unreachable!(),
+        }
+    }
+
+    async fn latest_quotes(&self, symbols: &[AssetSymbol]) -> Result<Vec<Quote>, GreedError> {
+        let entry = self.take("latest_quotes", |e| {
+            matches!(e, CassetteEntry::LatestQuotes { symbols: recorded, .. }
+                if recorded == symbols)
+        })?;
+        match entry {
+            CassetteEntry::LatestQuotes { response, .. } => response,
+            _ => unreachable!(),
+        }
+    }
+
+    async fn place_order(&self, order_request: OrderRequest) -> Result<Order, GreedError> {
+        let entry = self.take("place_order", |e| {
+            matches!(e, CassetteEntry::PlaceOrder { .. })
+        })?;
+        match entry {
+            CassetteEntry::PlaceOrder {
+                request, response, ..
+            } => {
+                if request != order_request {
+                    warn!("replayed order differs from recording: {order_request} vs {request}")
+                }
+                response
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    async fn positions(&self) -> Result<Vec<Position>, GreedError> {
+        match self.take("positions", |e| {
+            matches!(e, CassetteEntry::Positions { .. })
+        })? {
+            CassetteEntry::Positions { response, .. } => response,
+            _ => unreachable!(),
+        }
+    }
+
+    async fn open_orders(&self) -> Result<Vec<Order>, GreedError> {
+        match self.take("open_orders", |e| {
+            matches!(e, CassetteEntry::OpenOrders { .. })
+        })? {
+            CassetteEntry::OpenOrders { response, .. } => response,
+            _ => unreachable!(),
+        }
+    }
+
+    async fn recent_orders(&self) -> Result<Vec<Order>, GreedError> {
+        match self.take("recent_orders", |e| {
+            matches!(e, CassetteEntry::RecentOrders { .. })
+        })? {
+            CassetteEntry::RecentOrders { response, .. } => response,
+            _ => unreachable!(),
+        }
+    }
+
+    async fn cancel_order(&self, id: &Id) -> Result<(), GreedError> {
+        let entry = self.take(
+            "cancel_order",
+            |e| matches!(e, CassetteEntry::CancelOrder { id: recorded, .. } if recorded == id),
+        )?;
+        match entry {
+            CassetteEntry::CancelOrder { response, .. } => response,
+            _ => unreachable!(),
+        }
+    }
+
+    async fn replace_order(&self, id: &Id, request: ReplaceRequest) -> Result<Order, GreedError> {
+        let entry = self.take(
+            "replace_order",
+            |e| matches!(e, CassetteEntry::ReplaceOrder { id: recorded, .. } if recorded == id),
+        )?;
+        match entry {
+            CassetteEntry::ReplaceOrder {
+                request: recorded,
+                response,
+                ..
+            } => {
+                if recorded != request {
+                    warn!("replayed replacement differs from recording: {request} vs {recorded}")
+                }
+                response
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    async fn close_position(&self, symbol: &AssetSymbol) -> Result<Order, GreedError> {
+        let entry = self.take("close_position", |e| {
+            matches!(e, CassetteEntry::ClosePosition { symbol: recorded, .. }
+                if recorded == symbol)
+        })?;
+        match entry {
+            CassetteEntry::ClosePosition { response, .. } => response,
+            _ => unreachable!(),
+        }
+    }
+
+    async fn asset(&self, symbol: &AssetSymbol) -> Result<AssetInfo, GreedError> {
+        let entry = self.take(
+            "asset",
+            |e| matches!(e, CassetteEntry::Asset { symbol: recorded, .. } if recorded == symbol),
+        )?;
+        match entry {
+            CassetteEntry::Asset { response, .. } => response,
+            _ => unreachable!(),
+        }
+    }
+
+    async fn assets(&self, filter: AssetFilter) -> Result<Vec<AssetInfo>, GreedError> {
+        let entry = self.take(
+            "assets",
+            |e| matches!(e, CassetteEntry::Assets { filter: recorded, .. } if *recorded == filter),
+        )?;
+        match entry {
+            CassetteEntry::Assets { response, .. } => response,
+            _ => unreachable!(),
+        }
+    }
+
+    async fn clock(&self) -> Result<MarketClock, GreedError> {
+        match self.take("clock", |e| matches!(e, CassetteEntry::Clock { .. }))? {
+            CassetteEntry::Clock { response, .. } => response,
+            _ => unreachable!(),
+        }
+    }
+
+    async fn order_history(&self, _range: Range<DateTime<Utc>>) -> Result<Vec<Order>, GreedError> {
+        let entry = self.take("order history", |e| {
+            matches!(e, CassetteEntry::OrderHistory { .. })
+        })?;
+        match entry {
+            CassetteEntry::OrderHistory { response, .. } => response,
+            _ => unreachable!(),
+        }
+    }
+
+    async fn calendar(&self, _range: Range<NaiveDate>) -> Result<Vec<MarketDay>, GreedError> {
+        match self.take("calendar", |e| matches!(e, CassetteEntry::Calendar { .. }))? {
+            CassetteEntry::Calendar { response, .. } => response,
+            _ => unreachable!(),
+        }
+    }
+
+    fn now(&self) -> DateTime<Utc> {
+        self.state.lock().unwrap().now
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::tactic::TacticConfig;
+    use crate::date::DateTimeFixture;
+    use crate::platform::bar::Bar;
+    use crate::platform::record::RecordingPlatform;
+    use crate::platform::MockPlatform;
+    use crate::tactic::TacticRunner;
+    use std::env;
+    use std::fs;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn account_replays_in_order() {
+        let first = Account::fixture();
+        let second = Account {
+            cash: 1.0,
+            ..Default::default()
+        };
+        let platform = ReplayPlatform::new(vec![
+            account_entry(first.clone()),
+            account_entry(second.clone()),
+        ]);
+        assert_eq!(platform.account().await, Ok(first));
+        assert_eq!(platform.account().await, Ok(second));
+        assert!(platform.account().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn bars_matched_by_symbol() {
+        let platform = ReplayPlatform::new(vec![bars_entry("SPY"), bars_entry("VTI")]);
+        let request = BarRequest {
+            symbol: AssetSymbol::new("VTI"),
+            ..Default::default()
+        };
+        let bars = platform.bars(request).await.unwrap();
+        assert_eq!(bars.symbol, AssetSymbol::new("VTI"));
+        assert!(platform
+            .latest_quotes(&[AssetSymbol::new("VTI")])
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn replays_recorded_errors() {
+        let platform = ReplayPlatform::new(vec![CassetteEntry::PlaceOrder {
+            time: DateTimeFixture::utc(),
+            request: Default::default(),
+            response: Err(GreedError::new("rejected")),
+        }]);
+        let result = platform.place_order(Default::default()).await;
+        assert_eq!(result, Err(GreedError::new("rejected")));
+    }
+
+    #[tokio::test]
+    async fn close_position_matched_by_symbol() {
+        let spy = AssetSymbol::new("SPY");
+        let vti = AssetSymbol::new("VTI");
+        let platform =
+            ReplayPlatform::new(vec![close_entry(spy.clone()), close_entry(vti.clone())]);
+        let order = platform.close_position(&vti).await.unwrap();
+        assert_eq!(order.symbol, vti);
+        assert!(platform.close_position(&vti).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn replays_recorded_tactic_run() {
+        let path = env::temp_dir().join(format!("greed_replay_{}.jsonl", std::process::id()));
+        let vti = AssetSymbol::new("VTI");
+        let mock = MockPlatform::new()
+            .with_account(Account::fixture())
+            .with_positions(vec![Position::fixture(vti.clone())]);
+        let recording = Arc::new(RecordingPlatform::new(mock.arc(), &path).unwrap());
+        TacticRunner::new(tactic(), recording, Default::default())
+            .run(std::slice::from_ref(&vti))
+            .await
+            .unwrap();
+
+        let replay = Arc::new(ReplayPlatform::from_path(&path).unwrap());
+        fs::remove_file(&path).unwrap();
+        TacticRunner::new(tactic(), replay, Default::default())
+            .run(&[vti])
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    fn now_defaults_to_first_entry() {
+        let platform = ReplayPlatform::new(vec![account_entry(Account::fixture())]);
+        assert_eq!(platform.now(), DateTimeFixture::utc())
+    }
+
+    /// Sells on a cooldown shortly after the open, so the run reads both the order history and
+    /// the calendar.
+    fn tactic() -> TacticConfig {
+        toml::from_str(
+            r#"
+            name = "replayed"
+            buy = { for = { stock = "VTI" }, when = { never = true }, do = { buy_percent = 10 } }
+            sell = { for = { stock = "VTI" }, when = { all_of = [{ cooldown_hours = 24 }, { minutes_after_open = 30 }] }, do = { sell_all = true } }
+            "#,
+        )
+        .unwrap()
+    }
+
+    fn account_entry(account: Account) -> CassetteEntry {
+        CassetteEntry::Account {
+            time: DateTimeFixture::utc(),
+            response: Ok(account),
+        }
+    }
+
+    fn close_entry(symbol: AssetSymbol) -> CassetteEntry {
+        CassetteEntry::ClosePosition {
+            time: DateTimeFixture::utc(),
+            symbol: symbol.clone(),
+            response: Ok(Order::fixture(symbol)),
+        }
+    }
+
+    fn bars_entry(symbol: &str) -> CassetteEntry {
+        let symbol = AssetSymbol::new(symbol);
+        CassetteEntry::Bars {
+            time: DateTimeFixture::utc(),
+            request: BarRequest {
+                symbol: symbol.clone(),
+                ..Default::default()
+            },
+            response: Ok(Bars {
+                symbol,
+                bars: vec![Bar::fixture(100.0)],
+            }),
+        }
+    }
+}
@@ -1,3 +1,4 @@
+pub mod replace;
 pub mod stop_loss;
 pub mod take_profit;
 
@@ -10,9 +11,10 @@ use crate::platform::order::time_in_force::TimeInForce;
 use crate::platform::request::stop_loss::StopLoss;
 use crate::platform::request::take_profit::TakeProfit;
 use num_decimal::Num;
+use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
 
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 pub struct OrderRequest {
     pub symbol: AssetSymbol,
     pub class: OrderClass,
@@ -27,11 +29,14 @@ pub struct OrderRequest {
     pub take_profit: Option<TakeProfit>,
     pub stop_loss: Option<StopLoss>,
     pub extended_hours: bool,
+    /// Identifies the order to whoever placed it. Platforms keep it on the resulting order.
+    pub client_order_id: Option<String>,
 }
 
 impl OrderRequest {
     pub fn market_order_buy(symbol: AssetSymbol, amount: Amount) -> Self {
         Self {
+            time_in_force: TimeInForce::for_symbol(&symbol),
             symbol,
             amount,
             side: OrderSide::Buy,
@@ -41,6 +46,7 @@ impl OrderRequest {
 
     pub fn market_order_sell(symbol: AssetSymbol, amount: Amount) -> Self {
         Self {
+            time_in_force: TimeInForce::for_symbol(&symbol),
             symbol,
             amount,
             side: OrderSide::Sell,
@@ -64,6 +70,7 @@ mod test {
     use crate::asset::AssetSymbol;
     use crate::platform::order::amount::Amount;
     use crate::platform::order::side::OrderSide;
+    use crate::platform::order::time_in_force::TimeInForce;
     use crate::platform::request::OrderRequest;
 
     #[test]
@@ -99,4 +106,11 @@ mod test {
         };
         assert_eq!(expected, request)
     }
+
+    #[test]
+    fn market_order_buy_crypto() {
+        let request =
+            OrderRequest::market_order_buy(AssetSymbol::new("BTC/USD"), Amount::Quantity(0.5));
+        assert_eq!(request.time_in_force, TimeInForce::UntilCanceled)
+    }
 }
@@ -0,0 +1,80 @@
+use crate::platform::order::amount::Amount;
+use crate::platform::order::Order;
+use num_decimal::Num;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// Amends an open order. Fields which are `None` keep the order's current value.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct ReplaceRequest {
+    pub quantity: Option<f64>,
+    pub limit_price: Option<Num>,
+}
+
+impl ReplaceRequest {
+    /// Returns a copy of `order` with the requested changes applied.
+    pub fn apply_to(&self, order: &Order) -> Order {
+        let mut replaced = order.clone();
+        if let Some(quantity) = self.quantity {
+            replaced.amount = Amount::Quantity(quantity);
+        }
+        if let Some(limit_price) = &self.limit_price {
+            replaced.limit_price = Some(limit_price.clone());
+        }
+        replaced
+    }
+}
+
+impl Display for ReplaceRequest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let quantity = self
+            .quantity
+            .map(|q| format!("quantity {q}"))
+            .unwrap_or("same quantity".to_string());
+        let limit = self
+            .limit_price
+            .as_ref()
+            .map(|p| format!("limit {p}"))
+            .unwrap_or("same limit".to_string());
+        write!(f, "{quantity}, {limit}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::asset::AssetSymbol;
+
+    #[test]
+    fn apply_to() {
+        let order = Order {
+            limit_price: Some(Num::from(100)),
+            ..Order::fixture(AssetSymbol::new("VTI"))
+        };
+        let request = ReplaceRequest {
+            quantity: Some(2.0),
+            limit_price: Some(Num::from(95)),
+        };
+        let expected = Order {
+            amount: Amount::Quantity(2.0),
+            limit_price: Some(Num::from(95)),
+            ..order.clone()
+        };
+        assert_eq!(request.apply_to(&order), expected)
+    }
+
+    #[test]
+    fn apply_to_empty_request() {
+        let order = Order::fixture(AssetSymbol::new("VTI"));
+        assert_eq!(ReplaceRequest::default().apply_to(&order), order)
+    }
+
+    #[test]
+    fn display() {
+        let request = ReplaceRequest {
+            quantity: Some(2.0),
+            limit_price: None,
+        };
+        assert_eq!(request.to_string(), "quantity 2, same limit")
+    }
+}
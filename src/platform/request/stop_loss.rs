@@ -1,6 +1,8 @@
 use num_decimal::Num;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum StopLoss {
     /// The stop loss price to use.
     Stop(Num),
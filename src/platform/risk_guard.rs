@@ -0,0 +1,841 @@
+use crate::asset::AssetSymbol;
+use crate::config::risk::RiskConfig;
+use crate::error::GreedError;
+use crate::float::{FloatAmountRounding, PercentOps};
+use crate::journal::JournalEntry;
+use crate::platform::account::Account;
+use crate::platform::asset_filter::AssetFilter;
+use crate::platform::asset_info::AssetInfo;
+use crate::platform::bar::bar_request::BarRequest;
+use crate::platform::bars::Bars;
+use crate::platform::calendar::MarketDay;
+use crate::platform::clock::MarketClock;
+use crate::platform::id::Id;
+use crate::platform::order::amount::Amount;
+use crate::platform::order::order_type::OrderType;
+use crate::platform::order::side::OrderSide;
+use crate::platform::order::Order;
+use crate::platform::position::Position;
+use crate::platform::quote::Quote;
+use crate::platform::request::replace::ReplaceRequest;
+use crate::platform::request::OrderRequest;
+use crate::platform::side::Side;
+use crate::platform::FinancialPlatform;
+use crate::trading_days::eastern::eastern_date;
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use log::warn;
+use std::ops::Range;
+use std::sync::{Arc, Mutex};
+
+/// Wraps a platform so every order is checked against the configured risk limits first.
+///
+/// Buys and short sells are shrunk to fit the order, position and cash reserve limits, or rejected
+/// when nothing fits. Sells closing a long position are only shrunk to fit the order limit, stop
+/// sells protecting one and buys covering a short are only subject to the daily order count, so
+/// positions can always be exited. Orders which go past closing a position have the rest vetted as opening one. Replacing
+/// an order is vetted as if the replaced order were placed anew.
+pub struct RiskGuardPlatform {
+    platform: Arc<dyn FinancialPlatform>,
+    config: RiskConfig,
+    /// The trading day and the number of orders placed on it so far.
+    orders_today: Mutex<(NaiveDate, u32)>,
+}
+
+impl RiskGuardPlatform {
+    pub fn new(platform: Arc<dyn FinancialPlatform>, config: RiskConfig) -> Self {
+        Self {
+            platform,
+            config,
+            orders_today: Mutex::new((NaiveDate::default(), 0)),
+        }
+    }
+
+    /// Resumes the daily order count from the orders journaled today, so restarting doesn't
+    /// reset it.
+    pub fn with_journal(self, entries: &[JournalEntry]) -> Self {
+        let today = eastern_date(self.platform.now());
+        let count = entries
+            .iter()
+            .filter(|entry| match entry {
+                JournalEntry::OrderPlaced { time, response, .. } => {
+                    response.is_ok() && eastern_date(*time) == today
+                }
+                JournalEntry::OrderUpdated { .. } => false,
+            })
+            .count();
+        Self {
+            orders_today: Mutex::new((today, count as u32)),
+            ..self
+        }
+    }
+
+    fn check_order_count(&self) -> Result<(), GreedError> {
+        let Some(max_orders) = self.config.max_orders_per_day else {
+            return Ok(());
+        };
+        let today = eastern_date(self.platform.now());
+        let (day, count) = *self.orders_today.lock().unwrap();
+        if day == today && count >= max_orders {
+            return Err(rejected(format!(
+                "already placed {count} orders today, the daily limit is {max_orders}"
+            )));
+        }
+        Ok(())
+    }
+
+    fn count_order(&self) {
+        let today = eastern_date(self.platform.now());
+        let mut orders_today = self.orders_today.lock().unwrap();
+        if orders_today.0 != today {
+            *orders_today = (today, 0);
+        }
+        orders_today.1 += 1;
+    }
+
+    /// Returns the request with its amount shrunk to fit the limits, or an error when the order
+    /// isn't allowed at all. The part of the order which closes a position is vetted as a close,
+    /// and anything past it as opening a position on the order's side.
+    async fn vet(&self, request: OrderRequest) -> Result<OrderRequest, GreedError> {
+        let closable = self.closable(&request).await?;
+        let opening = request.amount.value() - closable;
+        if opening <= 0.0 {
+            return self.vet_close(request).await;
+        }
+        let opening_request = OrderRequest {
+            amount: request.amount.with_value(opening),
+            ..request.clone()
+        };
+        let opened = match self.vet_open(opening_request).await {
+            Ok(vetted) => vetted.amount.value(),
+            Err(e) if closable > 0.0 => {
+                warn!("{e}, only closing the {} position", request.symbol);
+                0.0
+            }
+            Err(e) => return Err(e),
+        };
+        let amount = match request.amount.with_value(closable + opened) {
+            Amount::Notional(notional) => Amount::Notional(notional.round_for_notional()),
+            amount => amount,
+        };
+        self.vet_close(OrderRequest { amount, ..request }).await
+    }
+
+    /// Buys and short sells add exposure, so they're shrunk to fit the order, position and cash
+    /// reserve limits, and halted past the daily loss limit.
+    async fn vet_open(&self, request: OrderRequest) -> Result<OrderRequest, GreedError> {
+        let account = self.platform.account().await?;
+        self.check_daily_loss(&account)?;
+        match self.open_cap(&request, &account).await? {
+            Some((cap, limit)) => self.fit(request, cap, limit).await,
+            None => Ok(request),
+        }
+    }
+
+    /// Covers are let through, so a short can always be exited. Sells only shrink exposure, so
+    /// the order limit is the only one which applies, and stops are let through too since a
+    /// shrunk stop would leave the rest of the position unprotected.
+    async fn vet_close(&self, request: OrderRequest) -> Result<OrderRequest, GreedError> {
+        match (request.side, self.config.max_order_notional) {
+            (OrderSide::Sell, Some(max)) if !is_stop(&request.order_type) => {
+                self.fit(request, max, format!("max order notional of ${max:.2}"))
+                    .await
+            }
+            _ => Ok(request),
+        }
+    }
+
+    /// Shrinks the request so it's worth no more than `cap`, which was set by `limit`.
+    async fn fit(
+        &self,
+        request: OrderRequest,
+        cap: f64,
+        limit: String,
+    ) -> Result<OrderRequest, GreedError> {
+        let (notional, price) = match request.amount {
+            Amount::Notional(notional) => (notional, None),
+            Amount::Quantity(quantity) => {
+                let price = self.price_for(&request).await?;
+                (quantity * price, Some(price))
+            }
+        };
+        if notional <= cap {
+            return Ok(request);
+        }
+        let amount = match price {
+            Some(price) => {
+                Amount::Quantity((cap.max(0.0) / price).round_quantity_for(&request.symbol))
+            }
+            None => Amount::Notional(cap.max(0.0).round_for_notional()),
+        };
+        if amount.is_empty() {
+            return Err(rejected(format!(
+                "the {} of {} would exceed the {limit}",
+                request.side, request.symbol
+            )));
+        }
+        warn!(
+            "🛡️ shrinking {} {} from ${notional:.2} to {amount} to stay within the {limit}",
+            request.symbol, request.side
+        );
+        Ok(OrderRequest { amount, ..request })
+    }
+
+    /// Vets the order as it would be once replaced, returning the replace request with its
+    /// quantity shrunk to fit.
+    async fn vet_replace(
+        &self,
+        id: &Id,
+        request: ReplaceRequest,
+    ) -> Result<ReplaceRequest, GreedError> {
+        let open_orders = self.platform.open_orders().await?;
+        let Some(order) = open_orders.iter().find(|o| &o.id == id) else {
+            return Ok(request);
+        };
+        let replaced = request.apply_to(order);
+        let vetted = self
+            .vet(OrderRequest {
+                symbol: replaced.symbol,
+                class: replaced.class,
+                order_type: replaced.order_type,
+                amount: replaced.amount.clone(),
+                side: replaced.side,
+                limit_price: replaced.limit_price,
+                ..Default::default()
+            })
+            .await?;
+        match vetted.amount {
+            Amount::Quantity(quantity) if vetted.amount != replaced.amount => Ok(ReplaceRequest {
+                quantity: Some(quantity),
+                ..request
+            }),
+            _ => Ok(request),
+        }
+    }
+
+    /// How much of the request's amount closes the position on the other side: the shares held
+    /// for a sell, or the shares short for a buy.
+    async fn closable(&self, request: &OrderRequest) -> Result<f64, GreedError> {
+        let opposite = self
+            .position(&request.symbol)
+            .await?
+            .filter(|p| p.side != opening_side(request.side))
+            .map(|p| p.quantity.abs())
+            .unwrap_or_default();
+        if opposite <= 0.0 {
+            return Ok(0.0);
+        }
+        match request.amount {
+            Amount::Quantity(_) => Ok(opposite),
+            Amount::Notional(_) => Ok(opposite * self.price_for(request).await?),
+        }
+    }
+
+    async fn position(&self, symbol: &AssetSymbol) -> Result<Option<Position>, GreedError> {
+        let positions = self.platform.positions().await?;
+        Ok(positions.into_iter().find(|p| &p.symbol == symbol))
+    }
+
+    fn check_daily_loss(&self, account: &Account) -> Result<(), GreedError> {
+        let Some(limit) = self.config.daily_loss_limit_percent else {
+            return Ok(());
+        };
+        if account.last_equity <= 0.0 {
+            return Ok(());
+        }
+        let loss = (account.last_equity - account.equity) / account.last_equity * 100.0;
+        if loss >= limit {
+            return Err(rejected(format!(
+                "equity is down {loss:.2}% today, buying is halted past the {limit:.2}% daily loss limit"
+            )));
+        }
+        Ok(())
+    }
+
+    /// The most an order opening a position may be worth along with the limit which set it, or
+    /// `None` when no limits apply.
+    async fn open_cap(
+        &self,
+        request: &OrderRequest,
+        account: &Account,
+    ) -> Result<Option<(f64, String)>, GreedError> {
+        let mut caps = Vec::new();
+        if let Some(max) = self.config.max_order_notional {
+            caps.push((max, format!("max order notional of ${max:.2}")));
+        }
+        if let Some(reserve) = self.config.min_cash_reserve {
+            caps.push((
+                account.cash - reserve,
+                format!("cash reserve of ${reserve:.2}"),
+            ));
+        }
+        if let Some(percent) = self.config.max_position_percent {
+            // Short positions carry a negative market value.
+            let held = self
+                .position(&request.symbol)
+                .await?
+                .filter(|p| p.side == opening_side(request.side))
+                .and_then(|p| p.market_value)
+                .map(f64::abs)
+                .unwrap_or(0.0);
+            caps.push((
+                account.equity.percent_of(percent) - held,
+                format!("max position of {percent:.2}% of equity"),
+            ));
+        }
+        Ok(caps.into_iter().min_by(|a, b| a.0.total_cmp(&b.0)))
+    }
+
+    /// The price used to value quantity orders, preferring the order's limit price. Orders which
+    /// can't be priced are rejected rather than valued at nothing.
+    async fn price_for(&self, request: &OrderRequest) -> Result<f64, GreedError> {
+        if let Some(limit_price) = request.limit_price.as_ref().and_then(|p| p.to_f64()) {
+            return Ok(limit_price);
+        }
+        let quotes = self
+            .platform
+            .latest_quotes(std::slice::from_ref(&request.symbol))
+            .await?;
+        quotes
+            .first()
+            .map(|q| match request.side {
+                OrderSide::Buy => q.ask_price,
+                OrderSide::Sell => q.bid_price,
+            })
+            .filter(|price| *price > 0.0)
+            .ok_or_else(|| {
+                rejected(format!(
+                    "there's no quote to value the {} of {}",
+                    request.side, request.symbol
+                ))
+            })
+    }
+}
+
+/// The side of the position an order adds to once it has closed any position on the other side.
+fn opening_side(side: OrderSide) -> Side {
+    match side {
+        OrderSide::Buy => Side::Long,
+        OrderSide::Sell => Side::Short,
+    }
+}
+
+fn is_stop(order_type: &OrderType) -> bool {
+    matches!(
+        order_type,
+        OrderType::Stop | OrderType::StopLimit | OrderType::TrailingStop
+    )
+}
+
+fn rejected(reason: String) -> GreedError {
+    GreedError::new(&format!("🛡️ order rejected by risk limits: {reason}"))
+}
+
+#[async_trait]
+impl FinancialPlatform for RiskGuardPlatform {
+    async fn account(&self) -> Result<Account, GreedError> {
+        self.platform.account().await
+    }
+
+    async fn bars(&self, bars_request: BarRequest) -> Result<Bars, GreedError> {
+        self.platform.bars(bars_request).await
+    }
+
+    async fn latest_quotes(&self, symbols: &[AssetSymbol]) -> Result<Vec<Quote>, GreedError> {
+        self.platform.latest_quotes(symbols).await
+    }
+
+    async fn place_order(&self, order_request: OrderRequest) -> Result<Order, GreedError> {
+        self.check_order_count().inspect_err(|e| warn!("{e}"))?;
+        let request = self
+            .vet(order_request)
+            .await
+            .inspect_err(|e| warn!("{e}"))?;
+        let order = self.platform.place_order(request).await?;
+        self.count_order();
+        Ok(order)
+    }
+
+    async fn positions(&self) -> Result<Vec<Position>, GreedError> {
+        self.platform.positions().await
+    }
+
+    async fn open_orders(&self) -> Result<Vec<Order>, GreedError> {
+        self.platform.open_orders().await
+    }
+
+    async fn recent_orders(&self) -> Result<Vec<Order>, GreedError> {
+        self.platform.recent_orders().await
+    }
+
+    async fn cancel_order(&self, id: &Id) -> Result<(), GreedError> {
+        self.platform.cancel_order(id).await
+    }
+
+    async fn replace_order(&self, id: &Id, request: ReplaceRequest) -> Result<Order, GreedError> {
+        self.check_order_count().inspect_err(|e| warn!("{e}"))?;
+        let request = self
+            .vet_replace(id, request)
+            .await
+            .inspect_err(|e| warn!("{e}"))?;
+        let order = self.platform.replace_order(id, request).await?;
+        self.count_order();
+        Ok(order)
+    }
+
+    async fn close_position(&self, symbol: &AssetSymbol) -> Result<Order, GreedError> {
+        self.platform.close_position(symbol).await
+    }
+
+    async fn order_history(&self, range: Range<DateTime<Utc>>) -> Result<Vec<Order>, GreedError> {
+        self.platform.order_history(range).await
+    }
+
+    async fn asset(&self, symbol: &AssetSymbol) -> Result<AssetInfo, GreedError> {
+        self.platform.asset(symbol).await
+    }
+
+    async fn assets(&self, filter: AssetFilter) -> Result<Vec<AssetInfo>, GreedError> {
+        self.platform.assets(filter).await
+    }
+
+    async fn clock(&self) -> Result<MarketClock, GreedError> {
+        self.platform.clock().await
+    }
+
+    async fn calendar(&self, range: Range<NaiveDate>) -> Result<Vec<MarketDay>, GreedError> {
+        self.platform.calendar(range).await
+    }
+
+    fn now(&self) -> DateTime<Utc> {
+        self.platform.now()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::platform::MockPlatform;
+    use crate::tactic::TacticRunner;
+    use chrono::Duration;
+
+    #[tokio::test]
+    async fn place_order_without_limits() {
+        let mock = Arc::new(MockPlatform::new());
+        let platform = RiskGuardPlatform::new(mock.clone(), Default::default());
+        platform
+            .place_order(buy(Amount::Notional(5000.0)))
+            .await
+            .unwrap();
+        assert_eq!(mock.placed_orders(), vec![buy(Amount::Notional(5000.0))]);
+    }
+
+    #[tokio::test]
+    async fn place_order_shrinks_to_max_notional() {
+        let mock = Arc::new(MockPlatform::new());
+        let config = RiskConfig {
+            max_order_notional: Some(100.0),
+            ..Default::default()
+        };
+        let platform = RiskGuardPlatform::new(mock.clone(), config);
+        platform
+            .place_order(buy(Amount::Notional(250.0)))
+            .await
+            .unwrap();
+        assert_eq!(mock.placed_orders(), vec![buy(Amount::Notional(100.0))]);
+    }
+
+    #[tokio::test]
+    async fn place_order_shrinks_quantity_to_cash_reserve() {
+        let mock = Arc::new(
+            MockPlatform::new()
+                .with_account(Account {
+                    cash: 1000.0,
+                    ..Default::default()
+                })
+                .with_quotes(vec![Quote::fixture(vti())]),
+        );
+        let config = RiskConfig {
+            min_cash_reserve: Some(600.0),
+            ..Default::default()
+        };
+        let platform = RiskGuardPlatform::new(mock.clone(), config);
+        // Quotes ask 200, so only 2 of the 5 shares fit in the spare 400.
+        platform
+            .place_order(buy(Amount::Quantity(5.0)))
+            .await
+            .unwrap();
+        assert_eq!(mock.placed_orders(), vec![buy(Amount::Quantity(2.0))]);
+    }
+
+    #[tokio::test]
+    async fn place_order_rejects_full_position() {
+        let mock = Arc::new(
+            MockPlatform::new()
+                .with_account(Account {
+                    equity: 1000.0,
+                    ..Default::default()
+                })
+                .with_positions(vec![Position {
+                    market_value: Some(250.0),
+                    ..Position::fixture(vti())
+                }]),
+        );
+        let config = RiskConfig {
+            max_position_percent: Some(25.0),
+            ..Default::default()
+        };
+        let platform = RiskGuardPlatform::new(mock.clone(), config);
+        let result = platform.place_order(buy(Amount::Notional(10.0))).await;
+        assert!(result.is_err());
+        assert!(mock.placed_orders().is_empty());
+    }
+
+    #[tokio::test]
+    async fn place_order_halts_buys_past_daily_loss_limit() {
+        let mock = Arc::new(
+            MockPlatform::new()
+                .with_account(Account {
+                    equity: 950.0,
+                    last_equity: 1000.0,
+                    ..Default::default()
+                })
+                .with_positions(vec![Position::fixture(vti())]),
+        );
+        let config = RiskConfig {
+            daily_loss_limit_percent: Some(5.0),
+            ..Default::default()
+        };
+        let platform = RiskGuardPlatform::new(mock.clone(), config);
+        assert!(platform
+            .place_order(buy(Amount::Notional(10.0)))
+            .await
+            .is_err());
+        platform
+            .place_order(sell(Amount::Quantity(1.0)))
+            .await
+            .unwrap();
+        assert_eq!(mock.placed_orders(), vec![sell(Amount::Quantity(1.0))]);
+    }
+
+    #[tokio::test]
+    async fn place_order_limits_orders_per_day() {
+        let mock = Arc::new(MockPlatform::new());
+        let config = RiskConfig {
+            max_orders_per_day: Some(2),
+            ..Default::default()
+        };
+        let platform = RiskGuardPlatform::new(mock.clone(), config);
+        platform
+            .place_order(buy(Amount::Notional(10.0)))
+            .await
+            .unwrap();
+        platform
+            .place_order(sell(Amount::Quantity(1.0)))
+            .await
+            .unwrap();
+        assert!(platform
+            .place_order(buy(Amount::Notional(10.0)))
+            .await
+            .is_err());
+        assert_eq!(mock.placed_orders().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn place_order_shrinks_sells_to_max_notional() {
+        let mock = Arc::new(
+            MockPlatform::new()
+                .with_positions(vec![Position::fixture(vti())])
+                .with_quotes(vec![Quote::fixture(vti())]),
+        );
+        let config = RiskConfig {
+            max_order_notional: Some(250.0),
+            ..Default::default()
+        };
+        let platform = RiskGuardPlatform::new(mock.clone(), config);
+        // Sells are valued at the 100 bid, so 2.5 of the 50 shares fit.
+        platform
+            .place_order(sell(Amount::Quantity(50.0)))
+            .await
+            .unwrap();
+        assert_eq!(mock.placed_orders(), vec![sell(Amount::Quantity(2.5))]);
+    }
+
+    #[tokio::test]
+    async fn place_order_stop_sells_are_not_shrunk() {
+        let mock = Arc::new(
+            MockPlatform::new()
+                .with_positions(vec![Position::fixture(vti())])
+                .with_quotes(vec![Quote::fixture(vti())]),
+        );
+        let config = RiskConfig {
+            max_order_notional: Some(250.0),
+            ..Default::default()
+        };
+        let platform = RiskGuardPlatform::new(mock.clone(), config);
+        let stop = OrderRequest {
+            order_type: OrderType::TrailingStop,
+            ..sell(Amount::Quantity(50.0))
+        };
+        platform.place_order(stop.clone()).await.unwrap();
+        assert_eq!(mock.placed_orders(), vec![stop]);
+    }
+
+    #[tokio::test]
+    async fn trailing_stop_exit_is_placed_in_full() {
+        let mock = Arc::new(
+            MockPlatform::new()
+                .with_positions(vec![Position::fixture(vti())])
+                .with_quotes(vec![Quote::fixture(vti())]),
+        );
+        let config = RiskConfig {
+            max_order_notional: Some(250.0),
+            ..Default::default()
+        };
+        let platform = Arc::new(RiskGuardPlatform::new(mock.clone(), config));
+        let tactic = toml::from_str(
+            r#"
+            name = "exit"
+            buy = { for = { stock = "VTI" }, when = { never = true }, do = { buy_percent = 10 } }
+            sell = { for = { stock = "VTI" }, when = { never = true }, do = { sell_all = true } }
+            exit = { trailing_stop_percent = 5 }
+            "#,
+        )
+        .unwrap();
+        TacticRunner::new(tactic, platform, Default::default())
+            .run(&[vti()])
+            .await
+            .unwrap();
+        // The stop covers all 50 shares held, rather than the 2.5 the order limit allows, so it's
+        // in line with the position on the next run and isn't replaced again.
+        let placed = mock.placed_orders();
+        assert_eq!(placed.len(), 1);
+        assert_eq!(placed[0].order_type, OrderType::TrailingStop);
+        assert_eq!(placed[0].amount, Amount::Quantity(50.0));
+    }
+
+    #[tokio::test]
+    async fn place_order_rejects_unpriced_quantity() {
+        let mock = Arc::new(MockPlatform::new());
+        let config = RiskConfig {
+            max_order_notional: Some(100.0),
+            ..Default::default()
+        };
+        let platform = RiskGuardPlatform::new(mock.clone(), config);
+        let result = platform.place_order(buy(Amount::Quantity(5.0))).await;
+        assert!(result.is_err());
+        assert!(mock.placed_orders().is_empty());
+    }
+
+    #[tokio::test]
+    async fn replace_order_shrinks_quantity() {
+        let order = Order {
+            side: OrderSide::Buy,
+            ..Order::fixture(vti())
+        };
+        let mock = Arc::new(
+            MockPlatform::new()
+                .with_open_orders(vec![order.clone()])
+                .with_quotes(vec![Quote::fixture(vti())]),
+        );
+        let config = RiskConfig {
+            max_order_notional: Some(500.0),
+            ..Default::default()
+        };
+        let platform = RiskGuardPlatform::new(mock.clone(), config);
+        let request = ReplaceRequest {
+            quantity: Some(10.0),
+            ..Default::default()
+        };
+        // Quotes ask 200, so only 2.5 of the 10 shares fit.
+        let replaced = platform.replace_order(&order.id, request).await.unwrap();
+        assert_eq!(replaced.amount, Amount::Quantity(2.5));
+    }
+
+    #[tokio::test]
+    async fn replace_order_counts_toward_daily_limit() {
+        let order = Order::fixture(vti());
+        let mock = Arc::new(MockPlatform::new().with_open_orders(vec![order.clone()]));
+        let config = RiskConfig {
+            max_orders_per_day: Some(1),
+            ..Default::default()
+        };
+        let platform = RiskGuardPlatform::new(mock.clone(), config);
+        platform
+            .replace_order(&order.id, ReplaceRequest::default())
+            .await
+            .unwrap();
+        assert!(platform
+            .place_order(buy(Amount::Notional(10.0)))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn with_journal_resumes_order_count() {
+        let mock = Arc::new(MockPlatform::new());
+        let config = RiskConfig {
+            max_orders_per_day: Some(2),
+            ..Default::default()
+        };
+        let placed =
+            |time: DateTime<Utc>, response: Result<Order, GreedError>| JournalEntry::OrderPlaced {
+                time,
+                source: Default::default(),
+                request: buy(Amount::Notional(10.0)),
+                response,
+            };
+        let now = mock.now();
+        let entries = vec![
+            placed(now - Duration::days(2), Ok(Order::fixture(vti()))),
+            placed(now, Err(GreedError::new("rejected"))),
+            placed(now, Ok(Order::fixture(vti()))),
+        ];
+        let platform = RiskGuardPlatform::new(mock.clone(), config).with_journal(&entries);
+        platform
+            .place_order(buy(Amount::Notional(10.0)))
+            .await
+            .unwrap();
+        assert!(platform
+            .place_order(buy(Amount::Notional(10.0)))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn place_order_covers_are_not_shrunk() {
+        let mock = Arc::new(MockPlatform::new().with_positions(vec![short()]));
+        let config = RiskConfig {
+            max_order_notional: Some(1.0),
+            ..Default::default()
+        };
+        let platform = RiskGuardPlatform::new(mock.clone(), config);
+        platform
+            .place_order(buy(Amount::Quantity(50.0)))
+            .await
+            .unwrap();
+        assert_eq!(mock.placed_orders(), vec![buy(Amount::Quantity(50.0))]);
+    }
+
+    #[tokio::test]
+    async fn place_order_vets_buys_past_the_cover() {
+        let mock = Arc::new(
+            MockPlatform::new()
+                .with_positions(vec![short()])
+                .with_quotes(vec![Quote::fixture(vti())]),
+        );
+        let config = RiskConfig {
+            max_order_notional: Some(1000.0),
+            ..Default::default()
+        };
+        let platform = RiskGuardPlatform::new(mock.clone(), config);
+        // The 50 short are covered as is, and 5 of the other 10 fit in the limit at the 200 ask.
+        platform
+            .place_order(buy(Amount::Quantity(60.0)))
+            .await
+            .unwrap();
+        assert_eq!(mock.placed_orders(), vec![buy(Amount::Quantity(55.0))]);
+    }
+
+    #[tokio::test]
+    async fn place_order_only_covers_when_the_rest_is_rejected() {
+        let mock = Arc::new(
+            MockPlatform::new()
+                .with_account(Account {
+                    equity: 950.0,
+                    last_equity: 1000.0,
+                    ..Default::default()
+                })
+                .with_positions(vec![short()]),
+        );
+        let config = RiskConfig {
+            daily_loss_limit_percent: Some(5.0),
+            ..Default::default()
+        };
+        let platform = RiskGuardPlatform::new(mock.clone(), config);
+        platform
+            .place_order(buy(Amount::Quantity(60.0)))
+            .await
+            .unwrap();
+        assert_eq!(mock.placed_orders(), vec![buy(Amount::Quantity(50.0))]);
+    }
+
+    #[tokio::test]
+    async fn place_order_vets_short_sells_like_buys() {
+        let mock = Arc::new(
+            MockPlatform::new()
+                .with_account(Account {
+                    equity: 1000.0,
+                    ..Default::default()
+                })
+                .with_positions(vec![Position {
+                    market_value: Some(-50.0),
+                    ..short()
+                }])
+                .with_quotes(vec![Quote::fixture(vti())]),
+        );
+        let config = RiskConfig {
+            max_position_percent: Some(25.0),
+            ..Default::default()
+        };
+        let platform = RiskGuardPlatform::new(mock.clone(), config);
+        // The 50 already short leave room for 200 more, which is 2 shares at the 100 bid.
+        platform
+            .place_order(sell(Amount::Quantity(5.0)))
+            .await
+            .unwrap();
+        assert_eq!(mock.placed_orders(), vec![sell(Amount::Quantity(2.0))]);
+    }
+
+    #[tokio::test]
+    async fn place_order_halts_short_sells_past_daily_loss_limit() {
+        let mock = Arc::new(
+            MockPlatform::new()
+                .with_account(Account {
+                    equity: 950.0,
+                    last_equity: 1000.0,
+                    ..Default::default()
+                })
+                .with_positions(vec![Position::fixture(vti())]),
+        );
+        let config = RiskConfig {
+            daily_loss_limit_percent: Some(5.0),
+            ..Default::default()
+        };
+        let platform = RiskGuardPlatform::new(mock.clone(), config);
+        assert!(platform
+            .place_order(OrderRequest::market_order_sell(
+                AssetSymbol::new("SPY"),
+                Amount::Quantity(1.0)
+            ))
+            .await
+            .is_err());
+        // Selling past the 50 held only sells the 50.
+        platform
+            .place_order(sell(Amount::Quantity(60.0)))
+            .await
+            .unwrap();
+        assert_eq!(mock.placed_orders(), vec![sell(Amount::Quantity(50.0))]);
+    }
+
+    fn vti() -> AssetSymbol {
+        AssetSymbol::new("VTI")
+    }
+
+    fn buy(amount: Amount) -> OrderRequest {
+        OrderRequest::market_order_buy(vti(), amount)
+    }
+
+    fn sell(amount: Amount) -> OrderRequest {
+        OrderRequest::market_order_sell(vti(), amount)
+    }
+
+    fn short() -> Position {
+        Position {
+            side: Side::Short,
+            quantity: -50.0,
+            quantity_available: -50.0,
+            ..Position::fixture(vti())
+        }
+    }
+}
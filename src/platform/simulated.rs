@@ -0,0 +1,318 @@
+use crate::asset::AssetSymbol;
+use crate::error::GreedError;
+use crate::platform::account::Account;
+use crate::platform::asset_filter::AssetFilter;
+use crate::platform::asset_info::AssetInfo;
+use crate::platform::bar::bar_request::BarRequest;
+use crate::platform::bar::history::BarHistory;
+use crate::platform::bars::Bars;
+use crate::platform::id::Id;
+use crate::platform::order::Order;
+use crate::platform::position::Position;
+use crate::platform::quote::Quote;
+use crate::platform::request::replace::ReplaceRequest;
+use crate::platform::request::OrderRequest;
+use crate::platform::simulated::book::OrderBook;
+use crate::platform::FinancialPlatform;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use log::info;
+use std::env;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+pub mod book;
+pub mod trade;
+
+const DEFAULT_STATE_PATH: &str = "greed_simulated.json";
+const DEFAULT_CASH: f64 = 100_000.0;
+
+/// A local brokerage which fills orders against bars loaded from disk. Cash, positions and
+/// orders are persisted to a JSON file so the sandbox survives between runs.
+pub struct SimulatedPlatform {
+    prices: BarHistory,
+    state_path: PathBuf,
+    book: Mutex<OrderBook>,
+}
+
+impl SimulatedPlatform {
+    /// Creates a platform configured from the environment:
+    /// - `GREED_SIMULATED_BARS_PATH`: a directory of `<SYMBOL>.csv` bar files (required).
+    /// - `GREED_SIMULATED_STATE_PATH`: where the order book is saved (default `greed_simulated.json`).
+    /// - `GREED_SIMULATED_CASH`: the starting cash for a new order book (default 100000).
+    pub fn new() -> Result<Self, GreedError> {
+        let bars_path = env::var("GREED_SIMULATED_BARS_PATH")?;
+        let state_path =
+            env::var("GREED_SIMULATED_STATE_PATH").unwrap_or(DEFAULT_STATE_PATH.to_string());
+        let cash = match env::var("GREED_SIMULATED_CASH") {
+            Ok(cash) => cash
+                .parse::<f64>()
+                .map_err(|e| GreedError::new(&format!("invalid GREED_SIMULATED_CASH: {e}")))?,
+            Err(_) => DEFAULT_CASH,
+        };
+        Self::from_paths(bars_path, state_path, cash)
+    }
+
+    /// Loads prices from `bars_path` and the order book from `state_path`. A new book holding
+    /// `cash` is created when there is no saved state.
+    pub fn from_paths<B: AsRef<Path>, S: AsRef<Path>>(
+        bars_path: B,
+        state_path: S,
+        cash: f64,
+    ) -> Result<Self, GreedError> {
+        let prices = BarHistory::from_directory_all(bars_path)?;
+        let state_path = state_path.as_ref().to_path_buf();
+        let book = if state_path.exists() {
+            info!("loading simulated order book from {}", state_path.display());
+            let json = std::fs::read_to_string(&state_path)?;
+            serde_json::from_str(&json)?
+        } else {
+            OrderBook::new(cash)
+        };
+        Ok(Self {
+            prices,
+            state_path,
+            book: Mutex::new(book),
+        })
+    }
+
+    fn price(&self, symbol: &AssetSymbol) -> Option<f64> {
+        self.prices.price_at(symbol, Utc::now())
+    }
+
+    /// Locks the book after filling any open orders which the latest prices satisfy.
+    fn settled_book(&self) -> Result<MutexGuard<'_, OrderBook>, GreedError> {
+        let mut book = self.book.lock().unwrap();
+        let trades = book.settle(|s| self.price(s), Utc::now());
+        if !trades.is_empty() {
+            self.save(&book)?;
+        }
+        Ok(book)
+    }
+
+    fn save(&self, book: &OrderBook) -> Result<(), GreedError> {
+        let json = serde_json::to_string_pretty(book)?;
+        std::fs::write(&self.state_path, json)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl FinancialPlatform for SimulatedPlatform {
+    async fn account(&self) -> Result<Account, GreedError> {
+        Ok(self.settled_book()?.account(|s| self.price(s)))
+    }
+
+    async fn bars(&self, bars_request: BarRequest) -> Result<Bars, GreedError> {
+        let mut bars = self.prices.bars_between(
+            &bars_request.symbol,
+            bars_request.start,
+            bars_request.end,
+            Utc::now(),
+        );
+        if let Some(limit) = bars_request.limit {
+            bars.truncate(limit);
+        }
+        Ok(Bars {
+            symbol: bars_request.symbol,
+            bars,
+        })
+    }
+
+    async fn latest_quotes(&self, symbols: &[AssetSymbol]) -> Result<Vec<Quote>, GreedError> {
+        let now = Utc::now();
+        let quotes = symbols
+            .iter()
+            .filter_map(|symbol| {
+                self.prices.latest_bar(symbol, now).map(|bar| Quote {
+                    time: bar.timestamp,
+                    ask_price: bar.close,
+                    bid_price: bar.close,
+                    symbol: symbol.clone(),
+                    ..Default::default()
+                })
+            })
+            .collect();
+        Ok(quotes)
+    }
+
+    async fn place_order(&self, order_request: OrderRequest) -> Result<Order, GreedError> {
+        let mut book = self.settled_book()?;
+        let price = self.price(&order_request.symbol);
+        let order = book.submit(order_request, price, Utc::now())?;
+        self.save(&book)?;
+        Ok(order)
+    }
+
+    async fn positions(&self) -> Result<Vec<Position>, GreedError> {
+        Ok(self.settled_book()?.positions(|s| self.price(s)))
+    }
+
+    async fn open_orders(&self) -> Result<Vec<Order>, GreedError> {
+        Ok(self.settled_book()?.open_orders())
+    }
+
+    async fn recent_orders(&self) -> Result<Vec<Order>, GreedError> {
+        Ok(self.settled_book()?.recent_orders())
+    }
+
+    async fn order_history(&self, range: Range<DateTime<Utc>>) -> Result<Vec<Order>, GreedError> {
+        Ok(self.settled_book()?.order_history(&range))
+    }
+
+    async fn cancel_order(&self, id: &Id) -> Result<(), GreedError> {
+        let mut book = self.settled_book()?;
+        book.cancel(id, Utc::now())?;
+        self.save(&book)
+    }
+
+    async fn replace_order(&self, id: &Id, request: ReplaceRequest) -> Result<Order, GreedError> {
+        let mut book = self.settled_book()?;
+        let order = book.replace(id, &request, Utc::now())?;
+        self.save(&book)?;
+        Ok(order)
+    }
+
+    async fn close_position(&self, symbol: &AssetSymbol) -> Result<Order, GreedError> {
+        let mut book = self.settled_book()?;
+        let order = book.close(symbol, self.price(symbol), Utc::now())?;
+        self.save(&book)?;
+        Ok(order)
+    }
+
+    /// Only assets with a bar file are known, since nothing else can be priced.
+    async fn asset(&self, symbol: &AssetSymbol) -> Result<AssetInfo, GreedError> {
+        if !self.prices.contains(symbol) {
+            return Err(GreedError::new(&format!("no bars for {symbol}")));
+        }
+        Ok(AssetInfo::unrestricted(symbol))
+    }
+
+    async fn assets(&self, filter: AssetFilter) -> Result<Vec<AssetInfo>, GreedError> {
+        Ok(self
+            .prices
+            .symbols()
+            .iter()
+            .map(AssetInfo::unrestricted)
+            .filter(|asset| filter.matches(asset))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixture;
+    use crate::platform::order::amount::Amount;
+    use crate::platform::order::status::Status;
+    use approx::assert_relative_eq;
+    use std::fs;
+
+    #[tokio::test]
+    async fn place_order_fills_at_latest_close() {
+        let state_path = state_path("fills");
+        let platform = SimulatedPlatform::from_paths(fixture::path("bars"), &state_path, 1000.0)
+            .expect("platform should be created");
+        let order = platform
+            .place_order(buy(Amount::Quantity(2.0)))
+            .await
+            .unwrap();
+        assert_eq!(order.status, Status::Filled);
+
+        let account = platform.account().await.unwrap();
+        assert_relative_eq!(account.cash, 780.0);
+        assert_relative_eq!(account.equity, 1000.0);
+        assert_eq!(platform.recent_orders().await.unwrap(), vec![order]);
+        fs::remove_file(state_path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn state_persists_between_instances() {
+        let state_path = state_path("persists");
+        let platform = SimulatedPlatform::from_paths(fixture::path("bars"), &state_path, 1000.0)
+            .expect("platform should be created");
+        platform
+            .place_order(buy(Amount::Quantity(1.0)))
+            .await
+            .unwrap();
+
+        let reloaded = SimulatedPlatform::from_paths(fixture::path("bars"), &state_path, 5.0)
+            .expect("platform should be reloaded");
+        let positions = reloaded.positions().await.unwrap();
+        assert_eq!(positions.len(), 1);
+        assert_relative_eq!(reloaded.account().await.unwrap().cash, 890.0);
+        fs::remove_file(state_path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn close_position_persists() {
+        let state_path = state_path("close");
+        let platform = SimulatedPlatform::from_paths(fixture::path("bars"), &state_path, 1000.0)
+            .expect("platform should be created");
+        platform
+            .place_order(buy(Amount::Quantity(1.0)))
+            .await
+            .unwrap();
+        platform
+            .close_position(&AssetSymbol::new("VTI"))
+            .await
+            .unwrap();
+
+        let reloaded = SimulatedPlatform::from_paths(fixture::path("bars"), &state_path, 5.0)
+            .expect("platform should be reloaded");
+        assert!(reloaded.positions().await.unwrap().is_empty());
+        assert_relative_eq!(reloaded.account().await.unwrap().cash, 1000.0);
+        fs::remove_file(state_path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn latest_quotes() {
+        let platform =
+            SimulatedPlatform::from_paths(fixture::path("bars"), state_path("quotes"), 1000.0)
+                .expect("platform should be created");
+        let quotes = platform
+            .latest_quotes(&[AssetSymbol::new("VTI"), AssetSymbol::new("NOPE")])
+            .await
+            .unwrap();
+        assert_eq!(quotes.len(), 1);
+        assert_eq!(quotes[0].ask_price, 110.0);
+    }
+
+    #[tokio::test]
+    async fn asset() {
+        let platform =
+            SimulatedPlatform::from_paths(fixture::path("bars"), state_path("asset"), 1000.0)
+                .expect("platform should be created");
+        let vti = AssetSymbol::new("VTI");
+        assert_eq!(
+            platform.asset(&vti).await.unwrap(),
+            AssetInfo::unrestricted(&vti)
+        );
+        platform
+            .asset(&AssetSymbol::new("NOPE"))
+            .await
+            .expect_err("unknown assets should fail");
+    }
+
+    #[tokio::test]
+    async fn assets() {
+        let platform =
+            SimulatedPlatform::from_paths(fixture::path("bars"), state_path("assets"), 1000.0)
+                .expect("platform should be created");
+        let assets = platform.assets(AssetFilter::default()).await.unwrap();
+        let symbols = assets.into_iter().map(|a| a.symbol).collect::<Vec<_>>();
+        assert_eq!(symbols, vec![AssetSymbol::new("VTI")]);
+    }
+
+    fn buy(amount: Amount) -> OrderRequest {
+        OrderRequest::market_order_buy(AssetSymbol::new("VTI"), amount)
+    }
+
+    fn state_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!(
+            "greed_simulated_{name}_{}.json",
+            std::process::id()
+        ))
+    }
+}
@@ -0,0 +1,613 @@
+use crate::asset::AssetSymbol;
+use crate::error::GreedError;
+use crate::num::NumFromFloat;
+use crate::platform::account::Account;
+use crate::platform::asset_class::AssetClass;
+use crate::platform::id::Id;
+use crate::platform::order::amount::Amount;
+use crate::platform::order::order_type::OrderType;
+use crate::platform::order::side::OrderSide;
+use crate::platform::order::status::Status;
+use crate::platform::order::Order;
+use crate::platform::position::Position;
+use crate::platform::request::replace::ReplaceRequest;
+use crate::platform::request::OrderRequest;
+use crate::platform::simulated::trade::Trade;
+use chrono::{DateTime, Utc};
+use num_decimal::Num;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ops::Range;
+use uuid::Uuid;
+
+/// Holdings smaller than this are treated as fully sold. Sell quantities may be rounded before
+/// they reach the book which would otherwise leave tiny fractional positions behind.
+const DUST_QUANTITY: f64 = 1e-6;
+/// Buys may exceed the available cash by this much to absorb floating point error.
+const CASH_TOLERANCE: f64 = 0.01;
+/// The number of orders returned by `recent_orders`, matching Alpaca's default page size.
+const RECENT_ORDER_LIMIT: usize = 50;
+
+/// An in-process order book tracking cash, holdings and orders for simulated platforms.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct OrderBook {
+    cash: f64,
+    holdings: HashMap<AssetSymbol, Holding>,
+    orders: Vec<Order>,
+    trades: Vec<Trade>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+struct Holding {
+    quantity: f64,
+    average_entry_price: f64,
+}
+
+impl OrderBook {
+    pub fn new(cash: f64) -> Self {
+        Self {
+            cash,
+            ..Default::default()
+        }
+    }
+
+    pub fn trades(&self) -> &[Trade] {
+        &self.trades
+    }
+
+    /// Submits an order to the book. Market orders are filled immediately at `price` while limit
+    /// orders stay open until `settle` finds a price which satisfies them.
+    pub fn submit(
+        &mut self,
+        request: OrderRequest,
+        price: Option<f64>,
+        now: DateTime<Utc>,
+    ) -> Result<Order, GreedError> {
+        let limit_price = match request.order_type {
+            OrderType::Market => None,
+            OrderType::Limit => Some(
+                request
+                    .limit_price
+                    .as_ref()
+                    .and_then(|p| p.to_f64())
+                    .ok_or(GreedError::new("limit orders require a limit price"))?,
+            ),
+            _ => {
+                return Err(GreedError::new(&format!(
+                    "{} orders are not supported by simulated platforms",
+                    request.order_type
+                )))
+            }
+        };
+        let mut order = Order {
+            id: self.next_id(),
+            status: Status::New,
+            created_at: now,
+            submitted_at: Some(now),
+            asset_class: AssetClass::UsEquity,
+            symbol: request.symbol,
+            amount: request.amount,
+            order_type: request.order_type,
+            class: request.class,
+            side: request.side,
+            time_in_force: request.time_in_force,
+            limit_price: request.limit_price,
+            extended_hours: request.extended_hours,
+            client_order_id: request.client_order_id,
+            ..Default::default()
+        };
+        match limit_price {
+            None => {
+                let price = price
+                    .filter(|p| *p > 0.0)
+                    .ok_or(GreedError::new(&format!("no price for {}", order.symbol)))?;
+                self.fill(&mut order, price, now)?;
+            }
+            Some(_) => self.check_buying_power(&order, price.unwrap_or_default())?,
+        }
+        self.orders.push(order.clone());
+        Ok(order)
+    }
+
+    /// Cancels an open order, returning the canceled order.
+    pub fn cancel(&mut self, id: &Id, now: DateTime<Utc>) -> Result<Order, GreedError> {
+        let index = self.open_order_index(id)?;
+        let order = &mut self.orders[index];
+        order.status = Status::Canceled;
+        order.canceled_at = Some(now);
+        order.updated_at = Some(now);
+        Ok(order.clone())
+    }
+
+    /// Marks an open order as replaced and opens an amended copy of it in its place.
+    pub fn replace(
+        &mut self,
+        id: &Id,
+        request: &ReplaceRequest,
+        now: DateTime<Utc>,
+    ) -> Result<Order, GreedError> {
+        let index = self.open_order_index(id)?;
+        let original = self.orders[index].clone();
+        let replacement = Order {
+            id: self.next_id(),
+            created_at: now,
+            submitted_at: Some(now),
+            updated_at: None,
+            ..request.apply_to(&original)
+        };
+        self.orders[index].status = Status::Replaced;
+        self.orders[index].updated_at = Some(now);
+        // The original no longer reserves buying power, restore it if the replacement can't fit.
+        if let Err(e) = self.check_buying_power(&replacement, 0.0) {
+            self.orders[index] = original;
+            return Err(e);
+        }
+        self.orders.push(replacement.clone());
+        Ok(replacement)
+    }
+
+    /// Sells the entire holding for `symbol` at `price`.
+    pub fn close(
+        &mut self,
+        symbol: &AssetSymbol,
+        price: Option<f64>,
+        now: DateTime<Utc>,
+    ) -> Result<Order, GreedError> {
+        let quantity = self
+            .holdings
+            .get(symbol)
+            .map(|h| h.quantity)
+            .ok_or(GreedError::new(&format!(
+                "no position to close for {symbol}"
+            )))?;
+        let request = OrderRequest::market_order_sell(symbol.clone(), Amount::Quantity(quantity));
+        self.submit(request, price, now)
+    }
+
+    /// Fills any open limit orders whose limit is satisfied by the current price.
+    pub fn settle<F>(&mut self, price_for: F, now: DateTime<Utc>) -> Vec<Trade>
+    where
+        F: Fn(&AssetSymbol) -> Option<f64>,
+    {
+        let start = self.trades.len();
+        let mut orders = std::mem::take(&mut self.orders);
+        for order in orders.iter_mut().filter(|o| o.status == Status::New) {
+            let Some(price) = price_for(&order.symbol).filter(|p| *p > 0.0) else {
+                continue;
+            };
+            let limit = order
+                .limit_price
+                .as_ref()
+                .and_then(|p| p.to_f64())
+                .unwrap_or_default();
+            let satisfied = match order.side {
+                OrderSide::Buy => price <= limit,
+                OrderSide::Sell => price >= limit,
+            };
+            if satisfied {
+                if let Err(e) = self.fill(order, price, now) {
+                    order.status = Status::Rejected;
+                    order.updated_at = Some(now);
+                    log::warn!("rejecting simulated order {}: {e}", order);
+                }
+            }
+        }
+        self.orders = orders;
+        self.trades[start..].to_vec()
+    }
+
+    pub fn account<F>(&self, price_for: F) -> Account
+    where
+        F: Fn(&AssetSymbol) -> Option<f64>,
+    {
+        let market_value_long = self
+            .positions(price_for)
+            .iter()
+            .filter_map(|p| p.market_value)
+            .sum::<f64>();
+        Account {
+            buying_power: self.buying_power(),
+            cash: self.cash,
+            currency: "USD".to_string(),
+            market_value_long,
+            equity: self.cash + market_value_long,
+            ..Default::default()
+        }
+    }
+
+    pub fn positions<F>(&self, price_for: F) -> Vec<Position>
+    where
+        F: Fn(&AssetSymbol) -> Option<f64>,
+    {
+        self.holdings
+            .iter()
+            .map(|(symbol, holding)| {
+                let price = price_for(symbol).unwrap_or(holding.average_entry_price);
+                holding.position(symbol, price)
+            })
+            .collect()
+    }
+
+    pub fn open_orders(&self) -> Vec<Order> {
+        self.orders
+            .iter()
+            .filter(|o| o.status == Status::New)
+            .cloned()
+            .collect()
+    }
+
+    /// The most recent orders, newest first.
+    pub fn recent_orders(&self) -> Vec<Order> {
+        self.orders
+            .iter()
+            .rev()
+            .take(RECENT_ORDER_LIMIT)
+            .cloned()
+            .collect()
+    }
+
+    /// Every order created within `range`, newest first.
+    pub fn order_history(&self, range: &Range<DateTime<Utc>>) -> Vec<Order> {
+        self.orders
+            .iter()
+            .rev()
+            .filter(|o| range.contains(&o.created_at))
+            .cloned()
+            .collect()
+    }
+
+    fn next_id(&self) -> Id {
+        Id::Uuid(Uuid::from_u128(self.orders.len() as u128 + 1))
+    }
+
+    fn open_order_index(&self, id: &Id) -> Result<usize, GreedError> {
+        self.orders
+            .iter()
+            .position(|o| &o.id == id && o.status == Status::New)
+            .ok_or(GreedError::new(&format!("no open order {id}")))
+    }
+
+    /// Cash which isn't reserved by open buy orders.
+    fn buying_power(&self) -> f64 {
+        let reserved = self
+            .orders
+            .iter()
+            .filter(|o| o.status == Status::New && o.side == OrderSide::Buy)
+            .map(|o| {
+                let limit = o.limit_price.as_ref().and_then(|p| p.to_f64());
+                o.estimated_value(limit.unwrap_or_default())
+            })
+            .sum::<f64>();
+        self.cash - reserved
+    }
+
+    fn check_buying_power(&self, order: &Order, price: f64) -> Result<(), GreedError> {
+        if order.side == OrderSide::Sell {
+            return Ok(());
+        }
+        let limit = order.limit_price.as_ref().and_then(|p| p.to_f64());
+        let cost = order.estimated_value(limit.unwrap_or(price));
+        let buying_power = self.buying_power();
+        if cost > buying_power + CASH_TOLERANCE {
+            return Err(GreedError::new(&format!(
+                "insufficient buying power for {}: {cost:.2} > {buying_power:.2}",
+                order.symbol
+            )));
+        }
+        Ok(())
+    }
+
+    fn fill(
+        &mut self,
+        order: &mut Order,
+        price: f64,
+        now: DateTime<Utc>,
+    ) -> Result<(), GreedError> {
+        let symbol = &order.symbol;
+        let quantity = match order.amount {
+            Amount::Quantity(quantity) => quantity,
+            Amount::Notional(notional) => notional / price,
+        };
+        if quantity <= 0.0 {
+            return Err(GreedError::new("order amount must be positive"));
+        }
+        let trade = match order.side {
+            OrderSide::Buy => {
+                let cost = quantity * price;
+                if cost > self.cash + CASH_TOLERANCE {
+                    return Err(GreedError::new(&format!(
+                        "insufficient cash to buy {symbol}: {cost:.2} > {:.2}",
+                        self.cash
+                    )));
+                }
+                self.cash -= cost;
+                let holding = self.holdings.entry(symbol.clone()).or_default();
+                let total_cost = holding.average_entry_price * holding.quantity + cost;
+                holding.quantity += quantity;
+                holding.average_entry_price = total_cost / holding.quantity;
+                Trade::new(now, symbol.clone(), OrderSide::Buy, quantity, price, 0.0)
+            }
+            OrderSide::Sell => {
+                let holding = self
+                    .holdings
+                    .get_mut(symbol)
+                    .ok_or(GreedError::new(&format!(
+                        "no position to sell for {symbol}"
+                    )))?;
+                let quantity = quantity.min(holding.quantity);
+                let realized_gain = (price - holding.average_entry_price) * quantity;
+                holding.quantity -= quantity;
+                if holding.quantity < DUST_QUANTITY {
+                    self.holdings.remove(symbol);
+                }
+                self.cash += quantity * price;
+                Trade::new(
+                    now,
+                    symbol.clone(),
+                    OrderSide::Sell,
+                    quantity,
+                    price,
+                    realized_gain,
+                )
+            }
+        };
+        order.status = Status::Filled;
+        order.updated_at = Some(now);
+        order.filled_at = Some(now);
+        order.filled_quantity = Num::from_f64(trade.quantity);
+        order.average_fill_price = Some(Num::from_f64(price));
+        self.trades.push(trade);
+        Ok(())
+    }
+}
+
+impl Holding {
+    fn position(&self, symbol: &AssetSymbol, price: f64) -> Position {
+        let cost_basis = self.average_entry_price * self.quantity;
+        let market_value = price * self.quantity;
+        let gain = market_value - cost_basis;
+        let gain_percent = if cost_basis > 0.0 {
+            (gain / cost_basis) * 100.0
+        } else {
+            0.0
+        };
+        Position {
+            asset_class: AssetClass::UsEquity,
+            average_entry_price: self.average_entry_price,
+            cost_basis: Num::from_f64(cost_basis),
+            current_price: Some(price),
+            market_value: Some(market_value),
+            quantity: self.quantity,
+            quantity_available: self.quantity,
+            symbol: symbol.clone(),
+            unrealized_gain_total: Some(gain),
+            unrealized_gain_total_percent: Some(gain_percent),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::date::DateTimeFixture;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn submit_market_buy_notional() {
+        let mut book = OrderBook::new(1000.0);
+        let order = book
+            .submit(buy(Amount::Notional(500.0)), Some(10.0), now())
+            .unwrap();
+        assert_eq!(order.status, Status::Filled);
+        assert_eq!(order.average_fill_price, Some(Num::from(10)));
+
+        let positions = book.positions(|_| Some(20.0));
+        assert_eq!(positions.len(), 1);
+        assert_relative_eq!(positions[0].quantity, 50.0);
+
+        let account = book.account(|_| Some(20.0));
+        assert_relative_eq!(account.cash, 500.0);
+        assert_relative_eq!(account.equity, 1500.0);
+    }
+
+    #[test]
+    fn submit_market_without_price() {
+        let mut book = OrderBook::new(1000.0);
+        let result = book.submit(buy(Amount::Quantity(1.0)), None, now());
+        assert!(result.is_err());
+        assert!(book.recent_orders().is_empty());
+    }
+
+    #[test]
+    fn submit_market_insufficient_cash() {
+        let mut book = OrderBook::new(1000.0);
+        let result = book.submit(buy(Amount::Notional(5000.0)), Some(10.0), now());
+        assert!(result.is_err())
+    }
+
+    #[test]
+    fn submit_market_sell_realizes_gain() {
+        let mut book = OrderBook::new(1000.0);
+        book.submit(buy(Amount::Quantity(10.0)), Some(10.0), now())
+            .unwrap();
+        book.submit(sell(Amount::Quantity(20.0)), Some(20.0), now())
+            .unwrap();
+
+        let trades = book.trades();
+        assert_eq!(trades.len(), 2);
+        assert_relative_eq!(trades[1].quantity, 10.0);
+        assert_relative_eq!(trades[1].realized_gain, 100.0);
+        assert!(book.positions(|_| None).is_empty());
+        assert_relative_eq!(book.account(|_| None).cash, 1100.0);
+    }
+
+    #[test]
+    fn submit_unsupported_order_type() {
+        let mut book = OrderBook::new(1000.0);
+        let request = OrderRequest {
+            order_type: OrderType::TrailingStop,
+            ..buy(Amount::Quantity(1.0))
+        };
+        assert!(book.submit(request, Some(10.0), now()).is_err())
+    }
+
+    #[test]
+    fn submit_limit_reserves_buying_power() {
+        let mut book = OrderBook::new(1000.0);
+        let order = book
+            .submit(limit_buy(Amount::Quantity(10.0), 9), Some(10.0), now())
+            .unwrap();
+        assert_eq!(order.status, Status::New);
+        assert_eq!(book.open_orders(), vec![order]);
+        assert_relative_eq!(book.account(|_| None).buying_power, 910.0);
+
+        let result = book.submit(limit_buy(Amount::Quantity(200.0), 9), Some(10.0), now());
+        assert!(result.is_err())
+    }
+
+    #[test]
+    fn settle_fills_satisfied_limit() {
+        let mut book = OrderBook::new(1000.0);
+        book.submit(limit_buy(Amount::Quantity(10.0), 9), Some(10.0), now())
+            .unwrap();
+
+        assert!(book.settle(|_| Some(9.5), now()).is_empty());
+        let trades = book.settle(|_| Some(8.0), now());
+
+        assert_eq!(trades.len(), 1);
+        assert_relative_eq!(trades[0].price, 8.0);
+        assert!(book.open_orders().is_empty());
+        assert_eq!(book.recent_orders()[0].status, Status::Filled);
+        assert_relative_eq!(book.account(|_| None).cash, 920.0);
+    }
+
+    #[test]
+    fn cancel_releases_buying_power() {
+        let mut book = OrderBook::new(1000.0);
+        let order = book
+            .submit(limit_buy(Amount::Quantity(10.0), 9), Some(10.0), now())
+            .unwrap();
+
+        let canceled = book.cancel(&order.id, now()).unwrap();
+
+        assert_eq!(canceled.status, Status::Canceled);
+        assert_eq!(canceled.canceled_at, Some(now()));
+        assert!(book.open_orders().is_empty());
+        assert_relative_eq!(book.account(|_| None).buying_power, 1000.0);
+        assert!(book.cancel(&order.id, now()).is_err());
+    }
+
+    #[test]
+    fn replace_amends_open_order() {
+        let mut book = OrderBook::new(1000.0);
+        let order = book
+            .submit(limit_buy(Amount::Quantity(10.0), 9), Some(10.0), now())
+            .unwrap();
+        let request = ReplaceRequest {
+            quantity: Some(20.0),
+            limit_price: Some(Num::from(8)),
+        };
+
+        let replacement = book.replace(&order.id, &request, now()).unwrap();
+
+        assert_ne!(replacement.id, order.id);
+        assert_eq!(replacement.amount, Amount::Quantity(20.0));
+        assert_eq!(book.open_orders(), vec![replacement]);
+        assert_eq!(book.recent_orders()[1].status, Status::Replaced);
+        assert_relative_eq!(book.account(|_| None).buying_power, 840.0);
+    }
+
+    #[test]
+    fn replace_insufficient_buying_power() {
+        let mut book = OrderBook::new(1000.0);
+        let order = book
+            .submit(limit_buy(Amount::Quantity(10.0), 9), Some(10.0), now())
+            .unwrap();
+        let request = ReplaceRequest {
+            quantity: Some(200.0),
+            ..Default::default()
+        };
+
+        assert!(book.replace(&order.id, &request, now()).is_err());
+        assert_eq!(book.open_orders(), vec![order]);
+    }
+
+    #[test]
+    fn close_sells_entire_holding() {
+        let mut book = OrderBook::new(1000.0);
+        book.submit(buy(Amount::Quantity(10.0)), Some(10.0), now())
+            .unwrap();
+
+        let order = book
+            .close(&AssetSymbol::new("VTI"), Some(12.0), now())
+            .unwrap();
+
+        assert_eq!(order.side, OrderSide::Sell);
+        assert_eq!(order.status, Status::Filled);
+        assert!(book.positions(|_| None).is_empty());
+        assert!(book
+            .close(&AssetSymbol::new("VTI"), Some(12.0), now())
+            .is_err());
+    }
+
+    #[test]
+    fn recent_orders_newest_first() {
+        let mut book = OrderBook::new(1000.0);
+        book.submit(buy(Amount::Quantity(1.0)), Some(10.0), now())
+            .unwrap();
+        book.submit(sell(Amount::Quantity(1.0)), Some(10.0), now())
+            .unwrap();
+        let sides = book
+            .recent_orders()
+            .iter()
+            .map(|o| o.side)
+            .collect::<Vec<_>>();
+        assert_eq!(sides, vec![OrderSide::Sell, OrderSide::Buy])
+    }
+
+    #[test]
+    fn order_history_filters_by_created_at() {
+        let mut book = OrderBook::new(1000.0);
+        let later = now() + chrono::Duration::days(1);
+        book.submit(buy(Amount::Quantity(1.0)), Some(10.0), now())
+            .unwrap();
+        book.submit(sell(Amount::Quantity(1.0)), Some(10.0), later)
+            .unwrap();
+        let history = book.order_history(&(later..later + chrono::Duration::days(1)));
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].side, OrderSide::Sell);
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        let mut book = OrderBook::new(1000.0);
+        book.submit(buy(Amount::Quantity(1.0)), Some(10.0), now())
+            .unwrap();
+        book.submit(limit_buy(Amount::Notional(10.0), 5), Some(10.0), now())
+            .unwrap();
+        let json = serde_json::to_string(&book).expect("book should serialize");
+        let restored: OrderBook = serde_json::from_str(&json).expect("book should deserialize");
+        assert_eq!(restored, book)
+    }
+
+    fn buy(amount: Amount) -> OrderRequest {
+        OrderRequest::market_order_buy(AssetSymbol::new("VTI"), amount)
+    }
+
+    fn sell(amount: Amount) -> OrderRequest {
+        OrderRequest::market_order_sell(AssetSymbol::new("VTI"), amount)
+    }
+
+    fn limit_buy(amount: Amount, limit: i32) -> OrderRequest {
+        OrderRequest {
+            order_type: OrderType::Limit,
+            limit_price: Some(Num::from(limit)),
+            ..buy(amount)
+        }
+    }
+
+    fn now() -> DateTime<Utc> {
+        DateTimeFixture::utc()
+    }
+}
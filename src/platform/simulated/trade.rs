@@ -0,0 +1,90 @@
+use crate::asset::AssetSymbol;
+use crate::platform::order::side::OrderSide;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// A fill which happened on a simulated platform.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Trade {
+    pub time: DateTime<Utc>,
+    pub symbol: AssetSymbol,
+    pub side: OrderSide,
+    pub quantity: f64,
+    pub price: f64,
+    /// The gain realized by this trade. This is always zero for buys.
+    pub realized_gain: f64,
+}
+
+impl Trade {
+    pub fn new(
+        time: DateTime<Utc>,
+        symbol: AssetSymbol,
+        side: OrderSide,
+        quantity: f64,
+        price: f64,
+        realized_gain: f64,
+    ) -> Self {
+        Self {
+            time,
+            symbol,
+            side,
+            quantity,
+            price,
+            realized_gain,
+        }
+    }
+}
+
+impl Display for Trade {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let time = self.time.format("%Y-%m-%d %H:%M");
+        write!(
+            f,
+            "{} {} {:.4} of {} @ {:.2}",
+            time, self.side, self.quantity, self.symbol, self.price
+        )?;
+        if self.side == OrderSide::Sell {
+            write!(f, " (realized: {:.2})", self.realized_gain)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date::DateTimeFixture;
+
+    #[test]
+    fn display_buy() {
+        let trade = Trade::new(
+            DateTimeFixture::utc(),
+            AssetSymbol::new("VTI"),
+            OrderSide::Buy,
+            2.0,
+            100.0,
+            0.0,
+        );
+        assert_eq!(
+            trade.to_string(),
+            "2023-12-04 08:00 buy 2.0000 of VTI @ 100.00"
+        )
+    }
+
+    #[test]
+    fn display_sell() {
+        let trade = Trade::new(
+            DateTimeFixture::utc(),
+            AssetSymbol::new("VTI"),
+            OrderSide::Sell,
+            2.0,
+            100.0,
+            10.0,
+        );
+        assert_eq!(
+            trade.to_string(),
+            "2023-12-04 08:00 sell 2.0000 of VTI @ 100.00 (realized: 10.00)"
+        )
+    }
+}
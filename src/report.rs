@@ -0,0 +1,417 @@
+use crate::asset::AssetSymbol;
+use crate::error::GreedError;
+use crate::journal::{read_journal, JournalEntry};
+use crate::platform;
+use crate::platform::args::PlatformArgs;
+use crate::platform::id::Id;
+use crate::platform::order::side::OrderSide;
+use crate::platform::order::Order;
+use crate::platform::position::Position;
+use crate::report::ledger::{GroupKey, Ledger, Performance};
+use crate::strategy::reader::read_config_from_path;
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use clap::ValueEnum;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+mod ledger;
+
+/// Shown in place of an empty strategy name, which belongs to tactics in the top level config.
+const CONFIG_STRATEGY: &str = "(config)";
+/// Groups filled orders which aren't in the journal, such as manual trades.
+const UNTRACKED: &str = "(untracked)";
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    #[default]
+    Table,
+    Csv,
+    Json,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ReportArgs {
+    pub config_path: PathBuf,
+    /// Only include orders created on or after this day.
+    pub start: Option<NaiveDate>,
+    /// Only include orders created on or before this day.
+    pub end: Option<NaiveDate>,
+    pub is_simulated: bool,
+}
+
+impl From<&ReportArgs> for PlatformArgs {
+    fn from(value: &ReportArgs) -> Self {
+        PlatformArgs {
+            is_simulated: value.is_simulated,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportLevel {
+    Strategy,
+    Tactic,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct PerformanceRow {
+    pub level: ReportLevel,
+    pub strategy: String,
+    /// The tactic, empty for strategy rows.
+    pub tactic: String,
+    pub cost_basis: f64,
+    pub realized_gain: f64,
+    pub unrealized_gain: f64,
+    pub return_percent: f64,
+    pub closed_trades: usize,
+    pub win_rate_percent: f64,
+    pub average_holding_days: f64,
+}
+
+impl PerformanceRow {
+    fn new(level: ReportLevel, strategy: &str, tactic: &str, performance: &Performance) -> Self {
+        Self {
+            level,
+            strategy: strategy.to_string(),
+            tactic: tactic.to_string(),
+            cost_basis: performance.cost_basis,
+            realized_gain: performance.realized_gain,
+            unrealized_gain: performance.unrealized_gain,
+            return_percent: performance.return_percent(),
+            closed_trades: performance.closed_trades,
+            win_rate_percent: performance.win_rate_percent(),
+            average_holding_days: performance.average_holding_days(),
+        }
+    }
+}
+
+/// Profit and loss per strategy and tactic, built by joining the journal with the platform's
+/// order history.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PerformanceReport {
+    pub rows: Vec<PerformanceRow>,
+}
+
+impl PerformanceReport {
+    /// Reads the config's journal and fetches the platform's order history and positions.
+    pub async fn from_args(args: &ReportArgs) -> Result<Self, GreedError> {
+        let config = read_config_from_path(&args.config_path).await?;
+        let journal = match config.journal_path(&args.config_path) {
+            Some(path) if path.exists() => read_journal(path)?,
+            _ => {
+                warn!("no journal found, every order will be reported as untracked");
+                Vec::new()
+            }
+        };
+        let platform = platform::for_type(&config.platform, args.into())?;
+        let start = match args.start {
+            Some(start) => start_of_day(start),
+            None => journal
+                .iter()
+                .map(JournalEntry::time)
+                .min()
+                .unwrap_or(DateTime::<Utc>::MIN_UTC),
+        };
+        let end = match args.end {
+            Some(end) => start_of_day(end + Days::new(1)),
+            None => platform.now(),
+        };
+        let orders = platform.order_history(start..end).await?;
+        let positions = platform.positions().await?;
+        Ok(Self::new(&journal, &orders, &positions))
+    }
+
+    pub fn new(journal: &[JournalEntry], orders: &[Order], positions: &[Position]) -> Self {
+        let groups = Self::groups_by_order(journal);
+        let mut fills = orders
+            .iter()
+            .filter(|o| o.filled_quantity > 0.into() && o.average_fill_price.is_some())
+            .collect::<Vec<_>>();
+        fills.sort_by_key(|o| o.filled_at.unwrap_or(o.created_at));
+
+        let mut ledger = Ledger::default();
+        for order in fills {
+            let quantity = order.filled_quantity.to_f64().unwrap_or(0.0);
+            let price = order
+                .average_fill_price
+                .as_ref()
+                .and_then(|p| p.to_f64())
+                .unwrap_or(0.0);
+            let time = order.filled_at.unwrap_or(order.created_at);
+            match order.side {
+                OrderSide::Buy => {
+                    let group = groups
+                        .get(&order.id)
+                        .cloned()
+                        .unwrap_or((UNTRACKED.to_string(), String::new()));
+                    ledger.buy(group, order.symbol.clone(), quantity, price, time)
+                }
+                OrderSide::Sell => {
+                    let unmatched = ledger.sell(&order.symbol, quantity, price, time);
+                    if unmatched > 0.0 {
+                        warn!(
+                            "no earlier buys found for {unmatched} shares of {} sold, skipping",
+                            order.symbol
+                        );
+                    }
+                }
+            }
+        }
+
+        let current_prices = positions
+            .iter()
+            .filter_map(|p| p.current_price.map(|price| (p.symbol.clone(), price)))
+            .collect::<HashMap<AssetSymbol, f64>>();
+        // Sorted so the output is stable between runs.
+        let by_tactic = ledger
+            .finish(&current_prices)
+            .into_iter()
+            .collect::<BTreeMap<_, _>>();
+        Self::from_performance(by_tactic)
+    }
+
+    fn groups_by_order(journal: &[JournalEntry]) -> HashMap<Id, GroupKey> {
+        journal
+            .iter()
+            .filter_map(|entry| match entry {
+                JournalEntry::OrderPlaced {
+                    source,
+                    response: Ok(order),
+                    ..
+                } => {
+                    let strategy = if source.strategy.is_empty() {
+                        CONFIG_STRATEGY.to_string()
+                    } else {
+                        source.strategy.clone()
+                    };
+                    Some((order.id.clone(), (strategy, source.tactic.clone())))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn from_performance(by_tactic: BTreeMap<GroupKey, Performance>) -> Self {
+        let mut by_strategy = BTreeMap::<String, Performance>::new();
+        for ((strategy, _), performance) in &by_tactic {
+            by_strategy
+                .entry(strategy.clone())
+                .or_default()
+                .merge(performance);
+        }
+        let strategy_rows = by_strategy.iter().map(|(strategy, performance)| {
+            PerformanceRow::new(ReportLevel::Strategy, strategy, "", performance)
+        });
+        let tactic_rows = by_tactic.iter().map(|((strategy, tactic), performance)| {
+            PerformanceRow::new(ReportLevel::Tactic, strategy, tactic, performance)
+        });
+        Self {
+            rows: strategy_rows.chain(tactic_rows).collect(),
+        }
+    }
+
+    pub fn render(&self, format: &ReportFormat) -> Result<String, GreedError> {
+        match format {
+            ReportFormat::Table => Ok(self.to_string()),
+            ReportFormat::Csv => {
+                let mut writer = csv::Writer::from_writer(Vec::new());
+                for row in &self.rows {
+                    writer.serialize(row)?;
+                }
+                let bytes = writer
+                    .into_inner()
+                    .map_err(|e| GreedError::new(&e.to_string()))?;
+                Ok(String::from_utf8_lossy(&bytes).to_string())
+            }
+            ReportFormat::Json => Ok(serde_json::to_string_pretty(&self.rows)?),
+        }
+    }
+
+    fn write_table(
+        &self,
+        f: &mut Formatter<'_>,
+        level: ReportLevel,
+        title: &str,
+    ) -> std::fmt::Result {
+        writeln!(f, "{title}:")?;
+        writeln!(
+            f,
+            "{:<24} {:>12} {:>12} {:>12} {:>9} {:>7} {:>9} {:>10}",
+            "name", "cost", "realized", "unrealized", "return", "trades", "win rate", "avg hold"
+        )?;
+        for row in self.rows.iter().filter(|r| r.level == level) {
+            let name = match row.level {
+                ReportLevel::Strategy => row.strategy.clone(),
+                ReportLevel::Tactic if row.tactic.is_empty() => format!("{} / -", row.strategy),
+                ReportLevel::Tactic => format!("{} / {}", row.strategy, row.tactic),
+            };
+            writeln!(
+                f,
+                "{:<24} {:>12.2} {:>12.2} {:>12.2} {:>8.2}% {:>7} {:>8.2}% {:>9.1}d",
+                name,
+                row.cost_basis,
+                row.realized_gain,
+                row.unrealized_gain,
+                row.return_percent,
+                row.closed_trades,
+                row.win_rate_percent,
+                row.average_holding_days
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for PerformanceReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.rows.is_empty() {
+            return write!(f, "No filled orders found.");
+        }
+        self.write_table(f, ReportLevel::Strategy, "Strategies")?;
+        writeln!(f)?;
+        self.write_table(f, ReportLevel::Tactic, "Tactics")
+    }
+}
+
+fn start_of_day(date: NaiveDate) -> DateTime<Utc> {
+    date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date::DateTimeFixture;
+    use crate::journal::OrderSource;
+    use crate::platform::order::amount::Amount;
+    use crate::platform::order::status::Status;
+    use crate::platform::request::OrderRequest;
+    use approx::assert_relative_eq;
+    use chrono::Duration;
+    use num_decimal::Num;
+    use uuid::Uuid;
+
+    #[test]
+    fn new_attributes_fills_to_journal_sources() {
+        let buy = filled_order(1, OrderSide::Buy, 2, 10, 0);
+        let untracked = filled_order(2, OrderSide::Buy, 1, 12, 1);
+        let sell = filled_order(3, OrderSide::Sell, 2, 15, 4);
+        let journal = vec![placed("", "ETF", &buy), placed("Growth", "", &sell)];
+        let positions = vec![Position {
+            current_price: Some(20.0),
+            ..Position::fixture(AssetSymbol::new("VTI"))
+        }];
+
+        let report = PerformanceReport::new(&journal, &[sell, untracked, buy], &positions);
+
+        let config = row(&report, ReportLevel::Strategy, CONFIG_STRATEGY);
+        assert_relative_eq!(config.cost_basis, 20.0);
+        assert_relative_eq!(config.realized_gain, 10.0);
+        assert_relative_eq!(config.return_percent, 50.0);
+        assert_eq!(config.closed_trades, 1);
+        assert_relative_eq!(config.win_rate_percent, 100.0);
+        assert_relative_eq!(config.average_holding_days, 4.0);
+        let untracked = row(&report, ReportLevel::Strategy, UNTRACKED);
+        assert_relative_eq!(untracked.unrealized_gain, 8.0);
+        assert_eq!(untracked.closed_trades, 0);
+        let tactic = row(&report, ReportLevel::Tactic, CONFIG_STRATEGY);
+        assert_eq!(tactic.tactic, "ETF");
+        assert_eq!(report.rows.len(), 4);
+    }
+
+    #[test]
+    fn new_skips_unfilled_orders() {
+        let order = Order {
+            status: Status::New,
+            ..Order::fixture(AssetSymbol::new("VTI"))
+        };
+        let report = PerformanceReport::new(&[], &[order], &[]);
+        assert!(report.rows.is_empty());
+        assert_eq!(report.to_string(), "No filled orders found.");
+    }
+
+    #[test]
+    fn render_csv() {
+        let report = PerformanceReport {
+            rows: vec![PerformanceRow::new(
+                ReportLevel::Strategy,
+                "Growth",
+                "",
+                &Default::default(),
+            )],
+        };
+        let csv = report.render(&ReportFormat::Csv).unwrap();
+        let expected = "level,strategy,tactic,cost_basis,realized_gain,unrealized_gain,\
+return_percent,closed_trades,win_rate_percent,average_holding_days
+strategy,Growth,,0.0,0.0,0.0,0.0,0,0.0,0.0
+";
+        assert_eq!(csv, expected)
+    }
+
+    #[test]
+    fn render_json() {
+        let rows = vec![PerformanceRow::new(
+            ReportLevel::Tactic,
+            "Growth",
+            "ETF",
+            &Default::default(),
+        )];
+        let report = PerformanceReport { rows: rows.clone() };
+        let json = report.render(&ReportFormat::Json).unwrap();
+        let parsed: Vec<PerformanceRow> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, rows)
+    }
+
+    #[test]
+    fn render_table() {
+        let buy = filled_order(1, OrderSide::Buy, 2, 10, 0);
+        let journal = vec![placed("Growth", "ETF", &buy)];
+        let report = PerformanceReport::new(&journal, &[buy], &[]);
+        let table = report.render(&ReportFormat::Table).unwrap();
+        assert!(table.starts_with("Strategies:\n"));
+        assert!(table.contains("\nGrowth "));
+        assert!(table.contains("\nTactics:\n"));
+        assert!(table.contains("\nGrowth / ETF "));
+    }
+
+    fn filled_order(id: u128, side: OrderSide, quantity: i32, price: i32, day: i64) -> Order {
+        Order {
+            id: Id::Uuid(Uuid::from_u128(id)),
+            status: Status::Filled,
+            side,
+            filled_at: Some(DateTimeFixture::utc() + Duration::days(day)),
+            filled_quantity: Num::from(quantity),
+            average_fill_price: Some(Num::from(price)),
+            ..Order::fixture(AssetSymbol::new("VTI"))
+        }
+    }
+
+    fn placed(strategy: &str, tactic: &str, order: &Order) -> JournalEntry {
+        JournalEntry::OrderPlaced {
+            time: DateTimeFixture::utc(),
+            source: OrderSource {
+                strategy: strategy.to_string(),
+                tactic: tactic.to_string(),
+                ..Default::default()
+            },
+            request: OrderRequest::market_order_buy(order.symbol.clone(), Amount::Quantity(1.0)),
+            response: Ok(order.clone()),
+        }
+    }
+
+    fn row<'a>(
+        report: &'a PerformanceReport,
+        level: ReportLevel,
+        strategy: &str,
+    ) -> &'a PerformanceRow {
+        report
+            .rows
+            .iter()
+            .find(|r| r.level == level && r.strategy == strategy)
+            .expect("row should exist")
+    }
+}
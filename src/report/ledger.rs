@@ -0,0 +1,224 @@
+use crate::asset::AssetSymbol;
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, VecDeque};
+
+/// The strategy and tactic which an order is attributed to.
+pub type GroupKey = (String, String);
+
+/// A filled buy which hasn't been fully sold yet.
+#[derive(Clone, Debug, PartialEq)]
+struct Lot {
+    group: GroupKey,
+    quantity: f64,
+    price: f64,
+    time: DateTime<Utc>,
+}
+
+/// The running totals for a single strategy or tactic.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Performance {
+    pub cost_basis: f64,
+    pub realized_gain: f64,
+    pub unrealized_gain: f64,
+    pub closed_trades: usize,
+    pub winning_trades: usize,
+    pub holding_days: f64,
+}
+
+impl Performance {
+    pub fn merge(&mut self, other: &Performance) {
+        self.cost_basis += other.cost_basis;
+        self.realized_gain += other.realized_gain;
+        self.unrealized_gain += other.unrealized_gain;
+        self.closed_trades += other.closed_trades;
+        self.winning_trades += other.winning_trades;
+        self.holding_days += other.holding_days;
+    }
+
+    pub fn return_percent(&self) -> f64 {
+        if self.cost_basis <= 0.0 {
+            return 0.0;
+        }
+        (self.realized_gain + self.unrealized_gain) / self.cost_basis * 100.0
+    }
+
+    pub fn win_rate_percent(&self) -> f64 {
+        if self.closed_trades == 0 {
+            return 0.0;
+        }
+        self.winning_trades as f64 / self.closed_trades as f64 * 100.0
+    }
+
+    pub fn average_holding_days(&self) -> f64 {
+        if self.closed_trades == 0 {
+            return 0.0;
+        }
+        self.holding_days / self.closed_trades as f64
+    }
+}
+
+/// Matches sells against earlier buys first in, first out.
+///
+/// Gains are credited to the group which bought each lot, so a strategy is judged on the capital
+/// it deployed even when another tactic (or a manual close) sells the position.
+#[derive(Debug, Default)]
+pub struct Ledger {
+    lots: HashMap<AssetSymbol, VecDeque<Lot>>,
+    performance: HashMap<GroupKey, Performance>,
+}
+
+impl Ledger {
+    pub fn buy(
+        &mut self,
+        group: GroupKey,
+        symbol: AssetSymbol,
+        quantity: f64,
+        price: f64,
+        time: DateTime<Utc>,
+    ) {
+        self.performance
+            .entry(group.clone())
+            .or_default()
+            .cost_basis += quantity * price;
+        self.lots.entry(symbol).or_default().push_back(Lot {
+            group,
+            quantity,
+            price,
+            time,
+        })
+    }
+
+    /// Sells against the oldest lots. Returns the quantity which had no lot to match, typically
+    /// because it was bought before the report's date range.
+    pub fn sell(
+        &mut self,
+        symbol: &AssetSymbol,
+        quantity: f64,
+        price: f64,
+        time: DateTime<Utc>,
+    ) -> f64 {
+        let mut remaining = quantity;
+        let Some(lots) = self.lots.get_mut(symbol) else {
+            return remaining;
+        };
+        while remaining > 0.0 {
+            let Some(lot) = lots.front_mut() else {
+                break;
+            };
+            let matched = remaining.min(lot.quantity);
+            let gain = (price - lot.price) * matched;
+            let performance = self.performance.entry(lot.group.clone()).or_default();
+            performance.realized_gain += gain;
+            performance.closed_trades += 1;
+            if gain > 0.0 {
+                performance.winning_trades += 1;
+            }
+            performance.holding_days += (time - lot.time).num_seconds() as f64 / 86_400.0;
+
+            lot.quantity -= matched;
+            remaining -= matched;
+            if lot.quantity <= f64::EPSILON {
+                lots.pop_front();
+            }
+        }
+        remaining
+    }
+
+    /// Values the remaining lots at their symbol's current price and returns the totals for each
+    /// group. Lots without a current price are valued at cost.
+    pub fn finish(
+        mut self,
+        current_prices: &HashMap<AssetSymbol, f64>,
+    ) -> HashMap<GroupKey, Performance> {
+        for (symbol, lots) in &self.lots {
+            for lot in lots {
+                let price = current_prices.get(symbol).copied().unwrap_or(lot.price);
+                let performance = self.performance.entry(lot.group.clone()).or_default();
+                performance.unrealized_gain += (price - lot.price) * lot.quantity;
+            }
+        }
+        self.performance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date::DateTimeFixture;
+    use approx::assert_relative_eq;
+    use chrono::Duration;
+
+    #[test]
+    fn sell_matches_oldest_lots() {
+        let vti = AssetSymbol::new("VTI");
+        let start = DateTimeFixture::utc();
+        let mut ledger = Ledger::default();
+        ledger.buy(group("a"), vti.clone(), 2.0, 10.0, start);
+        ledger.buy(
+            group("b"),
+            vti.clone(),
+            2.0,
+            20.0,
+            start + Duration::days(1),
+        );
+
+        let unmatched = ledger.sell(&vti, 3.0, 15.0, start + Duration::days(2));
+        let performance = ledger.finish(&HashMap::from([(vti, 30.0)]));
+
+        assert_eq!(unmatched, 0.0);
+        let a = &performance[&group("a")];
+        assert_relative_eq!(a.realized_gain, 10.0);
+        assert_eq!(a.closed_trades, 1);
+        assert_eq!(a.winning_trades, 1);
+        assert_relative_eq!(a.holding_days, 2.0);
+        let b = &performance[&group("b")];
+        assert_relative_eq!(b.realized_gain, -5.0);
+        assert_relative_eq!(b.unrealized_gain, 10.0);
+        assert_relative_eq!(b.cost_basis, 40.0);
+        assert_eq!(b.winning_trades, 0);
+    }
+
+    #[test]
+    fn sell_without_lots() {
+        let vti = AssetSymbol::new("VTI");
+        let mut ledger = Ledger::default();
+        let unmatched = ledger.sell(&vti, 3.0, 15.0, DateTimeFixture::utc());
+        assert_eq!(unmatched, 3.0);
+    }
+
+    #[test]
+    fn finish_values_lots_at_cost_without_price() {
+        let vti = AssetSymbol::new("VTI");
+        let mut ledger = Ledger::default();
+        ledger.buy(group("a"), vti, 2.0, 10.0, DateTimeFixture::utc());
+        let performance = ledger.finish(&HashMap::new());
+        assert_eq!(performance[&group("a")].unrealized_gain, 0.0);
+    }
+
+    #[test]
+    fn performance_ratios() {
+        let performance = Performance {
+            cost_basis: 200.0,
+            realized_gain: 10.0,
+            unrealized_gain: 10.0,
+            closed_trades: 4,
+            winning_trades: 3,
+            holding_days: 10.0,
+        };
+        assert_relative_eq!(performance.return_percent(), 10.0);
+        assert_relative_eq!(performance.win_rate_percent(), 75.0);
+        assert_relative_eq!(performance.average_holding_days(), 2.5);
+    }
+
+    #[test]
+    fn performance_ratios_empty() {
+        let performance = Performance::default();
+        assert_eq!(performance.return_percent(), 0.0);
+        assert_eq!(performance.win_rate_percent(), 0.0);
+        assert_eq!(performance.average_holding_days(), 0.0);
+    }
+
+    fn group(strategy: &str) -> GroupKey {
+        (strategy.to_string(), "tactic".to_string())
+    }
+}
@@ -2,14 +2,20 @@ use crate::asset::AssetSymbol;
 use crate::config::simple::SimpleConfig;
 use crate::config::Config;
 use crate::error::GreedError;
+use crate::journal::{read_journal, Journal};
 use crate::platform;
+use crate::platform::dry_run::DryRunPlatform;
+use crate::platform::record::RecordingPlatform;
+use crate::platform::replay::ReplayPlatform;
+use crate::platform::risk_guard::RiskGuardPlatform;
 use crate::platform::FinancialPlatform;
 use crate::strategy::factory::StrategyProviderFactory;
 use crate::strategy::provider::StrategyRunnerProvider;
 use crate::strategy::runner::{StrategyRunner, TacticStrategyRunner};
-use log::warn;
+use itertools::Itertools;
+use log::{info, warn};
 use std::ffi::OsStr;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::time::sleep;
@@ -18,9 +24,17 @@ use tokio::time::sleep;
 pub struct GreedRunnerArgs {
     pub config_path: PathBuf,
     pub is_simulated: bool,
+    /// Record every platform call to this JSONL cassette.
+    pub record_path: Option<PathBuf>,
+    /// Replay platform responses from this JSONL cassette instead of using a live platform.
+    pub replay_path: Option<PathBuf>,
+    /// Evaluate rules as usual but only log the orders which would be placed.
+    pub is_dry_run: bool,
 }
 
 pub struct GreedRunner {
+    platform: Arc<dyn FinancialPlatform>,
+    journal: Arc<Journal>,
     run_interval: u64,
     config_strategy: Box<dyn StrategyRunner>,
     strategy_providers: Vec<Box<dyn StrategyRunnerProvider>>,
@@ -32,9 +46,13 @@ impl GreedRunner {
         config: Config,
         config_path: PathBuf,
         platform: Arc<dyn FinancialPlatform>,
+        journal: Arc<Journal>,
     ) -> Result<Self, GreedError> {
-        let config_strategy = Box::new(TacticStrategyRunner::from_config(&config, &platform));
-        let factory = StrategyProviderFactory::new(&config, config_path, &platform);
+        let config_strategy = Box::new(TacticStrategyRunner::from_config(
+            &config, &platform, &journal,
+        ));
+        let factory =
+            StrategyProviderFactory::new(&config, config_path, &platform, journal.clone());
         let strategy_providers = factory.create_providers().await?;
 
         // Accumulate assets from all providers
@@ -46,6 +64,8 @@ impl GreedRunner {
         config_assets.extend(config.tactics.iter().flat_map(|t| t.assets()));
 
         Ok(Self {
+            platform,
+            journal,
             run_interval: config.interval,
             config_strategy,
             strategy_providers,
@@ -56,8 +76,109 @@ impl GreedRunner {
     pub async fn from_args(args: GreedRunnerArgs) -> Result<GreedRunner, GreedError> {
         let config_path = args.config_path.clone();
         let config = GreedRunner::read_config(&args).await?;
-        let platform = platform::for_type(&config.platform, args.into())?;
-        Self::new(config, config_path, platform).await
+        let journal = GreedRunner::create_journal(&config, &config_path, &args)?;
+        // A replayed run was already validated when it was recorded.
+        let is_replay = args.replay_path.is_some();
+        let platform = GreedRunner::create_platform(&config, args)?;
+        let runner = Self::new(config, config_path, platform, journal).await?;
+        if !is_replay {
+            Self::validate_assets(runner.platform.as_ref(), &runner.config_assets).await?;
+        }
+        Ok(runner)
+    }
+
+    /// Looks up every asset the config names, so a typo fails at startup instead of being
+    /// skipped on every pass of the loop.
+    async fn validate_assets(
+        platform: &dyn FinancialPlatform,
+        assets: &[AssetSymbol],
+    ) -> Result<(), GreedError> {
+        let mut problems = Vec::new();
+        for symbol in assets.iter().unique() {
+            match platform.asset(symbol).await {
+                Ok(info) if info.tradable => info!("✅ {info}"),
+                Ok(_) => problems.push(format!("{symbol} isn't tradable")),
+                Err(e) => problems.push(format!("{symbol} couldn't be found ({e})")),
+            }
+        }
+        if problems.is_empty() {
+            return Ok(());
+        }
+        Err(GreedError::new(&format!(
+            "the config has unknown assets: {}",
+            problems.join(", ")
+        )))
+    }
+
+    fn create_journal(
+        config: &Config,
+        config_path: &Path,
+        args: &GreedRunnerArgs,
+    ) -> Result<Arc<Journal>, GreedError> {
+        // Dry runs and replays don't place real orders, so there is nothing to journal.
+        if args.is_dry_run || args.replay_path.is_some() {
+            return Ok(Journal::disabled());
+        }
+        match config.journal_path(config_path) {
+            Some(journal_path) => {
+                info!("📓 Journaling orders to {}", journal_path.display());
+                Ok(Arc::new(Journal::new(journal_path)?))
+            }
+            None => Ok(Journal::disabled()),
+        }
+    }
+
+    /// Wraps the platform in the risk guard when the config sets any risk limits. The guard is
+    /// outermost so the account and quote calls it makes are recorded and replayed too.
+    fn create_platform(
+        config: &Config,
+        args: GreedRunnerArgs,
+    ) -> Result<Arc<dyn FinancialPlatform>, GreedError> {
+        // Replays don't place real orders, so they have no daily order count to resume.
+        let journal_path = config
+            .journal_path(&args.config_path)
+            .filter(|path| args.replay_path.is_none() && path.exists());
+        let platform = Self::create_base_platform(config, args)?;
+        if config.risk.is_empty() {
+            return Ok(platform);
+        }
+        info!("🛡️ Enforcing risk limits: {:?}", config.risk);
+        let guard = RiskGuardPlatform::new(platform, config.risk.clone());
+        match journal_path {
+            Some(journal_path) => Ok(Arc::new(guard.with_journal(&read_journal(journal_path)?))),
+            None => Ok(Arc::new(guard)),
+        }
+    }
+
+    fn create_base_platform(
+        config: &Config,
+        args: GreedRunnerArgs,
+    ) -> Result<Arc<dyn FinancialPlatform>, GreedError> {
+        if let Some(replay_path) = &args.replay_path {
+            info!(
+                "📼 Replaying platform responses from {}",
+                replay_path.display()
+            );
+            let platform = Arc::new(ReplayPlatform::from_path(replay_path)?);
+            if args.is_dry_run {
+                return Ok(Arc::new(DryRunPlatform::new(platform)));
+            }
+            return Ok(platform);
+        }
+        let record_path = args.record_path.clone();
+        let is_dry_run = args.is_dry_run;
+        let mut platform = platform::for_type(&config.platform, args.into())?;
+        if is_dry_run {
+            info!("🧪 Dry run, orders will be logged instead of placed");
+            platform = Arc::new(DryRunPlatform::new(platform));
+        }
+        match record_path {
+            Some(record_path) => {
+                info!("📼 Recording platform calls to {}", record_path.display());
+                Ok(Arc::new(RecordingPlatform::new(platform, record_path)?))
+            }
+            None => Ok(platform),
+        }
     }
 
     async fn read_config(args: &GreedRunnerArgs) -> Result<Config, GreedError> {
@@ -73,6 +194,7 @@ impl GreedRunner {
     pub async fn run_loop(&self) {
         let loop_interval = Duration::from_secs(self.run_interval);
         loop {
+            let market_open = self.wait_for_market_open().await;
             // Create strategy runners from providers
             let strategy_runners_result = self.provide_strategy_runners().await;
             if let Err(e) = strategy_runners_result {
@@ -84,16 +206,60 @@ impl GreedRunner {
             // Run each strategy runner
             let strategy_runners = strategy_runners_result.unwrap();
             for runner in strategy_runners {
-                let _ = runner.run(&self.config_assets).await;
+                let _ = runner.run(&self.config_assets, market_open).await;
                 sleep(loop_interval).await;
             }
             // Run the general strategy from the top level config
-            self.config_strategy.run(&self.config_assets).await;
+            self.config_strategy
+                .run(&self.config_assets, market_open)
+                .await;
+            // Journal fills for any orders placed on earlier passes.
+            self.journal.sync_orders(self.platform.as_ref()).await;
             // Sleep for the loop interval then start again.
             sleep(loop_interval).await;
         }
     }
 
+    /// Sleeps until the next session when the market is closed so orders aren't queued overnight
+    /// or across weekends and holidays, returning whether the market is open. Crypto trades around
+    /// the clock, so configs with any crypto keep running and leave it to each tactic to wait for
+    /// the market when it trades stocks.
+    async fn wait_for_market_open(&self) -> bool {
+        let clock = match self.platform.clock().await {
+            Ok(clock) => clock,
+            Err(e) => {
+                warn!("error fetching market clock, running anyway: {}", e);
+                return true;
+            }
+        };
+        if clock.is_open {
+            return true;
+        }
+        if Self::trades_crypto(&self.config_assets) {
+            info!("🌙 market is {clock}, only running tactics which trade around the clock");
+            return false;
+        }
+        if let Some(wait) = clock.time_until_open() {
+            info!("🌙 market is {clock}, sleeping until the next session");
+            sleep(wait).await;
+        }
+        true
+    }
+
+    fn trades_crypto(assets: &[AssetSymbol]) -> bool {
+        assets.iter().any(AssetSymbol::is_crypto)
+    }
+
+    /// Runs every strategy a single time without sleeping in between.
+    pub async fn run_once(&self) -> Result<(), GreedError> {
+        for runner in self.provide_strategy_runners().await? {
+            let _ = runner.run(&self.config_assets, true).await;
+        }
+        self.config_strategy.run(&self.config_assets, true).await;
+        self.journal.sync_orders(self.platform.as_ref()).await;
+        Ok(())
+    }
+
     async fn provide_strategy_runners(&self) -> Result<Vec<Box<dyn StrategyRunner>>, GreedError> {
         let mut runners = Vec::new();
         for provider in &self.strategy_providers {
@@ -103,3 +269,57 @@ impl GreedRunner {
         Ok(runners)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture;
+    use crate::platform::simulated::SimulatedPlatform;
+
+    #[tokio::test]
+    async fn validate_assets() {
+        let assets = [AssetSymbol::new("VTI"), AssetSymbol::new("VTI")];
+        GreedRunner::validate_assets(&simulated_platform("valid"), &assets)
+            .await
+            .expect("known assets should be valid")
+    }
+
+    #[tokio::test]
+    async fn validate_assets_unknown() {
+        let assets = [AssetSymbol::new("VTI"), AssetSymbol::new("VTII")];
+        let error = GreedRunner::validate_assets(&simulated_platform("unknown"), &assets)
+            .await
+            .expect_err("unknown assets should fail");
+        assert!(error.to_string().contains("VTII couldn't be found"))
+    }
+
+    fn simulated_platform(name: &str) -> SimulatedPlatform {
+        let state_path =
+            std::env::temp_dir().join(format!("greed_run_{name}_{}.json", std::process::id()));
+        SimulatedPlatform::from_paths(fixture::path("bars"), state_path, 1000.0)
+            .expect("platform should be created")
+    }
+
+    #[test]
+    fn trades_crypto_only_crypto() {
+        let assets = [AssetSymbol::new("BTC/USD"), AssetSymbol::new("ETH/USD")];
+        assert!(GreedRunner::trades_crypto(&assets))
+    }
+
+    #[test]
+    fn trades_crypto_mixed() {
+        let assets = [AssetSymbol::new("BTC/USD"), AssetSymbol::new("VTI")];
+        assert!(GreedRunner::trades_crypto(&assets))
+    }
+
+    #[test]
+    fn trades_crypto_stocks() {
+        let assets = [AssetSymbol::new("VTI")];
+        assert!(!GreedRunner::trades_crypto(&assets))
+    }
+
+    #[test]
+    fn trades_crypto_no_assets() {
+        assert!(!GreedRunner::trades_crypto(&[]))
+    }
+}
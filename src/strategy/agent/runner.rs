@@ -1,5 +1,6 @@
 use crate::asset::AssetSymbol;
 use crate::config::agent::{AgentConfig, AgentProvider};
+use crate::journal::Journal;
 use crate::platform::FinancialPlatform;
 use crate::strategy::agent::tools::account::AccountTool;
 use crate::strategy::agent::tools::buy::BuyTool;
@@ -26,6 +27,8 @@ pub struct AgentStrategyRunner {
     agent_config: AgentConfig,
     platform: Arc<dyn FinancialPlatform>,
     working_dir: PathBuf,
+    journal: Arc<Journal>,
+    strategy_name: String,
 }
 
 impl AgentStrategyRunner {
@@ -38,9 +41,29 @@ impl AgentStrategyRunner {
             agent_config,
             platform,
             working_dir,
+            journal: Journal::disabled(),
+            strategy_name: String::new(),
         }
     }
 
+    /// Records orders placed by the agent's tools to the journal under the given strategy name.
+    pub fn with_journal(self, journal: Arc<Journal>, strategy_name: String) -> Self {
+        Self {
+            journal,
+            strategy_name,
+            ..self
+        }
+    }
+
+    /// Agents allowed only crypto trade around the clock. Any other agent could trade stocks.
+    fn trades_around_the_clock(&self) -> bool {
+        let allow = &self.agent_config.allow;
+        !allow.is_empty()
+            && allow
+                .iter()
+                .all(|symbol| AssetSymbol::new(symbol).is_crypto())
+    }
+
     fn build_preamble(&self) -> String {
         let allow = &self.agent_config.allow;
         let deny = &self.agent_config.deny;
@@ -68,7 +91,11 @@ impl AgentStrategyRunner {
 
 #[async_trait(?Send)]
 impl StrategyRunner for AgentStrategyRunner {
-    async fn run(&self, _config_assets: &[AssetSymbol]) {
+    async fn run(&self, _config_assets: &[AssetSymbol], market_open: bool) {
+        if !market_open && !self.trades_around_the_clock() {
+            info!("skipping agent until the market opens");
+            return;
+        }
         let AgentProvider::Ollama { url, model } = &self.agent_config.agent_provider;
 
         let client = match ollama::Client::builder()
@@ -104,14 +131,14 @@ impl StrategyRunner for AgentStrategyRunner {
             tool_vec.push(Box::new(QuotesTool::new(platform.clone())));
         }
         if tool_config.buy {
-            tool_vec.push(Box::new(BuyTool::new(
-                platform.clone(),
-                allow.clone(),
-                deny.clone(),
-            )));
+            let tool = BuyTool::new(platform.clone(), allow.clone(), deny.clone())
+                .with_journal(self.journal.clone(), self.strategy_name.clone());
+            tool_vec.push(Box::new(tool));
         }
         if tool_config.sell {
-            tool_vec.push(Box::new(SellTool::new(platform.clone(), allow, deny)));
+            let tool = SellTool::new(platform.clone(), allow, deny)
+                .with_journal(self.journal.clone(), self.strategy_name.clone());
+            tool_vec.push(Box::new(tool));
         }
         if tool_config.web_fetch {
             tool_vec.push(Box::new(WebFetchTool));
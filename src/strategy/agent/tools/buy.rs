@@ -1,4 +1,5 @@
 use crate::asset::AssetSymbol;
+use crate::journal::{Journal, OrderSource};
 use crate::platform::order::amount::Amount;
 use crate::platform::request::OrderRequest;
 use crate::platform::FinancialPlatform;
@@ -25,6 +26,8 @@ pub struct BuyTool {
     platform: Arc<dyn FinancialPlatform>,
     allow: Vec<String>,
     deny: Vec<String>,
+    journal: Arc<Journal>,
+    strategy: String,
 }
 
 impl BuyTool {
@@ -37,6 +40,17 @@ impl BuyTool {
             platform,
             allow,
             deny,
+            journal: Journal::disabled(),
+            strategy: String::new(),
+        }
+    }
+
+    /// Records placed orders to the journal under the given strategy name.
+    pub fn with_journal(self, journal: Arc<Journal>, strategy: String) -> Self {
+        Self {
+            journal,
+            strategy,
+            ..self
         }
     }
 }
@@ -90,7 +104,19 @@ impl Tool for BuyTool {
         };
         let symbol = AssetSymbol::new(&args.symbol);
         let request = OrderRequest::market_order_buy(symbol, amount);
-        let order = self.platform.place_order(request).await?;
+        let source = OrderSource {
+            strategy: self.strategy.clone(),
+            rule: Self::NAME.to_string(),
+            inputs: vec![format!(
+                "{} {} ({})",
+                args.amount, args.symbol, args.amount_type
+            )],
+            ..Default::default()
+        };
+        let result = self.platform.place_order(request.clone()).await;
+        self.journal
+            .record_order(self.platform.now(), source, request, &result);
+        let order = result?;
         info!("Agent tool: buy order placed: {order}");
         Ok(format!("Buy order placed: {order}"))
     }
@@ -99,6 +125,7 @@ impl Tool for BuyTool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::journal::{read_journal, JournalEntry};
     use crate::platform::MockPlatform;
     use std::sync::Arc;
 
@@ -154,6 +181,37 @@ mod tests {
         assert_eq!(orders[0].amount, Amount::Quantity(3.0));
     }
 
+    #[tokio::test]
+    async fn call_records_order_to_journal() {
+        let path = std::env::temp_dir().join(format!(
+            "greed_buy_tool_journal_{}.jsonl",
+            std::process::id()
+        ));
+        let journal = Arc::new(Journal::new(&path).unwrap());
+        let tool = make_tool(Arc::new(MockPlatform::new()), vec![], vec![])
+            .with_journal(journal, "agent".to_string());
+        tool.call(BuyArgs {
+            symbol: "VTI".to_string(),
+            amount: 5.0,
+            amount_type: "quantity".to_string(),
+        })
+        .await
+        .unwrap();
+
+        let entries = read_journal(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let JournalEntry::OrderPlaced { source, .. } = &entries[0] else {
+            panic!("expected an order placed entry")
+        };
+        let expected = OrderSource {
+            strategy: "agent".to_string(),
+            tactic: "".to_string(),
+            rule: "buy".to_string(),
+            inputs: vec!["5 VTI (quantity)".to_string()],
+        };
+        assert_eq!(*source, expected);
+    }
+
     #[tokio::test]
     async fn call_deny_list_blocks_symbol() {
         let mock = Arc::new(MockPlatform::new());
@@ -1,4 +1,5 @@
 use crate::asset::AssetSymbol;
+use crate::journal::{Journal, OrderSource};
 use crate::platform::order::amount::Amount;
 use crate::platform::request::OrderRequest;
 use crate::platform::FinancialPlatform;
@@ -25,6 +26,8 @@ pub struct SellTool {
     platform: Arc<dyn FinancialPlatform>,
     allow: Vec<String>,
     deny: Vec<String>,
+    journal: Arc<Journal>,
+    strategy: String,
 }
 
 impl SellTool {
@@ -37,6 +40,17 @@ impl SellTool {
             platform,
             allow,
             deny,
+            journal: Journal::disabled(),
+            strategy: String::new(),
+        }
+    }
+
+    /// Records placed orders to the journal under the given strategy name.
+    pub fn with_journal(self, journal: Arc<Journal>, strategy: String) -> Self {
+        Self {
+            journal,
+            strategy,
+            ..self
         }
     }
 }
@@ -90,7 +104,19 @@ impl Tool for SellTool {
         };
         let symbol = AssetSymbol::new(&args.symbol);
         let request = OrderRequest::market_order_sell(symbol, amount);
-        let order = self.platform.place_order(request).await?;
+        let source = OrderSource {
+            strategy: self.strategy.clone(),
+            rule: Self::NAME.to_string(),
+            inputs: vec![format!(
+                "{} {} ({})",
+                args.amount, args.symbol, args.amount_type
+            )],
+            ..Default::default()
+        };
+        let result = self.platform.place_order(request.clone()).await;
+        self.journal
+            .record_order(self.platform.now(), source, request, &result);
+        let order = result?;
         info!("Agent tool: sell order placed: {order}");
         Ok(format!("Sell order placed: {order}"))
     }
@@ -1,6 +1,7 @@
 use crate::config::strategy::StrategyConfig;
 use crate::config::Config;
 use crate::error::GreedError;
+use crate::journal::Journal;
 use crate::platform::FinancialPlatform;
 use crate::strategy::provider::agent_provider::AgentStrategyProvider;
 use crate::strategy::provider::config_provider::ConfigStrategyProvider;
@@ -13,6 +14,7 @@ pub struct StrategyProviderFactory<'a> {
     config: &'a Config,
     config_path: PathBuf,
     platform: &'a Arc<dyn FinancialPlatform>,
+    journal: Arc<Journal>,
 }
 
 impl<'a> StrategyProviderFactory<'a> {
@@ -20,11 +22,13 @@ impl<'a> StrategyProviderFactory<'a> {
         config: &'a Config,
         config_path: PathBuf,
         platform: &'a Arc<dyn FinancialPlatform>,
+        journal: Arc<Journal>,
     ) -> StrategyProviderFactory<'a> {
         Self {
             config,
             config_path,
             platform,
+            journal,
         }
     }
 
@@ -51,6 +55,7 @@ impl<'a> StrategyProviderFactory<'a> {
                     Duration::from_secs(self.config.interval),
                     strategy_config,
                     self.platform.clone(),
+                    self.journal.clone(),
                 )
                 .await?;
                 Ok(Box::new(provider))
@@ -61,6 +66,7 @@ impl<'a> StrategyProviderFactory<'a> {
                     Duration::from_secs(self.config.interval),
                     self.platform.clone(),
                     strategy_config,
+                    self.journal.clone(),
                 )
                 .await?;
                 Ok(Box::new(provider))
@@ -2,6 +2,7 @@ use crate::asset::AssetSymbol;
 use crate::config::agent::AgentConfig;
 use crate::config::strategy::StrategyConfig;
 use crate::error::GreedError;
+use crate::journal::Journal;
 use crate::platform::FinancialPlatform;
 use crate::strategy::agent::runner::AgentStrategyRunner;
 use crate::strategy::path::path_for_config;
@@ -17,6 +18,7 @@ pub struct AgentStrategyProvider {
     strategy_config: StrategyConfig,
     agent_config: AgentConfig,
     platform: Arc<dyn FinancialPlatform>,
+    journal: Arc<Journal>,
     #[allow(dead_code)]
     loop_interval: Duration,
     working_dir: PathBuf,
@@ -28,6 +30,7 @@ impl AgentStrategyProvider {
         loop_interval: Duration,
         strategy_config: StrategyConfig,
         platform: Arc<dyn FinancialPlatform>,
+        journal: Arc<Journal>,
     ) -> Result<Self, GreedError> {
         let agent_path = path_for_config(&config_path, &strategy_config)?;
         let working_dir = agent_path.parent().unwrap_or(&agent_path).to_path_buf();
@@ -36,6 +39,7 @@ impl AgentStrategyProvider {
             strategy_config,
             agent_config,
             platform,
+            journal,
             loop_interval,
             working_dir,
         })
@@ -51,7 +55,8 @@ impl StrategyRunnerProvider for AgentStrategyProvider {
             self.agent_config.clone(),
             self.platform.clone(),
             self.working_dir.clone(),
-        );
+        )
+        .with_journal(self.journal.clone(), name);
         Ok(Box::new(runner))
     }
 
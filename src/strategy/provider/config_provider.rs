@@ -2,6 +2,7 @@ use crate::asset::AssetSymbol;
 use crate::config::strategy::StrategyConfig;
 use crate::config::tactic::TacticConfig;
 use crate::error::GreedError;
+use crate::journal::Journal;
 use crate::platform::FinancialPlatform;
 use crate::strategy::provider::StrategyRunnerProvider;
 use crate::strategy::reader::read_tactics_from_config;
@@ -13,6 +14,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 pub struct ConfigStrategyProvider {
+    journal: Arc<Journal>,
     loop_interval: Duration,
     platform: Arc<dyn FinancialPlatform>,
     strategy_config: StrategyConfig,
@@ -25,9 +27,11 @@ impl ConfigStrategyProvider {
         loop_interval: Duration,
         platform: Arc<dyn FinancialPlatform>,
         strategy_config: StrategyConfig,
+        journal: Arc<Journal>,
     ) -> Result<Self, GreedError> {
         let tactic_configs = read_tactics_from_config(&config_path, &strategy_config).await?;
         Ok(Self {
+            journal,
             loop_interval,
             platform,
             strategy_config,
@@ -48,6 +52,7 @@ impl StrategyRunnerProvider for ConfigStrategyProvider {
                     self.platform.clone(),
                     self.strategy_config.properties(),
                 )
+                .with_journal(self.journal.clone())
             })
             .collect();
         let strategy_runner = TacticStrategyRunner::new(
@@ -119,6 +124,7 @@ mod tests {
             Duration::from_secs(1),
             NoOpPlatform::arc(),
             strategy_config,
+            Journal::disabled(),
         )
         .await
         .expect("should create provider")
@@ -1,9 +1,10 @@
 use crate::config::simple::SimpleConfig;
-use crate::config::strategy::StrategyConfig;
+use crate::config::strategy::{StrategyConfig, StrategyProperties};
 use crate::config::tactic::TacticConfig;
 use crate::config::Config;
 use crate::error::GreedError;
 use crate::strategy::path::path_for_config;
+use log::warn;
 use std::ffi::OsStr;
 use std::path::Path;
 
@@ -16,7 +17,32 @@ pub async fn read_tactics_from_config(
     Ok(config.tactics)
 }
 
-async fn read_config_from_path(path: &Path) -> Result<Config, GreedError> {
+/// Collects the config's own tactics along with the tactics of each local file strategy. Agent
+/// strategies can't be evaluated outside of the greed loop so they are skipped.
+pub async fn read_all_tactics(
+    config_path: &Path,
+    config: &Config,
+) -> Result<Vec<(TacticConfig, StrategyProperties)>, GreedError> {
+    let mut configs = config
+        .tactics
+        .iter()
+        .map(|t| (t.clone(), Default::default()))
+        .collect::<Vec<_>>();
+    for strategy in &config.strategies {
+        match strategy {
+            StrategyConfig::Agent { .. } => {
+                warn!("skipping agent strategy {}", strategy.properties().name)
+            }
+            StrategyConfig::LocalFile { .. } => {
+                let tactics = read_tactics_from_config(config_path, strategy).await?;
+                configs.extend(tactics.into_iter().map(|t| (t, strategy.properties())));
+            }
+        }
+    }
+    Ok(configs)
+}
+
+pub async fn read_config_from_path(path: &Path) -> Result<Config, GreedError> {
     let ext = path.extension();
     if Some(OsStr::new("csv")) == ext {
         let simple_config = SimpleConfig::from_path(&path).await?;
@@ -30,6 +56,7 @@ async fn read_config_from_path(path: &Path) -> Result<Config, GreedError> {
 mod tests {
     use super::*;
     use crate::fixture;
+    use itertools::Itertools;
 
     #[tokio::test]
     async fn read_tactics_from_config_csv_config() {
@@ -59,6 +86,22 @@ mod tests {
         assert_eq!(tactics, expected_config.tactics);
     }
 
+    #[tokio::test]
+    async fn read_all_tactics_strategic_config() {
+        let config_path = fixture::path("config_strategic.toml");
+        let config = fixture::config("config_strategic.toml").await;
+        let tactics = read_all_tactics(&config_path, &config)
+            .await
+            .expect("should have read all tactics");
+        let strategy_names = tactics
+            .iter()
+            .map(|(_, properties)| properties.name.as_str())
+            .unique()
+            .collect::<Vec<_>>();
+        assert_eq!(tactics[0].0.name, "Spy");
+        assert_eq!(strategy_names, vec!["", "Multi Tactic", "CSV"]);
+    }
+
     #[tokio::test]
     async fn read_tactics_from_config_invalid_path() {
         let config_path = fixture::path("non_existent_config.toml");
@@ -1,6 +1,7 @@
 use crate::asset::AssetSymbol;
 use crate::config::strategy::StrategyProperties;
 use crate::config::Config;
+use crate::journal::Journal;
 use crate::platform::FinancialPlatform;
 use crate::tactic::TacticRunner;
 use async_trait::async_trait;
@@ -13,7 +14,9 @@ use tokio::time::sleep;
 
 #[async_trait(?Send)]
 pub trait StrategyRunner {
-    async fn run(&self, config_assets: &[AssetSymbol]);
+    /// Runs the strategy once. While the stock market is closed, only what trades around the
+    /// clock should run.
+    async fn run(&self, config_assets: &[AssetSymbol], market_open: bool);
     #[cfg(test)]
     fn as_any(&self) -> &dyn Any;
 }
@@ -37,7 +40,11 @@ impl TacticStrategyRunner {
         }
     }
 
-    pub fn from_config(config: &Config, platform: &Arc<dyn FinancialPlatform>) -> Self {
+    pub fn from_config(
+        config: &Config,
+        platform: &Arc<dyn FinancialPlatform>,
+        journal: &Arc<Journal>,
+    ) -> Self {
         let loop_interval = Duration::from_secs(config.interval);
 
         let tactic_runners = config
@@ -46,6 +53,7 @@ impl TacticStrategyRunner {
             .into_iter()
             .map(|tactic_config| {
                 TacticRunner::new(tactic_config.clone(), platform.clone(), Default::default())
+                    .with_journal(journal.clone())
             })
             .collect();
         Self::new(loop_interval, Default::default(), tactic_runners)
@@ -59,12 +67,19 @@ impl TacticStrategyRunner {
 
 #[async_trait(?Send)]
 impl StrategyRunner for TacticStrategyRunner {
-    async fn run(&self, config_assets: &[AssetSymbol]) {
+    async fn run(&self, config_assets: &[AssetSymbol], market_open: bool) {
         let name = self.strategy_properties.name.clone();
         if !name.is_empty() {
             info!("🚀 running strategy: {}", self.strategy_properties.name);
         }
         for tactic_runner in &self.tactic_runners {
+            if !market_open && !tactic_runner.trades_around_the_clock() {
+                info!(
+                    "skipping tactic {} until the market opens",
+                    tactic_runner.name()
+                );
+                continue;
+            }
             let _ = tactic_runner
                 .run(config_assets)
                 .await
@@ -82,14 +97,17 @@ impl StrategyRunner for TacticStrategyRunner {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::tactic::TacticConfig;
     use crate::fixture;
     use crate::platform::noop::NoOpPlatform;
+    use crate::platform::position::Position;
+    use crate::platform::MockPlatform;
 
     #[tokio::test]
     async fn from_config_empty_tactics() {
         let config = fixture::config("config_minimal.toml").await;
         let platform = NoOpPlatform::arc();
-        let runner = TacticStrategyRunner::from_config(&config, &platform);
+        let runner = TacticStrategyRunner::from_config(&config, &platform, &Journal::disabled());
         assert_eq!(runner.loop_interval, Duration::from_secs(60));
         assert_eq!(runner.tactic_runner_count(), 0);
     }
@@ -98,8 +116,43 @@ mod tests {
     async fn from_config_with_tactics() {
         let config = fixture::config("config_single_tactic.toml").await;
         let platform = NoOpPlatform::arc();
-        let runner = TacticStrategyRunner::from_config(&config, &platform);
+        let runner = TacticStrategyRunner::from_config(&config, &platform, &Journal::disabled());
         assert_eq!(runner.loop_interval, Duration::from_secs(300));
         assert_eq!(runner.tactic_runner_count(), 1);
     }
+
+    #[tokio::test]
+    async fn run_skips_stock_tactics_while_market_closed() {
+        let btc = AssetSymbol::new("BTC/USD");
+        let vti = AssetSymbol::new("VTI");
+        let mock = Arc::new(MockPlatform::new().with_positions(vec![
+            Position::fixture(btc.clone()),
+            Position::fixture(vti.clone()),
+        ]));
+        let platform: Arc<dyn FinancialPlatform> = mock.clone();
+        let tactic_runners = vec![
+            sell_all_runner(r#"crypto = "BTC/USD""#, &platform),
+            sell_all_runner(r#"stock = "VTI""#, &platform),
+        ];
+        let runner = TacticStrategyRunner::new(Duration::ZERO, Default::default(), tactic_runners);
+        runner.run(&[btc.clone(), vti], false).await;
+        let sold = mock
+            .placed_orders()
+            .into_iter()
+            .map(|o| o.symbol)
+            .collect::<Vec<_>>();
+        assert_eq!(sold, vec![btc]);
+    }
+
+    fn sell_all_runner(for_config: &str, platform: &Arc<dyn FinancialPlatform>) -> TacticRunner {
+        let toml = format!(
+            r#"
+            name = "sell"
+            buy = {{ for = {{ {for_config} }}, when = {{ never = true }}, do = {{ buy_percent = 10 }} }}
+            sell = {{ for = {{ {for_config} }}, when = {{ always = true }}, do = {{ sell_all = true }} }}
+            "#
+        );
+        let tactic: TacticConfig = toml::from_str(&toml).unwrap();
+        TacticRunner::new(tactic, platform.clone(), Default::default())
+    }
 }
@@ -1,38 +1,60 @@
-use crate::analysis::result::BarsResult;
 use crate::analysis::AssetAnalyzer;
 use crate::asset::AssetSymbol;
 use crate::config::quote_fetcher_config::QuoteFetcherConfig;
 use crate::config::strategy::StrategyProperties;
 use crate::config::tactic::TacticConfig;
 use crate::error::GreedError;
+use crate::journal::{Journal, OrderSource};
 use crate::platform::account::Account;
+use crate::platform::asset_info::AssetInfo;
+use crate::platform::calendar::MarketDay;
+use crate::platform::order::side::OrderSide;
 use crate::platform::order::Order;
 use crate::platform::position::Position;
 use crate::platform::quote::Quote;
+use crate::platform::request::OrderRequest;
 use crate::platform::FinancialPlatform;
-use crate::tactic::r#do::DoResult;
+use crate::tactic::exit::{ExitOrder, TrailingStopExit};
+use crate::tactic::explain::TacticExplanation;
+use crate::tactic::order_tag::OrderTag;
 use crate::tactic::rule::RuleType::{Buy, Sell};
-use crate::tactic::rule::{RuleType, TacticRuleset};
+use crate::tactic::rule::{RuleTrace, RuleType, TacticRuleset};
 use crate::tactic::state::TacticState;
+use crate::tactic::when::WhenTrace;
+use crate::trading_days::eastern::eastern_date;
+use chrono::{DateTime, Duration, Utc};
 use itertools::Itertools;
 use log::{info, warn};
 use std::collections::HashMap;
 use std::rc::Rc;
 use std::sync::Arc;
+use tokio::time::sleep;
 
 mod action;
 mod r#do;
+mod exit;
+pub mod explain;
 mod r#for;
 mod null;
+mod order_tag;
 mod rule;
 mod skip;
 mod state;
 mod target;
 mod when;
 
+/// How far back to look for the orders which opened the current positions or traded recently.
+const ORDER_HISTORY_DAYS: i64 = 365;
+
+/// How many times, and how often, to check that canceled trailing stops have left the open orders.
+const CANCEL_CHECKS: u32 = 10;
+const CANCEL_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
+
 pub struct TacticRunner {
     asset_analyzer: AssetAnalyzer,
     config: TacticConfig,
+    journal: Arc<Journal>,
+    order_tag: OrderTag,
     platform: Arc<dyn FinancialPlatform>,
     strategy_properties: StrategyProperties,
 }
@@ -44,31 +66,95 @@ impl TacticRunner {
         strategy_properties: StrategyProperties,
     ) -> Self {
         Self {
-            asset_analyzer: AssetAnalyzer::new(platform.clone()),
+            asset_analyzer: AssetAnalyzer::new(platform.clone())
+                .with_one_year(config.should_fetch_one_year()),
+            order_tag: OrderTag::new(&strategy_properties.name, &config.name),
             config,
+            journal: Journal::disabled(),
             platform,
             strategy_properties,
         }
     }
+
+    /// Records every order this runner places, along with the rule which produced it.
+    pub fn with_journal(self, journal: Arc<Journal>) -> Self {
+        Self { journal, ..self }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    /// Tactics which only trade crypto can run while the stock market is closed.
+    pub fn trades_around_the_clock(&self) -> bool {
+        let assets = self.config.assets();
+        !assets.is_empty() && assets.iter().all(AssetSymbol::is_crypto)
+    }
+
     pub async fn run(&self, config_assets: &[AssetSymbol]) -> Result<(), GreedError> {
         info!("🧠 running tactic: {}", self.config.name);
+        let state = self.build_state(config_assets).await?;
+        self.evaluate_rules(state).await?;
+        if let Some(trail_percent) = self.config.exit.trailing_stop_percent {
+            self.sync_trailing_stops(trail_percent).await?;
+        }
+        info!("----------");
+        Ok(())
+    }
+
+    /// Evaluates the tactic's rules once without placing any orders.
+    pub async fn explain(
+        &self,
+        config_assets: &[AssetSymbol],
+    ) -> Result<TacticExplanation, GreedError> {
+        let state = self.build_state(config_assets).await?;
+        let rules = TacticRuleset::from_config(self.config.clone());
+        Ok(TacticExplanation {
+            name: self.config.name.clone(),
+            buy: rules.buy.evaluate(&state, true),
+            sell: rules.sell.evaluate(&state, true),
+        })
+    }
+
+    async fn build_state(&self, config_assets: &[AssetSymbol]) -> Result<TacticState, GreedError> {
         let account = self.fetch_account().await?;
         let symbols = self.config.assets();
-        let positions = self.fetch_positions().await?;
-        let open_orders = self.fetch_open_orders().await?;
+        let mut positions = self.fetch_positions().await?;
+        let mut open_orders = self.fetch_open_orders().await?;
+        if let Some(exit) = self.trailing_stop_exit() {
+            exit.release(&mut positions, &mut open_orders);
+        }
+        let now = self.platform.now();
+        let history_start = now - Duration::days(ORDER_HISTORY_DAYS);
+        let order_history = if self.config.should_fetch_order_history() {
+            self.fetch_order_history(now).await?
+        } else {
+            Vec::new()
+        };
+        let market_day = if self.config.should_fetch_market_day() {
+            self.fetch_market_day(now).await?
+        } else {
+            None
+        };
 
-        let (bar_analysis, quotes): (
-            Rc<HashMap<AssetSymbol, BarsResult>>,
-            HashMap<AssetSymbol, Quote>,
-        ) = if self.config.should_fetch_quotes() {
-            let bar_analysis = self.asset_analyzer.analyze_bars(&symbols).await?;
-            let quotes: HashMap<AssetSymbol, Quote> = self.fetch_quotes(&symbols).await?;
-            (bar_analysis, quotes)
+        let asset_info = if self.config.should_fetch_asset_info() {
+            self.fetch_asset_info(&symbols).await
         } else {
-            (Rc::new(HashMap::new()), HashMap::new())
+            HashMap::new()
         };
 
-        let state = TacticState::new(
+        let bar_analysis = if self.config.should_fetch_bars() {
+            self.asset_analyzer.analyze_bars(&symbols).await?
+        } else {
+            Rc::new(HashMap::new())
+        };
+        let quotes = if self.config.should_fetch_quotes() {
+            self.fetch_quotes(&symbols).await?
+        } else {
+            HashMap::new()
+        };
+
+        Ok(TacticState::new(
             account,
             bar_analysis,
             open_orders,
@@ -76,10 +162,10 @@ impl TacticRunner {
             quotes,
             self.strategy_properties.clone(),
             config_assets.to_vec(),
-        );
-        self.evaluate_rules(state).await?;
-        info!("----------");
-        Ok(())
+        )
+        .with_order_history(&order_history, &self.order_tag, history_start)
+        .with_asset_info(asset_info)
+        .with_clock(now, market_day))
     }
 
     async fn fetch_account(&self) -> Result<Account, GreedError> {
@@ -104,6 +190,23 @@ impl TacticRunner {
         Ok(by_symbol)
     }
 
+    /// Assets whose info can't be fetched are left out, so they're treated as unavailable to
+    /// short and bought with notional orders rather than failing the whole tactic.
+    async fn fetch_asset_info(&self, symbols: &[AssetSymbol]) -> HashMap<AssetSymbol, AssetInfo> {
+        info!("- fetching asset info");
+        let mut asset_info = HashMap::new();
+        for symbol in symbols {
+            match self.platform.asset(symbol).await {
+                Ok(info) => {
+                    info!("-- {}", info);
+                    asset_info.insert(symbol.clone(), info);
+                }
+                Err(e) => warn!("failed to fetch asset info for {symbol}: {e}"),
+            }
+        }
+        asset_info
+    }
+
     async fn fetch_positions(&self) -> Result<HashMap<AssetSymbol, Position>, GreedError> {
         let positions = self.platform.positions().await?;
         let by_symbol = positions
@@ -113,6 +216,20 @@ impl TacticRunner {
         Ok(by_symbol)
     }
 
+    async fn fetch_order_history(&self, now: DateTime<Utc>) -> Result<Vec<Order>, GreedError> {
+        info!("- fetching order history");
+        self.platform
+            .order_history(now - Duration::days(ORDER_HISTORY_DAYS)..now)
+            .await
+    }
+
+    async fn fetch_market_day(&self, now: DateTime<Utc>) -> Result<Option<MarketDay>, GreedError> {
+        let today = eastern_date(now);
+        let tomorrow = today.succ_opt().unwrap_or(today);
+        let calendar = self.platform.calendar(today..tomorrow).await?;
+        Ok(calendar.into_iter().find(|day| day.date == today))
+    }
+
     async fn fetch_open_orders(&self) -> Result<HashMap<AssetSymbol, Vec<Order>>, GreedError> {
         info!("- fetching open orders");
         let orders = self.platform.open_orders().await?;
@@ -130,22 +247,25 @@ impl TacticRunner {
 
     async fn evaluate_rules(&self, state: TacticState) -> Result<(), GreedError> {
         let rules = TacticRuleset::from_config(self.config.clone());
-        let buy_result = rules.buy.evaluate(&state);
-        let sell_result = rules.sell.evaluate(&state);
+        // The when traces are only needed to journal the inputs behind each order.
+        let trace_when = self.journal.is_enabled();
+        let buy_trace = rules.buy.evaluate(&state, trace_when);
+        let sell_trace = rules.sell.evaluate(&state, trace_when);
 
-        info!("buy rule result: {:?}", buy_result);
-        info!("sell rule result: {:?}", sell_result);
+        info!("buy rule result: {:?}", buy_trace.result);
+        info!("sell rule result: {:?}", sell_trace.result);
 
-        self.perform_resulting_actions(Buy, buy_result).await?;
-        self.perform_resulting_actions(Sell, sell_result).await?;
+        self.perform_resulting_actions(Buy, buy_trace).await?;
+        self.perform_resulting_actions(Sell, sell_trace).await?;
         Ok(())
     }
 
     async fn perform_resulting_actions(
         &self,
         rule_type: RuleType,
-        result: DoResult,
+        trace: RuleTrace,
     ) -> Result<(), GreedError> {
+        let result = trace.result;
         if result.skipped {
             info!(
                 "Skipping {rule_type} actions. Reason: {}",
@@ -155,7 +275,17 @@ impl TacticRunner {
         }
         for action in result.actions {
             info!("performing {rule_type} action: {action}");
-            let result = self.platform.place_order(action.into_request()).await;
+            let request = self.tagged(action.into_request());
+            if request.side == OrderSide::Sell {
+                if let Err(e) = self.cancel_trailing_stops(&request.symbol).await {
+                    warn!("skipping sell of {}: {e}", request.symbol);
+                    continue;
+                }
+            }
+            let source = self.order_source(&rule_type, trace.when_trace.as_ref(), &request);
+            let result = self.platform.place_order(request.clone()).await;
+            self.journal
+                .record_order(self.platform.now(), source, request, &result);
 
             if let Err(e) = result {
                 warn!("error placing order: {e}");
@@ -164,7 +294,179 @@ impl TacticRunner {
         Ok(())
     }
 
+    fn trailing_stop_exit(&self) -> Option<TrailingStopExit> {
+        let trail_percent = self.config.exit.trailing_stop_percent?;
+        Some(TrailingStopExit::new(trail_percent, self.order_tag.clone()))
+    }
+
+    /// The rules see the shares held by the tactic's trailing stops as available, so the stops
+    /// are canceled ahead of each sell to free them. The stops are put back after the rules ran.
+    /// Brokers cancel asynchronously and hold the shares until they do, so this waits for the
+    /// stops to leave the open orders before the sell goes out.
+    async fn cancel_trailing_stops(&self, symbol: &AssetSymbol) -> Result<(), GreedError> {
+        let Some(exit) = self.trailing_stop_exit() else {
+            return Ok(());
+        };
+        let orders = self.platform.open_orders().await?;
+        let stops = exit.stops_for(symbol, &orders);
+        if stops.is_empty() {
+            return Ok(());
+        }
+        for id in &stops {
+            info!("canceling trailing stop {id} ahead of sell");
+            self.platform.cancel_order(id).await?;
+        }
+        for _ in 0..CANCEL_CHECKS {
+            let orders = self.platform.open_orders().await?;
+            if !orders.iter().any(|o| stops.contains(&o.id)) {
+                return Ok(());
+            }
+            sleep(CANCEL_CHECK_INTERVAL).await;
+        }
+        Err(GreedError::new(&format!(
+            "trailing stops for {symbol} are still open after canceling them"
+        )))
+    }
+
+    /// Refetches positions, open orders and the order history after the rules ran, so buys which
+    /// filled get their trailing stops right away and sells shrink them.
+    async fn sync_trailing_stops(&self, trail_percent: f64) -> Result<(), GreedError> {
+        info!("- syncing trailing stops at {trail_percent}%");
+        let positions = self.fetch_positions().await?;
+        let open_orders = self.fetch_open_orders().await?;
+        let order_history = self.fetch_order_history(self.platform.now()).await?;
+        let symbols = self.config.buy.assets();
+        let asset_info = self.fetch_asset_info(&symbols).await;
+        let exit = TrailingStopExit::new(trail_percent, self.order_tag.clone());
+        let exit_orders = exit.sync(
+            &symbols,
+            &positions,
+            &open_orders,
+            &order_history,
+            &asset_info,
+        );
+        for exit_order in exit_orders {
+            info!("performing exit: {exit_order}");
+            let result = match exit_order {
+                ExitOrder::Cancel(id) => self.platform.cancel_order(&id).await,
+                ExitOrder::Place(request) => {
+                    let source = OrderSource {
+                        strategy: self.strategy_properties.name.clone(),
+                        tactic: self.config.name.clone(),
+                        rule: "exit".to_string(),
+                        inputs: vec![format!("trailing_stop_percent={trail_percent}")],
+                    };
+                    let request = self.tagged(*request);
+                    let result = self.platform.place_order(request.clone()).await;
+                    self.journal
+                        .record_order(self.platform.now(), source, request, &result);
+                    result.map(|_| ())
+                }
+                ExitOrder::Replace(id, request) => {
+                    self.platform.replace_order(&id, request).await.map(|_| ())
+                }
+            };
+            if let Err(e) = result {
+                warn!("error syncing trailing stop: {e}");
+            }
+        }
+        Ok(())
+    }
+
+    /// Marks the request as this tactic's, so later passes can pick out its orders.
+    fn tagged(&self, request: OrderRequest) -> OrderRequest {
+        OrderRequest {
+            client_order_id: Some(self.order_tag.client_order_id()),
+            ..request
+        }
+    }
+
+    fn order_source(
+        &self,
+        rule_type: &RuleType,
+        when_trace: Option<&WhenTrace>,
+        request: &OrderRequest,
+    ) -> OrderSource {
+        OrderSource {
+            strategy: self.strategy_properties.name.clone(),
+            tactic: self.config.name.clone(),
+            rule: rule_type.to_string(),
+            inputs: when_trace
+                .map(|trace| trace.inputs_for(&request.symbol))
+                .unwrap_or_default(),
+        }
+    }
+
     fn symbols_string(symbols: &[AssetSymbol]) -> String {
         symbols.iter().map(|s| s.symbol.clone()).join(",")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::order::amount::Amount;
+    use crate::platform::order::order_type::OrderType;
+    use crate::platform::MockPlatform;
+
+    #[tokio::test]
+    async fn run_cancels_trailing_stops_before_selling() {
+        let mock = Arc::new(exit_platform(MockPlatform::new()));
+        run_exit_tactic(mock.clone()).await;
+        assert_eq!(mock.canceled_orders(), vec![stop().id]);
+        let placed = mock.placed_orders();
+        assert_eq!(placed[0].side, OrderSide::Sell);
+        assert_eq!(placed[0].order_type, OrderType::Market);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn run_skips_sell_while_trailing_stop_is_open() {
+        let mock = Arc::new(exit_platform(MockPlatform::new().with_stuck_cancels()));
+        run_exit_tactic(mock.clone()).await;
+        assert_eq!(mock.canceled_orders(), vec![stop().id]);
+        assert!(mock
+            .placed_orders()
+            .iter()
+            .all(|o| o.order_type == OrderType::TrailingStop));
+    }
+
+    async fn run_exit_tactic(platform: Arc<MockPlatform>) {
+        let tactic: TacticConfig = toml::from_str(
+            r#"
+            name = "exit"
+            buy = { for = { stock = "VTI" }, when = { never = true }, do = { buy_percent = 10 } }
+            sell = { for = { stock = "VTI" }, when = { always = true }, do = { sell_all = true } }
+            exit = { trailing_stop_percent = 5 }
+            "#,
+        )
+        .unwrap();
+        TacticRunner::new(tactic, platform, Default::default())
+            .run(&[vti()])
+            .await
+            .unwrap();
+    }
+
+    /// Holds 50 shares, all of them held by the tactic's trailing stop.
+    fn exit_platform(mock: MockPlatform) -> MockPlatform {
+        let position = Position {
+            quantity_available: 0.0,
+            ..Position::fixture(vti())
+        };
+        mock.with_positions(vec![position])
+            .with_open_orders(vec![stop()])
+            .with_quotes(vec![Quote::fixture(vti())])
+    }
+
+    fn stop() -> Order {
+        OrderTag::new("", "exit").order(Order {
+            amount: Amount::Quantity(50.0),
+            order_type: OrderType::TrailingStop,
+            side: OrderSide::Sell,
+            ..Order::fixture(vti())
+        })
+    }
+
+    fn vti() -> AssetSymbol {
+        AssetSymbol::new("VTI")
+    }
+}
@@ -2,12 +2,21 @@ use std::fmt::{Display, Formatter};
 
 use crate::asset::AssetSymbol;
 use crate::platform::order::amount::Amount;
+use crate::platform::order::side::OrderSide;
 use crate::platform::request::OrderRequest;
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Action {
-    Buy { symbol: AssetSymbol, amount: Amount },
-    Sell { symbol: AssetSymbol, amount: Amount },
+    Buy {
+        symbol: AssetSymbol,
+        amount: Amount,
+    },
+    Sell {
+        symbol: AssetSymbol,
+        amount: Amount,
+    },
+    /// An order other than a plain market order, placed as is.
+    Order(Box<OrderRequest>),
 }
 
 impl Action {
@@ -16,7 +25,11 @@ impl Action {
         Self::Buy { symbol, amount }
     }
 
-    #[allow(dead_code)]
+    pub fn buy_quantity(symbol: AssetSymbol, quantity: f64) -> Self {
+        let amount = Amount::Quantity(quantity);
+        Self::Buy { symbol, amount }
+    }
+
     pub fn sell_notional(symbol: AssetSymbol, notional: f64) -> Self {
         let amount = Amount::Notional(notional);
         Self::Sell { symbol, amount }
@@ -31,6 +44,7 @@ impl Action {
         match self {
             Action::Buy { symbol, amount } => OrderRequest::market_order_buy(symbol, amount),
             Action::Sell { symbol, amount } => OrderRequest::market_order_sell(symbol, amount),
+            Action::Order(request) => *request,
         }
     }
 
@@ -44,6 +58,7 @@ impl Action {
                 symbol: _symbol,
                 amount,
             } => amount.is_empty(),
+            Action::Order(request) => request.amount.is_empty(),
         }
     }
 }
@@ -57,6 +72,13 @@ impl Display for Action {
             Action::Sell { symbol, amount } => {
                 write!(f, "💰sell {} - amount: {}", symbol, amount)
             }
+            Action::Order(request) => {
+                let emoji = match request.side {
+                    OrderSide::Buy => "💸",
+                    OrderSide::Sell => "💰",
+                };
+                write!(f, "{}{}", emoji, request)
+            }
         }
     }
 }
@@ -64,6 +86,8 @@ impl Display for Action {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::platform::order::order_type::OrderType;
+    use num_decimal::Num;
 
     #[test]
     fn into_request_buy() {
@@ -106,4 +130,17 @@ mod tests {
         let action = Action::sell_notional(AssetSymbol::new("VTI"), 1.0);
         assert!(!action.is_empty())
     }
+
+    #[test]
+    fn into_request_order() {
+        let request = OrderRequest {
+            order_type: OrderType::Limit,
+            limit_price: Some(Num::from(10)),
+            ..OrderRequest::market_order_buy(AssetSymbol::new("VTI"), Amount::Quantity(2.0))
+        };
+        let action = Action::Order(Box::new(request.clone()));
+        assert_eq!(action.to_string(), "💸limit buy 2.00 units of VTI");
+        assert!(!action.is_empty());
+        assert_eq!(request, action.into_request());
+    }
 }
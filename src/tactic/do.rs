@@ -1,11 +1,21 @@
 mod do_buy;
+mod do_cover_all;
 pub mod do_factory;
+mod do_invest_notional;
+mod do_order;
+mod do_rebalance;
+mod do_sell_notional;
+mod do_sell_percent;
 pub mod do_sellall;
+mod do_short;
+mod do_trim_to;
 
+use crate::asset::AssetSymbol;
 use crate::tactic::action::Action;
 use crate::tactic::skip::SkipReason;
 use crate::tactic::state::TacticState;
 use crate::tactic::when::WhenResult;
+use log::info;
 
 pub trait DoRule {
     fn evaluate(&self, state: &TacticState, when_result: WhenResult) -> DoResult;
@@ -26,6 +36,65 @@ impl DoResult {
             ..Default::default()
         }
     }
+
+    /// Creates a result from the actions, skipping for the provided reason when there are none.
+    pub fn from_actions(actions: Vec<Action>, skip_reason: SkipReason) -> Self {
+        if actions.is_empty() {
+            Self::skip(skip_reason)
+        } else {
+            Self {
+                actions,
+                ..Default::default()
+            }
+        }
+    }
+}
+
+/// The buy spending up to `amount` of `symbol`, along with what it costs. Assets the broker won't
+/// trade in fractions are bought in whole shares priced off the ask, since notional orders for them
+/// are rejected, and buys under the broker's minimum order size are skipped.
+fn buy_up_to(
+    state: &TacticState,
+    symbol: &AssetSymbol,
+    amount: f64,
+) -> Result<(Action, f64), SkipReason> {
+    let info = state.asset_info.get(symbol);
+    let whole_shares_only = info.is_some_and(|info| !info.fractionable);
+    let min_order_size = info.and_then(|info| info.min_order_size);
+    if !whole_shares_only && min_order_size.is_none() {
+        return Ok((Action::buy_notional(symbol.clone(), amount), amount));
+    }
+    let skip = |reason: SkipReason| {
+        info!("skipping buy of {symbol} for ${amount:.2}: {reason}");
+        reason
+    };
+    let price = ask_price(state, symbol).ok_or_else(|| skip(SkipReason::NoQuote))?;
+    let quantity = amount / price;
+    if !whole_shares_only {
+        return match min_order_size {
+            Some(min) if quantity < min => Err(skip(SkipReason::BelowMinOrderSize)),
+            _ => Ok((Action::buy_notional(symbol.clone(), amount), amount)),
+        };
+    }
+    let quantity = quantity.floor();
+    if quantity < 1.0 {
+        return Err(skip(SkipReason::LessThanOneShare));
+    }
+    if min_order_size.is_some_and(|min| quantity < min) {
+        return Err(skip(SkipReason::BelowMinOrderSize));
+    }
+    Ok((
+        Action::buy_quantity(symbol.clone(), quantity),
+        quantity * price,
+    ))
+}
+
+/// The ask, falling back to the bid when it's missing.
+fn ask_price(state: &TacticState, symbol: &AssetSymbol) -> Option<f64> {
+    let quote = state.quotes.get(symbol)?;
+    [quote.ask_price, quote.bid_price]
+        .into_iter()
+        .find(|p| *p > 0.0)
 }
 
 #[cfg(test)]
@@ -42,4 +111,21 @@ mod tests {
         };
         assert_eq!(expected, result);
     }
+
+    #[test]
+    fn from_actions_empty() {
+        let result = DoResult::from_actions(vec![], SkipReason::NoPosition);
+        assert_eq!(DoResult::skip(SkipReason::NoPosition), result);
+    }
+
+    #[test]
+    fn from_actions() {
+        let actions = vec![Action::sell_quantity(Default::default(), 1.0)];
+        let result = DoResult::from_actions(actions, SkipReason::NoPosition);
+        let expected = DoResult {
+            actions: vec![Action::sell_quantity(Default::default(), 1.0)],
+            ..Default::default()
+        };
+        assert_eq!(expected, result);
+    }
 }
@@ -1,6 +1,6 @@
 use crate::float::PercentOps;
 use crate::tactic::action::Action;
-use crate::tactic::r#do::{DoResult, DoRule};
+use crate::tactic::r#do::{buy_up_to, DoResult, DoRule};
 use crate::tactic::skip::SkipReason;
 use crate::tactic::state::TacticState;
 use crate::tactic::target::TargetAsset;
@@ -15,20 +15,34 @@ impl DoBuyRule {
         Box::new(Self { buy_percent })
     }
 
-    fn actions(&self, state: &TacticState, assets: &[TargetAsset]) -> Vec<Action> {
+    /// The buys for each asset, along with why the last asset skipped wasn't bought.
+    fn actions(
+        &self,
+        state: &TacticState,
+        assets: &[TargetAsset],
+    ) -> (Vec<Action>, Option<SkipReason>) {
         let mut remaining_cash = state.account.cash;
-        assets
+        let mut skip_reason = None;
+        let actions = assets
             .iter()
             .filter_map(|asset| {
                 let amount = self.calculate_buy_amount(state, asset, remaining_cash);
-                remaining_cash -= amount;
-                if amount > 0.0 {
-                    Some(Action::buy_notional(asset.symbol.clone(), amount))
-                } else {
-                    None
+                if amount <= 0.0 {
+                    return None;
+                }
+                match buy_up_to(state, &asset.symbol, amount) {
+                    Ok((action, cost)) => {
+                        remaining_cash -= cost;
+                        Some(action)
+                    }
+                    Err(reason) => {
+                        skip_reason = Some(reason);
+                        None
+                    }
                 }
             })
-            .collect()
+            .collect();
+        (actions, skip_reason)
     }
 
     fn calculate_buy_amount(
@@ -67,13 +81,15 @@ impl DoBuyRule {
 
 impl DoRule for DoBuyRule {
     fn evaluate(&self, state: &TacticState, when_result: WhenResult) -> DoResult {
-        let actions = self.actions(state, &when_result.target_assets);
-        let skipped = actions.is_empty();
-        DoResult {
-            actions,
-            skipped,
-            skip_reason: SkipReason::NoTargetAssets,
-        }
+        let (actions, skip_reason) = self.actions(state, &when_result.target_assets);
+        let skip_reason = if when_result.target_assets.is_empty() {
+            SkipReason::NoTargetAssets
+        } else if state.account.cash <= 0.0 {
+            SkipReason::InsufficientCash
+        } else {
+            skip_reason.unwrap_or(SkipReason::TargetReached)
+        };
+        DoResult::from_actions(actions, skip_reason)
     }
 }
 
@@ -82,6 +98,8 @@ mod tests {
     use crate::asset::AssetSymbol;
     use crate::config::strategy::StrategyProperties;
     use crate::platform::account::Account;
+    use crate::platform::asset_info::AssetInfo;
+    use std::collections::HashMap;
 
     use super::*;
 
@@ -102,8 +120,7 @@ mod tests {
                 Action::buy_notional(AssetSymbol::new("VTI"), 50.0),
                 Action::buy_notional(AssetSymbol::new("SPY"), 50.0),
             ],
-            skipped: false,
-            skip_reason: SkipReason::NoTargetAssets,
+            ..Default::default()
         };
         assert_eq!(expected, result)
     }
@@ -133,8 +150,7 @@ mod tests {
                 Action::buy_notional(AssetSymbol::new("VTI"), 125.0),
                 Action::buy_notional(AssetSymbol::new("SPY"), 125.0),
             ],
-            skipped: false,
-            skip_reason: SkipReason::NoTargetAssets,
+            ..Default::default()
         };
         assert_eq!(expected, result)
     }
@@ -151,11 +167,7 @@ mod tests {
             ..Default::default()
         };
         let result = rule.evaluate(&state, when_result);
-        let expected = DoResult {
-            actions: vec![],
-            skipped: true,
-            skip_reason: SkipReason::NoTargetAssets,
-        };
+        let expected = DoResult::skip(SkipReason::TargetReached);
         assert_eq!(expected, result)
     }
 
@@ -177,11 +189,7 @@ mod tests {
             ..Default::default()
         };
         let result = rule.evaluate(&state, when_result);
-        let expected = DoResult {
-            actions: vec![],
-            skipped: true,
-            skip_reason: SkipReason::NoTargetAssets,
-        };
+        let expected = DoResult::skip(SkipReason::InsufficientCash);
         assert_eq!(expected, result)
     }
 
@@ -205,9 +213,103 @@ mod tests {
         let result = rule.evaluate(&state, when_result);
         let expected = DoResult {
             actions: vec![Action::buy_notional(AssetSymbol::new("VTI"), 5.0)],
-            skipped: false,
-            skip_reason: SkipReason::NoTargetAssets,
+            ..Default::default()
+        };
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn evaluate_whole_shares() {
+        let rule = DoBuyRule::boxed(100.0);
+        let result = rule.evaluate(&whole_share_state(900.0), vti_when_result());
+        // $900 buys 4 whole shares at the 200.00 ask.
+        let expected = DoResult {
+            actions: vec![Action::buy_quantity(AssetSymbol::new("VTI"), 4.0)],
+            ..Default::default()
         };
         assert_eq!(expected, result)
     }
+
+    #[test]
+    fn evaluate_whole_shares_less_than_a_share() {
+        let rule = DoBuyRule::boxed(100.0);
+        let result = rule.evaluate(&whole_share_state(150.0), vti_when_result());
+        assert_eq!(DoResult::skip(SkipReason::LessThanOneShare), result)
+    }
+
+    #[test]
+    fn evaluate_whole_shares_no_quote() {
+        let rule = DoBuyRule::boxed(100.0);
+        let state = TacticState {
+            quotes: HashMap::new(),
+            ..whole_share_state(1000.0)
+        };
+        let result = rule.evaluate(&state, vti_when_result());
+        assert_eq!(DoResult::skip(SkipReason::NoQuote), result)
+    }
+
+    #[test]
+    fn evaluate_whole_shares_below_min_order_size() {
+        let rule = DoBuyRule::boxed(100.0);
+        let mut state = whole_share_state(900.0);
+        let vti = AssetSymbol::new("VTI");
+        state.asset_info.get_mut(&vti).unwrap().min_order_size = Some(5.0);
+        let result = rule.evaluate(&state, vti_when_result());
+        assert_eq!(DoResult::skip(SkipReason::BelowMinOrderSize), result)
+    }
+
+    #[test]
+    fn evaluate_fractionable_below_min_order_size() {
+        let rule = DoBuyRule::boxed(100.0);
+        let vti = AssetSymbol::new("VTI");
+        let info = AssetInfo {
+            min_order_size: Some(0.5),
+            ..AssetInfo::fixture(vti.clone())
+        };
+        let state = TacticState {
+            asset_info: HashMap::from([(vti.clone(), info)]),
+            ..whole_share_state(50.0)
+        };
+        // $50 is a quarter share at the 200.00 ask.
+        let result = rule.evaluate(&state, vti_when_result());
+        assert_eq!(DoResult::skip(SkipReason::BelowMinOrderSize), result)
+    }
+
+    #[test]
+    fn evaluate_fractionable() {
+        let rule = DoBuyRule::boxed(100.0);
+        let vti = AssetSymbol::new("VTI");
+        let state = TacticState {
+            asset_info: HashMap::from([(vti.clone(), AssetInfo::fixture(vti.clone()))]),
+            ..whole_share_state(1000.0)
+        };
+        let result = rule.evaluate(&state, vti_when_result());
+        assert_eq!(result.actions, vec![Action::buy_notional(vti, 1000.0)])
+    }
+
+    fn whole_share_state(cash: f64) -> TacticState {
+        let vti = AssetSymbol::new("VTI");
+        let info = AssetInfo {
+            fractionable: false,
+            ..AssetInfo::fixture(vti.clone())
+        };
+        TacticState {
+            account: Account {
+                cash,
+                equity: cash,
+                ..Account::fixture()
+            },
+            asset_info: HashMap::from([(vti, info)]),
+            open_orders: HashMap::new(),
+            positions: HashMap::new(),
+            ..TacticState::fixture()
+        }
+    }
+
+    fn vti_when_result() -> WhenResult {
+        WhenResult {
+            target_assets: vec![TargetAsset::full_percent(AssetSymbol::new("VTI"))],
+            ..Default::default()
+        }
+    }
 }
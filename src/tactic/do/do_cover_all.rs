@@ -0,0 +1,105 @@
+use crate::float::FloatAmountRounding;
+use crate::tactic::action::Action;
+use crate::tactic::r#do::{DoResult, DoRule};
+use crate::tactic::skip::SkipReason;
+use crate::tactic::state::TacticState;
+use crate::tactic::when::WhenResult;
+
+/// Buys back the short positions of the target assets, the mirror of `sell_all`. Long positions
+/// are left alone.
+pub struct DoCoverAllRule;
+
+impl DoCoverAllRule {
+    pub fn boxed() -> Box<dyn DoRule> {
+        Box::new(Self {})
+    }
+}
+
+impl DoRule for DoCoverAllRule {
+    fn evaluate(&self, state: &TacticState, when_result: WhenResult) -> DoResult {
+        let target_assets_empty = when_result.target_assets.is_empty();
+        let actions = when_result
+            .target_assets
+            .into_iter()
+            .map(|target_asset| {
+                let symbol = &target_asset.symbol;
+                // Short quantities may be reported as negative.
+                let short_amount = state
+                    .short_position(symbol)
+                    .map(|p| p.quantity_available.abs())
+                    .unwrap_or_default();
+                let cover_amount = target_asset
+                    .apply_percent(short_amount)
+                    .round_quantity_for(symbol);
+                Action::buy_quantity(symbol.clone(), cover_amount)
+            })
+            .filter(|a| !a.is_empty())
+            .collect::<Vec<_>>();
+        let skip_reason = if target_assets_empty {
+            SkipReason::NoTargetAssets
+        } else {
+            SkipReason::NoShortPosition
+        };
+        DoResult::from_actions(actions, skip_reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asset::AssetSymbol;
+    use crate::platform::position::Position;
+    use crate::platform::side::Side;
+    use crate::tactic::target::TargetAsset;
+    use std::collections::HashMap;
+
+    #[test]
+    fn evaluate() {
+        let rule = DoCoverAllRule::boxed();
+        let result = rule.evaluate(&state(), when_result());
+        let expected = DoResult {
+            actions: vec![Action::buy_quantity(AssetSymbol::new("SPY"), 10.0)],
+            ..Default::default()
+        };
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn evaluate_no_short_position() {
+        let rule = DoCoverAllRule::boxed();
+        let result = rule.evaluate(&TacticState::fixture(), when_result());
+        assert_eq!(DoResult::skip(SkipReason::NoShortPosition), result)
+    }
+
+    #[test]
+    fn evaluate_empty_when() {
+        let rule = DoCoverAllRule::boxed();
+        let result = rule.evaluate(&state(), WhenResult::default());
+        assert_eq!(DoResult::skip(SkipReason::NoTargetAssets), result)
+    }
+
+    fn state() -> TacticState {
+        let spy = AssetSymbol::new("SPY");
+        let vti = AssetSymbol::new("VTI");
+        let short = Position {
+            quantity: -10.0,
+            quantity_available: -10.0,
+            side: Side::Short,
+            ..Position::fixture(spy.clone())
+        };
+        TacticState {
+            positions: HashMap::from([(spy, short), (vti.clone(), Position::fixture(vti))]),
+            ..TacticState::fixture()
+        }
+    }
+
+    fn when_result() -> WhenResult {
+        WhenResult {
+            conditions_satisfied: true,
+            target_assets: vec![
+                TargetAsset::full_percent(AssetSymbol::new("SPY")),
+                TargetAsset::full_percent(AssetSymbol::new("VTI")),
+            ],
+        }
+    }
+}
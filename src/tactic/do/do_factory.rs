@@ -1,17 +1,44 @@
 use crate::config::tactic::r#do::DoConfig;
 use crate::tactic::null::NullRule;
 use crate::tactic::r#do::do_buy::DoBuyRule;
+use crate::tactic::r#do::do_cover_all::DoCoverAllRule;
+use crate::tactic::r#do::do_invest_notional::DoInvestNotionalRule;
+use crate::tactic::r#do::do_order::DoOrderRule;
+use crate::tactic::r#do::do_rebalance::DoRebalanceRule;
+use crate::tactic::r#do::do_sell_notional::DoSellNotionalRule;
+use crate::tactic::r#do::do_sell_percent::DoSellPercentRule;
 use crate::tactic::r#do::do_sellall::DoSellAllRule;
+use crate::tactic::r#do::do_short::DoShortRule;
+use crate::tactic::r#do::do_trim_to::DoTrimToRule;
 use crate::tactic::r#do::DoRule;
 
 pub struct DoFactory;
 
 impl DoFactory {
     pub fn create_rule(config: DoConfig) -> Box<dyn DoRule> {
-        match config {
-            DoConfig::Buy { buy_percent } => DoBuyRule::boxed(buy_percent),
+        let order = config.order().cloned().unwrap_or_default();
+        let rule = match config {
+            DoConfig::Buy { buy_percent, .. } => DoBuyRule::boxed(buy_percent),
+            DoConfig::CoverAll { .. } => DoCoverAllRule::boxed(),
+            DoConfig::InvestNotional {
+                invest_notional, ..
+            } => DoInvestNotionalRule::boxed(invest_notional),
             DoConfig::Nothing { .. } => NullRule::do_boxed(),
+            DoConfig::Rebalance {
+                drift_percent,
+                min_trade,
+                ..
+            } => DoRebalanceRule::boxed(drift_percent, min_trade),
             DoConfig::SellAll { .. } => DoSellAllRule::boxed(),
-        }
+            DoConfig::SellNotional { sell_notional, .. } => {
+                DoSellNotionalRule::boxed(sell_notional)
+            }
+            DoConfig::SellPercent { sell_percent, .. } => DoSellPercentRule::boxed(sell_percent),
+            DoConfig::ShortPercent { short_percent, .. } => DoShortRule::boxed(short_percent),
+            DoConfig::TrimToPercent {
+                trim_to_percent, ..
+            } => DoTrimToRule::boxed(trim_to_percent),
+        };
+        DoOrderRule::wrap(rule, order)
     }
 }
@@ -0,0 +1,166 @@
+use crate::float::FloatAmountRounding;
+use crate::tactic::r#do::{buy_up_to, DoResult, DoRule};
+use crate::tactic::skip::SkipReason;
+use crate::tactic::state::TacticState;
+use crate::tactic::when::WhenResult;
+
+/// Buys a fixed dollar amount split across the target assets by their percents, regardless of
+/// how large the positions already are. Pair it with a schedule to dollar-cost average. Assets
+/// which only trade in whole shares get as many shares as the amount covers.
+pub struct DoInvestNotionalRule {
+    invest_notional: f64,
+}
+
+impl DoInvestNotionalRule {
+    pub fn boxed(invest_notional: f64) -> Box<dyn DoRule> {
+        Box::new(Self { invest_notional })
+    }
+}
+
+impl DoRule for DoInvestNotionalRule {
+    fn evaluate(&self, state: &TacticState, when_result: WhenResult) -> DoResult {
+        let mut remaining_cash = state.account.cash;
+        let mut skip_reason = None;
+        let actions = when_result
+            .target_assets
+            .iter()
+            .filter_map(|target_asset| {
+                let amount = (self.invest_notional * target_asset.percent / 100.0)
+                    .min(remaining_cash)
+                    .round_for_notional();
+                if amount <= 0.0 {
+                    return None;
+                }
+                match buy_up_to(state, &target_asset.symbol, amount) {
+                    Ok((action, cost)) => {
+                        remaining_cash -= cost;
+                        Some(action)
+                    }
+                    Err(reason) => {
+                        skip_reason = Some(reason);
+                        None
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+        let skip_reason = if when_result.target_assets.is_empty() {
+            SkipReason::NoTargetAssets
+        } else {
+            skip_reason.unwrap_or(SkipReason::InsufficientCash)
+        };
+        DoResult::from_actions(actions, skip_reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asset::AssetSymbol;
+    use crate::platform::account::Account;
+    use crate::platform::asset_info::AssetInfo;
+    use crate::tactic::action::Action;
+    use crate::tactic::target::TargetAsset;
+    use std::collections::HashMap;
+
+    // The fixture has 500 in cash.
+
+    #[test]
+    fn evaluate() {
+        let rule = DoInvestNotionalRule::boxed(200.0);
+        let result = rule.evaluate(&TacticState::fixture(), when_result(vec!["VTI"]));
+        let expected = DoResult {
+            actions: vec![Action::buy_notional(AssetSymbol::new("VTI"), 200.0)],
+            ..Default::default()
+        };
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn evaluate_splits_across_assets() {
+        let rule = DoInvestNotionalRule::boxed(200.0);
+        let result = rule.evaluate(&TacticState::fixture(), when_result(vec!["VTI", "VXUS"]));
+        assert_eq!(
+            result.actions,
+            vec![
+                Action::buy_notional(AssetSymbol::new("VTI"), 100.0),
+                Action::buy_notional(AssetSymbol::new("VXUS"), 100.0),
+            ]
+        )
+    }
+
+    #[test]
+    fn evaluate_limited_by_cash() {
+        let rule = DoInvestNotionalRule::boxed(800.0);
+        let result = rule.evaluate(&TacticState::fixture(), when_result(vec!["VTI", "VXUS"]));
+        assert_eq!(
+            result.actions,
+            vec![
+                Action::buy_notional(AssetSymbol::new("VTI"), 400.0),
+                Action::buy_notional(AssetSymbol::new("VXUS"), 100.0),
+            ]
+        )
+    }
+
+    #[test]
+    fn evaluate_no_cash() {
+        let rule = DoInvestNotionalRule::boxed(200.0);
+        let state = TacticState {
+            account: Account::default(),
+            ..TacticState::fixture()
+        };
+        let result = rule.evaluate(&state, when_result(vec!["VTI"]));
+        assert_eq!(DoResult::skip(SkipReason::InsufficientCash), result)
+    }
+
+    #[test]
+    fn evaluate_whole_shares() {
+        let rule = DoInvestNotionalRule::boxed(450.0);
+        let result = rule.evaluate(&whole_share_state(), when_result(vec!["VTI"]));
+        // $450 buys 2 whole shares at the 200.00 ask.
+        assert_eq!(
+            result.actions,
+            vec![Action::buy_quantity(AssetSymbol::new("VTI"), 2.0)]
+        )
+    }
+
+    #[test]
+    fn evaluate_whole_shares_less_than_a_share() {
+        let rule = DoInvestNotionalRule::boxed(150.0);
+        let result = rule.evaluate(&whole_share_state(), when_result(vec!["VTI"]));
+        assert_eq!(DoResult::skip(SkipReason::LessThanOneShare), result)
+    }
+
+    #[test]
+    fn evaluate_empty_when() {
+        let rule = DoInvestNotionalRule::boxed(200.0);
+        let result = rule.evaluate(&TacticState::fixture(), WhenResult::default());
+        assert_eq!(DoResult::skip(SkipReason::NoTargetAssets), result)
+    }
+
+    fn whole_share_state() -> TacticState {
+        let vti = AssetSymbol::new("VTI");
+        let info = AssetInfo {
+            fractionable: false,
+            ..AssetInfo::fixture(vti.clone())
+        };
+        TacticState {
+            asset_info: HashMap::from([(vti, info)]),
+            ..TacticState::fixture()
+        }
+    }
+
+    fn when_result(symbols: Vec<&str>) -> WhenResult {
+        let percent = 100.0 / symbols.len() as f64;
+        let target_assets = symbols
+            .into_iter()
+            .map(|symbol| TargetAsset {
+                symbol: AssetSymbol::new(symbol),
+                percent,
+            })
+            .collect();
+        WhenResult {
+            conditions_satisfied: true,
+            target_assets,
+        }
+    }
+}
@@ -0,0 +1,333 @@
+use crate::asset::AssetSymbol;
+use crate::config::tactic::order::OrderConfig;
+use crate::float::FloatAmountRounding;
+use crate::num::NumFromFloat;
+use crate::platform::asset_info::AssetInfo;
+use crate::platform::order::amount::Amount;
+use crate::platform::order::class::OrderClass;
+use crate::platform::order::order_type::OrderType;
+use crate::platform::order::side::OrderSide;
+use crate::platform::order::time_in_force::TimeInForce;
+use crate::platform::request::stop_loss::StopLoss;
+use crate::platform::request::take_profit::TakeProfit;
+use crate::platform::request::OrderRequest;
+use crate::tactic::action::Action;
+use crate::tactic::r#do::{DoResult, DoRule};
+use crate::tactic::skip::SkipReason;
+use crate::tactic::state::TacticState;
+use crate::tactic::when::WhenResult;
+use log::warn;
+use num_decimal::Num;
+
+/// Turns the market orders of another `do` rule into the configured kind of order, priced off
+/// each asset's quote. Actions for assets without a quote are dropped since they can't be priced.
+///
+/// Trailing stop and bracket orders are for whole shares, unless the asset allows fractions, and
+/// stay open until canceled, so the broker keeps managing the exits between runs. Brackets only apply to buys, sells are left as
+/// market orders.
+pub struct DoOrderRule {
+    rule: Box<dyn DoRule>,
+    order: OrderConfig,
+}
+
+impl DoOrderRule {
+    /// Wraps the rule, leaving it as is for market orders.
+    pub fn wrap(rule: Box<dyn DoRule>, order: OrderConfig) -> Box<dyn DoRule> {
+        if order.is_market() {
+            rule
+        } else {
+            Box::new(Self { rule, order })
+        }
+    }
+
+    fn convert(&self, state: &TacticState, action: Action) -> Option<Action> {
+        let (symbol, amount, side) = match &action {
+            Action::Buy { symbol, amount } => (symbol, amount, OrderSide::Buy),
+            Action::Sell { symbol, amount } => (symbol, amount, OrderSide::Sell),
+            Action::Order(_) => return Some(action),
+        };
+        if matches!(self.order, OrderConfig::Bracket { .. }) && side == OrderSide::Sell {
+            return Some(action);
+        }
+        let Some(price) = Self::quote_price(state, symbol, side) else {
+            warn!("no quote to price the order for {symbol}, skipping {action}");
+            return None;
+        };
+        let quantity = match amount {
+            Amount::Quantity(quantity) => *quantity,
+            Amount::Notional(notional) => notional / price,
+        };
+        let exit_quantity =
+            AssetInfo::order_quantity(symbol, state.asset_info.get(symbol), quantity);
+        let request = OrderRequest {
+            symbol: symbol.clone(),
+            side,
+            amount: Amount::Quantity(quantity.round_quantity_for(symbol)),
+            time_in_force: TimeInForce::for_symbol(symbol),
+            ..Default::default()
+        };
+        let request = match &self.order {
+            OrderConfig::Market => request,
+            OrderConfig::Limit { offset_percent } => OrderRequest {
+                order_type: OrderType::Limit,
+                limit_price: Some(Self::price_num(price, *offset_percent)),
+                ..request
+            },
+            OrderConfig::Stop { offset_percent } => OrderRequest {
+                order_type: OrderType::Stop,
+                stop_price: Some(Self::price_num(price, *offset_percent)),
+                ..request
+            },
+            OrderConfig::TrailingStop { trail_percent } => OrderRequest {
+                order_type: OrderType::TrailingStop,
+                amount: Amount::Quantity(exit_quantity),
+                time_in_force: TimeInForce::UntilCanceled,
+                trail_percent: Some(Num::from_f64(*trail_percent)),
+                ..request
+            },
+            OrderConfig::Bracket {
+                take_profit_percent,
+                stop_loss_percent,
+            } => OrderRequest {
+                class: OrderClass::Bracket,
+                amount: Amount::Quantity(exit_quantity),
+                time_in_force: TimeInForce::UntilCanceled,
+                take_profit: Some(TakeProfit::Limit(Self::price_num(
+                    price,
+                    *take_profit_percent,
+                ))),
+                stop_loss: Some(StopLoss::Stop(Self::price_num(price, -stop_loss_percent))),
+                ..request
+            },
+        };
+        if request.amount.is_empty() {
+            warn!("{action} is less than a share, skipping the {side} order");
+            return None;
+        }
+        Some(Action::Order(Box::new(request)))
+    }
+
+    /// The ask for buys and the bid for sells, falling back to the other side when it's missing.
+    fn quote_price(state: &TacticState, symbol: &AssetSymbol, side: OrderSide) -> Option<f64> {
+        let quote = state.quotes.get(symbol)?;
+        let (price, fallback) = match side {
+            OrderSide::Buy => (quote.ask_price, quote.bid_price),
+            OrderSide::Sell => (quote.bid_price, quote.ask_price),
+        };
+        [price, fallback].into_iter().find(|p| *p > 0.0)
+    }
+
+    /// The price offset by the percent, rounded to the cent.
+    fn price_num(price: f64, offset_percent: f64) -> Num {
+        let offset = price * (1.0 + offset_percent / 100.0);
+        Num::from_f64((offset * 100.0).round() / 100.0)
+    }
+}
+
+impl DoRule for DoOrderRule {
+    fn evaluate(&self, state: &TacticState, when_result: WhenResult) -> DoResult {
+        let result = self.rule.evaluate(state, when_result);
+        if result.skipped {
+            return result;
+        }
+        let actions = result
+            .actions
+            .into_iter()
+            .filter_map(|action| self.convert(state, action))
+            .collect();
+        DoResult::from_actions(actions, SkipReason::NoQuote)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::quote::Quote;
+    use crate::tactic::target::TargetAsset;
+    use std::collections::HashMap;
+
+    // VTI is quoted at 100.00 bid, 100.50 ask.
+
+    #[test]
+    fn wrap_market() {
+        let rule = DoOrderRule::wrap(actions(vec![buy(201.0)]), OrderConfig::Market);
+        let result = rule.evaluate(&state(), when_result());
+        assert_eq!(result.actions, vec![buy(201.0)])
+    }
+
+    #[test]
+    fn limit_buy() {
+        let order = OrderConfig::Limit {
+            offset_percent: -0.5,
+        };
+        let rule = DoOrderRule::wrap(actions(vec![buy(201.0)]), order);
+        let result = rule.evaluate(&state(), when_result());
+        let expected = OrderRequest {
+            order_type: OrderType::Limit,
+            limit_price: Some(Num::from_f64(100.0)),
+            ..request(OrderSide::Buy, 2.0)
+        };
+        assert_eq!(result.actions, vec![Action::Order(Box::new(expected))])
+    }
+
+    #[test]
+    fn stop_sell() {
+        let order = OrderConfig::Stop {
+            offset_percent: -5.0,
+        };
+        let sell = Action::sell_quantity(vti(), 3.0);
+        let rule = DoOrderRule::wrap(actions(vec![sell]), order);
+        let result = rule.evaluate(&state(), when_result());
+        let expected = OrderRequest {
+            order_type: OrderType::Stop,
+            stop_price: Some(Num::from_f64(95.0)),
+            ..request(OrderSide::Sell, 3.0)
+        };
+        assert_eq!(result.actions, vec![Action::Order(Box::new(expected))])
+    }
+
+    #[test]
+    fn trailing_stop_whole_shares() {
+        let order = OrderConfig::TrailingStop { trail_percent: 5.0 };
+        let sell = Action::sell_quantity(vti(), 3.5);
+        let rule = DoOrderRule::wrap(actions(vec![sell]), order);
+        let result = rule.evaluate(&state(), when_result());
+        let expected = OrderRequest {
+            order_type: OrderType::TrailingStop,
+            time_in_force: TimeInForce::UntilCanceled,
+            trail_percent: Some(Num::from(5)),
+            ..request(OrderSide::Sell, 3.0)
+        };
+        assert_eq!(result.actions, vec![Action::Order(Box::new(expected))])
+    }
+
+    #[test]
+    fn trailing_stop_fractional_shares() {
+        let order = OrderConfig::TrailingStop { trail_percent: 5.0 };
+        let sell = Action::sell_quantity(vti(), 3.5);
+        let rule = DoOrderRule::wrap(actions(vec![sell]), order);
+        let state = TacticState {
+            asset_info: HashMap::from([(vti(), AssetInfo::fixture(vti()))]),
+            ..state()
+        };
+        let result = rule.evaluate(&state, when_result());
+        let expected = OrderRequest {
+            order_type: OrderType::TrailingStop,
+            time_in_force: TimeInForce::UntilCanceled,
+            trail_percent: Some(Num::from(5)),
+            ..request(OrderSide::Sell, 3.5)
+        };
+        assert_eq!(result.actions, vec![Action::Order(Box::new(expected))])
+    }
+
+    #[test]
+    fn bracket_buy() {
+        let order = OrderConfig::Bracket {
+            take_profit_percent: 10.0,
+            stop_loss_percent: 5.0,
+        };
+        let rule = DoOrderRule::wrap(actions(vec![buy(350.0)]), order);
+        let result = rule.evaluate(&state(), when_result());
+        let expected = OrderRequest {
+            class: OrderClass::Bracket,
+            time_in_force: TimeInForce::UntilCanceled,
+            take_profit: Some(TakeProfit::Limit(Num::from_f64(110.55))),
+            stop_loss: Some(StopLoss::Stop(Num::from_f64(95.48))),
+            ..request(OrderSide::Buy, 3.0)
+        };
+        assert_eq!(result.actions, vec![Action::Order(Box::new(expected))])
+    }
+
+    #[test]
+    fn bracket_leaves_sells_alone() {
+        let order = OrderConfig::Bracket {
+            take_profit_percent: 10.0,
+            stop_loss_percent: 5.0,
+        };
+        let sell = Action::sell_quantity(vti(), 3.0);
+        let rule = DoOrderRule::wrap(actions(vec![sell]), order);
+        let result = rule.evaluate(&state(), when_result());
+        assert_eq!(result.actions, vec![Action::sell_quantity(vti(), 3.0)])
+    }
+
+    #[test]
+    fn under_a_share() {
+        let order = OrderConfig::TrailingStop { trail_percent: 5.0 };
+        let sell = Action::sell_quantity(vti(), 0.5);
+        let rule = DoOrderRule::wrap(actions(vec![sell]), order);
+        let result = rule.evaluate(&state(), when_result());
+        assert_eq!(DoResult::skip(SkipReason::NoQuote), result)
+    }
+
+    #[test]
+    fn no_quote() {
+        let order = OrderConfig::Limit {
+            offset_percent: 0.0,
+        };
+        let rule = DoOrderRule::wrap(actions(vec![buy(201.0)]), order);
+        let state = TacticState {
+            quotes: HashMap::new(),
+            ..state()
+        };
+        let result = rule.evaluate(&state, when_result());
+        assert_eq!(DoResult::skip(SkipReason::NoQuote), result)
+    }
+
+    #[test]
+    fn skipped() {
+        let order = OrderConfig::Limit {
+            offset_percent: 0.0,
+        };
+        let rule = DoOrderRule::wrap(actions(vec![]), order);
+        let result = rule.evaluate(&state(), when_result());
+        assert_eq!(DoResult::skip(SkipReason::TargetReached), result)
+    }
+
+    struct FixedActions(Vec<Action>);
+
+    impl DoRule for FixedActions {
+        fn evaluate(&self, _state: &TacticState, _when_result: WhenResult) -> DoResult {
+            DoResult::from_actions(self.0.clone(), SkipReason::TargetReached)
+        }
+    }
+
+    fn actions(actions: Vec<Action>) -> Box<dyn DoRule> {
+        Box::new(FixedActions(actions))
+    }
+
+    fn buy(notional: f64) -> Action {
+        Action::buy_notional(vti(), notional)
+    }
+
+    fn request(side: OrderSide, quantity: f64) -> OrderRequest {
+        OrderRequest {
+            symbol: vti(),
+            side,
+            amount: Amount::Quantity(quantity),
+            ..Default::default()
+        }
+    }
+
+    fn vti() -> AssetSymbol {
+        AssetSymbol::new("VTI")
+    }
+
+    fn state() -> TacticState {
+        let quote = Quote {
+            ask_price: 100.5,
+            bid_price: 100.0,
+            ..Quote::fixture(vti())
+        };
+        TacticState {
+            quotes: HashMap::from([(vti(), quote)]),
+            ..TacticState::fixture()
+        }
+    }
+
+    fn when_result() -> WhenResult {
+        WhenResult {
+            conditions_satisfied: true,
+            target_assets: vec![TargetAsset::full_percent(vti())],
+        }
+    }
+}
@@ -0,0 +1,323 @@
+use crate::float::{FloatAmountRounding, PercentOps};
+use crate::tactic::action::Action;
+use crate::tactic::r#do::{buy_up_to, DoResult, DoRule};
+use crate::tactic::skip::SkipReason;
+use crate::tactic::state::TacticState;
+use crate::tactic::target::TargetAsset;
+use crate::tactic::when::WhenResult;
+
+/// Trades the target assets back toward their percents of the portfolio. Assets which have
+/// drifted less than the tolerance band are left alone, and sells come first so their proceeds
+/// can fund the buys.
+pub struct DoRebalanceRule {
+    drift_percent: f64,
+    min_trade: f64,
+}
+
+impl DoRebalanceRule {
+    pub fn boxed(drift_percent: f64, min_trade: f64) -> Box<dyn DoRule> {
+        Box::new(Self {
+            drift_percent,
+            min_trade,
+        })
+    }
+
+    /// The dollar amount each target asset is away from its target value, positive when it's
+    /// under weight. Assets within the drift band are left out, as are assets with open orders
+    /// since an earlier rebalance may still be filling.
+    fn drifts(&self, state: &TacticState, assets: &[TargetAsset]) -> Vec<(TargetAsset, f64)> {
+        let portfolio_value = state
+            .account
+            .equity
+            .percent_of(state.strategy_properties.portfolio_percent);
+        if portfolio_value <= 0.0 {
+            return vec![];
+        }
+        assets
+            .iter()
+            .filter(|asset| !Self::has_open_orders(state, asset))
+            .filter_map(|asset| {
+                let desired_value = portfolio_value.percent_of(asset.percent);
+                let current_value = self.position_value(state, asset);
+                let drift = desired_value - current_value;
+                let drift_percent = (drift.abs() / portfolio_value) * 100.0;
+                (drift_percent > self.drift_percent).then(|| (asset.clone(), drift))
+            })
+            .collect()
+    }
+
+    fn has_open_orders(state: &TacticState, target_asset: &TargetAsset) -> bool {
+        state
+            .open_orders
+            .get(&target_asset.symbol)
+            .is_some_and(|orders| !orders.is_empty())
+    }
+
+    fn position_value(&self, state: &TacticState, target_asset: &TargetAsset) -> f64 {
+        state
+            .positions
+            .get(&target_asset.symbol)
+            .map(|p| p.price().unwrap_or(p.average_entry_price) * p.quantity)
+            .unwrap_or(0.0)
+    }
+
+    /// The sell bringing an over weight asset back down, along with its expected proceeds.
+    fn sell(&self, state: &TacticState, asset: &TargetAsset, excess: f64) -> Option<(Action, f64)> {
+        let position = state.long_position(&asset.symbol)?;
+        let price = position.price().filter(|p| *p > 0.0)?;
+        let quantity = (excess / price)
+            .min(position.quantity_available)
+            .round_quantity_for(&asset.symbol);
+        let proceeds = quantity * price;
+        (proceeds >= self.min_trade).then(|| {
+            (
+                Action::sell_quantity(asset.symbol.clone(), quantity),
+                proceeds,
+            )
+        })
+    }
+}
+
+impl DoRule for DoRebalanceRule {
+    fn evaluate(&self, state: &TacticState, when_result: WhenResult) -> DoResult {
+        if when_result.target_assets.is_empty() {
+            return DoResult::skip(SkipReason::NoTargetAssets);
+        }
+        let drifts = self.drifts(state, &when_result.target_assets);
+
+        let (sells, proceeds): (Vec<_>, Vec<_>) = drifts
+            .iter()
+            .filter(|(_, drift)| *drift < 0.0)
+            .filter_map(|(asset, drift)| self.sell(state, asset, -drift))
+            .unzip();
+
+        let mut remaining_cash = state.account.cash.max(0.0) + proceeds.iter().sum::<f64>();
+        let under_weight = drifts
+            .iter()
+            .filter(|(_, drift)| *drift >= self.min_trade)
+            .collect::<Vec<_>>();
+        let mut buy_skip_reason = None;
+        let buys = under_weight
+            .iter()
+            .filter_map(|(asset, drift)| {
+                let amount = drift.min(remaining_cash).round_for_notional();
+                if amount < self.min_trade {
+                    return None;
+                }
+                match buy_up_to(state, &asset.symbol, amount) {
+                    Ok((action, cost)) => {
+                        remaining_cash -= cost;
+                        Some(action)
+                    }
+                    Err(reason) => {
+                        buy_skip_reason = Some(reason);
+                        None
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let skip_reason = if !under_weight.is_empty() && buys.is_empty() {
+            buy_skip_reason.unwrap_or(SkipReason::InsufficientCash)
+        } else {
+            SkipReason::TargetReached
+        };
+        let actions = sells.into_iter().chain(buys).collect();
+        DoResult::from_actions(actions, skip_reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asset::AssetSymbol;
+    use crate::config::strategy::StrategyProperties;
+    use crate::platform::account::Account;
+    use crate::platform::asset_info::AssetInfo;
+    use crate::platform::order::Order;
+    use crate::platform::position::Position;
+    use crate::platform::quote::Quote;
+    use std::collections::HashMap;
+
+    // Equity is 1000 with 100 in cash, VTI holds $700 and VXUS and BND hold $100 each, against
+    // weights of 60/30/10.
+
+    #[test]
+    fn evaluate() {
+        let rule = DoRebalanceRule::boxed(5.0, 1.0);
+        let result = rule.evaluate(&state(100.0), when_result());
+        // VTI sells $100 (2 shares), which with the cash funds VXUS's $200 shortfall.
+        let expected = DoResult {
+            actions: vec![
+                Action::sell_quantity(AssetSymbol::new("VTI"), 2.0),
+                Action::buy_notional(AssetSymbol::new("VXUS"), 200.0),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn evaluate_within_drift_band() {
+        let rule = DoRebalanceRule::boxed(25.0, 1.0);
+        let result = rule.evaluate(&state(100.0), when_result());
+        assert_eq!(DoResult::skip(SkipReason::TargetReached), result)
+    }
+
+    #[test]
+    fn evaluate_below_min_trade() {
+        let rule = DoRebalanceRule::boxed(5.0, 150.0);
+        let result = rule.evaluate(&state(100.0), when_result());
+        // The $100 sell is too small, leaving only the cash for VXUS's $200 buy.
+        assert_eq!(DoResult::skip(SkipReason::InsufficientCash), result)
+    }
+
+    #[test]
+    fn evaluate_limited_by_cash() {
+        let rule = DoRebalanceRule::boxed(5.0, 1.0);
+        let state = TacticState {
+            positions: HashMap::from([position("VTI", 50.0, 12.0), position("BND", 10.0, 10.0)]),
+            ..state(100.0)
+        };
+        let result = rule.evaluate(&state, when_result());
+        // VXUS wants $300 but only the $100 of cash is available.
+        assert_eq!(
+            result.actions,
+            vec![Action::buy_notional(AssetSymbol::new("VXUS"), 100.0)]
+        )
+    }
+
+    #[test]
+    fn evaluate_portfolio_percent() {
+        let rule = DoRebalanceRule::boxed(5.0, 1.0);
+        let result = rule.evaluate(&state(50.0), when_result());
+        // Half the portfolio is $500, so VTI and BND sell down to $300 and $50 and VXUS tops up
+        // to $150.
+        assert_eq!(
+            result.actions,
+            vec![
+                Action::sell_quantity(AssetSymbol::new("VTI"), 8.0),
+                Action::sell_quantity(AssetSymbol::new("BND"), 5.0),
+                Action::buy_notional(AssetSymbol::new("VXUS"), 50.0),
+            ]
+        )
+    }
+
+    #[test]
+    fn evaluate_skips_open_orders() {
+        let rule = DoRebalanceRule::boxed(5.0, 1.0);
+        let vti = AssetSymbol::new("VTI");
+        let state = TacticState {
+            open_orders: HashMap::from([(vti.clone(), vec![Order::fixture(vti)])]),
+            ..state(100.0)
+        };
+        let result = rule.evaluate(&state, when_result());
+        // Without VTI's sale only the cash is left for VXUS.
+        assert_eq!(
+            result.actions,
+            vec![Action::buy_notional(AssetSymbol::new("VXUS"), 100.0)]
+        )
+    }
+
+    #[test]
+    fn evaluate_whole_shares() {
+        let rule = DoRebalanceRule::boxed(5.0, 1.0);
+        let vxus = AssetSymbol::new("VXUS");
+        let state = TacticState {
+            asset_info: HashMap::from([whole_shares(&vxus)]),
+            quotes: HashMap::from([(vxus.clone(), Quote::fixture(vxus.clone()))]),
+            ..state(100.0)
+        };
+        let result = rule.evaluate(&state, when_result());
+        // VXUS can't be bought in fractions, so its $200 shortfall buys one share at the $200 ask.
+        assert_eq!(
+            result.actions,
+            vec![
+                Action::sell_quantity(AssetSymbol::new("VTI"), 2.0),
+                Action::buy_quantity(vxus, 1.0),
+            ]
+        )
+    }
+
+    #[test]
+    fn evaluate_less_than_one_share() {
+        let rule = DoRebalanceRule::boxed(5.0, 1.0);
+        let vxus = AssetSymbol::new("VXUS");
+        let state = TacticState {
+            asset_info: HashMap::from([whole_shares(&vxus)]),
+            quotes: HashMap::from([(
+                vxus.clone(),
+                Quote {
+                    ask_price: 500.0,
+                    ..Quote::fixture(vxus.clone())
+                },
+            )]),
+            positions: HashMap::from([position("VTI", 50.0, 12.0), position("BND", 10.0, 10.0)]),
+            ..state(100.0)
+        };
+        let result = rule.evaluate(&state, when_result());
+        assert_eq!(DoResult::skip(SkipReason::LessThanOneShare), result)
+    }
+
+    #[test]
+    fn evaluate_empty_when() {
+        let rule = DoRebalanceRule::boxed(5.0, 1.0);
+        let result = rule.evaluate(&state(100.0), WhenResult::default());
+        assert_eq!(DoResult::skip(SkipReason::NoTargetAssets), result)
+    }
+
+    fn state(portfolio_percent: f64) -> TacticState {
+        TacticState {
+            account: Account {
+                cash: 100.0,
+                equity: 1000.0,
+                ..Default::default()
+            },
+            open_orders: HashMap::new(),
+            positions: HashMap::from([
+                position("VTI", 50.0, 14.0),
+                position("VXUS", 10.0, 10.0),
+                position("BND", 10.0, 10.0),
+            ]),
+            strategy_properties: StrategyProperties {
+                portfolio_percent,
+                ..Default::default()
+            },
+            ..TacticState::fixture()
+        }
+    }
+
+    fn position(symbol: &str, price: f64, quantity: f64) -> (AssetSymbol, Position) {
+        let symbol = AssetSymbol::new(symbol);
+        let position = Position {
+            current_price: Some(price),
+            quantity,
+            quantity_available: quantity,
+            ..Position::fixture(symbol.clone())
+        };
+        (symbol, position)
+    }
+
+    fn whole_shares(symbol: &AssetSymbol) -> (AssetSymbol, AssetInfo) {
+        let info = AssetInfo {
+            fractionable: false,
+            ..AssetInfo::fixture(symbol.clone())
+        };
+        (symbol.clone(), info)
+    }
+
+    fn when_result() -> WhenResult {
+        let target = |symbol: &str, percent: f64| TargetAsset {
+            symbol: AssetSymbol::new(symbol),
+            percent,
+        };
+        WhenResult {
+            conditions_satisfied: true,
+            target_assets: vec![
+                target("VTI", 60.0),
+                target("VXUS", 30.0),
+                target("BND", 10.0),
+            ],
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use crate::float::FloatAmountRounding;
+use crate::platform::position::Position;
+use crate::tactic::action::Action;
+use crate::tactic::r#do::{DoResult, DoRule};
+use crate::tactic::skip::SkipReason;
+use crate::tactic::state::TacticState;
+use crate::tactic::target::TargetAsset;
+use crate::tactic::when::WhenResult;
+
+/// Sells a dollar amount of each position. Positions worth less than the amount are sold in full.
+pub struct DoSellNotionalRule {
+    sell_notional: f64,
+}
+
+impl DoSellNotionalRule {
+    pub fn boxed(sell_notional: f64) -> Box<dyn DoRule> {
+        Box::new(Self { sell_notional })
+    }
+
+    fn action(&self, position: &Position, target_asset: &TargetAsset) -> Action {
+        let symbol = target_asset.symbol.clone();
+        let notional = target_asset
+            .apply_percent(self.sell_notional)
+            .round_for_notional();
+        match position.available_value() {
+            Some(value) if value > notional => Action::sell_notional(symbol, notional),
+            Some(_) => {
+                let quantity = position.quantity_available.round_quantity_for(&symbol);
+                Action::sell_quantity(symbol, quantity)
+            }
+            // Without a price we can't tell whether the position covers the amount.
+            None => Action::sell_quantity(symbol, 0.0),
+        }
+    }
+}
+
+impl DoRule for DoSellNotionalRule {
+    fn evaluate(&self, state: &TacticState, when_result: WhenResult) -> DoResult {
+        let target_assets_empty = when_result.target_assets.is_empty();
+        let actions = when_result
+            .target_assets
+            .iter()
+            .filter_map(|t| {
+                let position = state.long_position(&t.symbol)?;
+                Some(self.action(position, t))
+            })
+            .filter(|a| !a.is_empty())
+            .collect::<Vec<_>>();
+        let skip_reason = if target_assets_empty {
+            SkipReason::NoTargetAssets
+        } else {
+            SkipReason::NoPosition
+        };
+        DoResult::from_actions(actions, skip_reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asset::AssetSymbol;
+    use std::collections::HashMap;
+
+    #[test]
+    fn evaluate() {
+        let rule = DoSellNotionalRule::boxed(500.0);
+        let when_result = WhenResult {
+            conditions_satisfied: true,
+            target_assets: vec![
+                TargetAsset::full_percent(AssetSymbol::new("SPY")),
+                TargetAsset::new(AssetSymbol::new("VTI"), 50.0),
+            ],
+        };
+        let result = rule.evaluate(&state(), when_result);
+        let expected = DoResult {
+            actions: vec![
+                Action::sell_notional(AssetSymbol::new("SPY"), 500.0),
+                Action::sell_notional(AssetSymbol::new("VTI"), 250.0),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn evaluate_sells_small_position_in_full() {
+        let rule = DoSellNotionalRule::boxed(5000.0);
+        let when_result = WhenResult {
+            conditions_satisfied: true,
+            target_assets: vec![TargetAsset::full_percent(AssetSymbol::new("SPY"))],
+        };
+        let result = rule.evaluate(&state(), when_result);
+        assert_eq!(
+            result.actions,
+            vec![Action::sell_quantity(AssetSymbol::new("SPY"), 50.0)]
+        )
+    }
+
+    #[test]
+    fn evaluate_no_price() {
+        let rule = DoSellNotionalRule::boxed(500.0);
+        let when_result = WhenResult {
+            conditions_satisfied: true,
+            target_assets: vec![TargetAsset::full_percent(AssetSymbol::new("SPY"))],
+        };
+        let result = rule.evaluate(&TacticState::fixture(), when_result);
+        assert_eq!(DoResult::skip(SkipReason::NoPosition), result)
+    }
+
+    #[test]
+    fn evaluate_empty_when() {
+        let rule = DoSellNotionalRule::boxed(500.0);
+        let result = rule.evaluate(&state(), WhenResult::default());
+        assert_eq!(DoResult::skip(SkipReason::NoTargetAssets), result)
+    }
+
+    fn state() -> TacticState {
+        // Both positions hold 50 shares worth $20 each.
+        let positions = ["SPY", "VTI"]
+            .into_iter()
+            .map(|s| {
+                let symbol = AssetSymbol::new(s);
+                let position = Position {
+                    current_price: Some(20.0),
+                    ..Position::fixture(symbol.clone())
+                };
+                (symbol, position)
+            })
+            .collect::<HashMap<_, _>>();
+        TacticState {
+            positions,
+            ..TacticState::fixture()
+        }
+    }
+}
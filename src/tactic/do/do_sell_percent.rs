@@ -0,0 +1,106 @@
+use crate::float::{FloatAmountRounding, PercentOps};
+use crate::tactic::action::Action;
+use crate::tactic::r#do::{DoResult, DoRule};
+use crate::tactic::skip::SkipReason;
+use crate::tactic::state::TacticState;
+use crate::tactic::when::WhenResult;
+
+/// Sells a percent of each position's available quantity, scaling out instead of selling it all.
+pub struct DoSellPercentRule {
+    sell_percent: f64,
+}
+
+impl DoSellPercentRule {
+    pub fn boxed(sell_percent: f64) -> Box<dyn DoRule> {
+        Box::new(Self { sell_percent })
+    }
+}
+
+impl DoRule for DoSellPercentRule {
+    fn evaluate(&self, state: &TacticState, when_result: WhenResult) -> DoResult {
+        let target_assets_empty = when_result.target_assets.is_empty();
+        let actions = when_result
+            .target_assets
+            .into_iter()
+            .map(|target_asset| {
+                let symbol = &target_asset.symbol;
+                let position_amount = state
+                    .long_position(symbol)
+                    .map(|p| p.quantity_available)
+                    .unwrap_or_default();
+                let sell_amount = target_asset
+                    .apply_percent(position_amount)
+                    .percent_of(self.sell_percent.min(100.0))
+                    .round_quantity_for(symbol);
+                Action::sell_quantity(symbol.clone(), sell_amount)
+            })
+            .filter(|a| !a.is_empty())
+            .collect::<Vec<_>>();
+        let skip_reason = if target_assets_empty {
+            SkipReason::NoTargetAssets
+        } else {
+            SkipReason::NoPosition
+        };
+        DoResult::from_actions(actions, skip_reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asset::AssetSymbol;
+    use crate::tactic::target::TargetAsset;
+
+    #[test]
+    fn evaluate() {
+        let rule = DoSellPercentRule::boxed(25.0);
+        let when_result = WhenResult {
+            conditions_satisfied: true,
+            target_assets: vec![
+                TargetAsset::full_percent(AssetSymbol::new("SPY")),
+                TargetAsset::new(AssetSymbol::new("VTI"), 50.0),
+            ],
+        };
+        let result = rule.evaluate(&TacticState::fixture(), when_result);
+        let expected = DoResult {
+            actions: vec![
+                Action::sell_quantity(AssetSymbol::new("SPY"), 12.5),
+                Action::sell_quantity(AssetSymbol::new("VTI"), 6.25),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn evaluate_caps_at_position() {
+        let rule = DoSellPercentRule::boxed(150.0);
+        let when_result = WhenResult {
+            conditions_satisfied: true,
+            target_assets: vec![TargetAsset::full_percent(AssetSymbol::new("SPY"))],
+        };
+        let result = rule.evaluate(&TacticState::fixture(), when_result);
+        assert_eq!(
+            result.actions,
+            vec![Action::sell_quantity(AssetSymbol::new("SPY"), 50.0)]
+        )
+    }
+
+    #[test]
+    fn evaluate_no_position() {
+        let rule = DoSellPercentRule::boxed(25.0);
+        let when_result = WhenResult {
+            conditions_satisfied: true,
+            target_assets: vec![TargetAsset::full_percent(AssetSymbol::new("QQQ"))],
+        };
+        let result = rule.evaluate(&TacticState::fixture(), when_result);
+        assert_eq!(DoResult::skip(SkipReason::NoPosition), result)
+    }
+
+    #[test]
+    fn evaluate_empty_when() {
+        let rule = DoSellPercentRule::boxed(25.0);
+        let result = rule.evaluate(&TacticState::fixture(), WhenResult::default());
+        assert_eq!(DoResult::skip(SkipReason::NoTargetAssets), result)
+    }
+}
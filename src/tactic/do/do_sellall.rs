@@ -15,28 +15,31 @@ impl DoSellAllRule {
 
 impl DoRule for DoSellAllRule {
     fn evaluate(&self, state: &TacticState, when_result: WhenResult) -> DoResult {
+        let target_assets_empty = when_result.target_assets.is_empty();
         let actions = when_result
             .target_assets
             .into_iter()
             .map(|target_asset| {
                 let symbol = &target_asset.symbol;
-                let position = state.positions.get(symbol);
+                // Short positions are left for `cover_all`.
+                let position = state.long_position(symbol);
                 let position_amount = position.map(|p| p.quantity_available).unwrap_or_default();
-                // We need to round down after 7 significant digits because anything more than that
-                // does not serialize correctly in num.
+                // We need to round down to the asset's quantity precision (7 decimal places for
+                // stocks, 8 for crypto) because anything more than that does not serialize
+                // correctly in num.
                 let sell_amount = target_asset
                     .apply_percent(position_amount)
-                    .round_for_quantity();
+                    .round_quantity_for(symbol);
                 Action::sell_quantity(symbol.clone(), sell_amount)
             })
             .filter(|a| !a.is_empty())
             .collect::<Vec<_>>();
-        let skipped = actions.is_empty();
-        DoResult {
-            actions,
-            skipped,
-            skip_reason: SkipReason::NoTargetAssets,
-        }
+        let skip_reason = if target_assets_empty {
+            SkipReason::NoTargetAssets
+        } else {
+            SkipReason::NoPosition
+        };
+        DoResult::from_actions(actions, skip_reason)
     }
 }
 
@@ -46,6 +49,7 @@ mod tests {
 
     use crate::asset::AssetSymbol;
     use crate::platform::position::Position;
+    use crate::platform::side::Side;
     use crate::tactic::target::TargetAsset;
 
     use super::*;
@@ -62,19 +66,34 @@ mod tests {
         ];
         let expected = DoResult {
             actions: expected_actions,
-            skip_reason: SkipReason::NoTargetAssets,
             ..Default::default()
         };
         assert_eq!(expected, do_result)
     }
 
+    #[test]
+    fn evaluate_skips_short() {
+        let rule = DoSellAllRule::boxed();
+        let spy = AssetSymbol::new("SPY");
+        let short = Position {
+            side: Side::Short,
+            ..Position::fixture(spy.clone())
+        };
+        let state = TacticState {
+            positions: HashMap::from([(spy, short)]),
+            ..Default::default()
+        };
+        let do_result = rule.evaluate(&state, create_when_result());
+        assert_eq!(DoResult::skip(SkipReason::NoPosition), do_result)
+    }
+
     #[test]
     fn evaluate_empty_state() {
         let rule = DoSellAllRule::boxed();
         let state = TacticState::default();
         let when_result = create_when_result();
         let do_result = rule.evaluate(&state, when_result);
-        let expected = DoResult::skip(SkipReason::NoTargetAssets);
+        let expected = DoResult::skip(SkipReason::NoPosition);
         assert_eq!(expected, do_result)
     }
 
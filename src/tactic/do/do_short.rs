@@ -0,0 +1,231 @@
+use crate::asset::AssetSymbol;
+use crate::float::PercentOps;
+use crate::platform::asset_info::AssetInfo;
+use crate::tactic::action::Action;
+use crate::tactic::r#do::{DoResult, DoRule};
+use crate::tactic::skip::SkipReason;
+use crate::tactic::state::TacticState;
+use crate::tactic::target::TargetAsset;
+use crate::tactic::when::WhenResult;
+use log::info;
+
+/// Sells the target assets short until each short is worth its percent of the portfolio, the
+/// mirror of `buy_percent`. Short sales are for whole shares priced off the bid, and are only
+/// placed when the broker has shares to borrow. Assets held long or with open orders are left
+/// alone.
+pub struct DoShortRule {
+    short_percent: f64,
+}
+
+impl DoShortRule {
+    pub fn boxed(short_percent: f64) -> Box<dyn DoRule> {
+        Box::new(Self { short_percent })
+    }
+
+    fn action(
+        &self,
+        state: &TacticState,
+        target_asset: &TargetAsset,
+    ) -> Result<Action, SkipReason> {
+        let symbol = &target_asset.symbol;
+        if state.long_position(symbol).is_some() {
+            info!("do_short: {symbol} is held long, skipping the short");
+            return Err(SkipReason::TargetReached);
+        }
+        if Self::has_open_orders(state, symbol) {
+            return Err(SkipReason::TargetReached);
+        }
+        if !state
+            .asset_info
+            .get(symbol)
+            .is_some_and(AssetInfo::can_short)
+        {
+            info!("do_short: {symbol} has no shares available to borrow");
+            return Err(SkipReason::NotShortable);
+        }
+        let price = Self::price(state, symbol).ok_or(SkipReason::NoQuote)?;
+        let desired_value = state
+            .account
+            .equity
+            .percent_of(self.target_percent(state, target_asset));
+        let quantity = ((desired_value - Self::short_value(state, symbol, price)) / price).floor();
+        if quantity < 1.0 {
+            return Err(SkipReason::TargetReached);
+        }
+        Ok(Action::sell_quantity(symbol.clone(), quantity))
+    }
+
+    fn target_percent(&self, state: &TacticState, target_asset: &TargetAsset) -> f64 {
+        let short_percent =
+            self.short_percent * (state.strategy_properties.portfolio_percent / 100.0);
+        (target_asset.percent * short_percent) / 100.0
+    }
+
+    fn has_open_orders(state: &TacticState, symbol: &AssetSymbol) -> bool {
+        state
+            .open_orders
+            .get(symbol)
+            .is_some_and(|orders| !orders.is_empty())
+    }
+
+    /// The bid, falling back to the ask when it's missing.
+    fn price(state: &TacticState, symbol: &AssetSymbol) -> Option<f64> {
+        let quote = state.quotes.get(symbol)?;
+        [quote.bid_price, quote.ask_price]
+            .into_iter()
+            .find(|p| *p > 0.0)
+    }
+
+    /// The value of the shares already sold short.
+    fn short_value(state: &TacticState, symbol: &AssetSymbol, price: f64) -> f64 {
+        state
+            .short_position(symbol)
+            .map(|p| p.quantity.abs() * p.price().unwrap_or(price))
+            .unwrap_or(0.0)
+    }
+}
+
+impl DoRule for DoShortRule {
+    fn evaluate(&self, state: &TacticState, when_result: WhenResult) -> DoResult {
+        if when_result.target_assets.is_empty() {
+            return DoResult::skip(SkipReason::NoTargetAssets);
+        }
+        // When nothing is shorted, the first asset's reason explains why.
+        let mut skip_reason = None;
+        let actions = when_result
+            .target_assets
+            .iter()
+            .filter_map(|t| match self.action(state, t) {
+                Ok(action) => Some(action),
+                Err(reason) => {
+                    skip_reason.get_or_insert(reason);
+                    None
+                }
+            })
+            .collect();
+        let skip_reason = skip_reason.unwrap_or(SkipReason::TargetReached);
+        DoResult::from_actions(actions, skip_reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::account::Account;
+    use crate::platform::position::Position;
+    use crate::platform::quote::Quote;
+    use crate::platform::side::Side;
+    use std::collections::HashMap;
+
+    // Equity is 10000 and VTI is quoted at 100.00 bid, 100.50 ask.
+
+    #[test]
+    fn evaluate() {
+        let rule = DoShortRule::boxed(5.0);
+        let result = rule.evaluate(&state(), when_result());
+        let expected = DoResult {
+            actions: vec![Action::sell_quantity(vti(), 5.0)],
+            ..Default::default()
+        };
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn evaluate_adds_to_short() {
+        let rule = DoShortRule::boxed(5.0);
+        let short = Position {
+            current_price: Some(100.0),
+            quantity: -3.0,
+            quantity_available: -3.0,
+            side: Side::Short,
+            ..Position::fixture(vti())
+        };
+        let state = TacticState {
+            positions: HashMap::from([(vti(), short)]),
+            ..state()
+        };
+        let result = rule.evaluate(&state, when_result());
+        assert_eq!(result.actions, vec![Action::sell_quantity(vti(), 2.0)])
+    }
+
+    #[test]
+    fn evaluate_target_reached() {
+        let rule = DoShortRule::boxed(0.5);
+        let result = rule.evaluate(&state(), when_result());
+        assert_eq!(DoResult::skip(SkipReason::TargetReached), result)
+    }
+
+    #[test]
+    fn evaluate_held_long() {
+        let rule = DoShortRule::boxed(5.0);
+        let state = TacticState {
+            positions: HashMap::from([(vti(), Position::fixture(vti()))]),
+            ..state()
+        };
+        let result = rule.evaluate(&state, when_result());
+        assert_eq!(DoResult::skip(SkipReason::TargetReached), result)
+    }
+
+    #[test]
+    fn evaluate_not_shortable() {
+        let rule = DoShortRule::boxed(5.0);
+        let info = AssetInfo {
+            easy_to_borrow: false,
+            ..AssetInfo::fixture(vti())
+        };
+        let state = TacticState {
+            asset_info: HashMap::from([(vti(), info)]),
+            ..state()
+        };
+        let result = rule.evaluate(&state, when_result());
+        assert_eq!(DoResult::skip(SkipReason::NotShortable), result)
+    }
+
+    #[test]
+    fn evaluate_no_quote() {
+        let rule = DoShortRule::boxed(5.0);
+        let state = TacticState {
+            quotes: HashMap::new(),
+            ..state()
+        };
+        let result = rule.evaluate(&state, when_result());
+        assert_eq!(DoResult::skip(SkipReason::NoQuote), result)
+    }
+
+    #[test]
+    fn evaluate_empty_when() {
+        let rule = DoShortRule::boxed(5.0);
+        let result = rule.evaluate(&state(), WhenResult::default());
+        assert_eq!(DoResult::skip(SkipReason::NoTargetAssets), result)
+    }
+
+    fn vti() -> AssetSymbol {
+        AssetSymbol::new("VTI")
+    }
+
+    fn state() -> TacticState {
+        let quote = Quote {
+            ask_price: 100.5,
+            bid_price: 100.0,
+            ..Quote::fixture(vti())
+        };
+        TacticState {
+            account: Account {
+                equity: 10000.0,
+                ..Default::default()
+            },
+            asset_info: HashMap::from([(vti(), AssetInfo::fixture(vti()))]),
+            open_orders: HashMap::new(),
+            positions: HashMap::new(),
+            quotes: HashMap::from([(vti(), quote)]),
+            ..TacticState::fixture()
+        }
+    }
+
+    fn when_result() -> WhenResult {
+        WhenResult {
+            conditions_satisfied: true,
+            target_assets: vec![TargetAsset::full_percent(vti())],
+        }
+    }
+}
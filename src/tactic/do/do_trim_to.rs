@@ -0,0 +1,164 @@
+use crate::float::{FloatAmountRounding, PercentOps};
+use crate::tactic::action::Action;
+use crate::tactic::r#do::{DoResult, DoRule};
+use crate::tactic::skip::SkipReason;
+use crate::tactic::state::TacticState;
+use crate::tactic::target::TargetAsset;
+use crate::tactic::when::WhenResult;
+
+/// Sells each position down to a target percent of equity, leaving positions under the target
+/// alone.
+pub struct DoTrimToRule {
+    trim_to_percent: f64,
+}
+
+impl DoTrimToRule {
+    pub fn boxed(trim_to_percent: f64) -> Box<dyn DoRule> {
+        Box::new(Self { trim_to_percent })
+    }
+
+    fn sell_quantity(&self, state: &TacticState, target_asset: &TargetAsset) -> f64 {
+        let Some(position) = state.long_position(&target_asset.symbol) else {
+            return 0.0;
+        };
+        let Some(price) = position.price().filter(|p| *p > 0.0) else {
+            return 0.0;
+        };
+        // The whole position counts toward the target, but shares held by open orders can't be
+        // sold until those orders are done.
+        let value = price * position.quantity;
+        let desired_value = state
+            .account
+            .equity
+            .percent_of(self.target_percent(state, target_asset));
+        let excess = value - desired_value;
+        if excess <= 0.0 {
+            return 0.0;
+        }
+        (excess / price)
+            .min(position.quantity_available)
+            .round_quantity_for(&target_asset.symbol)
+    }
+
+    fn target_percent(&self, state: &TacticState, target_asset: &TargetAsset) -> f64 {
+        let trim_percent =
+            self.trim_to_percent * (state.strategy_properties.portfolio_percent / 100.0);
+        (target_asset.percent * trim_percent) / 100.0
+    }
+}
+
+impl DoRule for DoTrimToRule {
+    fn evaluate(&self, state: &TacticState, when_result: WhenResult) -> DoResult {
+        let actions = when_result
+            .target_assets
+            .iter()
+            .map(|t| Action::sell_quantity(t.symbol.clone(), self.sell_quantity(state, t)))
+            .filter(|a| !a.is_empty())
+            .collect::<Vec<_>>();
+        let has_position = when_result
+            .target_assets
+            .iter()
+            .any(|t| state.positions.contains_key(&t.symbol));
+        let skip_reason = if when_result.target_assets.is_empty() {
+            SkipReason::NoTargetAssets
+        } else if !has_position {
+            SkipReason::NoPosition
+        } else {
+            SkipReason::TargetReached
+        };
+        DoResult::from_actions(actions, skip_reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asset::AssetSymbol;
+    use crate::config::strategy::StrategyProperties;
+    use crate::platform::position::Position;
+    use std::collections::HashMap;
+
+    // The fixture's equity is 500 and each position holds 50 shares, worth 300 at $6.
+
+    #[test]
+    fn evaluate() {
+        let rule = DoTrimToRule::boxed(20.0);
+        let result = rule.evaluate(&state(100.0), when_result());
+        // Trimming to $100 sells $200, 33.33 shares.
+        let expected = DoResult {
+            actions: vec![Action::sell_quantity(AssetSymbol::new("SPY"), 33.3333333)],
+            ..Default::default()
+        };
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn evaluate_portfolio_percent() {
+        let rule = DoTrimToRule::boxed(20.0);
+        let result = rule.evaluate(&state(50.0), when_result());
+        // Half the portfolio shrinks the target to $50, selling $250.
+        assert_eq!(
+            result.actions,
+            vec![Action::sell_quantity(AssetSymbol::new("SPY"), 41.6666666)]
+        )
+    }
+
+    #[test]
+    fn evaluate_shares_held_by_orders() {
+        let rule = DoTrimToRule::boxed(20.0);
+        let mut state = state(100.0);
+        let spy = AssetSymbol::new("SPY");
+        state.positions.get_mut(&spy).unwrap().quantity_available = 20.0;
+        let result = rule.evaluate(&state, when_result());
+        // All 50 shares count toward the target, but only the 20 available can be sold.
+        assert_eq!(result.actions, vec![Action::sell_quantity(spy, 20.0)])
+    }
+
+    #[test]
+    fn evaluate_under_target() {
+        let rule = DoTrimToRule::boxed(80.0);
+        let result = rule.evaluate(&state(100.0), when_result());
+        assert_eq!(DoResult::skip(SkipReason::TargetReached), result)
+    }
+
+    #[test]
+    fn evaluate_no_position() {
+        let rule = DoTrimToRule::boxed(20.0);
+        let state = TacticState {
+            positions: HashMap::new(),
+            ..state(100.0)
+        };
+        let result = rule.evaluate(&state, when_result());
+        assert_eq!(DoResult::skip(SkipReason::NoPosition), result)
+    }
+
+    #[test]
+    fn evaluate_empty_when() {
+        let rule = DoTrimToRule::boxed(20.0);
+        let result = rule.evaluate(&state(100.0), WhenResult::default());
+        assert_eq!(DoResult::skip(SkipReason::NoTargetAssets), result)
+    }
+
+    fn state(portfolio_percent: f64) -> TacticState {
+        let spy = AssetSymbol::new("SPY");
+        let position = Position {
+            current_price: Some(6.0),
+            ..Position::fixture(spy.clone())
+        };
+        TacticState {
+            positions: HashMap::from([(spy, position)]),
+            strategy_properties: StrategyProperties {
+                portfolio_percent,
+                ..Default::default()
+            },
+            ..TacticState::fixture()
+        }
+    }
+
+    fn when_result() -> WhenResult {
+        WhenResult {
+            conditions_satisfied: true,
+            target_assets: vec![TargetAsset::full_percent(AssetSymbol::new("SPY"))],
+        }
+    }
+}
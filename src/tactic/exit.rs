@@ -0,0 +1,592 @@
+use crate::asset::AssetSymbol;
+use crate::num::NumFromFloat;
+use crate::platform::asset_info::AssetInfo;
+use crate::platform::id::Id;
+use crate::platform::order::amount::Amount;
+use crate::platform::order::order_type::OrderType;
+use crate::platform::order::side::OrderSide;
+use crate::platform::order::time_in_force::TimeInForce;
+use crate::platform::order::Order;
+use crate::platform::position::Position;
+use crate::platform::request::replace::ReplaceRequest;
+use crate::platform::request::OrderRequest;
+use crate::tactic::order_tag::OrderTag;
+use itertools::Itertools;
+use num_decimal::Num;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+/// A change needed to keep an exit in line with its position.
+#[derive(Debug, PartialEq)]
+pub enum ExitOrder {
+    Cancel(Id),
+    Place(Box<OrderRequest>),
+    Replace(Id, ReplaceRequest),
+}
+
+impl Display for ExitOrder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExitOrder::Cancel(id) => write!(f, "cancel {id}"),
+            ExitOrder::Place(request) => write!(f, "place {request}"),
+            ExitOrder::Replace(id, request) => write!(f, "replace {id} with {request}"),
+        }
+    }
+}
+
+/// Keeps a broker-side trailing stop sell covering the shares the tactic bought of each long
+/// position, rounded down to whole shares unless the asset allows fractions. Only the tactic's own orders count, so shares bought by other tactics or by hand are
+/// left free, except for shares held since before the order history, which can't be told apart
+/// and are covered too. Fills show up in the order history on the next run, so a missing stop is
+/// placed and one out of line with the tactic's holding is resized, or canceled once there's
+/// nothing left to cover.
+pub struct TrailingStopExit {
+    tag: OrderTag,
+    trail_percent: f64,
+}
+
+impl TrailingStopExit {
+    pub fn new(trail_percent: f64, tag: OrderTag) -> Self {
+        Self { tag, trail_percent }
+    }
+
+    pub fn sync(
+        &self,
+        symbols: &[AssetSymbol],
+        positions: &HashMap<AssetSymbol, Position>,
+        open_orders: &HashMap<AssetSymbol, Vec<Order>>,
+        order_history: &[Order],
+        asset_info: &HashMap<AssetSymbol, AssetInfo>,
+    ) -> Vec<ExitOrder> {
+        let held = self.held_quantities(positions, order_history);
+        symbols
+            .iter()
+            .filter_map(|symbol| {
+                let stop = open_orders.get(symbol).and_then(|orders| self.stop(orders));
+                let covered = positions
+                    .get(symbol)
+                    .filter(|p| p.is_long())
+                    .map(|p| self.covered(p, stop, held.get(symbol), asset_info.get(symbol)))
+                    .unwrap_or_default();
+                self.sync_stop(symbol, covered, stop)
+            })
+            .collect()
+    }
+
+    /// Hands the shares held by the tactic's stops back to the rules, so its sells can use them
+    /// and its stops don't count as open orders. The stops have to be canceled before any sell.
+    pub fn release(
+        &self,
+        positions: &mut HashMap<AssetSymbol, Position>,
+        open_orders: &mut HashMap<AssetSymbol, Vec<Order>>,
+    ) {
+        for (symbol, orders) in open_orders.iter_mut() {
+            let (stops, others): (Vec<_>, Vec<_>) =
+                orders.drain(..).partition(|o| self.is_own_stop(o));
+            *orders = others;
+            if let Some(position) = positions.get_mut(symbol) {
+                position.quantity_available += stops.iter().map(Self::quantity).sum::<f64>();
+            }
+        }
+        open_orders.retain(|_, orders| !orders.is_empty());
+    }
+
+    /// The tactic's stops for `symbol` among `orders`.
+    pub fn stops_for(&self, symbol: &AssetSymbol, orders: &[Order]) -> Vec<Id> {
+        orders
+            .iter()
+            .filter(|o| &o.symbol == symbol && self.is_own_stop(o))
+            .map(|o| o.id.clone())
+            .collect()
+    }
+
+    fn stop<'a>(&self, orders: &'a [Order]) -> Option<&'a Order> {
+        orders.iter().find(|o| self.is_own_stop(o))
+    }
+
+    /// The shares the stop should cover. The stop holds its shares, so they aren't counted as
+    /// available, and shares the tactic has sold or has open sells for aren't covered.
+    fn covered(
+        &self,
+        position: &Position,
+        stop: Option<&Order>,
+        held: Option<&f64>,
+        info: Option<&AssetInfo>,
+    ) -> f64 {
+        let stop_quantity = stop.map(Self::quantity).unwrap_or_default();
+        let available = position.quantity_available + stop_quantity;
+        let covered = held.copied().unwrap_or_default().min(available);
+        AssetInfo::order_quantity(&position.symbol, info, covered)
+    }
+
+    fn sync_stop(
+        &self,
+        symbol: &AssetSymbol,
+        covered: f64,
+        stop: Option<&Order>,
+    ) -> Option<ExitOrder> {
+        match stop {
+            None if covered > 0.0 => {
+                Some(ExitOrder::Place(Box::new(self.request(symbol, covered))))
+            }
+            Some(order) if covered <= 0.0 => Some(ExitOrder::Cancel(order.id.clone())),
+            Some(order) if covered != Self::quantity(order) => {
+                let request = ReplaceRequest {
+                    quantity: Some(covered),
+                    ..Default::default()
+                };
+                Some(ExitOrder::Replace(order.id.clone(), request))
+            }
+            _ => None,
+        }
+    }
+
+    /// Nets the filled quantity of the tactic's orders for each asset, oldest first. Sells include
+    /// stops which triggered. The netting starts from the long shares the order history doesn't
+    /// account for, and never drops below zero, so sells of shares bought before the history don't
+    /// eat into later buys.
+    fn held_quantities(
+        &self,
+        positions: &HashMap<AssetSymbol, Position>,
+        order_history: &[Order],
+    ) -> HashMap<AssetSymbol, f64> {
+        let mut held: HashMap<AssetSymbol, f64> = positions
+            .iter()
+            .filter(|(_, position)| position.is_long())
+            .map(|(symbol, position)| (symbol.clone(), position.quantity))
+            .collect();
+        for order in order_history {
+            *held.entry(order.symbol.clone()).or_default() -= Self::filled(order);
+        }
+        held.values_mut()
+            .for_each(|quantity| *quantity = quantity.max(0.0));
+        let own_fills = order_history
+            .iter()
+            .filter(|o| self.tag.matches(o))
+            .sorted_by_key(|o| o.filled_at);
+        for order in own_fills {
+            let quantity = held.entry(order.symbol.clone()).or_default();
+            *quantity = (*quantity + Self::filled(order)).max(0.0);
+        }
+        held
+    }
+
+    /// The filled quantity of `order`, negative for a sell.
+    fn filled(order: &Order) -> f64 {
+        let filled = order.filled_quantity.to_f64().unwrap_or_default();
+        match order.side {
+            OrderSide::Buy => filled,
+            OrderSide::Sell => -filled,
+        }
+    }
+
+    fn request(&self, symbol: &AssetSymbol, quantity: f64) -> OrderRequest {
+        OrderRequest {
+            order_type: OrderType::TrailingStop,
+            time_in_force: TimeInForce::UntilCanceled,
+            trail_percent: Some(Num::from_f64(self.trail_percent)),
+            ..OrderRequest::market_order_sell(symbol.clone(), Amount::Quantity(quantity))
+        }
+    }
+
+    fn is_own_stop(&self, order: &Order) -> bool {
+        order.side == OrderSide::Sell
+            && order.order_type == OrderType::TrailingStop
+            && self.tag.matches(order)
+    }
+
+    fn quantity(order: &Order) -> f64 {
+        match order.amount {
+            Amount::Quantity(quantity) => quantity,
+            Amount::Notional(_) => 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date::DateTimeFixture;
+    use crate::platform::side::Side;
+    use chrono::Duration;
+    use uuid::Uuid;
+
+    #[test]
+    fn sync_places_stop() {
+        let exit = exit();
+        let history = vec![buy(10.5)];
+        let orders = exit.sync(
+            &[vti()],
+            &positions(10.5, 10.5),
+            &HashMap::new(),
+            &history,
+            &HashMap::new(),
+        );
+        let expected = OrderRequest {
+            order_type: OrderType::TrailingStop,
+            time_in_force: TimeInForce::UntilCanceled,
+            trail_percent: Some(Num::from(7)),
+            ..OrderRequest::market_order_sell(vti(), Amount::Quantity(10.0))
+        };
+        assert_eq!(orders, vec![ExitOrder::Place(Box::new(expected))]);
+    }
+
+    #[test]
+    fn sync_covers_only_own_buys() {
+        let exit = exit();
+        // Another 20 shares were bought by hand.
+        let history = vec![
+            buy(10.0),
+            Order {
+                client_order_id: None,
+                ..buy(20.0)
+            },
+        ];
+        let orders = exit.sync(
+            &[vti()],
+            &positions(30.0, 30.0),
+            &HashMap::new(),
+            &history,
+            &HashMap::new(),
+        );
+        let expected = exit.request(&vti(), 10.0);
+        assert_eq!(orders, vec![ExitOrder::Place(Box::new(expected))]);
+    }
+
+    #[test]
+    fn sync_grows_stop() {
+        let exit = exit();
+        let stop = stop(10.0);
+        let open_orders = HashMap::from([(vti(), vec![stop.clone()])]);
+        // Another 5 shares were bought since the stop was placed.
+        let history = vec![buy(10.0), buy(5.0)];
+        let orders = exit.sync(
+            &[vti()],
+            &positions(15.0, 5.0),
+            &open_orders,
+            &history,
+            &HashMap::new(),
+        );
+        let expected = ReplaceRequest {
+            quantity: Some(15.0),
+            ..Default::default()
+        };
+        assert_eq!(orders, vec![ExitOrder::Replace(stop.id, expected)]);
+    }
+
+    #[test]
+    fn sync_shrinks_stop() {
+        let exit = exit();
+        let stop = stop(10.0);
+        let open_orders = HashMap::from([(vti(), vec![stop.clone()])]);
+        // The tactic sold 4 shares since the stop was placed.
+        let history = vec![buy(10.0), sell(4.0)];
+        let orders = exit.sync(
+            &[vti()],
+            &positions(6.0, 0.0),
+            &open_orders,
+            &history,
+            &HashMap::new(),
+        );
+        let expected = ReplaceRequest {
+            quantity: Some(6.0),
+            ..Default::default()
+        };
+        assert_eq!(orders, vec![ExitOrder::Replace(stop.id, expected)]);
+    }
+
+    #[test]
+    fn sync_cancels_stop_when_sold() {
+        let exit = exit();
+        let stop = stop(10.0);
+        let open_orders = HashMap::from([(vti(), vec![stop.clone()])]);
+        let history = vec![buy(10.0), sell(10.0)];
+        let orders = exit.sync(
+            &[vti()],
+            &HashMap::new(),
+            &open_orders,
+            &history,
+            &HashMap::new(),
+        );
+        assert_eq!(orders, vec![ExitOrder::Cancel(stop.id)]);
+    }
+
+    #[test]
+    fn sync_in_line() {
+        let exit = exit();
+        let open_orders = HashMap::from([(vti(), vec![stop(10.0)])]);
+        let history = vec![buy(10.5)];
+        let orders = exit.sync(
+            &[vti()],
+            &positions(10.5, 0.5),
+            &open_orders,
+            &history,
+            &HashMap::new(),
+        );
+        assert!(orders.is_empty());
+    }
+
+    #[test]
+    fn sync_ignores_other_stops() {
+        let exit = exit();
+        let other = Order {
+            client_order_id: None,
+            ..stop(10.0)
+        };
+        let open_orders = HashMap::from([(vti(), vec![other])]);
+        let history = vec![buy(5.0)];
+        let orders = exit.sync(
+            &[vti()],
+            &positions(15.0, 5.0),
+            &open_orders,
+            &history,
+            &HashMap::new(),
+        );
+        let expected = exit.request(&vti(), 5.0);
+        assert_eq!(orders, vec![ExitOrder::Place(Box::new(expected))]);
+    }
+
+    #[test]
+    fn sync_covers_shares_held_before_history() {
+        let exit = exit();
+        let orders = exit.sync(
+            &[vti()],
+            &positions(20.0, 20.0),
+            &HashMap::new(),
+            &[],
+            &HashMap::new(),
+        );
+        let expected = exit.request(&vti(), 20.0);
+        assert_eq!(orders, vec![ExitOrder::Place(Box::new(expected))]);
+    }
+
+    #[test]
+    fn sync_covers_shares_held_before_history_partly_sold() {
+        let exit = exit();
+        // 20 older shares, 5 of which the tactic sold before buying 2 more.
+        let history = vec![filled_on(sell(5.0), 1), filled_on(buy(2.0), 2)];
+        let orders = exit.sync(
+            &[vti()],
+            &positions(17.0, 17.0),
+            &HashMap::new(),
+            &history,
+            &HashMap::new(),
+        );
+        let expected = exit.request(&vti(), 17.0);
+        assert_eq!(orders, vec![ExitOrder::Place(Box::new(expected))]);
+    }
+
+    #[test]
+    fn sync_sells_of_others_shares_dont_go_negative() {
+        let exit = exit();
+        // The tactic sold 10 shares bought by hand, then bought 5 of its own.
+        let history = vec![
+            filled_on(
+                Order {
+                    client_order_id: None,
+                    ..buy(10.0)
+                },
+                1,
+            ),
+            filled_on(sell(10.0), 2),
+            filled_on(buy(5.0), 3),
+        ];
+        let orders = exit.sync(
+            &[vti()],
+            &positions(5.0, 5.0),
+            &HashMap::new(),
+            &history,
+            &HashMap::new(),
+        );
+        let expected = exit.request(&vti(), 5.0);
+        assert_eq!(orders, vec![ExitOrder::Place(Box::new(expected))]);
+    }
+
+    #[test]
+    fn sync_fractional_shares() {
+        let exit = exit();
+        let history = vec![buy(10.5)];
+        let asset_info = HashMap::from([(vti(), AssetInfo::fixture(vti()))]);
+        let orders = exit.sync(
+            &[vti()],
+            &positions(10.5, 10.5),
+            &HashMap::new(),
+            &history,
+            &asset_info,
+        );
+        let expected = exit.request(&vti(), 10.5);
+        assert_eq!(orders, vec![ExitOrder::Place(Box::new(expected))]);
+    }
+
+    #[test]
+    fn sync_crypto() {
+        let exit = exit();
+        let btc = AssetSymbol::new("BTC/USD");
+        let history = vec![Order {
+            symbol: btc.clone(),
+            ..buy(0.25)
+        }];
+        let position = Position {
+            quantity: 0.25,
+            quantity_available: 0.25,
+            ..Position::fixture(btc.clone())
+        };
+        let positions = HashMap::from([(btc.clone(), position)]);
+        let orders = exit.sync(
+            std::slice::from_ref(&btc),
+            &positions,
+            &HashMap::new(),
+            &history,
+            &HashMap::new(),
+        );
+        let expected = exit.request(&btc, 0.25);
+        assert_eq!(orders, vec![ExitOrder::Place(Box::new(expected))]);
+    }
+
+    #[test]
+    fn sync_fractional_position() {
+        let exit = exit();
+        let history = vec![buy(0.5)];
+        let orders = exit.sync(
+            &[vti()],
+            &positions(0.5, 0.5),
+            &HashMap::new(),
+            &history,
+            &HashMap::new(),
+        );
+        assert!(orders.is_empty());
+    }
+
+    #[test]
+    fn sync_short_position() {
+        let exit = exit();
+        let short = Position {
+            side: Side::Short,
+            ..position(10.0, 10.0)
+        };
+        let positions = HashMap::from([(vti(), short)]);
+        let orders = exit.sync(
+            &[vti()],
+            &positions,
+            &HashMap::new(),
+            &[buy(10.0)],
+            &HashMap::new(),
+        );
+        assert!(orders.is_empty());
+    }
+
+    #[test]
+    fn sync_no_position() {
+        let exit = exit();
+        let orders = exit.sync(
+            &[vti()],
+            &HashMap::new(),
+            &HashMap::new(),
+            &[],
+            &HashMap::new(),
+        );
+        assert!(orders.is_empty());
+    }
+
+    #[test]
+    fn release() {
+        let exit = exit();
+        let other = Order::fixture(vti());
+        let mut positions = positions(10.5, 0.5);
+        let mut open_orders = HashMap::from([(vti(), vec![stop(10.0), other.clone()])]);
+        exit.release(&mut positions, &mut open_orders);
+        assert_eq!(positions[&vti()].quantity_available, 10.5);
+        assert_eq!(open_orders, HashMap::from([(vti(), vec![other])]));
+    }
+
+    #[test]
+    fn release_drops_emptied_assets() {
+        let exit = exit();
+        let mut positions = positions(10.0, 0.0);
+        let mut open_orders = HashMap::from([(vti(), vec![stop(10.0)])]);
+        exit.release(&mut positions, &mut open_orders);
+        assert!(open_orders.is_empty());
+    }
+
+    #[test]
+    fn stops_for() {
+        let exit = exit();
+        let stop = stop(10.0);
+        let orders = vec![
+            stop.clone(),
+            Order::fixture(vti()),
+            Order {
+                symbol: AssetSymbol::new("SPY"),
+                ..stop.clone()
+            },
+        ];
+        assert_eq!(exit.stops_for(&vti(), &orders), vec![stop.id]);
+    }
+
+    #[test]
+    fn display() {
+        let request = exit().request(&vti(), 2.0);
+        let place = ExitOrder::Place(Box::new(request));
+        assert_eq!(
+            place.to_string(),
+            "place trailingstop sell 2.00 units of VTI"
+        );
+    }
+
+    fn exit() -> TrailingStopExit {
+        TrailingStopExit::new(7.0, tag())
+    }
+
+    fn tag() -> OrderTag {
+        OrderTag::new("strategy", "tactic")
+    }
+
+    fn vti() -> AssetSymbol {
+        AssetSymbol::new("VTI")
+    }
+
+    fn position(quantity: f64, quantity_available: f64) -> Position {
+        Position {
+            quantity,
+            quantity_available,
+            ..Position::fixture(vti())
+        }
+    }
+
+    fn positions(quantity: f64, quantity_available: f64) -> HashMap<AssetSymbol, Position> {
+        HashMap::from([(vti(), position(quantity, quantity_available))])
+    }
+
+    fn stop(quantity: f64) -> Order {
+        tag().order(Order {
+            id: Id::Uuid(Uuid::from_u128(1)),
+            amount: Amount::Quantity(quantity),
+            order_type: OrderType::TrailingStop,
+            side: OrderSide::Sell,
+            ..Order::fixture(vti())
+        })
+    }
+
+    fn buy(quantity: f64) -> Order {
+        tag().order(Order {
+            amount: Amount::Quantity(quantity),
+            filled_quantity: Num::from_f64(quantity),
+            side: OrderSide::Buy,
+            ..Order::fixture(vti())
+        })
+    }
+
+    fn filled_on(order: Order, day: i64) -> Order {
+        Order {
+            filled_at: Some(DateTimeFixture::utc() + Duration::days(day)),
+            ..order
+        }
+    }
+
+    fn sell(quantity: f64) -> Order {
+        Order {
+            side: OrderSide::Sell,
+            ..buy(quantity)
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use crate::tactic::rule::RuleTrace;
+use crate::tactic::when::WhenTrace;
+use itertools::Itertools;
+use std::fmt::{Display, Formatter};
+
+/// Why each of a tactic's rule sets acted or skipped during a single evaluation.
+#[derive(Debug, Default, PartialEq)]
+pub struct TacticExplanation {
+    pub name: String,
+    pub buy: RuleTrace,
+    pub sell: RuleTrace,
+}
+
+impl Display for TacticExplanation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "📋 tactic: {}", self.name)?;
+        write_rule_trace(f, "buy", &self.buy)?;
+        write_rule_trace(f, "sell", &self.sell)
+    }
+}
+
+fn write_rule_trace(f: &mut Formatter<'_>, name: &str, trace: &RuleTrace) -> std::fmt::Result {
+    writeln!(f, "  {name}:")?;
+    let targets = trace
+        .target_assets
+        .iter()
+        .map(|t| format!("{} ({:.2}%)", t.symbol, t.percent))
+        .join(", ");
+    if targets.is_empty() {
+        writeln!(f, "    for: no assets")?;
+    } else {
+        writeln!(f, "    for: {targets}")?;
+    }
+    if let Some(when_trace) = &trace.when_trace {
+        write_when_trace(f, when_trace, 2)?;
+    }
+    if trace.result.skipped {
+        writeln!(f, "    do: skipped - {}", trace.result.skip_reason)
+    } else {
+        for action in &trace.result.actions {
+            writeln!(f, "    do: {action}")?;
+        }
+        Ok(())
+    }
+}
+
+fn write_when_trace(f: &mut Formatter<'_>, trace: &WhenTrace, depth: usize) -> std::fmt::Result {
+    let indent = "  ".repeat(depth);
+    writeln!(f, "{indent}when: {}", trace.description)?;
+    for verdict in &trace.assets {
+        let icon = if verdict.satisfied { "✅" } else { "❌" };
+        match &verdict.detail {
+            Some(detail) => writeln!(f, "{indent}  {icon} {} - {detail}", verdict.symbol)?,
+            None => writeln!(f, "{indent}  {icon} {}", verdict.symbol)?,
+        }
+    }
+    for child in &trace.children {
+        write_when_trace(f, child, depth + 1)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asset::AssetSymbol;
+    use crate::tactic::action::Action;
+    use crate::tactic::r#do::DoResult;
+    use crate::tactic::skip::SkipReason;
+    use crate::tactic::target::TargetAsset;
+    use crate::tactic::when::AssetVerdict;
+
+    #[test]
+    fn display() {
+        let spy = AssetSymbol::new("SPY");
+        let explanation = TacticExplanation {
+            name: "Spy".to_string(),
+            buy: RuleTrace {
+                target_assets: vec![TargetAsset::full_percent(spy.clone())],
+                when_trace: Some(WhenTrace {
+                    description: "all of".to_string(),
+                    assets: vec![AssetVerdict::new(spy.clone(), true, None)],
+                    children: vec![WhenTrace {
+                        description: "gain above 5.00%".to_string(),
+                        assets: vec![AssetVerdict::new(
+                            spy.clone(),
+                            true,
+                            Some("gain is 10.00%".to_string()),
+                        )],
+                        ..Default::default()
+                    }],
+                }),
+                result: DoResult {
+                    actions: vec![Action::buy_notional(spy, 10.0)],
+                    ..Default::default()
+                },
+            },
+            sell: RuleTrace {
+                result: DoResult::skip(SkipReason::NoTargetAssets),
+                ..Default::default()
+            },
+        };
+        let expected = "📋 tactic: Spy
+  buy:
+    for: SPY (100.00%)
+    when: all of
+      ✅ SPY
+      when: gain above 5.00%
+        ✅ SPY - gain is 10.00%
+    do: 💸buy SPY - amount: $10.00
+  sell:
+    for: no assets
+    do: skipped - no target assets
+";
+        assert_eq!(explanation.to_string(), expected)
+    }
+}
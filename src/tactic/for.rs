@@ -2,8 +2,11 @@ mod for_all_other_positions;
 mod for_any;
 pub mod for_factory;
 pub mod for_stock;
+mod for_weights;
 pub mod other_positions_filter;
 
+#[cfg(test)]
+use crate::asset::AssetSymbol;
 use crate::tactic::state::TacticState;
 use crate::tactic::target::TargetAsset;
 use std::fmt::Debug;
@@ -28,6 +31,17 @@ impl ForResult {
             target_assets: vec![TargetAsset::fixture()],
         }
     }
+
+    /// Targets each of `symbols` with its full percent.
+    #[cfg(test)]
+    pub fn full_percent_fixture(symbols: &[&str]) -> Self {
+        Self {
+            target_assets: symbols
+                .iter()
+                .map(|symbol| TargetAsset::full_percent(AssetSymbol::new(symbol)))
+                .collect(),
+        }
+    }
 }
 
 #[cfg(test)]
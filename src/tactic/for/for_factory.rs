@@ -1,8 +1,10 @@
+use crate::asset::AssetSymbol;
 use crate::config::tactic::r#for::ForConfig;
 use crate::tactic::null::NullRule;
 use crate::tactic::r#for::for_all_other_positions::ForAllOtherPositionsRule;
 use crate::tactic::r#for::for_any::ForAnyStockRule;
 use crate::tactic::r#for::for_stock::ForStockRule;
+use crate::tactic::r#for::for_weights::ForWeightsRule;
 use crate::tactic::r#for::ForRule;
 
 pub struct ForFactory;
@@ -12,8 +14,12 @@ impl ForFactory {
         match config {
             ForConfig::AllOtherPositions { .. } => ForAllOtherPositionsRule::boxed(),
             ForConfig::AnyOf { any_of } => ForAnyStockRule::boxed(any_of),
+            ForConfig::Crypto { crypto } => {
+                ForStockRule::boxed(AssetSymbol::crypto(&crypto.symbol))
+            }
             ForConfig::Nothing { .. } => NullRule::for_boxed(),
             ForConfig::Stock { stock } => ForStockRule::boxed(stock),
+            ForConfig::Weights { weights } => ForWeightsRule::boxed(weights),
         }
     }
 }
@@ -0,0 +1,102 @@
+use crate::asset::AssetSymbol;
+use crate::tactic::r#for::{ForResult, ForRule};
+use crate::tactic::state::TacticState;
+use crate::tactic::target::TargetAsset;
+use itertools::Itertools;
+use std::collections::HashMap;
+
+/// Targets each asset at its share of the total declared weight.
+#[derive(Debug, Default, PartialEq)]
+pub struct ForWeightsRule {
+    weights: HashMap<AssetSymbol, f64>,
+}
+
+impl ForWeightsRule {
+    pub fn boxed(weights: HashMap<AssetSymbol, f64>) -> Box<dyn ForRule> {
+        Box::new(Self { weights })
+    }
+}
+
+impl ForRule for ForWeightsRule {
+    fn evaluate(&self, _state: &TacticState) -> ForResult {
+        let total = self.weights.values().filter(|w| **w > 0.0).sum::<f64>();
+        if total <= 0.0 {
+            return ForResult::default();
+        }
+        let target_assets = self
+            .weights
+            .iter()
+            .filter(|(_, weight)| **weight > 0.0)
+            .sorted_by(|(a, _), (b, _)| a.symbol.cmp(&b.symbol))
+            .map(|(symbol, weight)| TargetAsset {
+                symbol: symbol.clone(),
+                percent: weight / total * 100.0,
+            })
+            .collect();
+        ForResult { target_assets }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evaluate() {
+        let state: TacticState = Default::default();
+        let rule = ForWeightsRule::boxed(HashMap::from([
+            (AssetSymbol::new("VTI"), 60.0),
+            (AssetSymbol::new("VXUS"), 30.0),
+            (AssetSymbol::new("BND"), 10.0),
+        ]));
+        let result = rule.evaluate(&state);
+        let expected = ForResult {
+            target_assets: vec![
+                TargetAsset {
+                    symbol: AssetSymbol::new("BND"),
+                    percent: 10.0,
+                },
+                TargetAsset {
+                    symbol: AssetSymbol::new("VTI"),
+                    percent: 60.0,
+                },
+                TargetAsset {
+                    symbol: AssetSymbol::new("VXUS"),
+                    percent: 30.0,
+                },
+            ],
+        };
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn evaluate_normalizes_weights() {
+        let state: TacticState = Default::default();
+        let rule = ForWeightsRule::boxed(HashMap::from([
+            (AssetSymbol::new("VTI"), 3.0),
+            (AssetSymbol::new("BND"), 1.0),
+            (AssetSymbol::new("VXUS"), 0.0),
+        ]));
+        let result = rule.evaluate(&state);
+        let expected = ForResult {
+            target_assets: vec![
+                TargetAsset {
+                    symbol: AssetSymbol::new("BND"),
+                    percent: 25.0,
+                },
+                TargetAsset {
+                    symbol: AssetSymbol::new("VTI"),
+                    percent: 75.0,
+                },
+            ],
+        };
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn evaluate_empty_weights() {
+        let state: TacticState = Default::default();
+        let rule = ForWeightsRule::boxed(HashMap::new());
+        assert_eq!(ForResult::default(), rule.evaluate(&state))
+    }
+}
@@ -16,6 +16,10 @@ impl WhenRule for NullRule {
     fn evaluate(&self, _: &TacticState, _: ForResult) -> WhenResult {
         Default::default()
     }
+
+    fn description(&self) -> String {
+        "never".to_string()
+    }
 }
 
 impl DoRule for NullRule {
@@ -0,0 +1,80 @@
+use crate::platform::order::Order;
+use uuid::Uuid;
+
+const PREFIX: &str = "greed";
+
+/// Marks the client order ids of a tactic's orders, so its own orders can be told apart from
+/// other orders for the same assets, whether they were placed by another tactic or by hand.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OrderTag {
+    prefix: String,
+}
+
+impl OrderTag {
+    /// Alpaca limits client order ids to 128 characters, so names are hashed rather than used
+    /// as is. The hash has to stay the same between builds since tags outlive the process.
+    pub fn new(strategy: &str, tactic: &str) -> Self {
+        let hash = fnv1a(&format!("{strategy}/{tactic}"));
+        Self {
+            prefix: format!("{PREFIX}-{hash:016x}-"),
+        }
+    }
+
+    /// A new client order id carrying this tag. Each one is unique, as brokers require.
+    pub fn client_order_id(&self) -> String {
+        format!("{}{}", self.prefix, Uuid::new_v4().simple())
+    }
+
+    pub fn matches(&self, order: &Order) -> bool {
+        order
+            .client_order_id
+            .as_ref()
+            .is_some_and(|id| id.starts_with(&self.prefix))
+    }
+
+    #[cfg(test)]
+    pub fn order(&self, order: Order) -> Order {
+        Order {
+            client_order_id: Some(self.client_order_id()),
+            ..order
+        }
+    }
+}
+
+/// 64 bit FNV-1a, which unlike std's hasher is stable across Rust releases.
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asset::AssetSymbol;
+
+    #[test]
+    fn client_order_id() {
+        let tag = OrderTag::new("strategy", "tactic");
+        let id = tag.client_order_id();
+        assert!(id.starts_with("greed-"));
+        assert!(id.len() <= 128);
+        assert_ne!(id, tag.client_order_id());
+    }
+
+    #[test]
+    fn matches() {
+        let tag = OrderTag::new("strategy", "tactic");
+        let other = OrderTag::new("strategy", "other");
+        let order = tag.order(Order::fixture(AssetSymbol::new("VTI")));
+        assert!(tag.matches(&order));
+        assert!(!other.matches(&order));
+        assert!(!tag.matches(&Order::fixture(AssetSymbol::new("VTI"))));
+    }
+
+    #[test]
+    fn fnv1a_is_stable() {
+        assert_eq!(fnv1a(""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a("a"), 0xaf63dc4c8601ec8c);
+    }
+}
@@ -8,8 +8,9 @@ use crate::tactic::r#for::for_factory::ForFactory;
 use crate::tactic::r#for::ForRule;
 use crate::tactic::skip::SkipReason;
 use crate::tactic::state::TacticState;
+use crate::tactic::target::TargetAsset;
 use crate::tactic::when::when_factory::WhenFactory;
-use crate::tactic::when::WhenRule;
+use crate::tactic::when::{WhenRule, WhenTrace};
 
 pub struct RuleSet {
     for_rule: Box<dyn ForRule>,
@@ -36,21 +37,43 @@ impl RuleSet {
         }
     }
 
-    pub fn evaluate(&self, state: &TacticState) -> DoResult {
+    /// Evaluates the rules, keeping the target assets alongside the result. The when rule is only
+    /// traced when `trace_when` is set, since tracing checks its conditions a second time.
+    pub fn evaluate(&self, state: &TacticState, trace_when: bool) -> RuleTrace {
         let for_result = self.for_rule.evaluate(state);
         if for_result.is_empty() {
-            return DoResult::skip(SkipReason::NoTargetAssets);
+            return RuleTrace {
+                result: DoResult::skip(SkipReason::NoTargetAssets),
+                ..Default::default()
+            };
         }
 
+        let target_assets = for_result.target_assets.clone();
+        let when_trace = trace_when.then(|| self.when_rule.trace(state, for_result.clone()));
         let when_result = self.when_rule.evaluate(state, for_result);
-        if !when_result.conditions_satisfied {
-            return DoResult::skip(SkipReason::ConditionsUnsatisfied);
+        let result = if when_result.conditions_satisfied {
+            self.do_rule.evaluate(state, when_result)
+        } else {
+            DoResult::skip(SkipReason::ConditionsUnsatisfied)
+        };
+        RuleTrace {
+            target_assets,
+            when_trace,
+            result,
         }
-
-        self.do_rule.evaluate(state, when_result)
     }
 }
 
+/// Each step of a rule set evaluation.
+#[derive(Debug, Default, PartialEq)]
+pub struct RuleTrace {
+    pub target_assets: Vec<TargetAsset>,
+    /// The when rule trace. This is missing when it wasn't asked for or the for rule didn't target
+    /// any assets.
+    pub when_trace: Option<WhenTrace>,
+    pub result: DoResult,
+}
+
 pub struct TacticRuleset {
     pub buy: RuleSet,
     pub sell: RuleSet,
@@ -75,9 +98,11 @@ lowercase_enum_display!(RuleType);
 
 #[cfg(test)]
 mod tests {
+    use crate::asset::AssetSymbol;
     use crate::tactic::r#do::do_sellall::DoSellAllRule;
     use crate::tactic::r#for::for_stock::ForStockRule;
     use crate::tactic::when::when_always::WhenAlwaysRule;
+    use crate::tactic::when::AssetVerdict;
 
     use super::*;
 
@@ -85,7 +110,7 @@ mod tests {
     fn evaluate_skip_no_assets() {
         let rule_set = RuleSet::default();
         let state = TacticState::default();
-        let result = rule_set.evaluate(&state);
+        let result = rule_set.evaluate(&state, false).result;
         assert_eq!(DoResult::skip(SkipReason::NoTargetAssets), result);
     }
 
@@ -96,7 +121,7 @@ mod tests {
             ..Default::default()
         };
         let state = TacticState::default();
-        let result = rule_set.evaluate(&state);
+        let result = rule_set.evaluate(&state, false).result;
         assert_eq!(DoResult::skip(SkipReason::ConditionsUnsatisfied), result);
     }
 
@@ -108,12 +133,57 @@ mod tests {
             do_rule: DoSellAllRule::boxed(),
         };
         let state = TacticState::default();
-        let result = rule_set.evaluate(&state);
-        let expected = DoResult {
-            actions: vec![],
-            skipped: true,
-            skip_reason: SkipReason::NoTargetAssets,
-        };
+        let result = rule_set.evaluate(&state, false).result;
+        let expected = DoResult::skip(SkipReason::NoPosition);
         assert_eq!(expected, result);
     }
+
+    #[test]
+    fn evaluate_untraced() {
+        let rule_set = RuleSet {
+            for_rule: ForStockRule::boxed("SPY"),
+            ..Default::default()
+        };
+        let state = TacticState::default();
+        let trace = rule_set.evaluate(&state, false);
+        let expected = RuleTrace {
+            target_assets: vec![TargetAsset::full_percent(AssetSymbol::new("SPY"))],
+            when_trace: None,
+            result: DoResult::skip(SkipReason::ConditionsUnsatisfied),
+        };
+        assert_eq!(expected, trace);
+    }
+
+    #[test]
+    fn explain_no_assets() {
+        let rule_set = RuleSet::default();
+        let state = TacticState::default();
+        let trace = rule_set.evaluate(&state, true);
+        let expected = RuleTrace {
+            result: DoResult::skip(SkipReason::NoTargetAssets),
+            ..Default::default()
+        };
+        assert_eq!(expected, trace);
+    }
+
+    #[test]
+    fn explain_conditions_unsatisfied() {
+        let rule_set = RuleSet {
+            for_rule: ForStockRule::boxed("SPY"),
+            ..Default::default()
+        };
+        let state = TacticState::default();
+        let trace = rule_set.evaluate(&state, true);
+        let spy = AssetSymbol::new("SPY");
+        let expected = RuleTrace {
+            target_assets: vec![TargetAsset::full_percent(spy.clone())],
+            when_trace: Some(WhenTrace {
+                description: "never".to_string(),
+                assets: vec![AssetVerdict::new(spy, false, None)],
+                ..Default::default()
+            }),
+            result: DoResult::skip(SkipReason::ConditionsUnsatisfied),
+        };
+        assert_eq!(expected, trace);
+    }
 }
@@ -2,8 +2,24 @@ use std::fmt::Display;
 
 #[derive(Debug, Default, PartialEq)]
 pub enum SkipReason {
+    /// The order would be smaller than the broker accepts for the asset.
+    BelowMinOrderSize,
     ConditionsUnsatisfied,
+    /// There wasn't any cash left to buy with.
+    InsufficientCash,
+    /// The asset is only traded in whole shares and the amount doesn't cover one.
+    LessThanOneShare,
+    /// There wasn't a position to sell.
+    NoPosition,
+    /// There wasn't a quote to price the order with.
+    NoQuote,
+    /// There wasn't a short position to cover.
+    NoShortPosition,
     NoTargetAssets,
+    /// The broker doesn't have shares to borrow for a short sale.
+    NotShortable,
+    /// Positions and open orders already meet the desired allocation.
+    TargetReached,
     #[default]
     Unknown,
 }
@@ -11,8 +27,16 @@ pub enum SkipReason {
 impl SkipReason {
     pub fn as_str(&self) -> &'static str {
         match self {
+            SkipReason::BelowMinOrderSize => "below the minimum order size",
             SkipReason::ConditionsUnsatisfied => "when conditions were unsatisfied",
+            SkipReason::InsufficientCash => "insufficient cash",
+            SkipReason::LessThanOneShare => "less than a whole share",
+            SkipReason::NoPosition => "no position to sell",
+            SkipReason::NoQuote => "no quote to price the order",
+            SkipReason::NoShortPosition => "no short position to cover",
             SkipReason::NoTargetAssets => "no target assets",
+            SkipReason::NotShortable => "no shares available to borrow",
+            SkipReason::TargetReached => "target allocation already reached",
             SkipReason::Unknown => "unknown",
         }
     }
@@ -28,6 +52,14 @@ impl Display for SkipReason {
 mod tests {
     use super::*;
 
+    #[test]
+    fn as_str_below_min_order_size() {
+        assert_eq!(
+            "below the minimum order size",
+            SkipReason::BelowMinOrderSize.as_str()
+        )
+    }
+
     #[test]
     fn as_str_conditions_unsatisfied() {
         assert_eq!(
@@ -36,6 +68,53 @@ mod tests {
         )
     }
 
+    #[test]
+    fn as_str_insufficient_cash() {
+        assert_eq!("insufficient cash", SkipReason::InsufficientCash.as_str())
+    }
+
+    #[test]
+    fn as_str_less_than_one_share() {
+        assert_eq!(
+            "less than a whole share",
+            SkipReason::LessThanOneShare.as_str()
+        )
+    }
+
+    #[test]
+    fn as_str_no_position() {
+        assert_eq!("no position to sell", SkipReason::NoPosition.as_str())
+    }
+
+    #[test]
+    fn as_str_no_quote() {
+        assert_eq!("no quote to price the order", SkipReason::NoQuote.as_str())
+    }
+
+    #[test]
+    fn as_str_no_short_position() {
+        assert_eq!(
+            "no short position to cover",
+            SkipReason::NoShortPosition.as_str()
+        )
+    }
+
+    #[test]
+    fn as_str_not_shortable() {
+        assert_eq!(
+            "no shares available to borrow",
+            SkipReason::NotShortable.as_str()
+        )
+    }
+
+    #[test]
+    fn as_str_target_reached() {
+        assert_eq!(
+            "target allocation already reached",
+            SkipReason::TargetReached.as_str()
+        )
+    }
+
     #[test]
     fn as_str_no_target_assets() {
         assert_eq!("no target assets", SkipReason::NoTargetAssets.as_str())
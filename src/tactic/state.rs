@@ -1,3 +1,5 @@
+use chrono::{DateTime, Utc};
+use itertools::Itertools;
 use std::collections::HashMap;
 use std::rc::Rc;
 
@@ -5,9 +7,17 @@ use crate::analysis::result::BarsResult;
 use crate::asset::AssetSymbol;
 use crate::config::strategy::StrategyProperties;
 use crate::platform::account::Account;
+use crate::platform::asset_info::AssetInfo;
+use crate::platform::calendar::MarketDay;
+use crate::platform::order::side::OrderSide;
+use crate::platform::order::status::Status;
 use crate::platform::order::Order;
 use crate::platform::position::Position;
 use crate::platform::quote::Quote;
+use crate::tactic::order_tag::OrderTag;
+
+#[cfg(test)]
+use crate::date::DateTimeFixture;
 
 #[derive(Default, PartialEq)]
 pub struct TacticState {
@@ -18,6 +28,21 @@ pub struct TacticState {
     pub quotes: HashMap<AssetSymbol, Quote>,
     pub strategy_properties: StrategyProperties,
     pub all_assets: Vec<AssetSymbol>,
+    /// When each current position was opened. Only populated for tactics with rules which need it.
+    pub entry_times: HashMap<AssetSymbol, DateTime<Utc>>,
+    /// When this tactic last traded each asset, by the fill time of its latest order or the time it
+    /// was placed while it's still working. Only populated for tactics with rules which need it.
+    pub last_order_times: HashMap<AssetSymbol, DateTime<Utc>>,
+    /// When this tactic last placed a buy order for each asset which filled or is still working.
+    /// Only populated for tactics with rules which need it.
+    pub last_buy_times: HashMap<AssetSymbol, DateTime<Utc>>,
+    /// The time the state was built, as seen by the platform.
+    pub now: DateTime<Utc>,
+    /// Today's trading session. Only populated for tactics with rules which need it.
+    pub market_day: Option<MarketDay>,
+    /// Whether each asset can be traded and borrowed. Only populated for tactics with rules which
+    /// need it.
+    pub asset_info: HashMap<AssetSymbol, AssetInfo>,
 }
 
 impl TacticState {
@@ -38,14 +63,159 @@ impl TacticState {
             quotes,
             strategy_properties,
             all_assets,
+            entry_times: HashMap::new(),
+            last_order_times: HashMap::new(),
+            last_buy_times: HashMap::new(),
+            now: Default::default(),
+            market_day: None,
+            asset_info: HashMap::new(),
+        }
+    }
+
+    pub fn with_clock(self, now: DateTime<Utc>, market_day: Option<MarketDay>) -> Self {
+        Self {
+            now,
+            market_day,
+            ..self
+        }
+    }
+
+    pub fn with_asset_info(self, asset_info: HashMap<AssetSymbol, AssetInfo>) -> Self {
+        Self { asset_info, ..self }
+    }
+
+    /// Works out the times the rules need from the order history, which starts at `history_start`.
+    /// `tag` picks out the orders this tactic placed.
+    pub fn with_order_history(
+        self,
+        orders: &[Order],
+        tag: &OrderTag,
+        history_start: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            entry_times: Self::entry_times_from_orders(orders, &self.positions, history_start),
+            last_order_times: Self::last_order_times_from_orders(orders, tag),
+            last_buy_times: Self::last_buy_times_from_orders(orders, tag),
+            ..self
+        }
+    }
+
+    /// Finds when each long position was opened by replaying filled orders oldest first. A
+    /// position's entry is the buy which took it from flat or short to long, so adding to a
+    /// position keeps its entry, and short positions have none. The replay starts from whatever
+    /// the fills don't explain in `positions`, so a position already long before `history_start`
+    /// is taken to have been entered then.
+    pub fn entry_times_from_orders(
+        orders: &[Order],
+        positions: &HashMap<AssetSymbol, Position>,
+        history_start: DateTime<Utc>,
+    ) -> HashMap<AssetSymbol, DateTime<Utc>> {
+        let filled = orders
+            .iter()
+            .filter_map(|o| o.filled_at.map(|filled_at| (filled_at, o)))
+            .sorted_by_key(|(filled_at, _)| *filled_at)
+            .collect::<Vec<_>>();
+        let mut quantities: HashMap<AssetSymbol, f64> = positions
+            .iter()
+            .map(|(symbol, position)| {
+                let quantity = position.quantity.abs();
+                let signed = if position.is_short() {
+                    -quantity
+                } else {
+                    quantity
+                };
+                (symbol.clone(), signed)
+            })
+            .collect();
+        for (_, order) in &filled {
+            let quantity = quantities.entry(order.symbol.clone()).or_default();
+            *quantity -= Self::signed_fill(order);
+        }
+        let mut entry_times = quantities
+            .iter()
+            .filter(|(_, quantity)| **quantity > f64::EPSILON)
+            .map(|(symbol, _)| (symbol.clone(), history_start))
+            .collect::<HashMap<_, _>>();
+        for (filled_at, order) in filled {
+            let quantity = quantities.entry(order.symbol.clone()).or_default();
+            let was_long = *quantity > f64::EPSILON;
+            *quantity += Self::signed_fill(order);
+            let is_long = *quantity > f64::EPSILON;
+            if is_long && !was_long {
+                entry_times.insert(order.symbol.clone(), filled_at);
+            } else if !is_long {
+                entry_times.remove(&order.symbol);
+            }
         }
+        entry_times
+    }
+
+    /// The filled quantity of `order`, negative for a sell.
+    fn signed_fill(order: &Order) -> f64 {
+        let filled_quantity = order.filled_quantity.to_f64().unwrap_or_default();
+        match order.side {
+            OrderSide::Buy => filled_quantity,
+            OrderSide::Sell => -filled_quantity,
+        }
+    }
+
+    /// The latest order `tag` placed for each asset, whether it was a buy or a sell, skipping
+    /// orders which ended without filling. An order counts from its fill, or from when it was
+    /// placed while it's still working.
+    pub fn last_order_times_from_orders(
+        orders: &[Order],
+        tag: &OrderTag,
+    ) -> HashMap<AssetSymbol, DateTime<Utc>> {
+        orders
+            .iter()
+            .filter(|o| tag.matches(o) && !Self::ended_unfilled(o))
+            .map(|o| (o.symbol.clone(), o.filled_at.unwrap_or(o.created_at)))
+            .into_grouping_map()
+            .max()
     }
 
+    /// The latest buy order `tag` placed for each asset, skipping orders which ended without
+    /// filling. Buys placed by other tactics or by hand don't count.
+    pub fn last_buy_times_from_orders(
+        orders: &[Order],
+        tag: &OrderTag,
+    ) -> HashMap<AssetSymbol, DateTime<Utc>> {
+        orders
+            .iter()
+            .filter(|o| o.side == OrderSide::Buy && tag.matches(o) && !Self::ended_unfilled(o))
+            .map(|o| (o.symbol.clone(), o.created_at))
+            .into_grouping_map()
+            .max()
+    }
+
+    /// Whether `order` was canceled, expired or rejected before any of it filled.
+    fn ended_unfilled(order: &Order) -> bool {
+        let unfilled = order.filled_quantity.to_f64().unwrap_or_default() <= 0.0;
+        let ended = matches!(
+            order.status,
+            Status::Canceled | Status::Expired | Status::Rejected
+        );
+        ended && unfilled
+    }
+
+    /// The position in `symbol` when it's held long, which is all the sell rules act on.
+    pub fn long_position(&self, symbol: &AssetSymbol) -> Option<&Position> {
+        self.positions.get(symbol).filter(|p| p.is_long())
+    }
+
+    /// The position in `symbol` when it's held short.
+    pub fn short_position(&self, symbol: &AssetSymbol) -> Option<&Position> {
+        self.positions.get(symbol).filter(|p| p.is_short())
+    }
+
+    /// The estimated value of the open buy orders for `symbol`. Open sells, such as trailing stop
+    /// exits, don't add to the position.
     pub fn open_order_value(&self, symbol: &AssetSymbol) -> f64 {
         let ask_price = self.quotes.get(symbol).map_or(0.0, |quote| quote.ask_price);
         self.open_orders.get(symbol).map_or(0.0, |orders| {
             orders
                 .iter()
+                .filter(|order| order.side == OrderSide::Buy)
                 .map(|order| order.estimated_value(ask_price))
                 .reduce(|a, b| a + b)
                 .unwrap_or(0.0)
@@ -86,6 +256,12 @@ impl TacticState {
             quotes,
             strategy_properties,
             all_assets,
+            entry_times: HashMap::new(),
+            last_order_times: HashMap::new(),
+            last_buy_times: HashMap::new(),
+            now: DateTimeFixture::utc(),
+            market_day: None,
+            asset_info: HashMap::new(),
         }
     }
 }
@@ -93,6 +269,9 @@ impl TacticState {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::platform::side::Side;
+    use chrono::Duration;
+    use num_decimal::Num;
 
     #[test]
     fn open_order_value() {
@@ -101,6 +280,20 @@ mod tests {
         assert_eq!(open_order_value, 200.0)
     }
 
+    #[test]
+    fn open_order_value_skips_sells() {
+        let spy = AssetSymbol::new("SPY");
+        let sell = Order {
+            side: OrderSide::Sell,
+            ..Order::fixture(spy.clone())
+        };
+        let state = TacticState {
+            open_orders: HashMap::from([(spy.clone(), vec![sell])]),
+            ..TacticState::fixture()
+        };
+        assert_eq!(state.open_order_value(&spy), 0.0)
+    }
+
     #[test]
     fn open_order_value_no_orders() {
         let state = TacticState {
@@ -121,6 +314,185 @@ mod tests {
         assert_eq!(open_order_value, 0.0)
     }
 
+    #[test]
+    fn entry_times_from_orders() {
+        let spy = AssetSymbol::new("SPY");
+        let vti = AssetSymbol::new("VTI");
+        let start = DateTimeFixture::utc();
+        let orders = vec![
+            filled(&spy, OrderSide::Buy, 2.0, start + Duration::days(1)),
+            filled(&spy, OrderSide::Buy, 1.0, start),
+            filled(&spy, OrderSide::Sell, 3.0, start + Duration::days(2)),
+            filled(&spy, OrderSide::Buy, 1.0, start + Duration::days(3)),
+            filled(&spy, OrderSide::Buy, 1.0, start + Duration::days(4)),
+            filled(&vti, OrderSide::Buy, 1.0, start),
+            filled(&vti, OrderSide::Sell, 1.0, start + Duration::days(1)),
+            Order::fixture(vti.clone()),
+        ];
+        let entry_times = TacticState::entry_times_from_orders(&orders, &long(&spy, 2.0), start);
+        assert_eq!(
+            entry_times,
+            HashMap::from([(spy, start + Duration::days(3))])
+        );
+    }
+
+    #[test]
+    fn entry_times_from_orders_with_shorts() {
+        let spy = AssetSymbol::new("SPY");
+        let vti = AssetSymbol::new("VTI");
+        let start = DateTimeFixture::utc();
+        let orders = vec![
+            // Shorted, then covered with a buy which only took it back to flat.
+            filled(&spy, OrderSide::Sell, 2.0, start),
+            filled(&spy, OrderSide::Buy, 2.0, start + Duration::days(1)),
+            // Shorted, then bought past the cover into a long.
+            filled(&vti, OrderSide::Sell, 2.0, start),
+            filled(&vti, OrderSide::Buy, 1.0, start + Duration::days(1)),
+            filled(&vti, OrderSide::Buy, 3.0, start + Duration::days(2)),
+        ];
+        let entry_times = TacticState::entry_times_from_orders(&orders, &long(&vti, 2.0), start);
+        assert_eq!(
+            entry_times,
+            HashMap::from([(vti, start + Duration::days(2))])
+        );
+    }
+
+    #[test]
+    fn entry_times_from_orders_held_before_history() {
+        let spy = AssetSymbol::new("SPY");
+        let vti = AssetSymbol::new("VTI");
+        let start = DateTimeFixture::utc();
+        let positions = HashMap::from([
+            (spy.clone(), Position::fixture(spy.clone())),
+            (vti.clone(), Position::fixture(vti.clone())),
+        ]);
+        let orders = vec![
+            // Some of the older shares were sold, then the position was added to.
+            filled(&vti, OrderSide::Sell, 30.0, start + Duration::days(1)),
+            filled(&vti, OrderSide::Buy, 30.0, start + Duration::days(2)),
+        ];
+        let entry_times = TacticState::entry_times_from_orders(&orders, &positions, start);
+        assert_eq!(entry_times, HashMap::from([(spy, start), (vti, start)]));
+    }
+
+    #[test]
+    fn entry_times_from_orders_reentered_after_history_start() {
+        let spy = AssetSymbol::new("SPY");
+        let start = DateTimeFixture::utc();
+        let positions = HashMap::from([(spy.clone(), Position::fixture(spy.clone()))]);
+        let orders = vec![
+            // The older shares were all sold before the current position was bought.
+            filled(&spy, OrderSide::Sell, 20.0, start + Duration::days(1)),
+            filled(&spy, OrderSide::Buy, 50.0, start + Duration::days(2)),
+        ];
+        let entry_times = TacticState::entry_times_from_orders(&orders, &positions, start);
+        assert_eq!(
+            entry_times,
+            HashMap::from([(spy, start + Duration::days(2))])
+        );
+    }
+
+    #[test]
+    fn last_order_times_from_orders() {
+        let spy = AssetSymbol::new("SPY");
+        let vti = AssetSymbol::new("VTI");
+        let qqq = AssetSymbol::new("QQQ");
+        let start = DateTimeFixture::utc();
+        let tag = OrderTag::new("strategy", "tactic");
+        let orders = vec![
+            tag.order(filled(
+                &spy,
+                OrderSide::Sell,
+                1.0,
+                start + Duration::days(2),
+            )),
+            tag.order(filled(&spy, OrderSide::Buy, 1.0, start)),
+            // Still working, so it counts from when it was placed.
+            tag.order(Order {
+                created_at: start + Duration::days(1),
+                status: Status::New,
+                ..Order::fixture(vti.clone())
+            }),
+            tag.order(Order {
+                created_at: start + Duration::days(3),
+                status: Status::Canceled,
+                ..Order::fixture(vti.clone())
+            }),
+            // Placed by hand, or by another tactic.
+            filled(&qqq, OrderSide::Buy, 1.0, start),
+            OrderTag::new("strategy", "other").order(filled(
+                &spy,
+                OrderSide::Sell,
+                1.0,
+                start + Duration::days(4),
+            )),
+        ];
+        let last_order_times = TacticState::last_order_times_from_orders(&orders, &tag);
+        assert_eq!(
+            last_order_times,
+            HashMap::from([
+                (spy, start + Duration::days(2)),
+                (vti, start + Duration::days(1)),
+            ])
+        );
+    }
+
+    #[test]
+    fn last_buy_times_from_orders() {
+        let spy = AssetSymbol::new("SPY");
+        let vti = AssetSymbol::new("VTI");
+        let start = DateTimeFixture::utc();
+        let tag = OrderTag::new("strategy", "tactic");
+        let placed = |symbol: &AssetSymbol, side, status, days| {
+            tag.order(Order {
+                created_at: start + Duration::days(days),
+                side,
+                status,
+                ..Order::fixture(symbol.clone())
+            })
+        };
+        let orders = vec![
+            placed(&spy, OrderSide::Buy, Status::New, 1),
+            placed(&spy, OrderSide::Buy, Status::Filled, 0),
+            placed(&spy, OrderSide::Sell, Status::New, 2),
+            placed(&vti, OrderSide::Buy, Status::Canceled, 3),
+            placed(&vti, OrderSide::Buy, Status::Rejected, 4),
+            // Placed by hand, or by another tactic.
+            Order {
+                created_at: start + Duration::days(5),
+                side: OrderSide::Buy,
+                status: Status::Filled,
+                ..Order::fixture(spy.clone())
+            },
+            OrderTag::new("strategy", "other").order(Order {
+                created_at: start + Duration::days(5),
+                side: OrderSide::Buy,
+                status: Status::Filled,
+                ..Order::fixture(vti.clone())
+            }),
+        ];
+        let last_buy_times = TacticState::last_buy_times_from_orders(&orders, &tag);
+        assert_eq!(
+            last_buy_times,
+            HashMap::from([(spy, start + Duration::days(1))])
+        );
+    }
+
+    #[test]
+    fn long_and_short_positions() {
+        let spy = AssetSymbol::new("SPY");
+        let short = Position {
+            side: Side::Short,
+            ..Position::fixture(spy.clone())
+        };
+        let state = TacticState {
+            positions: HashMap::from([(spy.clone(), short.clone())]),
+            ..TacticState::fixture()
+        };
+        assert_eq!(state.long_position(&spy), None);
+        assert_eq!(state.short_position(&spy), Some(&short));
+    }
+
     #[test]
     fn fixture_includes_all_assets() {
         let state = TacticState::fixture();
@@ -128,4 +500,22 @@ mod tests {
         assert!(state.all_assets.contains(&AssetSymbol::new("SPY")));
         assert!(state.all_assets.contains(&AssetSymbol::new("VTI")));
     }
+
+    fn long(symbol: &AssetSymbol, quantity: f64) -> HashMap<AssetSymbol, Position> {
+        let position = Position {
+            quantity,
+            quantity_available: quantity,
+            ..Position::fixture(symbol.clone())
+        };
+        HashMap::from([(symbol.clone(), position)])
+    }
+
+    fn filled(symbol: &AssetSymbol, side: OrderSide, quantity: f64, time: DateTime<Utc>) -> Order {
+        Order {
+            filled_at: Some(time),
+            filled_quantity: Num::from(quantity as i64),
+            side,
+            ..Order::fixture(symbol.clone())
+        }
+    }
 }
@@ -1,19 +1,157 @@
 mod when_all_of;
 pub mod when_always;
+mod when_any_of;
 mod when_below_median;
+mod when_bollinger_band;
+mod when_cooldown;
+mod when_drawdown;
 pub mod when_factory;
 mod when_gain_above;
+mod when_held_at_least;
+mod when_loss_below;
+mod when_market_time;
+mod when_moving_average_cross;
+mod when_not;
+mod when_price_moving_average;
+mod when_rsi;
+mod when_schedule;
 
+use crate::asset::AssetSymbol;
+use crate::lowercase_enum_display;
 use crate::tactic::r#for::ForResult;
 use crate::tactic::state::TacticState;
 use crate::tactic::target::TargetAsset;
 
 pub trait WhenRule {
     fn evaluate(&self, state: &TacticState, for_result: ForResult) -> WhenResult;
+
+    /// A short description of the condition this rule checks.
+    fn description(&self) -> String;
+
+    /// Explains the verdict for each target asset. By default this only reports whether each
+    /// asset satisfied the rule, rules which compare values should override it to include them.
+    fn trace(&self, state: &TacticState, for_result: ForResult) -> WhenTrace {
+        let assets = for_result
+            .target_assets
+            .into_iter()
+            .map(|target_asset| {
+                let symbol = target_asset.symbol.clone();
+                let asset_result = ForResult {
+                    target_assets: vec![target_asset],
+                };
+                let satisfied = self.evaluate(state, asset_result).conditions_satisfied;
+                AssetVerdict::new(symbol, satisfied, None)
+            })
+            .collect();
+        WhenTrace {
+            description: self.description(),
+            assets,
+            ..Default::default()
+        }
+    }
+}
+
+/// Which side of a threshold a value has to be on to satisfy a rule.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Threshold {
+    Above,
+    Below,
+}
+
+impl Threshold {
+    pub fn is_satisfied(&self, value: f64, threshold: f64) -> bool {
+        match self {
+            Threshold::Above => value > threshold,
+            Threshold::Below => value < threshold,
+        }
+    }
 }
 
+lowercase_enum_display!(Threshold);
+
 #[derive(Debug, Default, PartialEq)]
 pub struct WhenResult {
     pub conditions_satisfied: bool,
     pub target_assets: Vec<TargetAsset>,
 }
+
+/// The per asset verdicts of a when rule, along with the traces of any nested rules.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WhenTrace {
+    pub description: String,
+    pub assets: Vec<AssetVerdict>,
+    pub children: Vec<WhenTrace>,
+}
+
+impl WhenTrace {
+    /// Flattens the verdicts for a single asset into lines such as "gain above 5.00% - gain is
+    /// 10.00%", so they can be recorded alongside the orders they produced.
+    pub fn inputs_for(&self, symbol: &AssetSymbol) -> Vec<String> {
+        let own = self
+            .assets
+            .iter()
+            .filter(|verdict| &verdict.symbol == symbol)
+            .map(|verdict| match &verdict.detail {
+                Some(detail) => format!("{} - {detail}", self.description),
+                None => self.description.clone(),
+            });
+        let children = self.children.iter().flat_map(|c| c.inputs_for(symbol));
+        own.chain(children).collect()
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AssetVerdict {
+    pub symbol: AssetSymbol,
+    pub satisfied: bool,
+    /// The values behind the verdict, when the rule has any.
+    pub detail: Option<String>,
+}
+
+impl AssetVerdict {
+    pub fn new(symbol: AssetSymbol, satisfied: bool, detail: Option<String>) -> Self {
+        Self {
+            symbol,
+            satisfied,
+            detail,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn threshold_is_satisfied() {
+        assert!(Threshold::Above.is_satisfied(2.0, 1.0));
+        assert!(!Threshold::Above.is_satisfied(1.0, 1.0));
+        assert!(Threshold::Below.is_satisfied(1.0, 2.0));
+        assert!(!Threshold::Below.is_satisfied(2.0, 2.0));
+    }
+
+    #[test]
+    fn inputs_for() {
+        let spy = AssetSymbol::new("SPY");
+        let vti = AssetSymbol::new("VTI");
+        let trace = WhenTrace {
+            description: "all of".to_string(),
+            assets: vec![
+                AssetVerdict::new(spy.clone(), true, None),
+                AssetVerdict::new(vti.clone(), false, None),
+            ],
+            children: vec![WhenTrace {
+                description: "gain above 5.00%".to_string(),
+                assets: vec![
+                    AssetVerdict::new(spy.clone(), true, Some("gain is 10.00%".to_string())),
+                    AssetVerdict::new(vti, false, Some("gain is 1.00%".to_string())),
+                ],
+                ..Default::default()
+            }],
+        };
+        assert_eq!(
+            trace.inputs_for(&spy),
+            vec!["all of", "gain above 5.00% - gain is 10.00%"]
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use crate::tactic::r#for::ForResult;
 use crate::tactic::state::TacticState;
-use crate::tactic::when::{WhenResult, WhenRule};
+use crate::tactic::when::{AssetVerdict, WhenResult, WhenRule, WhenTrace};
 
 pub struct WhenAllOfRule {
     rules: Vec<Box<dyn WhenRule>>,
@@ -31,6 +31,35 @@ impl WhenRule for WhenAllOfRule {
             target_assets: filtered_assets,
         }
     }
+
+    fn description(&self) -> String {
+        "all of".to_string()
+    }
+
+    fn trace(&self, state: &TacticState, for_result: ForResult) -> WhenTrace {
+        let children = self
+            .rules
+            .iter()
+            .map(|rule| rule.trace(state, for_result.clone()))
+            .collect::<Vec<_>>();
+        let assets = for_result
+            .target_assets
+            .iter()
+            .map(|t| {
+                let satisfied = children
+                    .iter()
+                    .flat_map(|c| c.assets.iter())
+                    .filter(|a| a.symbol == t.symbol)
+                    .all(|a| a.satisfied);
+                AssetVerdict::new(t.symbol.clone(), satisfied, None)
+            })
+            .collect();
+        WhenTrace {
+            description: self.description(),
+            assets,
+            children,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -42,7 +71,7 @@ mod tests {
     use crate::tactic::target::TargetAsset;
     use crate::tactic::when::when_all_of::WhenAllOfRule;
     use crate::tactic::when::when_always::WhenAlwaysRule;
-    use crate::tactic::when::WhenResult;
+    use crate::tactic::when::{AssetVerdict, WhenResult, WhenTrace};
 
     #[test]
     fn evaluate_not_satisfied() {
@@ -76,6 +105,34 @@ mod tests {
         assert_eq!(expected, result)
     }
 
+    #[test]
+    fn trace() {
+        let state = TacticState::fixture();
+        let rule = WhenAllOfRule::boxed(vec![WhenAlwaysRule::boxed(), NullRule::when_boxed()]);
+        let for_result = ForResult {
+            target_assets: vec![TargetAsset::full_percent(AssetSymbol::new("SPY"))],
+        };
+        let trace = rule.trace(&state, for_result);
+        let spy = AssetSymbol::new("SPY");
+        let expected = WhenTrace {
+            description: "all of".to_string(),
+            assets: vec![AssetVerdict::new(spy.clone(), false, None)],
+            children: vec![
+                WhenTrace {
+                    description: "always".to_string(),
+                    assets: vec![AssetVerdict::new(spy.clone(), true, None)],
+                    ..Default::default()
+                },
+                WhenTrace {
+                    description: "never".to_string(),
+                    assets: vec![AssetVerdict::new(spy, false, None)],
+                    ..Default::default()
+                },
+            ],
+        };
+        assert_eq!(expected, trace)
+    }
+
     fn target_assets() -> Vec<TargetAsset> {
         vec![
             TargetAsset::full_percent(AssetSymbol::new("SPY")),
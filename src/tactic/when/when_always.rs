@@ -11,6 +11,10 @@ impl WhenRule for WhenAlwaysRule {
             target_assets: for_result.target_assets,
         }
     }
+
+    fn description(&self) -> String {
+        "always".to_string()
+    }
 }
 
 impl WhenAlwaysRule {
@@ -23,6 +27,7 @@ impl WhenAlwaysRule {
 mod tests {
     use super::*;
     use crate::tactic::target::TargetAsset;
+    use crate::tactic::when::{AssetVerdict, WhenTrace};
 
     #[test]
     fn evaluate() {
@@ -36,4 +41,17 @@ mod tests {
         };
         assert_eq!(expected, result)
     }
+
+    #[test]
+    fn trace() {
+        let state = TacticState::default();
+        let rule = WhenAlwaysRule {};
+        let trace = rule.trace(&state, ForResult::fixture());
+        let expected = WhenTrace {
+            description: "always".to_string(),
+            assets: vec![AssetVerdict::new(TargetAsset::fixture().symbol, true, None)],
+            ..Default::default()
+        };
+        assert_eq!(expected, trace)
+    }
 }
@@ -6,7 +6,7 @@ use crate::platform::bars::Bars;
 use crate::tactic::r#for::ForResult;
 use crate::tactic::state::TacticState;
 use crate::tactic::target::TargetAsset;
-use crate::tactic::when::{WhenResult, WhenRule};
+use crate::tactic::when::{AssetVerdict, WhenResult, WhenRule, WhenTrace};
 use log::{info, warn};
 
 #[derive(Debug, Default, PartialEq)]
@@ -15,6 +15,14 @@ pub struct WhenBelowMedianRule {
     median_period: MedianPeriod,
 }
 
+/// The values compared when checking if an asset is below its median.
+#[derive(Debug, PartialEq)]
+struct MedianComparison {
+    ask_price: f64,
+    median: f64,
+    percent_below: f64,
+}
+
 impl WhenBelowMedianRule {
     pub fn boxed(below_median_percent: f64, median_period: MedianPeriod) -> Box<dyn WhenRule> {
         Box::new(Self {
@@ -24,55 +32,67 @@ impl WhenBelowMedianRule {
     }
 
     fn is_below_median(&self, state: &TacticState, target_asset: &TargetAsset) -> bool {
+        match self.compare(state, target_asset) {
+            Ok(comparison) => self
+                .is_satisfied(&comparison)
+                .when_false(|| self.log_quote_is_not_below_median(comparison.percent_below)),
+            Err(reason) => {
+                warn!("when_below_median: {reason} for: {}", target_asset.symbol);
+                false
+            }
+        }
+    }
+
+    fn is_satisfied(&self, comparison: &MedianComparison) -> bool {
+        comparison.percent_below >= self.below_median_percent
+    }
+
+    fn compare(
+        &self,
+        state: &TacticState,
+        target_asset: &TargetAsset,
+    ) -> Result<MedianComparison, &'static str> {
         match self.median_period {
-            MedianPeriod::Day => self.is_below_median_for_func(state, target_asset, |analysis| {
-                &analysis.last_trading_day
-            }),
+            MedianPeriod::Day => {
+                self.compare_for_func(state, target_asset, |analysis| &analysis.last_trading_day)
+            }
             MedianPeriod::Week => {
-                self.is_below_median_for_func(state, target_asset, |analysis| &analysis.seven_day)
+                self.compare_for_func(state, target_asset, |analysis| &analysis.seven_day)
             }
             MedianPeriod::Month => {
-                self.is_below_median_for_func(state, target_asset, |analysis| &analysis.thirty_day)
+                self.compare_for_func(state, target_asset, |analysis| &analysis.thirty_day)
             }
         }
     }
 
-    fn is_below_median_for_func<F>(
+    fn compare_for_func<F>(
         &self,
         state: &TacticState,
         target_asset: &TargetAsset,
         func: F,
-    ) -> bool
+    ) -> Result<MedianComparison, &'static str>
     where
         F: Fn(&BarsResult) -> &Bars,
     {
         if self.is_state_valid(state, target_asset) {
-            warn!(
-                "when_below_median: state was not valid for: {}",
-                target_asset.symbol
-            );
-            return false;
+            return Err("state was not valid");
         }
 
         let quote = &state.quotes[&target_asset.symbol];
         if !quote.valid_ask() {
-            warn!(
-                "when_below_median: Ask price is not valid for: {}",
-                target_asset.symbol
-            );
-            return false;
+            return Err("ask price is not valid");
         }
 
         let analysis = &state.bar_analysis[&target_asset.symbol];
-        let median = func(analysis).average_median();
-        median
+        let median = func(analysis)
+            .average_median()
             .filter(Self::is_median_valid)
-            .map(|m| {
-                let difference_percent = quote.clone().ask_price.percent_below(m);
-                (difference_percent >= self.below_median_percent)
-                    .when_false(|| self.log_quote_is_not_below_median(difference_percent))
-            })
-            .unwrap_or(false)
+            .ok_or("median is not valid")?;
+        Ok(MedianComparison {
+            ask_price: quote.ask_price,
+            median,
+            percent_below: quote.ask_price.percent_below(median),
+        })
     }
 
     fn log_quote_is_not_below_median(&self, difference_percent: f64) {
@@ -105,6 +125,35 @@ impl WhenRule for WhenBelowMedianRule {
             target_assets: assets_below_median,
         }
     }
+
+    fn description(&self) -> String {
+        format!(
+            "below {} median by {:.2}%",
+            self.median_period, self.below_median_percent
+        )
+    }
+
+    fn trace(&self, state: &TacticState, for_result: ForResult) -> WhenTrace {
+        let assets = for_result
+            .target_assets
+            .into_iter()
+            .map(|t| match self.compare(state, &t) {
+                Ok(comparison) => {
+                    let detail = format!(
+                        "ask {:.2} is {:.2}% below median {:.2}",
+                        comparison.ask_price, comparison.percent_below, comparison.median
+                    );
+                    AssetVerdict::new(t.symbol, self.is_satisfied(&comparison), Some(detail))
+                }
+                Err(reason) => AssetVerdict::new(t.symbol, false, Some(reason.to_string())),
+            })
+            .collect();
+        WhenTrace {
+            description: self.description(),
+            assets,
+            ..Default::default()
+        }
+    }
 }
 
 #[cfg(test)]
@@ -254,6 +303,33 @@ mod tests {
         validate_evaluation(1.0, MedianPeriod::Month, false);
     }
 
+    #[test]
+    fn trace() {
+        let state = TacticState::fixture();
+        let rule = WhenBelowMedianRule::boxed(50.0, MedianPeriod::Day);
+        let for_result = ForResult {
+            target_assets: vec![
+                TargetAsset::full_percent(AssetSymbol::new("SPY")),
+                TargetAsset::full_percent(AssetSymbol::new("NOPE")),
+            ],
+        };
+        let trace = rule.trace(&state, for_result);
+        assert_eq!(trace.description, "below day median by 50.00%");
+        assert_eq!(
+            trace.assets[1],
+            AssetVerdict::new(
+                AssetSymbol::new("NOPE"),
+                false,
+                Some("state was not valid".to_string())
+            )
+        );
+        assert!(trace.assets[0].satisfied);
+        assert!(trace.assets[0]
+            .detail
+            .as_ref()
+            .is_some_and(|d| d.contains("50.00% below median")));
+    }
+
     fn validate_evaluation(
         below_median_percent: f64,
         median_period: MedianPeriod,
@@ -0,0 +1,238 @@
+use crate::analysis::indicator::bollinger::{BollingerBands, BollingerPeriod};
+use crate::bool::BooleanWhen;
+use crate::tactic::r#for::ForResult;
+use crate::tactic::state::TacticState;
+use crate::tactic::target::TargetAsset;
+use crate::tactic::when::{AssetVerdict, Threshold, WhenResult, WhenRule, WhenTrace};
+use log::{info, warn};
+
+/// Checks the ask price against the daily Bollinger Bands. `Below` is satisfied under the lower
+/// band and `Above` over the upper band.
+#[derive(Debug, PartialEq)]
+pub struct WhenBollingerBandRule {
+    side: Threshold,
+    period: BollingerPeriod,
+}
+
+/// The values compared when checking the price against a band.
+#[derive(Debug, PartialEq)]
+struct BandComparison {
+    ask_price: f64,
+    bands: BollingerBands,
+}
+
+impl WhenBollingerBandRule {
+    pub fn boxed(side: Threshold, period: usize, std_devs: f64) -> Box<dyn WhenRule> {
+        Box::new(Self {
+            side,
+            period: BollingerPeriod { period, std_devs },
+        })
+    }
+
+    fn is_outside_band(&self, state: &TacticState, target_asset: &TargetAsset) -> bool {
+        match self.compare(state, target_asset) {
+            Ok(comparison) => self.is_satisfied(&comparison).when_false(|| {
+                info!(
+                    "when_bollinger_band: ask {:.2} was not {} {:.2} for {}",
+                    comparison.ask_price,
+                    self.side,
+                    self.band(&comparison.bands),
+                    target_asset.symbol
+                )
+            }),
+            Err(reason) => {
+                warn!("when_bollinger_band: {reason} for: {}", target_asset.symbol);
+                false
+            }
+        }
+    }
+
+    fn is_satisfied(&self, comparison: &BandComparison) -> bool {
+        self.side
+            .is_satisfied(comparison.ask_price, self.band(&comparison.bands))
+    }
+
+    fn band(&self, bands: &BollingerBands) -> f64 {
+        match self.side {
+            Threshold::Above => bands.upper,
+            Threshold::Below => bands.lower,
+        }
+    }
+
+    fn band_name(&self) -> &'static str {
+        match self.side {
+            Threshold::Above => "upper",
+            Threshold::Below => "lower",
+        }
+    }
+
+    fn compare(
+        &self,
+        state: &TacticState,
+        target_asset: &TargetAsset,
+    ) -> Result<BandComparison, &'static str> {
+        let quote = state.quotes.get(&target_asset.symbol).ok_or("no quote")?;
+        if !quote.valid_ask() {
+            return Err("ask price is not valid");
+        }
+        let analysis = state
+            .bar_analysis
+            .get(&target_asset.symbol)
+            .ok_or("no bar analysis")?;
+        let bands = analysis
+            .one_year
+            .bollinger_bands(self.period)
+            .ok_or("not enough bars")?;
+        Ok(BandComparison {
+            ask_price: quote.ask_price,
+            bands,
+        })
+    }
+}
+
+impl WhenRule for WhenBollingerBandRule {
+    fn evaluate(&self, state: &TacticState, for_result: ForResult) -> WhenResult {
+        let matching_assets = for_result
+            .target_assets
+            .iter()
+            .filter(|t| self.is_outside_band(state, t))
+            .cloned()
+            .collect::<Vec<_>>();
+        WhenResult {
+            conditions_satisfied: !matching_assets.is_empty(),
+            target_assets: matching_assets,
+        }
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "price {} {} bollinger band ({}, {:.1})",
+            self.side,
+            self.band_name(),
+            self.period.period,
+            self.period.std_devs
+        )
+    }
+
+    fn trace(&self, state: &TacticState, for_result: ForResult) -> WhenTrace {
+        let assets = for_result
+            .target_assets
+            .into_iter()
+            .map(|t| match self.compare(state, &t) {
+                Ok(comparison) => {
+                    let detail = format!(
+                        "ask {:.2}, bands {:.2} - {:.2} - {:.2}",
+                        comparison.ask_price,
+                        comparison.bands.lower,
+                        comparison.bands.middle,
+                        comparison.bands.upper
+                    );
+                    AssetVerdict::new(t.symbol, self.is_satisfied(&comparison), Some(detail))
+                }
+                Err(reason) => AssetVerdict::new(t.symbol, false, Some(reason.to_string())),
+            })
+            .collect();
+        WhenTrace {
+            description: self.description(),
+            assets,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asset::AssetSymbol;
+    use crate::platform::quote::Quote;
+    use std::collections::HashMap;
+
+    // The fixture's one year bars close at 600, 700 and 800, giving bands of roughly 618 - 700 -
+    // 782 at one standard deviation. Its ask price is 200.
+
+    #[test]
+    fn evaluate_below_lower_band() {
+        let rule = WhenBollingerBandRule::boxed(Threshold::Below, 3, 2.0);
+        let result = rule.evaluate(
+            &TacticState::fixture(),
+            ForResult::full_percent_fixture(&["SPY", "VTI"]),
+        );
+        let expected = WhenResult {
+            conditions_satisfied: true,
+            target_assets: ForResult::full_percent_fixture(&["SPY", "VTI"]).target_assets,
+        };
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn evaluate_above_upper_band() {
+        let spy = AssetSymbol::new("SPY");
+        let quote = Quote {
+            ask_price: 900.0,
+            ..Quote::fixture(spy.clone())
+        };
+        let state = TacticState {
+            quotes: HashMap::from([(spy.clone(), quote)]),
+            ..TacticState::fixture()
+        };
+        let rule = WhenBollingerBandRule::boxed(Threshold::Above, 3, 1.0);
+        let result = rule.evaluate(&state, ForResult::full_percent_fixture(&["SPY", "VTI"]));
+        let expected = WhenResult {
+            conditions_satisfied: true,
+            target_assets: vec![TargetAsset::full_percent(spy)],
+        };
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn evaluate_inside_bands() {
+        let rule = WhenBollingerBandRule::boxed(Threshold::Above, 3, 1.0);
+        let result = rule.evaluate(
+            &TacticState::fixture(),
+            ForResult::full_percent_fixture(&["SPY", "VTI"]),
+        );
+        assert_eq!(WhenResult::default(), result);
+    }
+
+    #[test]
+    fn evaluate_not_enough_bars() {
+        let rule = WhenBollingerBandRule::boxed(Threshold::Below, 20, 2.0);
+        let result = rule.evaluate(
+            &TacticState::fixture(),
+            ForResult::full_percent_fixture(&["SPY", "VTI"]),
+        );
+        assert_eq!(WhenResult::default(), result);
+    }
+
+    #[test]
+    fn evaluate_no_quote() {
+        let state = TacticState {
+            quotes: HashMap::new(),
+            ..TacticState::fixture()
+        };
+        let rule = WhenBollingerBandRule::boxed(Threshold::Below, 3, 2.0);
+        let result = rule.evaluate(&state, ForResult::full_percent_fixture(&["SPY", "VTI"]));
+        assert_eq!(WhenResult::default(), result);
+    }
+
+    #[test]
+    fn trace() {
+        let rule = WhenBollingerBandRule::boxed(Threshold::Below, 3, 1.0);
+        let trace = rule.trace(
+            &TacticState::fixture(),
+            ForResult::full_percent_fixture(&["SPY", "VTI"]),
+        );
+        assert_eq!(
+            trace.description,
+            "price below lower bollinger band (3, 1.0)"
+        );
+        assert_eq!(
+            trace.assets[0],
+            AssetVerdict::new(
+                AssetSymbol::new("SPY"),
+                true,
+                Some("ask 200.00, bands 618.35 - 700.00 - 781.65".to_string())
+            )
+        );
+    }
+}
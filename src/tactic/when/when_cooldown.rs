@@ -0,0 +1,165 @@
+use crate::tactic::r#for::ForResult;
+use crate::tactic::state::TacticState;
+use crate::tactic::target::TargetAsset;
+use crate::tactic::when::{AssetVerdict, WhenResult, WhenRule, WhenTrace};
+use log::info;
+
+/// Satisfied when this tactic hasn't traded an asset within the last `cooldown_hours`, so a rule
+/// doesn't trade the same asset every interval. Orders still working count from when they were
+/// placed, and trades made by hand or by other tactics are ignored.
+#[derive(Debug, Default, PartialEq)]
+pub struct WhenCooldownRule {
+    cooldown_hours: f64,
+}
+
+impl WhenCooldownRule {
+    pub fn boxed(cooldown_hours: f64) -> Box<dyn WhenRule> {
+        Box::new(Self { cooldown_hours })
+    }
+
+    /// Hours since the tactic last traded the asset, `None` if it hasn't traded recently.
+    fn hours_since_trade(&self, state: &TacticState, target_asset: &TargetAsset) -> Option<f64> {
+        state
+            .last_order_times
+            .get(&target_asset.symbol)
+            .map(|traded_at| (state.now - *traded_at).num_seconds() as f64 / 3600.0)
+    }
+
+    fn is_satisfied(&self, hours_since_trade: Option<f64>) -> bool {
+        hours_since_trade.is_none_or(|hours| hours >= self.cooldown_hours)
+    }
+
+    fn is_cooled_down(&self, state: &TacticState, target_asset: &TargetAsset) -> bool {
+        let hours_since_trade = self.hours_since_trade(state, target_asset);
+        let satisfied = self.is_satisfied(hours_since_trade);
+        if let (false, Some(hours)) = (satisfied, hours_since_trade) {
+            info!(
+                "when_cooldown: {} traded {:.2} hours ago, expecting {:.2}",
+                target_asset.symbol, hours, self.cooldown_hours
+            )
+        }
+        satisfied
+    }
+}
+
+impl WhenRule for WhenCooldownRule {
+    fn evaluate(&self, state: &TacticState, for_result: ForResult) -> WhenResult {
+        let cooled_down_assets = for_result
+            .target_assets
+            .iter()
+            .filter(|t| self.is_cooled_down(state, t))
+            .cloned()
+            .collect::<Vec<_>>();
+        WhenResult {
+            conditions_satisfied: !cooled_down_assets.is_empty(),
+            target_assets: cooled_down_assets,
+        }
+    }
+
+    fn description(&self) -> String {
+        format!("no trades in {:.2} hours", self.cooldown_hours)
+    }
+
+    fn trace(&self, state: &TacticState, for_result: ForResult) -> WhenTrace {
+        let assets = for_result
+            .target_assets
+            .into_iter()
+            .map(|t| {
+                let hours_since_trade = self.hours_since_trade(state, &t);
+                let detail = match hours_since_trade {
+                    Some(hours) => format!("last traded {hours:.2} hours ago"),
+                    None => "no recent trades".to_string(),
+                };
+                AssetVerdict::new(t.symbol, self.is_satisfied(hours_since_trade), Some(detail))
+            })
+            .collect();
+        WhenTrace {
+            description: self.description(),
+            assets,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asset::AssetSymbol;
+    use chrono::Duration;
+    use std::collections::HashMap;
+
+    #[test]
+    fn evaluate_no_recent_trades() {
+        let rule = WhenCooldownRule::boxed(24.0);
+        let result = rule.evaluate(
+            &TacticState::fixture(),
+            ForResult::full_percent_fixture(&["SPY", "VTI"]),
+        );
+        let expected = WhenResult {
+            conditions_satisfied: true,
+            target_assets: ForResult::full_percent_fixture(&["SPY", "VTI"]).target_assets,
+        };
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn evaluate_traded_recently() {
+        let rule = WhenCooldownRule::boxed(24.0);
+        let result = rule.evaluate(
+            &state(Duration::hours(23)),
+            ForResult::full_percent_fixture(&["SPY", "VTI"]),
+        );
+        let expected = WhenResult {
+            conditions_satisfied: true,
+            target_assets: vec![TargetAsset::full_percent(AssetSymbol::new("VTI"))],
+        };
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn evaluate_cooled_down() {
+        let rule = WhenCooldownRule::boxed(24.0);
+        let result = rule.evaluate(
+            &state(Duration::hours(24)),
+            ForResult::full_percent_fixture(&["SPY", "VTI"]),
+        );
+        assert_eq!(
+            ForResult::full_percent_fixture(&["SPY", "VTI"]).target_assets,
+            result.target_assets
+        );
+    }
+
+    #[test]
+    fn trace() {
+        let rule = WhenCooldownRule::boxed(24.0);
+        let trace = rule.trace(
+            &state(Duration::hours(12)),
+            ForResult::full_percent_fixture(&["SPY", "VTI"]),
+        );
+        assert_eq!(trace.description, "no trades in 24.00 hours");
+        assert_eq!(
+            trace.assets,
+            vec![
+                AssetVerdict::new(
+                    AssetSymbol::new("SPY"),
+                    false,
+                    Some("last traded 12.00 hours ago".to_string())
+                ),
+                AssetVerdict::new(
+                    AssetSymbol::new("VTI"),
+                    true,
+                    Some("no recent trades".to_string())
+                ),
+            ]
+        );
+    }
+
+    fn state(since_fill: Duration) -> TacticState {
+        let fixture = TacticState::fixture();
+        let filled_at = fixture.now - since_fill;
+        TacticState {
+            last_order_times: HashMap::from([(AssetSymbol::new("SPY"), filled_at)]),
+            ..fixture
+        }
+    }
+}
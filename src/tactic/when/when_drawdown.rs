@@ -0,0 +1,257 @@
+use crate::bool::BooleanWhen;
+use crate::tactic::r#for::ForResult;
+use crate::tactic::state::TacticState;
+use crate::tactic::target::TargetAsset;
+use crate::tactic::when::{AssetVerdict, WhenResult, WhenRule, WhenTrace};
+use log::{info, warn};
+
+/// A trailing stop, satisfied once a position's price falls `drawdown_from_high_percent` below
+/// the highest price it reached since it was opened.
+#[derive(Debug, Default, PartialEq)]
+pub struct WhenDrawdownRule {
+    drawdown_from_high_percent: f64,
+}
+
+/// The values compared when checking a position's drawdown.
+#[derive(Debug, PartialEq)]
+struct Drawdown {
+    high: f64,
+    current_price: f64,
+    percent: f64,
+}
+
+impl WhenDrawdownRule {
+    pub fn boxed(drawdown_from_high_percent: f64) -> Box<dyn WhenRule> {
+        Box::new(Self {
+            drawdown_from_high_percent,
+        })
+    }
+
+    fn is_drawn_down(&self, state: &TacticState, target_asset: &TargetAsset) -> bool {
+        match self.drawdown(state, target_asset) {
+            Ok(drawdown) => self.is_satisfied(&drawdown).when_false(|| {
+                info!(
+                    "when_drawdown: {} was {:.2}% below its high of {:.2}, expecting {:.2}%",
+                    target_asset.symbol,
+                    drawdown.percent,
+                    drawdown.high,
+                    self.drawdown_from_high_percent
+                )
+            }),
+            Err(reason) => {
+                warn!("when_drawdown: {reason} for: {}", target_asset.symbol);
+                false
+            }
+        }
+    }
+
+    fn is_satisfied(&self, drawdown: &Drawdown) -> bool {
+        drawdown.percent >= self.drawdown_from_high_percent
+    }
+
+    /// The high water mark is the highest of the entry price, the current price and the daily
+    /// highs since the position's entry date. Only long positions draw down from a high.
+    fn drawdown(
+        &self,
+        state: &TacticState,
+        target_asset: &TargetAsset,
+    ) -> Result<Drawdown, &'static str> {
+        let symbol = &target_asset.symbol;
+        let position = state.long_position(symbol).ok_or("no long position")?;
+        let current_price = position.current_price.ok_or("no current price")?;
+        let entry_time = state.entry_times.get(symbol).ok_or("no entry time")?;
+        let analysis = state.bar_analysis.get(symbol).ok_or("no bar analysis")?;
+        let entry_date = entry_time.date_naive();
+        let high = analysis
+            .one_year
+            .bars
+            .iter()
+            .filter(|bar| bar.timestamp.date_naive() >= entry_date)
+            .map(|bar| bar.high)
+            .fold(position.average_entry_price.max(current_price), f64::max);
+        if high <= 0.0 {
+            return Err("high is not valid");
+        }
+        Ok(Drawdown {
+            high,
+            current_price,
+            percent: (high - current_price) / high * 100.0,
+        })
+    }
+}
+
+impl WhenRule for WhenDrawdownRule {
+    fn evaluate(&self, state: &TacticState, for_result: ForResult) -> WhenResult {
+        let drawn_down_assets = for_result
+            .target_assets
+            .iter()
+            .filter(|t| self.is_drawn_down(state, t))
+            .cloned()
+            .collect::<Vec<_>>();
+        WhenResult {
+            conditions_satisfied: !drawn_down_assets.is_empty(),
+            target_assets: drawn_down_assets,
+        }
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "drawdown from high of {:.2}%",
+            self.drawdown_from_high_percent
+        )
+    }
+
+    fn trace(&self, state: &TacticState, for_result: ForResult) -> WhenTrace {
+        let assets = for_result
+            .target_assets
+            .into_iter()
+            .map(|t| match self.drawdown(state, &t) {
+                Ok(drawdown) => {
+                    let detail = format!(
+                        "price {:.2} is {:.2}% below high {:.2}",
+                        drawdown.current_price, drawdown.percent, drawdown.high
+                    );
+                    AssetVerdict::new(t.symbol, self.is_satisfied(&drawdown), Some(detail))
+                }
+                Err(reason) => AssetVerdict::new(t.symbol, false, Some(reason.to_string())),
+            })
+            .collect();
+        WhenTrace {
+            description: self.description(),
+            assets,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asset::AssetSymbol;
+    use crate::date::DateTimeFixture;
+    use crate::platform::position::Position;
+    use crate::platform::side::Side;
+    use crate::tactic::order_tag::OrderTag;
+    use chrono::Duration;
+    use std::collections::HashMap;
+
+    // The fixture's one year bars all share the fixture date and have highs of 600, 700 and 800.
+
+    #[test]
+    fn evaluate_satisfied() {
+        let rule = WhenDrawdownRule::boxed(10.0);
+        let result = rule.evaluate(
+            &state(720.0, Duration::zero()),
+            ForResult::full_percent_fixture(&["SPY", "VTI"]),
+        );
+        let expected = WhenResult {
+            conditions_satisfied: true,
+            target_assets: vec![TargetAsset::full_percent(AssetSymbol::new("SPY"))],
+        };
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn evaluate_not_satisfied() {
+        let rule = WhenDrawdownRule::boxed(10.0);
+        let result = rule.evaluate(
+            &state(721.0, Duration::zero()),
+            ForResult::full_percent_fixture(&["SPY", "VTI"]),
+        );
+        assert_eq!(WhenResult::default(), result);
+    }
+
+    #[test]
+    fn evaluate_ignores_bars_before_entry() {
+        // Entered the day after the bars, so the high is the entry price of 500.
+        let rule = WhenDrawdownRule::boxed(10.0);
+        let result = rule.evaluate(
+            &state(460.0, Duration::days(1)),
+            ForResult::full_percent_fixture(&["SPY", "VTI"]),
+        );
+        assert_eq!(WhenResult::default(), result);
+    }
+
+    #[test]
+    fn evaluate_no_entry_time() {
+        let state = TacticState {
+            entry_times: HashMap::new(),
+            ..state(100.0, Duration::zero())
+        };
+        let rule = WhenDrawdownRule::boxed(10.0);
+        let result = rule.evaluate(&state, ForResult::full_percent_fixture(&["SPY", "VTI"]));
+        assert_eq!(WhenResult::default(), result);
+    }
+
+    #[test]
+    fn evaluate_held_before_order_history() {
+        // Bought before the order history starts, so every bar since then counts.
+        let history_start = DateTimeFixture::utc() - Duration::days(365);
+        let state = TacticState {
+            entry_times: HashMap::new(),
+            ..state(720.0, Duration::zero())
+        }
+        .with_order_history(&[], &OrderTag::new("test", "sell"), history_start);
+        let rule = WhenDrawdownRule::boxed(10.0);
+        let result = rule.evaluate(&state, ForResult::full_percent_fixture(&["SPY", "VTI"]));
+        let expected = WhenResult {
+            conditions_satisfied: true,
+            target_assets: vec![TargetAsset::full_percent(AssetSymbol::new("SPY"))],
+        };
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn evaluate_short_position() {
+        let mut state = state(100.0, Duration::zero());
+        state
+            .positions
+            .get_mut(&AssetSymbol::new("SPY"))
+            .unwrap()
+            .side = Side::Short;
+        let rule = WhenDrawdownRule::boxed(10.0);
+        let result = rule.evaluate(&state, ForResult::full_percent_fixture(&["SPY", "VTI"]));
+        assert_eq!(WhenResult::default(), result);
+    }
+
+    #[test]
+    fn trace() {
+        let rule = WhenDrawdownRule::boxed(10.0);
+        let trace = rule.trace(
+            &state(720.0, Duration::zero()),
+            ForResult::full_percent_fixture(&["SPY", "VTI"]),
+        );
+        assert_eq!(trace.description, "drawdown from high of 10.00%");
+        assert_eq!(
+            trace.assets,
+            vec![
+                AssetVerdict::new(
+                    AssetSymbol::new("SPY"),
+                    true,
+                    Some("price 720.00 is 10.00% below high 800.00".to_string())
+                ),
+                AssetVerdict::new(
+                    AssetSymbol::new("VTI"),
+                    false,
+                    Some("no current price".to_string())
+                ),
+            ]
+        );
+    }
+
+    fn state(current_price: f64, entry_offset: Duration) -> TacticState {
+        let spy = AssetSymbol::new("SPY");
+        let position = Position {
+            average_entry_price: 500.0,
+            current_price: Some(current_price),
+            ..Position::fixture(spy.clone())
+        };
+        let mut positions = TacticState::fixture().positions;
+        positions.insert(spy.clone(), position);
+        TacticState {
+            positions,
+            entry_times: HashMap::from([(spy, DateTimeFixture::utc() + entry_offset)]),
+            ..TacticState::fixture()
+        }
+    }
+}
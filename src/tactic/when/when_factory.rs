@@ -2,28 +2,127 @@ use crate::config::tactic::when::WhenConfig;
 use crate::tactic::null::NullRule;
 use crate::tactic::when::when_all_of::WhenAllOfRule;
 use crate::tactic::when::when_always::WhenAlwaysRule;
+use crate::tactic::when::when_any_of::WhenAnyOfRule;
 use crate::tactic::when::when_below_median::WhenBelowMedianRule;
+use crate::tactic::when::when_bollinger_band::WhenBollingerBandRule;
+use crate::tactic::when::when_cooldown::WhenCooldownRule;
+use crate::tactic::when::when_drawdown::WhenDrawdownRule;
 use crate::tactic::when::when_gain_above::WhenGainAboveRule;
-use crate::tactic::when::WhenRule;
+use crate::tactic::when::when_held_at_least::WhenHeldAtLeastRule;
+use crate::tactic::when::when_loss_below::WhenLossBelowRule;
+use crate::tactic::when::when_market_time::{MarketTime, WhenMarketTimeRule};
+use crate::tactic::when::when_moving_average_cross::{CrossDirection, WhenMovingAverageCrossRule};
+use crate::tactic::when::when_not::WhenNotRule;
+use crate::tactic::when::when_price_moving_average::WhenPriceMovingAverageRule;
+use crate::tactic::when::when_rsi::WhenRsiRule;
+use crate::tactic::when::when_schedule::{Schedule, WhenScheduleRule};
+use crate::tactic::when::{Threshold, WhenRule};
 
 pub struct WhenFactory;
 
 impl WhenFactory {
     pub fn create_rule(config: WhenConfig) -> Box<dyn WhenRule> {
         match config {
-            WhenConfig::AllOf { all_off } => {
-                let rules = all_off.into_iter().map(Self::create_rule).collect();
+            WhenConfig::AboveMovingAverage {
+                above_moving_average_days,
+                moving_average,
+            } => WhenPriceMovingAverageRule::boxed(
+                Threshold::Above,
+                above_moving_average_days,
+                moving_average,
+            ),
+            WhenConfig::AboveUpperBand {
+                band_period,
+                band_stddev,
+                ..
+            } => WhenBollingerBandRule::boxed(Threshold::Above, band_period, band_stddev),
+            WhenConfig::AllOf { all_of } => {
+                let rules = all_of.into_iter().map(Self::create_rule).collect();
                 WhenAllOfRule::boxed(rules)
             }
+            WhenConfig::AnyOf { any_of } => {
+                let rules = any_of.into_iter().map(Self::create_rule).collect();
+                WhenAnyOfRule::boxed(rules)
+            }
             WhenConfig::Always { .. } => WhenAlwaysRule::boxed(),
             WhenConfig::BelowMedian {
                 below_median_percent,
                 median_period,
             } => WhenBelowMedianRule::boxed(below_median_percent, median_period),
+            WhenConfig::BelowLowerBand {
+                band_period,
+                band_stddev,
+                ..
+            } => WhenBollingerBandRule::boxed(Threshold::Below, band_period, band_stddev),
+            WhenConfig::BelowMovingAverage {
+                below_moving_average_days,
+                moving_average,
+            } => WhenPriceMovingAverageRule::boxed(
+                Threshold::Below,
+                below_moving_average_days,
+                moving_average,
+            ),
+            WhenConfig::Between {
+                between: (start, end),
+            } => WhenMarketTimeRule::boxed(MarketTime::Between(start, end)),
+            WhenConfig::Cooldown { cooldown_hours } => WhenCooldownRule::boxed(cooldown_hours),
+            WhenConfig::DeathCross {
+                fast_period,
+                slow_period,
+                moving_average,
+                ..
+            } => WhenMovingAverageCrossRule::boxed(
+                CrossDirection::Death,
+                fast_period,
+                slow_period,
+                moving_average,
+            ),
+            WhenConfig::DrawdownFromHigh {
+                drawdown_from_high_percent,
+            } => WhenDrawdownRule::boxed(drawdown_from_high_percent),
+            WhenConfig::Every { every } => WhenScheduleRule::boxed(Schedule::Every(every)),
             WhenConfig::GainAbove { gain_above_percent } => {
                 WhenGainAboveRule::boxed(gain_above_percent)
             }
+            WhenConfig::GoldenCross {
+                fast_period,
+                slow_period,
+                moving_average,
+                ..
+            } => WhenMovingAverageCrossRule::boxed(
+                CrossDirection::Golden,
+                fast_period,
+                slow_period,
+                moving_average,
+            ),
+            WhenConfig::HeldAtLeast { held_at_least_days } => {
+                WhenHeldAtLeastRule::boxed(held_at_least_days)
+            }
+            WhenConfig::LossBelow { loss_below_percent } => {
+                WhenLossBelowRule::boxed(loss_below_percent)
+            }
+            WhenConfig::MinutesAfterOpen { minutes_after_open } => {
+                WhenMarketTimeRule::boxed(MarketTime::MinutesAfterOpen(minutes_after_open))
+            }
+            WhenConfig::MinutesBeforeClose {
+                minutes_before_close,
+            } => WhenMarketTimeRule::boxed(MarketTime::MinutesBeforeClose(minutes_before_close)),
             WhenConfig::Never { .. } => NullRule::when_boxed(),
+            WhenConfig::Not { not } => WhenNotRule::boxed(Self::create_rule(*not)),
+            WhenConfig::OnDayOfMonth { on_day_of_month } => {
+                WhenScheduleRule::boxed(Schedule::OnDayOfMonth(on_day_of_month))
+            }
+            WhenConfig::RsiAbove {
+                rsi_above,
+                rsi_period,
+            } => WhenRsiRule::boxed(Threshold::Above, rsi_above, rsi_period),
+            WhenConfig::RsiBelow {
+                rsi_below,
+                rsi_period,
+            } => WhenRsiRule::boxed(Threshold::Below, rsi_below, rsi_period),
+            WhenConfig::Weekdays { weekdays } => {
+                WhenMarketTimeRule::boxed(MarketTime::Weekdays(weekdays))
+            }
         }
     }
 }
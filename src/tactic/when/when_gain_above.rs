@@ -3,7 +3,7 @@ use crate::bool::BooleanWhen;
 use crate::tactic::r#for::ForResult;
 use crate::tactic::state::TacticState;
 use crate::tactic::target::TargetAsset;
-use crate::tactic::when::{WhenResult, WhenRule};
+use crate::tactic::when::{AssetVerdict, WhenResult, WhenRule, WhenTrace};
 
 #[derive(Debug, Default, PartialEq)]
 pub struct WhenGainAboveRule {
@@ -16,17 +16,21 @@ impl WhenGainAboveRule {
     }
 
     fn is_gain_above(&self, state: &TacticState, target_asset: &TargetAsset) -> bool {
+        self.gain(state, target_asset)
+            .map(|g| {
+                (g >= self.gain_above_percent)
+                    .when_false(|| self.log_gain_not_above(g, &target_asset.symbol))
+            })
+            .unwrap_or(false)
+    }
+
+    fn gain(&self, state: &TacticState, target_asset: &TargetAsset) -> Result<f64, &'static str> {
         if self.is_state_valid(state, target_asset) {
-            return false;
+            return Err("no position");
         }
 
         let position = &state.positions[&target_asset.symbol];
-        let gain = position.unrealized_gain_total_percent;
-        gain.map(|g| {
-            (g >= self.gain_above_percent)
-                .when_false(|| self.log_gain_not_above(g, &position.symbol))
-        })
-        .unwrap_or(false)
+        position.gain_percent().ok_or("no unrealized gain")
     }
 
     fn log_gain_not_above(&self, g: f64, symbol: &AssetSymbol) {
@@ -56,6 +60,30 @@ impl WhenRule for WhenGainAboveRule {
             target_assets: assets_above_gain,
         }
     }
+
+    fn description(&self) -> String {
+        format!("gain above {:.2}%", self.gain_above_percent)
+    }
+
+    fn trace(&self, state: &TacticState, for_result: ForResult) -> WhenTrace {
+        let assets = for_result
+            .target_assets
+            .into_iter()
+            .map(|t| match self.gain(state, &t) {
+                Ok(gain) => AssetVerdict::new(
+                    t.symbol,
+                    gain >= self.gain_above_percent,
+                    Some(format!("gain is {gain:.2}%")),
+                ),
+                Err(reason) => AssetVerdict::new(t.symbol, false, Some(reason.to_string())),
+            })
+            .collect();
+        WhenTrace {
+            description: self.description(),
+            assets,
+            ..Default::default()
+        }
+    }
 }
 
 #[cfg(test)]
@@ -63,6 +91,7 @@ mod tests {
     use super::*;
     use crate::asset::AssetSymbol;
     use crate::platform::position::Position;
+    use crate::platform::side::Side;
     use std::collections::HashMap;
 
     #[test]
@@ -156,6 +185,61 @@ mod tests {
         assert_eq!(expected, result);
     }
 
+    #[test]
+    fn evaluate_short_falling() {
+        let spy = AssetSymbol::new("SPY");
+        let position = Position {
+            average_entry_price: 100.0,
+            current_price: Some(85.0),
+            side: Side::Short,
+            ..Position::fixture(spy.clone())
+        };
+        let state = TacticState {
+            positions: HashMap::from([(spy.clone(), position)]),
+            ..TacticState::fixture()
+        };
+        let rule = WhenGainAboveRule::boxed(10.0);
+        let for_result = ForResult {
+            target_assets: target_assets(),
+        };
+        let result = rule.evaluate(&state, for_result);
+        assert_eq!(
+            result.target_assets,
+            vec![TargetAsset::full_percent(AssetSymbol::new("SPY"))]
+        );
+    }
+
+    #[test]
+    fn trace() {
+        let spy = AssetSymbol::new("SPY");
+        let position = Position {
+            unrealized_gain_total_percent: Some(12.5),
+            ..Position::fixture(spy.clone())
+        };
+        let state = TacticState {
+            positions: HashMap::from([(spy.clone(), position)]),
+            ..TacticState::fixture()
+        };
+        let rule = WhenGainAboveRule::boxed(10.0);
+        let for_result = ForResult {
+            target_assets: target_assets(),
+        };
+        let trace = rule.trace(&state, for_result);
+        let expected = WhenTrace {
+            description: "gain above 10.00%".to_string(),
+            assets: vec![
+                AssetVerdict::new(spy, true, Some("gain is 12.50%".to_string())),
+                AssetVerdict::new(
+                    AssetSymbol::new("VTI"),
+                    false,
+                    Some("no position".to_string()),
+                ),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(expected, trace);
+    }
+
     fn target_assets() -> Vec<TargetAsset> {
         vec![
             TargetAsset::full_percent(AssetSymbol::new("SPY")),
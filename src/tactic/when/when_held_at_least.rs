@@ -0,0 +1,180 @@
+use crate::bool::BooleanWhen;
+use crate::tactic::r#for::ForResult;
+use crate::tactic::state::TacticState;
+use crate::tactic::target::TargetAsset;
+use crate::tactic::when::{AssetVerdict, WhenResult, WhenRule, WhenTrace};
+use log::{info, warn};
+
+/// Satisfied once a position has been held for at least `held_at_least_days`, counted from the
+/// first buy after the position was last flat. Positions held since before the order history are
+/// counted from its start.
+#[derive(Debug, Default, PartialEq)]
+pub struct WhenHeldAtLeastRule {
+    held_at_least_days: f64,
+}
+
+impl WhenHeldAtLeastRule {
+    pub fn boxed(held_at_least_days: f64) -> Box<dyn WhenRule> {
+        Box::new(Self { held_at_least_days })
+    }
+
+    fn days_held(
+        &self,
+        state: &TacticState,
+        target_asset: &TargetAsset,
+    ) -> Result<f64, &'static str> {
+        if !state.positions.contains_key(&target_asset.symbol) {
+            return Err("no position");
+        }
+        let entry_time = state
+            .entry_times
+            .get(&target_asset.symbol)
+            .ok_or("no entry time")?;
+        Ok((state.now - *entry_time).num_seconds() as f64 / 86_400.0)
+    }
+
+    fn is_held_long_enough(&self, state: &TacticState, target_asset: &TargetAsset) -> bool {
+        match self.days_held(state, target_asset) {
+            Ok(days) => (days >= self.held_at_least_days).when_false(|| {
+                info!(
+                    "when_held_at_least: {} was held {:.2} days, expecting {:.2}",
+                    target_asset.symbol, days, self.held_at_least_days
+                )
+            }),
+            Err(reason) => {
+                warn!("when_held_at_least: {reason} for: {}", target_asset.symbol);
+                false
+            }
+        }
+    }
+}
+
+impl WhenRule for WhenHeldAtLeastRule {
+    fn evaluate(&self, state: &TacticState, for_result: ForResult) -> WhenResult {
+        let held_assets = for_result
+            .target_assets
+            .iter()
+            .filter(|t| self.is_held_long_enough(state, t))
+            .cloned()
+            .collect::<Vec<_>>();
+        WhenResult {
+            conditions_satisfied: !held_assets.is_empty(),
+            target_assets: held_assets,
+        }
+    }
+
+    fn description(&self) -> String {
+        format!("held at least {:.2} days", self.held_at_least_days)
+    }
+
+    fn trace(&self, state: &TacticState, for_result: ForResult) -> WhenTrace {
+        let assets = for_result
+            .target_assets
+            .into_iter()
+            .map(|t| match self.days_held(state, &t) {
+                Ok(days) => AssetVerdict::new(
+                    t.symbol,
+                    days >= self.held_at_least_days,
+                    Some(format!("held {days:.2} days")),
+                ),
+                Err(reason) => AssetVerdict::new(t.symbol, false, Some(reason.to_string())),
+            })
+            .collect();
+        WhenTrace {
+            description: self.description(),
+            assets,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asset::AssetSymbol;
+    use crate::tactic::order_tag::OrderTag;
+    use chrono::Duration;
+    use std::collections::HashMap;
+
+    #[test]
+    fn evaluate_held_long_enough() {
+        let rule = WhenHeldAtLeastRule::boxed(30.0);
+        let result = rule.evaluate(
+            &state(Duration::days(30)),
+            ForResult::full_percent_fixture(&["SPY", "VTI"]),
+        );
+        let expected = WhenResult {
+            conditions_satisfied: true,
+            target_assets: vec![TargetAsset::full_percent(AssetSymbol::new("SPY"))],
+        };
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn evaluate_not_held_long_enough() {
+        let rule = WhenHeldAtLeastRule::boxed(30.0);
+        let result = rule.evaluate(
+            &state(Duration::days(29)),
+            ForResult::full_percent_fixture(&["SPY", "VTI"]),
+        );
+        assert_eq!(WhenResult::default(), result);
+    }
+
+    #[test]
+    fn evaluate_held_before_order_history() {
+        let fixture = TacticState::fixture();
+        let history_start = fixture.now - Duration::days(365);
+        let state = fixture.with_order_history(&[], &OrderTag::new("test", "sell"), history_start);
+        let rule = WhenHeldAtLeastRule::boxed(30.0);
+        let result = rule.evaluate(&state, ForResult::full_percent_fixture(&["SPY", "VTI"]));
+        assert_eq!(
+            ForResult::full_percent_fixture(&["SPY", "VTI"]).target_assets,
+            result.target_assets
+        );
+    }
+
+    #[test]
+    fn evaluate_no_position() {
+        let state = TacticState {
+            positions: HashMap::new(),
+            ..state(Duration::days(30))
+        };
+        let rule = WhenHeldAtLeastRule::boxed(30.0);
+        let result = rule.evaluate(&state, ForResult::full_percent_fixture(&["SPY", "VTI"]));
+        assert_eq!(WhenResult::default(), result);
+    }
+
+    #[test]
+    fn trace() {
+        let rule = WhenHeldAtLeastRule::boxed(30.0);
+        let trace = rule.trace(
+            &state(Duration::days(45)),
+            ForResult::full_percent_fixture(&["SPY", "VTI"]),
+        );
+        assert_eq!(trace.description, "held at least 30.00 days");
+        assert_eq!(
+            trace.assets,
+            vec![
+                AssetVerdict::new(
+                    AssetSymbol::new("SPY"),
+                    true,
+                    Some("held 45.00 days".to_string())
+                ),
+                AssetVerdict::new(
+                    AssetSymbol::new("VTI"),
+                    false,
+                    Some("no entry time".to_string())
+                ),
+            ]
+        );
+    }
+
+    fn state(held: Duration) -> TacticState {
+        let fixture = TacticState::fixture();
+        let entry_time = fixture.now - held;
+        TacticState {
+            entry_times: HashMap::from([(AssetSymbol::new("SPY"), entry_time)]),
+            ..fixture
+        }
+    }
+}
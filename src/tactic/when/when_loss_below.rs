@@ -0,0 +1,198 @@
+use crate::asset::AssetSymbol;
+use crate::bool::BooleanWhen;
+use crate::tactic::r#for::ForResult;
+use crate::tactic::state::TacticState;
+use crate::tactic::target::TargetAsset;
+use crate::tactic::when::{AssetVerdict, WhenResult, WhenRule, WhenTrace};
+
+/// A stop loss, satisfied once a position's total unrealized loss reaches `loss_below_percent`.
+#[derive(Debug, Default, PartialEq)]
+pub struct WhenLossBelowRule {
+    loss_below_percent: f64,
+}
+
+impl WhenLossBelowRule {
+    pub fn boxed(loss_below_percent: f64) -> Box<dyn WhenRule> {
+        // Accept the loss either as a positive amount or as a negative gain.
+        Box::new(Self {
+            loss_below_percent: loss_below_percent.abs(),
+        })
+    }
+
+    fn is_loss_below(&self, state: &TacticState, target_asset: &TargetAsset) -> bool {
+        self.gain(state, target_asset)
+            .map(|g| {
+                self.is_satisfied(g)
+                    .when_false(|| self.log_loss_not_below(g, &target_asset.symbol))
+            })
+            .unwrap_or(false)
+    }
+
+    fn is_satisfied(&self, gain: f64) -> bool {
+        gain <= -self.loss_below_percent
+    }
+
+    fn gain(&self, state: &TacticState, target_asset: &TargetAsset) -> Result<f64, &'static str> {
+        let position = state
+            .positions
+            .get(&target_asset.symbol)
+            .ok_or("no position")?;
+        position.gain_percent().ok_or("no unrealized gain")
+    }
+
+    fn log_loss_not_below(&self, g: f64, symbol: &AssetSymbol) {
+        log::info!(
+            "when_loss_below: Gain for {} was {:.2}, expecting -{}",
+            symbol,
+            g,
+            self.loss_below_percent
+        )
+    }
+}
+
+impl WhenRule for WhenLossBelowRule {
+    fn evaluate(&self, state: &TacticState, for_result: ForResult) -> WhenResult {
+        let assets_below_loss = for_result
+            .target_assets
+            .iter()
+            .filter(|target_asset| self.is_loss_below(state, target_asset))
+            .cloned()
+            .collect::<Vec<_>>();
+        WhenResult {
+            conditions_satisfied: !assets_below_loss.is_empty(),
+            target_assets: assets_below_loss,
+        }
+    }
+
+    fn description(&self) -> String {
+        format!("loss below -{:.2}%", self.loss_below_percent)
+    }
+
+    fn trace(&self, state: &TacticState, for_result: ForResult) -> WhenTrace {
+        let assets = for_result
+            .target_assets
+            .into_iter()
+            .map(|t| match self.gain(state, &t) {
+                Ok(gain) => AssetVerdict::new(
+                    t.symbol,
+                    self.is_satisfied(gain),
+                    Some(format!("gain is {gain:.2}%")),
+                ),
+                Err(reason) => AssetVerdict::new(t.symbol, false, Some(reason.to_string())),
+            })
+            .collect();
+        WhenTrace {
+            description: self.description(),
+            assets,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::position::Position;
+    use crate::platform::side::Side;
+    use std::collections::HashMap;
+
+    #[test]
+    fn evaluate_no_positions() {
+        let state = TacticState {
+            positions: HashMap::new(),
+            ..TacticState::fixture()
+        };
+        let rule = WhenLossBelowRule::boxed(8.0);
+        let result = rule.evaluate(&state, ForResult::full_percent_fixture(&["SPY", "VTI"]));
+        assert_eq!(WhenResult::default(), result);
+    }
+
+    #[test]
+    fn evaluate_not_satisfied() {
+        let rule = WhenLossBelowRule::boxed(8.0);
+        let result = rule.evaluate(
+            &state_with_gain(-7.9),
+            ForResult::full_percent_fixture(&["SPY", "VTI"]),
+        );
+        assert_eq!(WhenResult::default(), result);
+    }
+
+    #[test]
+    fn evaluate_satisfied() {
+        let rule = WhenLossBelowRule::boxed(8.0);
+        let result = rule.evaluate(
+            &state_with_gain(-8.0),
+            ForResult::full_percent_fixture(&["SPY", "VTI"]),
+        );
+        let expected = WhenResult {
+            conditions_satisfied: true,
+            target_assets: vec![TargetAsset::full_percent(AssetSymbol::new("SPY"))],
+        };
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn evaluate_negative_threshold() {
+        let rule = WhenLossBelowRule::boxed(-8.0);
+        let result = rule.evaluate(
+            &state_with_gain(-10.0),
+            ForResult::full_percent_fixture(&["SPY", "VTI"]),
+        );
+        assert!(result.conditions_satisfied);
+    }
+
+    #[test]
+    fn evaluate_short_rising() {
+        let spy = AssetSymbol::new("SPY");
+        let position = Position {
+            average_entry_price: 100.0,
+            current_price: Some(110.0),
+            side: Side::Short,
+            ..Position::fixture(spy.clone())
+        };
+        let state = TacticState {
+            positions: HashMap::from([(spy, position)]),
+            ..TacticState::fixture()
+        };
+        let rule = WhenLossBelowRule::boxed(8.0);
+        let result = rule.evaluate(&state, ForResult::full_percent_fixture(&["SPY", "VTI"]));
+        assert!(result.conditions_satisfied);
+    }
+
+    #[test]
+    fn trace() {
+        let rule = WhenLossBelowRule::boxed(8.0);
+        let trace = rule.trace(
+            &state_with_gain(-12.5),
+            ForResult::full_percent_fixture(&["SPY", "VTI"]),
+        );
+        assert_eq!(trace.description, "loss below -8.00%");
+        assert_eq!(
+            trace.assets,
+            vec![
+                AssetVerdict::new(
+                    AssetSymbol::new("SPY"),
+                    true,
+                    Some("gain is -12.50%".to_string())
+                ),
+                AssetVerdict::new(
+                    AssetSymbol::new("VTI"),
+                    false,
+                    Some("no position".to_string())
+                ),
+            ]
+        );
+    }
+
+    fn state_with_gain(gain: f64) -> TacticState {
+        let spy = AssetSymbol::new("SPY");
+        let position = Position {
+            unrealized_gain_total_percent: Some(gain),
+            ..Position::fixture(spy.clone())
+        };
+        TacticState {
+            positions: HashMap::from([(spy, position)]),
+            ..TacticState::fixture()
+        }
+    }
+}
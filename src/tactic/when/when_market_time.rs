@@ -0,0 +1,307 @@
+use crate::platform::calendar::MarketDay;
+use crate::tactic::r#for::ForResult;
+use crate::tactic::state::TacticState;
+use crate::tactic::when::{AssetVerdict, WhenResult, WhenRule, WhenTrace};
+use crate::trading_days::eastern::eastern_date_time;
+use chrono::{Datelike, Duration, NaiveTime, Weekday};
+use itertools::Itertools;
+use log::info;
+
+/// A condition on the market clock. These apply to every target asset alike.
+#[derive(Debug, PartialEq)]
+pub enum MarketTime {
+    /// The eastern time of day is within the range, inclusive.
+    Between(NaiveTime, NaiveTime),
+    /// The eastern day of the week is one of these.
+    Weekdays(Vec<Weekday>),
+    /// At least this many minutes have passed since today's open.
+    MinutesAfterOpen(i64),
+    /// At least this many minutes remain before today's close.
+    MinutesBeforeClose(i64),
+}
+
+pub struct WhenMarketTimeRule {
+    condition: MarketTime,
+}
+
+impl WhenMarketTimeRule {
+    pub fn boxed(condition: MarketTime) -> Box<dyn WhenRule> {
+        Box::new(Self { condition })
+    }
+
+    fn check(&self, state: &TacticState) -> Result<bool, &'static str> {
+        let now = state.now;
+        let eastern = eastern_date_time(now);
+        match &self.condition {
+            MarketTime::Between(start, end) => {
+                let time = eastern.time();
+                Ok(*start <= time && time <= *end)
+            }
+            MarketTime::Weekdays(weekdays) => Ok(weekdays.contains(&eastern.weekday())),
+            MarketTime::MinutesAfterOpen(minutes) => {
+                let day = Self::market_day(state)?;
+                Ok(now >= day.open + Duration::minutes(*minutes) && now < day.close)
+            }
+            MarketTime::MinutesBeforeClose(minutes) => {
+                let day = Self::market_day(state)?;
+                Ok(now >= day.open && now <= day.close - Duration::minutes(*minutes))
+            }
+        }
+    }
+
+    fn market_day(state: &TacticState) -> Result<&MarketDay, &'static str> {
+        state.market_day.as_ref().ok_or("market is closed today")
+    }
+
+    fn is_satisfied(&self, state: &TacticState) -> bool {
+        match self.check(state) {
+            Ok(true) => true,
+            Ok(false) => {
+                info!("when_market_time: not {}", self.description());
+                false
+            }
+            Err(reason) => {
+                info!("when_market_time: {reason}");
+                false
+            }
+        }
+    }
+}
+
+impl WhenRule for WhenMarketTimeRule {
+    fn evaluate(&self, state: &TacticState, for_result: ForResult) -> WhenResult {
+        if self.is_satisfied(state) {
+            WhenResult {
+                conditions_satisfied: !for_result.target_assets.is_empty(),
+                target_assets: for_result.target_assets,
+            }
+        } else {
+            WhenResult::default()
+        }
+    }
+
+    fn description(&self) -> String {
+        match &self.condition {
+            MarketTime::Between(start, end) => format!(
+                "between {} and {} eastern",
+                start.format("%H:%M"),
+                end.format("%H:%M")
+            ),
+            MarketTime::Weekdays(weekdays) => {
+                format!("on {}", weekdays.iter().map(|d| d.to_string()).join(", "))
+            }
+            MarketTime::MinutesAfterOpen(minutes) => format!("{minutes} minutes after open"),
+            MarketTime::MinutesBeforeClose(minutes) => format!("{minutes} minutes before close"),
+        }
+    }
+
+    fn trace(&self, state: &TacticState, for_result: ForResult) -> WhenTrace {
+        let eastern = eastern_date_time(state.now);
+        let (satisfied, detail) = match self.check(state) {
+            Ok(satisfied) => (
+                satisfied,
+                format!("now is {} eastern", eastern.format("%a %H:%M")),
+            ),
+            Err(reason) => (false, reason.to_string()),
+        };
+        let assets = for_result
+            .target_assets
+            .into_iter()
+            .map(|t| AssetVerdict::new(t.symbol, satisfied, Some(detail.clone())))
+            .collect();
+        WhenTrace {
+            description: self.description(),
+            assets,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asset::AssetSymbol;
+    use crate::trading_days::market_day;
+    use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+
+    // 2024-07-02 is a Tuesday, eastern time is UTC-4 and the session runs 13:30 to 20:00 UTC.
+
+    #[test]
+    fn between() {
+        let rule = WhenMarketTimeRule::boxed(MarketTime::Between(time(10, 0), time(15, 30)));
+        assert!(
+            rule.evaluate(
+                &state(14, 0),
+                ForResult::full_percent_fixture(&["SPY", "VTI"])
+            )
+            .conditions_satisfied
+        );
+        assert!(
+            rule.evaluate(
+                &state(19, 30),
+                ForResult::full_percent_fixture(&["SPY", "VTI"])
+            )
+            .conditions_satisfied
+        );
+        assert!(
+            !rule
+                .evaluate(
+                    &state(13, 59),
+                    ForResult::full_percent_fixture(&["SPY", "VTI"])
+                )
+                .conditions_satisfied
+        );
+        assert!(
+            !rule
+                .evaluate(
+                    &state(19, 31),
+                    ForResult::full_percent_fixture(&["SPY", "VTI"])
+                )
+                .conditions_satisfied
+        );
+    }
+
+    #[test]
+    fn weekdays() {
+        let tuesday = WhenMarketTimeRule::boxed(MarketTime::Weekdays(vec![Weekday::Tue]));
+        let monday = WhenMarketTimeRule::boxed(MarketTime::Weekdays(vec![Weekday::Mon]));
+        let result = tuesday.evaluate(
+            &state(14, 0),
+            ForResult::full_percent_fixture(&["SPY", "VTI"]),
+        );
+        let expected = WhenResult {
+            conditions_satisfied: true,
+            target_assets: ForResult::full_percent_fixture(&["SPY", "VTI"]).target_assets,
+        };
+        assert_eq!(expected, result);
+        assert_eq!(
+            WhenResult::default(),
+            monday.evaluate(
+                &state(14, 0),
+                ForResult::full_percent_fixture(&["SPY", "VTI"])
+            )
+        );
+    }
+
+    #[test]
+    fn weekdays_uses_eastern_date() {
+        // 02:00 UTC on Wednesday is still Tuesday evening in New York.
+        let rule = WhenMarketTimeRule::boxed(MarketTime::Weekdays(vec![Weekday::Tue]));
+        let state = TacticState {
+            now: Utc.with_ymd_and_hms(2024, 7, 3, 2, 0, 0).unwrap(),
+            ..TacticState::fixture()
+        };
+        assert!(
+            rule.evaluate(&state, ForResult::full_percent_fixture(&["SPY", "VTI"]))
+                .conditions_satisfied
+        );
+    }
+
+    #[test]
+    fn minutes_after_open() {
+        let rule = WhenMarketTimeRule::boxed(MarketTime::MinutesAfterOpen(30));
+        assert!(
+            !rule
+                .evaluate(
+                    &state(13, 59),
+                    ForResult::full_percent_fixture(&["SPY", "VTI"])
+                )
+                .conditions_satisfied
+        );
+        assert!(
+            rule.evaluate(
+                &state(14, 0),
+                ForResult::full_percent_fixture(&["SPY", "VTI"])
+            )
+            .conditions_satisfied
+        );
+        assert!(
+            !rule
+                .evaluate(
+                    &state(20, 0),
+                    ForResult::full_percent_fixture(&["SPY", "VTI"])
+                )
+                .conditions_satisfied
+        );
+    }
+
+    #[test]
+    fn minutes_before_close() {
+        let rule = WhenMarketTimeRule::boxed(MarketTime::MinutesBeforeClose(15));
+        assert!(
+            rule.evaluate(
+                &state(19, 45),
+                ForResult::full_percent_fixture(&["SPY", "VTI"])
+            )
+            .conditions_satisfied
+        );
+        assert!(
+            !rule
+                .evaluate(
+                    &state(19, 46),
+                    ForResult::full_percent_fixture(&["SPY", "VTI"])
+                )
+                .conditions_satisfied
+        );
+        assert!(
+            !rule
+                .evaluate(
+                    &state(13, 0),
+                    ForResult::full_percent_fixture(&["SPY", "VTI"])
+                )
+                .conditions_satisfied
+        );
+    }
+
+    #[test]
+    fn minutes_after_open_without_session() {
+        let rule = WhenMarketTimeRule::boxed(MarketTime::MinutesAfterOpen(30));
+        let state = TacticState {
+            market_day: None,
+            ..state(15, 0)
+        };
+        assert_eq!(
+            WhenResult::default(),
+            rule.evaluate(&state, ForResult::full_percent_fixture(&["SPY", "VTI"]))
+        );
+    }
+
+    #[test]
+    fn trace() {
+        let rule = WhenMarketTimeRule::boxed(MarketTime::Between(time(10, 0), time(15, 30)));
+        let trace = rule.trace(
+            &state(13, 45),
+            ForResult::full_percent_fixture(&["SPY", "VTI"]),
+        );
+        assert_eq!(trace.description, "between 10:00 and 15:30 eastern");
+        assert_eq!(
+            trace.assets[0],
+            AssetVerdict::new(
+                AssetSymbol::new("SPY"),
+                false,
+                Some("now is Tue 09:45 eastern".to_string())
+            )
+        );
+    }
+
+    #[test]
+    fn description_weekdays() {
+        let rule =
+            WhenMarketTimeRule::boxed(MarketTime::Weekdays(vec![Weekday::Mon, Weekday::Wed]));
+        assert_eq!(rule.description(), "on Mon, Wed");
+    }
+
+    fn state(hour: u32, minute: u32) -> TacticState {
+        let now: DateTime<Utc> = Utc.with_ymd_and_hms(2024, 7, 2, hour, minute, 0).unwrap();
+        let date = NaiveDate::from_ymd_opt(2024, 7, 2).unwrap();
+        TacticState {
+            now,
+            market_day: market_day(date),
+            ..TacticState::fixture()
+        }
+    }
+
+    fn time(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+}
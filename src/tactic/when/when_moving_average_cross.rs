@@ -0,0 +1,269 @@
+use crate::bool::BooleanWhen;
+use crate::config::tactic::moving_average::MovingAverage;
+use crate::platform::bars::Bars;
+use crate::tactic::r#for::ForResult;
+use crate::tactic::state::TacticState;
+use crate::tactic::target::TargetAsset;
+use crate::tactic::when::{AssetVerdict, WhenResult, WhenRule, WhenTrace};
+use log::{info, warn};
+
+/// Which way the fast moving average has to cross the slow one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CrossDirection {
+    /// The fast average crossed above the slow average.
+    Golden,
+    /// The fast average crossed below the slow average.
+    Death,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct WhenMovingAverageCrossRule {
+    direction: CrossDirection,
+    fast_period: usize,
+    slow_period: usize,
+    moving_average: MovingAverage,
+}
+
+/// The fast and slow averages as of the latest daily bar and the bar before it.
+#[derive(Debug, PartialEq)]
+struct CrossComparison {
+    previous_fast: f64,
+    previous_slow: f64,
+    fast: f64,
+    slow: f64,
+}
+
+impl WhenMovingAverageCrossRule {
+    pub fn boxed(
+        direction: CrossDirection,
+        fast_period: usize,
+        slow_period: usize,
+        moving_average: MovingAverage,
+    ) -> Box<dyn WhenRule> {
+        Box::new(Self {
+            direction,
+            fast_period,
+            slow_period,
+            moving_average,
+        })
+    }
+
+    fn has_crossed(&self, state: &TacticState, target_asset: &TargetAsset) -> bool {
+        match self.compare(state, target_asset) {
+            Ok(comparison) => self.is_satisfied(&comparison).when_false(|| {
+                info!(
+                    "when_moving_average_cross: no {} for {}",
+                    self.cross_name(),
+                    target_asset.symbol
+                )
+            }),
+            Err(reason) => {
+                warn!(
+                    "when_moving_average_cross: {reason} for: {}",
+                    target_asset.symbol
+                );
+                false
+            }
+        }
+    }
+
+    fn is_satisfied(&self, comparison: &CrossComparison) -> bool {
+        match self.direction {
+            CrossDirection::Golden => {
+                comparison.previous_fast <= comparison.previous_slow
+                    && comparison.fast > comparison.slow
+            }
+            CrossDirection::Death => {
+                comparison.previous_fast >= comparison.previous_slow
+                    && comparison.fast < comparison.slow
+            }
+        }
+    }
+
+    fn compare(
+        &self,
+        state: &TacticState,
+        target_asset: &TargetAsset,
+    ) -> Result<CrossComparison, &'static str> {
+        let analysis = state
+            .bar_analysis
+            .get(&target_asset.symbol)
+            .ok_or("no bar analysis")?;
+        let bars = &analysis.one_year;
+        let previous_bars =
+            Bars::with_bars(bars.bars[..bars.bars.len().saturating_sub(1)].to_vec());
+        let average = |bars: &Bars, period| {
+            self.moving_average
+                .calculate(bars, period)
+                .ok_or("not enough bars")
+        };
+        Ok(CrossComparison {
+            previous_fast: average(&previous_bars, self.fast_period)?,
+            previous_slow: average(&previous_bars, self.slow_period)?,
+            fast: average(bars, self.fast_period)?,
+            slow: average(bars, self.slow_period)?,
+        })
+    }
+
+    fn cross_name(&self) -> &'static str {
+        match self.direction {
+            CrossDirection::Golden => "golden cross",
+            CrossDirection::Death => "death cross",
+        }
+    }
+}
+
+impl WhenRule for WhenMovingAverageCrossRule {
+    fn evaluate(&self, state: &TacticState, for_result: ForResult) -> WhenResult {
+        let crossed_assets = for_result
+            .target_assets
+            .iter()
+            .filter(|t| self.has_crossed(state, t))
+            .cloned()
+            .collect::<Vec<_>>();
+        WhenResult {
+            conditions_satisfied: !crossed_assets.is_empty(),
+            target_assets: crossed_assets,
+        }
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "{} of {} {} over {}",
+            self.cross_name(),
+            self.moving_average,
+            self.fast_period,
+            self.slow_period
+        )
+    }
+
+    fn trace(&self, state: &TacticState, for_result: ForResult) -> WhenTrace {
+        let assets = for_result
+            .target_assets
+            .into_iter()
+            .map(|t| match self.compare(state, &t) {
+                Ok(comparison) => {
+                    let detail = format!(
+                        "fast {:.2} -> {:.2}, slow {:.2} -> {:.2}",
+                        comparison.previous_fast,
+                        comparison.fast,
+                        comparison.previous_slow,
+                        comparison.slow
+                    );
+                    AssetVerdict::new(t.symbol, self.is_satisfied(&comparison), Some(detail))
+                }
+                Err(reason) => AssetVerdict::new(t.symbol, false, Some(reason.to_string())),
+            })
+            .collect();
+        WhenTrace {
+            description: self.description(),
+            assets,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::result::BarsResult;
+    use crate::asset::AssetSymbol;
+    use crate::platform::bar::Bar;
+    use std::collections::HashMap;
+    use std::rc::Rc;
+
+    #[test]
+    fn evaluate_golden_cross() {
+        // The fast average sits below the slow one until the last close jumps.
+        let state = state(&[10.0, 10.0, 9.0, 9.0, 15.0]);
+        let rule = rule(CrossDirection::Golden);
+        let result = rule.evaluate(&state, ForResult::full_percent_fixture(&["SPY"]));
+        let expected = WhenResult {
+            conditions_satisfied: true,
+            target_assets: vec![TargetAsset::full_percent(AssetSymbol::new("SPY"))],
+        };
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn evaluate_golden_cross_already_above() {
+        let state = state(&[9.0, 10.0, 11.0, 12.0, 13.0]);
+        let rule = rule(CrossDirection::Golden);
+        let result = rule.evaluate(&state, ForResult::full_percent_fixture(&["SPY"]));
+        assert_eq!(WhenResult::default(), result);
+    }
+
+    #[test]
+    fn evaluate_death_cross() {
+        let state = state(&[10.0, 10.0, 11.0, 11.0, 5.0]);
+        let rule = rule(CrossDirection::Death);
+        let result = rule.evaluate(&state, ForResult::full_percent_fixture(&["SPY"]));
+        assert!(result.conditions_satisfied);
+    }
+
+    #[test]
+    fn evaluate_death_cross_not_crossed() {
+        let state = state(&[10.0, 10.0, 9.0, 9.0, 15.0]);
+        let rule = rule(CrossDirection::Death);
+        let result = rule.evaluate(&state, ForResult::full_percent_fixture(&["SPY"]));
+        assert_eq!(WhenResult::default(), result);
+    }
+
+    #[test]
+    fn evaluate_not_enough_bars() {
+        let state = state(&[10.0, 10.0, 9.0, 15.0]);
+        let rule = rule(CrossDirection::Golden);
+        let result = rule.evaluate(&state, ForResult::full_percent_fixture(&["SPY"]));
+        assert_eq!(WhenResult::default(), result);
+    }
+
+    #[test]
+    fn evaluate_no_analysis() {
+        let state = TacticState {
+            bar_analysis: Rc::new(HashMap::new()),
+            ..TacticState::fixture()
+        };
+        let rule = rule(CrossDirection::Golden);
+        let result = rule.evaluate(&state, ForResult::full_percent_fixture(&["SPY"]));
+        assert_eq!(WhenResult::default(), result);
+    }
+
+    #[test]
+    fn trace() {
+        let state = state(&[10.0, 10.0, 9.0, 9.0, 15.0]);
+        let rule = rule(CrossDirection::Golden);
+        let trace = rule.trace(&state, ForResult::full_percent_fixture(&["SPY"]));
+        assert_eq!(trace.description, "golden cross of sma 2 over 4");
+        assert_eq!(
+            trace.assets,
+            vec![AssetVerdict::new(
+                AssetSymbol::new("SPY"),
+                true,
+                Some("fast 9.00 -> 12.00, slow 9.50 -> 10.75".to_string())
+            )]
+        );
+    }
+
+    fn rule(direction: CrossDirection) -> Box<dyn WhenRule> {
+        WhenMovingAverageCrossRule::boxed(direction, 2, 4, MovingAverage::Sma)
+    }
+
+    fn state(closes: &[f64]) -> TacticState {
+        let spy = AssetSymbol::new("SPY");
+        let bars = closes
+            .iter()
+            .map(|&close| Bar {
+                close,
+                ..Default::default()
+            })
+            .collect();
+        let bar_result = BarsResult {
+            one_year: Bars::with_bars(bars),
+            ..Default::default()
+        };
+        TacticState {
+            bar_analysis: Rc::new(HashMap::from([(spy, bar_result)])),
+            ..TacticState::fixture()
+        }
+    }
+}
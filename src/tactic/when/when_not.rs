@@ -0,0 +1,156 @@
+use crate::tactic::r#for::ForResult;
+use crate::tactic::state::TacticState;
+use crate::tactic::when::{AssetVerdict, WhenResult, WhenRule, WhenTrace};
+
+/// Inverts the nested rule for each target asset.
+pub struct WhenNotRule {
+    rule: Box<dyn WhenRule>,
+}
+
+impl WhenNotRule {
+    pub fn boxed(rule: Box<dyn WhenRule>) -> Box<dyn WhenRule> {
+        Box::new(Self { rule })
+    }
+}
+
+impl WhenRule for WhenNotRule {
+    fn evaluate(&self, state: &TacticState, for_result: ForResult) -> WhenResult {
+        let filtered_assets = for_result
+            .target_assets
+            .into_iter()
+            .filter(|t| {
+                let asset_result = ForResult {
+                    target_assets: vec![t.clone()],
+                };
+                !self.rule.evaluate(state, asset_result).conditions_satisfied
+            })
+            .collect::<Vec<_>>();
+        WhenResult {
+            conditions_satisfied: !filtered_assets.is_empty(),
+            target_assets: filtered_assets,
+        }
+    }
+
+    fn description(&self) -> String {
+        "not".to_string()
+    }
+
+    fn trace(&self, state: &TacticState, for_result: ForResult) -> WhenTrace {
+        let child = self.rule.trace(state, for_result.clone());
+        let assets = for_result
+            .target_assets
+            .iter()
+            .map(|t| {
+                let satisfied = !child
+                    .assets
+                    .iter()
+                    .filter(|a| a.symbol == t.symbol)
+                    .any(|a| a.satisfied);
+                AssetVerdict::new(t.symbol.clone(), satisfied, None)
+            })
+            .collect();
+        WhenTrace {
+            description: self.description(),
+            assets,
+            children: vec![child],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::asset::AssetSymbol;
+    use crate::platform::position::Position;
+    use crate::tactic::null::NullRule;
+    use crate::tactic::r#for::ForResult;
+    use crate::tactic::state::TacticState;
+    use crate::tactic::target::TargetAsset;
+    use crate::tactic::when::when_always::WhenAlwaysRule;
+    use crate::tactic::when::when_gain_above::WhenGainAboveRule;
+    use crate::tactic::when::when_not::WhenNotRule;
+    use crate::tactic::when::{AssetVerdict, WhenResult, WhenTrace};
+    use std::collections::HashMap;
+
+    #[test]
+    fn evaluate_not_satisfied() {
+        let state = TacticState::fixture();
+        let rule = WhenNotRule::boxed(WhenAlwaysRule::boxed());
+        let for_result = ForResult {
+            target_assets: target_assets(),
+        };
+        let result = rule.evaluate(&state, for_result);
+        let expected = WhenResult {
+            conditions_satisfied: false,
+            target_assets: vec![],
+        };
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn evaluate_satisfied() {
+        let state = TacticState::fixture();
+        let rule = WhenNotRule::boxed(NullRule::when_boxed());
+        let target_assets = target_assets();
+        let for_result = ForResult {
+            target_assets: target_assets.clone(),
+        };
+        let result = rule.evaluate(&state, for_result);
+        let expected = WhenResult {
+            conditions_satisfied: true,
+            target_assets,
+        };
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn evaluate_per_asset() {
+        // Only SPY has a gain, so only VTI should satisfy the inverted rule.
+        let spy = AssetSymbol::new("SPY");
+        let position = Position {
+            unrealized_gain_total_percent: Some(10.0),
+            ..Position::fixture(spy.clone())
+        };
+        let state = TacticState {
+            positions: HashMap::from([(spy, position)]),
+            ..TacticState::fixture()
+        };
+        let rule = WhenNotRule::boxed(WhenGainAboveRule::boxed(5.0));
+        let for_result = ForResult {
+            target_assets: target_assets(),
+        };
+        let result = rule.evaluate(&state, for_result);
+        let expected = WhenResult {
+            conditions_satisfied: true,
+            target_assets: vec![TargetAsset::full_percent(AssetSymbol::new("VTI"))],
+        };
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn trace() {
+        let state = TacticState::fixture();
+        let rule = WhenNotRule::boxed(NullRule::when_boxed());
+        let for_result = ForResult {
+            target_assets: vec![TargetAsset::full_percent(AssetSymbol::new("SPY"))],
+        };
+        let trace = rule.trace(&state, for_result);
+        let spy = AssetSymbol::new("SPY");
+        let expected = WhenTrace {
+            description: "not".to_string(),
+            assets: vec![AssetVerdict::new(spy.clone(), true, None)],
+            children: vec![WhenTrace {
+                description: "never".to_string(),
+                assets: vec![AssetVerdict::new(spy, false, None)],
+                ..Default::default()
+            }],
+        };
+        assert_eq!(expected, trace)
+    }
+
+    fn target_assets() -> Vec<TargetAsset> {
+        vec![
+            TargetAsset::full_percent(AssetSymbol::new("SPY")),
+            TargetAsset::full_percent(AssetSymbol::new("VTI")),
+        ]
+    }
+}
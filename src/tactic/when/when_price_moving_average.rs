@@ -0,0 +1,218 @@
+use crate::bool::BooleanWhen;
+use crate::config::tactic::moving_average::MovingAverage;
+use crate::tactic::r#for::ForResult;
+use crate::tactic::state::TacticState;
+use crate::tactic::target::TargetAsset;
+use crate::tactic::when::{AssetVerdict, Threshold, WhenResult, WhenRule, WhenTrace};
+use log::{info, warn};
+
+#[derive(Debug, PartialEq)]
+pub struct WhenPriceMovingAverageRule {
+    side: Threshold,
+    period: usize,
+    moving_average: MovingAverage,
+}
+
+/// The values compared when checking the price against its moving average.
+#[derive(Debug, PartialEq)]
+struct AverageComparison {
+    ask_price: f64,
+    average: f64,
+}
+
+impl WhenPriceMovingAverageRule {
+    pub fn boxed(
+        side: Threshold,
+        period: usize,
+        moving_average: MovingAverage,
+    ) -> Box<dyn WhenRule> {
+        Box::new(Self {
+            side,
+            period,
+            moving_average,
+        })
+    }
+
+    fn is_on_side(&self, state: &TacticState, target_asset: &TargetAsset) -> bool {
+        match self.compare(state, target_asset) {
+            Ok(comparison) => self.is_satisfied(&comparison).when_false(|| {
+                info!(
+                    "when_price_moving_average: ask {:.2} was not {} {:.2} for {}",
+                    comparison.ask_price, self.side, comparison.average, target_asset.symbol
+                )
+            }),
+            Err(reason) => {
+                warn!(
+                    "when_price_moving_average: {reason} for: {}",
+                    target_asset.symbol
+                );
+                false
+            }
+        }
+    }
+
+    fn is_satisfied(&self, comparison: &AverageComparison) -> bool {
+        self.side
+            .is_satisfied(comparison.ask_price, comparison.average)
+    }
+
+    fn compare(
+        &self,
+        state: &TacticState,
+        target_asset: &TargetAsset,
+    ) -> Result<AverageComparison, &'static str> {
+        let quote = state.quotes.get(&target_asset.symbol).ok_or("no quote")?;
+        if !quote.valid_ask() {
+            return Err("ask price is not valid");
+        }
+        let analysis = state
+            .bar_analysis
+            .get(&target_asset.symbol)
+            .ok_or("no bar analysis")?;
+        let average = self
+            .moving_average
+            .calculate(&analysis.one_year, self.period)
+            .ok_or("not enough bars")?;
+        Ok(AverageComparison {
+            ask_price: quote.ask_price,
+            average,
+        })
+    }
+}
+
+impl WhenRule for WhenPriceMovingAverageRule {
+    fn evaluate(&self, state: &TacticState, for_result: ForResult) -> WhenResult {
+        let matching_assets = for_result
+            .target_assets
+            .iter()
+            .filter(|t| self.is_on_side(state, t))
+            .cloned()
+            .collect::<Vec<_>>();
+        WhenResult {
+            conditions_satisfied: !matching_assets.is_empty(),
+            target_assets: matching_assets,
+        }
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "price {} {} day {}",
+            self.side, self.period, self.moving_average
+        )
+    }
+
+    fn trace(&self, state: &TacticState, for_result: ForResult) -> WhenTrace {
+        let assets = for_result
+            .target_assets
+            .into_iter()
+            .map(|t| match self.compare(state, &t) {
+                Ok(comparison) => {
+                    let detail = format!(
+                        "ask {:.2}, {} {:.2}",
+                        comparison.ask_price, self.moving_average, comparison.average
+                    );
+                    AssetVerdict::new(t.symbol, self.is_satisfied(&comparison), Some(detail))
+                }
+                Err(reason) => AssetVerdict::new(t.symbol, false, Some(reason.to_string())),
+            })
+            .collect();
+        WhenTrace {
+            description: self.description(),
+            assets,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asset::AssetSymbol;
+    use crate::platform::quote::Quote;
+    use std::collections::HashMap;
+
+    // The fixture's one year bars close at 600, 700 and 800 and its ask price is 200.
+
+    #[test]
+    fn evaluate_below() {
+        let rule = WhenPriceMovingAverageRule::boxed(Threshold::Below, 3, MovingAverage::Sma);
+        let result = rule.evaluate(
+            &TacticState::fixture(),
+            ForResult::full_percent_fixture(&["SPY", "VTI"]),
+        );
+        let expected = WhenResult {
+            conditions_satisfied: true,
+            target_assets: ForResult::full_percent_fixture(&["SPY", "VTI"]).target_assets,
+        };
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn evaluate_above() {
+        let rule = WhenPriceMovingAverageRule::boxed(Threshold::Above, 3, MovingAverage::Sma);
+        let result = rule.evaluate(
+            &TacticState::fixture(),
+            ForResult::full_percent_fixture(&["SPY", "VTI"]),
+        );
+        assert_eq!(WhenResult::default(), result);
+    }
+
+    #[test]
+    fn evaluate_above_with_high_ask() {
+        let spy = AssetSymbol::new("SPY");
+        let quote = Quote {
+            ask_price: 750.0,
+            ..Quote::fixture(spy.clone())
+        };
+        let state = TacticState {
+            quotes: HashMap::from([(spy.clone(), quote)]),
+            ..TacticState::fixture()
+        };
+        let rule = WhenPriceMovingAverageRule::boxed(Threshold::Above, 3, MovingAverage::Sma);
+        let result = rule.evaluate(&state, ForResult::full_percent_fixture(&["SPY", "VTI"]));
+        let expected = WhenResult {
+            conditions_satisfied: true,
+            target_assets: vec![TargetAsset::full_percent(spy)],
+        };
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn evaluate_not_enough_bars() {
+        let rule = WhenPriceMovingAverageRule::boxed(Threshold::Below, 200, MovingAverage::Sma);
+        let result = rule.evaluate(
+            &TacticState::fixture(),
+            ForResult::full_percent_fixture(&["SPY", "VTI"]),
+        );
+        assert_eq!(WhenResult::default(), result);
+    }
+
+    #[test]
+    fn evaluate_no_quote() {
+        let state = TacticState {
+            quotes: HashMap::new(),
+            ..TacticState::fixture()
+        };
+        let rule = WhenPriceMovingAverageRule::boxed(Threshold::Below, 3, MovingAverage::Sma);
+        let result = rule.evaluate(&state, ForResult::full_percent_fixture(&["SPY", "VTI"]));
+        assert_eq!(WhenResult::default(), result);
+    }
+
+    #[test]
+    fn trace() {
+        let rule = WhenPriceMovingAverageRule::boxed(Threshold::Below, 3, MovingAverage::Sma);
+        let trace = rule.trace(
+            &TacticState::fixture(),
+            ForResult::full_percent_fixture(&["SPY", "VTI"]),
+        );
+        assert_eq!(trace.description, "price below 3 day sma");
+        assert_eq!(
+            trace.assets[0],
+            AssetVerdict::new(
+                AssetSymbol::new("SPY"),
+                true,
+                Some("ask 200.00, sma 700.00".to_string())
+            )
+        );
+    }
+}
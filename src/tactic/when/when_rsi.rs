@@ -0,0 +1,160 @@
+use crate::bool::BooleanWhen;
+use crate::tactic::r#for::ForResult;
+use crate::tactic::state::TacticState;
+use crate::tactic::target::TargetAsset;
+use crate::tactic::when::{AssetVerdict, Threshold, WhenResult, WhenRule, WhenTrace};
+use log::{info, warn};
+
+/// Checks the daily relative strength index against a threshold, below for oversold buys and
+/// above for overbought sells.
+#[derive(Debug, PartialEq)]
+pub struct WhenRsiRule {
+    side: Threshold,
+    threshold: f64,
+    period: usize,
+}
+
+impl WhenRsiRule {
+    pub fn boxed(side: Threshold, threshold: f64, period: usize) -> Box<dyn WhenRule> {
+        Box::new(Self {
+            side,
+            threshold,
+            period,
+        })
+    }
+
+    fn is_past_threshold(&self, state: &TacticState, target_asset: &TargetAsset) -> bool {
+        match self.rsi(state, target_asset) {
+            Ok(rsi) => self.side.is_satisfied(rsi, self.threshold).when_false(|| {
+                info!(
+                    "when_rsi: rsi for {} was {:.2}, expecting {} {:.2}",
+                    target_asset.symbol, rsi, self.side, self.threshold
+                )
+            }),
+            Err(reason) => {
+                warn!("when_rsi: {reason} for: {}", target_asset.symbol);
+                false
+            }
+        }
+    }
+
+    fn rsi(&self, state: &TacticState, target_asset: &TargetAsset) -> Result<f64, &'static str> {
+        let analysis = state
+            .bar_analysis
+            .get(&target_asset.symbol)
+            .ok_or("no bar analysis")?;
+        analysis.one_year.rsi(self.period).ok_or("not enough bars")
+    }
+}
+
+impl WhenRule for WhenRsiRule {
+    fn evaluate(&self, state: &TacticState, for_result: ForResult) -> WhenResult {
+        let matching_assets = for_result
+            .target_assets
+            .iter()
+            .filter(|t| self.is_past_threshold(state, t))
+            .cloned()
+            .collect::<Vec<_>>();
+        WhenResult {
+            conditions_satisfied: !matching_assets.is_empty(),
+            target_assets: matching_assets,
+        }
+    }
+
+    fn description(&self) -> String {
+        format!("rsi({}) {} {:.2}", self.period, self.side, self.threshold)
+    }
+
+    fn trace(&self, state: &TacticState, for_result: ForResult) -> WhenTrace {
+        let assets = for_result
+            .target_assets
+            .into_iter()
+            .map(|t| match self.rsi(state, &t) {
+                Ok(rsi) => AssetVerdict::new(
+                    t.symbol,
+                    self.side.is_satisfied(rsi, self.threshold),
+                    Some(format!("rsi is {rsi:.2}")),
+                ),
+                Err(reason) => AssetVerdict::new(t.symbol, false, Some(reason.to_string())),
+            })
+            .collect();
+        WhenTrace {
+            description: self.description(),
+            assets,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asset::AssetSymbol;
+    use std::collections::HashMap;
+    use std::rc::Rc;
+
+    // The fixture's one year bars only ever rise, so their rsi is 100.
+
+    #[test]
+    fn evaluate_above() {
+        let rule = WhenRsiRule::boxed(Threshold::Above, 70.0, 2);
+        let result = rule.evaluate(
+            &TacticState::fixture(),
+            ForResult::full_percent_fixture(&["SPY", "VTI"]),
+        );
+        let expected = WhenResult {
+            conditions_satisfied: true,
+            target_assets: ForResult::full_percent_fixture(&["SPY", "VTI"]).target_assets,
+        };
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn evaluate_below() {
+        let rule = WhenRsiRule::boxed(Threshold::Below, 30.0, 2);
+        let result = rule.evaluate(
+            &TacticState::fixture(),
+            ForResult::full_percent_fixture(&["SPY", "VTI"]),
+        );
+        assert_eq!(WhenResult::default(), result);
+    }
+
+    #[test]
+    fn evaluate_not_enough_bars() {
+        let rule = WhenRsiRule::boxed(Threshold::Above, 70.0, 14);
+        let result = rule.evaluate(
+            &TacticState::fixture(),
+            ForResult::full_percent_fixture(&["SPY", "VTI"]),
+        );
+        assert_eq!(WhenResult::default(), result);
+    }
+
+    #[test]
+    fn evaluate_no_analysis() {
+        let state = TacticState {
+            bar_analysis: Rc::new(HashMap::new()),
+            ..TacticState::fixture()
+        };
+        let rule = WhenRsiRule::boxed(Threshold::Above, 70.0, 2);
+        let result = rule.evaluate(&state, ForResult::full_percent_fixture(&["SPY", "VTI"]));
+        assert_eq!(WhenResult::default(), result);
+    }
+
+    #[test]
+    fn trace() {
+        let rule = WhenRsiRule::boxed(Threshold::Below, 30.0, 2);
+        let trace = rule.trace(
+            &TacticState::fixture(),
+            ForResult::full_percent_fixture(&["SPY", "VTI"]),
+        );
+        assert_eq!(trace.description, "rsi(2) below 30.00");
+        assert_eq!(
+            trace.assets[0],
+            AssetVerdict::new(
+                AssetSymbol::new("SPY"),
+                false,
+                Some("rsi is 100.00".to_string())
+            )
+        );
+    }
+}
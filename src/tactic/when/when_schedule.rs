@@ -0,0 +1,229 @@
+use crate::config::tactic::schedule::SchedulePeriod;
+use crate::tactic::r#for::ForResult;
+use crate::tactic::state::TacticState;
+use crate::tactic::target::TargetAsset;
+use crate::tactic::when::{AssetVerdict, WhenResult, WhenRule, WhenTrace};
+use crate::trading_days::eastern::eastern_date;
+use chrono::{Datelike, Duration, NaiveDate};
+use log::info;
+
+/// When a recurring buy is due, on the eastern calendar.
+#[derive(Debug, PartialEq)]
+pub enum Schedule {
+    /// Once each day, week (starting Monday) or month.
+    Every(SchedulePeriod),
+    /// Once each month, on or after this day. Days past the end of a month fall on its last day.
+    OnDayOfMonth(u32),
+}
+
+/// Satisfied when an asset hasn't had a buy placed since its current scheduled period began.
+/// The last buy comes from the order history, so restarting doesn't buy twice in one period.
+pub struct WhenScheduleRule {
+    schedule: Schedule,
+}
+
+impl WhenScheduleRule {
+    pub fn boxed(schedule: Schedule) -> Box<dyn WhenRule> {
+        Box::new(Self { schedule })
+    }
+
+    /// The first day of the current period, `None` if this month's day hasn't come yet.
+    fn period_start(&self, today: NaiveDate) -> Option<NaiveDate> {
+        match &self.schedule {
+            Schedule::Every(SchedulePeriod::Day) => Some(today),
+            Schedule::Every(SchedulePeriod::Week) => {
+                let days_from_monday = today.weekday().num_days_from_monday() as i64;
+                Some(today - Duration::days(days_from_monday))
+            }
+            Schedule::Every(SchedulePeriod::Month) => today.with_day(1),
+            Schedule::OnDayOfMonth(day) => {
+                let scheduled = (1..=*day).rev().find_map(|d| today.with_day(d))?;
+                (scheduled <= today).then_some(scheduled)
+            }
+        }
+    }
+
+    fn last_buy_date(&self, state: &TacticState, target_asset: &TargetAsset) -> Option<NaiveDate> {
+        state
+            .last_buy_times
+            .get(&target_asset.symbol)
+            .map(|time| eastern_date(*time))
+    }
+
+    fn is_satisfied(&self, state: &TacticState, target_asset: &TargetAsset) -> bool {
+        let Some(start) = self.period_start(eastern_date(state.now)) else {
+            return false;
+        };
+        self.last_buy_date(state, target_asset)
+            .is_none_or(|bought| bought < start)
+    }
+
+    fn is_due(&self, state: &TacticState, target_asset: &TargetAsset) -> bool {
+        let satisfied = self.is_satisfied(state, target_asset);
+        if !satisfied {
+            info!(
+                "when_schedule: {} not due {}",
+                target_asset.symbol,
+                self.description()
+            )
+        }
+        satisfied
+    }
+}
+
+impl WhenRule for WhenScheduleRule {
+    fn evaluate(&self, state: &TacticState, for_result: ForResult) -> WhenResult {
+        let due_assets = for_result
+            .target_assets
+            .iter()
+            .filter(|t| self.is_due(state, t))
+            .cloned()
+            .collect::<Vec<_>>();
+        WhenResult {
+            conditions_satisfied: !due_assets.is_empty(),
+            target_assets: due_assets,
+        }
+    }
+
+    fn description(&self) -> String {
+        match &self.schedule {
+            Schedule::Every(period) => format!("every {period}"),
+            Schedule::OnDayOfMonth(day) => format!("on day {day} of the month"),
+        }
+    }
+
+    fn trace(&self, state: &TacticState, for_result: ForResult) -> WhenTrace {
+        let assets = for_result
+            .target_assets
+            .into_iter()
+            .map(|t| {
+                let detail = match self.last_buy_date(state, &t) {
+                    Some(date) => format!("last bought {date}"),
+                    None => "no recent buys".to_string(),
+                };
+                let satisfied = self.is_satisfied(state, &t);
+                AssetVerdict::new(t.symbol, satisfied, Some(detail))
+            })
+            .collect();
+        WhenTrace {
+            description: self.description(),
+            assets,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asset::AssetSymbol;
+    use chrono::{DateTime, TimeZone, Utc};
+    use std::collections::HashMap;
+
+    // 2024-07-03 is a Wednesday. Times are at 16:00 UTC, noon eastern.
+
+    #[test]
+    fn every_day() {
+        let rule = WhenScheduleRule::boxed(Schedule::Every(SchedulePeriod::Day));
+        assert!(!is_satisfied(rule.as_ref(), date(7, 3), Some(date(7, 3))));
+        assert!(is_satisfied(rule.as_ref(), date(7, 3), Some(date(7, 2))));
+    }
+
+    #[test]
+    fn every_week() {
+        let rule = WhenScheduleRule::boxed(Schedule::Every(SchedulePeriod::Week));
+        assert!(!is_satisfied(rule.as_ref(), date(7, 3), Some(date(7, 1))));
+        assert!(is_satisfied(rule.as_ref(), date(7, 3), Some(date(6, 28))));
+        assert!(is_satisfied(rule.as_ref(), date(7, 3), None));
+    }
+
+    #[test]
+    fn every_month() {
+        let rule = WhenScheduleRule::boxed(Schedule::Every(SchedulePeriod::Month));
+        assert!(!is_satisfied(rule.as_ref(), date(7, 3), Some(date(7, 1))));
+        assert!(is_satisfied(rule.as_ref(), date(7, 3), Some(date(6, 30))));
+    }
+
+    #[test]
+    fn on_day_of_month() {
+        let rule = WhenScheduleRule::boxed(Schedule::OnDayOfMonth(2));
+        assert!(!is_satisfied(rule.as_ref(), date(7, 1), Some(date(6, 3))));
+        assert!(is_satisfied(rule.as_ref(), date(7, 3), Some(date(6, 3))));
+        assert!(!is_satisfied(rule.as_ref(), date(7, 3), Some(date(7, 2))));
+    }
+
+    #[test]
+    fn on_day_of_month_past_month_end() {
+        let rule = WhenScheduleRule::boxed(Schedule::OnDayOfMonth(31));
+        assert!(!is_satisfied(rule.as_ref(), date(6, 29), Some(date(5, 31))));
+        assert!(is_satisfied(rule.as_ref(), date(6, 30), Some(date(5, 31))));
+    }
+
+    #[test]
+    fn evaluate() {
+        let rule = WhenScheduleRule::boxed(Schedule::Every(SchedulePeriod::Week));
+        let result = rule.evaluate(
+            &state(date(7, 3), Some(date(7, 1))),
+            ForResult::full_percent_fixture(&["SPY", "VTI"]),
+        );
+        let expected = WhenResult {
+            conditions_satisfied: true,
+            target_assets: vec![TargetAsset::full_percent(AssetSymbol::new("VTI"))],
+        };
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn trace() {
+        let rule = WhenScheduleRule::boxed(Schedule::Every(SchedulePeriod::Week));
+        let trace = rule.trace(
+            &state(date(7, 3), Some(date(7, 1))),
+            ForResult::full_percent_fixture(&["SPY", "VTI"]),
+        );
+        assert_eq!(trace.description, "every week");
+        assert_eq!(
+            trace.assets,
+            vec![
+                AssetVerdict::new(
+                    AssetSymbol::new("SPY"),
+                    false,
+                    Some("last bought 2024-07-01".to_string())
+                ),
+                AssetVerdict::new(
+                    AssetSymbol::new("VTI"),
+                    true,
+                    Some("no recent buys".to_string())
+                ),
+            ]
+        );
+    }
+
+    fn is_satisfied(
+        rule: &dyn WhenRule,
+        now: DateTime<Utc>,
+        bought: Option<DateTime<Utc>>,
+    ) -> bool {
+        rule.evaluate(
+            &state(now, bought),
+            ForResult::full_percent_fixture(&["SPY", "VTI"]),
+        )
+        .target_assets
+        .iter()
+        .any(|t| t.symbol == AssetSymbol::new("SPY"))
+    }
+
+    fn date(month: u32, day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, month, day, 16, 0, 0).unwrap()
+    }
+
+    fn state(now: DateTime<Utc>, bought: Option<DateTime<Utc>>) -> TacticState {
+        let last_buy_times = bought
+            .map(|time| HashMap::from([(AssetSymbol::new("SPY"), time)]))
+            .unwrap_or_default();
+        TacticState {
+            now,
+            last_buy_times,
+            ..TacticState::fixture()
+        }
+    }
+}
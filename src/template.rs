@@ -7,6 +7,14 @@ platform = "alpaca"
 # How often (in seconds) to run the trading loop.
 interval = 60
 
+# Risk limits checked before every order is placed. Each limit is optional.
+# [risk]
+# max_order_notional = 500.0        # Largest buy in dollars
+# max_position_percent = 20.0       # Largest position as a percent of equity
+# max_orders_per_day = 20           # Buys and sells combined
+# min_cash_reserve = 1000.0         # Cash buys may never spend
+# daily_loss_limit_percent = 3.0    # Stop buying once equity is down this much today
+
 # Strategies allow you to compose multiple tactic configs together.
 # Each strategy references a file path or agent config, and gets a share of your portfolio.
 # [[strategies]]
@@ -1,24 +1,104 @@
-use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use crate::platform::calendar::MarketDay;
+use crate::platform::clock::MarketClock;
+use crate::trading_days::eastern::{eastern_date, eastern_to_utc};
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, Utc, Weekday};
+use std::ops::Range;
 
+pub mod eastern;
+mod holidays;
+
+/// Trading days follow the bundled NYSE holiday table. Dates outside of the table only skip
+/// weekends.
 pub trait TradingDaysOffset {
+    fn is_trading_day(&self) -> bool;
     fn previous_trading_day(&self) -> NaiveDate;
+    fn next_trading_day(&self) -> NaiveDate;
 }
 
 impl TradingDaysOffset for NaiveDate {
+    fn is_trading_day(&self) -> bool {
+        let is_weekend = matches!(self.weekday(), Weekday::Sat | Weekday::Sun);
+        !is_weekend && !holidays::is_holiday(*self)
+    }
+
     fn previous_trading_day(&self) -> NaiveDate {
-        let offset = match self.weekday() {
-            Weekday::Sun => 2,
-            Weekday::Mon => 3,
-            _ => 1,
-        };
+        let mut date = *self;
+        loop {
+            date = date.pred_opt().expect("date out of range");
+            if date.is_trading_day() {
+                return date;
+            }
+        }
+    }
+
+    fn next_trading_day(&self) -> NaiveDate {
+        let mut date = *self;
+        loop {
+            date = date.succ_opt().expect("date out of range");
+            if date.is_trading_day() {
+                return date;
+            }
+        }
+    }
+}
 
-        *self - Duration::days(offset)
+/// The regular session on `date`, or `None` if the market is closed all day.
+pub fn market_day(date: NaiveDate) -> Option<MarketDay> {
+    if !date.is_trading_day() {
+        return None;
     }
+    let open = NaiveTime::from_hms_opt(9, 30, 0).expect("open time was invalid");
+    let close_hour = if holidays::is_early_close(date) {
+        13
+    } else {
+        16
+    };
+    let close = NaiveTime::from_hms_opt(close_hour, 0, 0).expect("close time was invalid");
+    Some(MarketDay {
+        date,
+        open: eastern_to_utc(date, open),
+        close: eastern_to_utc(date, close),
+    })
+}
+
+/// Every session from the start of `range` up to, but not including, the end.
+pub fn market_calendar(range: Range<NaiveDate>) -> Vec<MarketDay> {
+    range
+        .start
+        .iter_days()
+        .take_while(|date| *date < range.end)
+        .filter_map(market_day)
+        .collect()
+}
+
+/// The market clock at `now`, based on the bundled calendar.
+pub fn market_clock(now: DateTime<Utc>) -> MarketClock {
+    let today = eastern_date(now);
+    let session = market_day(today)
+        .filter(|day| now < day.close)
+        .unwrap_or_else(|| next_market_day(today));
+    let is_open = session.open <= now;
+    let next_open = if is_open {
+        next_market_day(session.date).open
+    } else {
+        session.open
+    };
+    MarketClock {
+        is_open,
+        timestamp: now,
+        next_open,
+        next_close: session.close,
+    }
+}
+
+fn next_market_day(date: NaiveDate) -> MarketDay {
+    market_day(date.next_trading_day()).expect("next trading day should have a session")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::TimeZone;
 
     #[test]
     fn previous_trading_day_saturday() {
@@ -47,4 +127,92 @@ mod tests {
         let expected = NaiveDate::from_ymd_opt(2024, 4, 22).unwrap();
         assert_eq!(expected, tuesday.previous_trading_day())
     }
+
+    #[test]
+    fn previous_trading_day_after_holiday() {
+        let friday = NaiveDate::from_ymd_opt(2024, 7, 5).unwrap();
+        let expected = NaiveDate::from_ymd_opt(2024, 7, 3).unwrap();
+        assert_eq!(expected, friday.previous_trading_day())
+    }
+
+    #[test]
+    fn next_trading_day_holiday_weekend() {
+        let thursday = NaiveDate::from_ymd_opt(2024, 3, 28).unwrap();
+        let expected = NaiveDate::from_ymd_opt(2024, 4, 1).unwrap();
+        assert_eq!(expected, thursday.next_trading_day())
+    }
+
+    #[test]
+    fn market_day_early_close() {
+        let date = NaiveDate::from_ymd_opt(2024, 11, 29).unwrap();
+        let expected = MarketDay {
+            date,
+            open: Utc.with_ymd_and_hms(2024, 11, 29, 14, 30, 0).unwrap(),
+            close: Utc.with_ymd_and_hms(2024, 11, 29, 18, 0, 0).unwrap(),
+        };
+        assert_eq!(market_day(date), Some(expected))
+    }
+
+    #[test]
+    fn market_day_holiday() {
+        let date = NaiveDate::from_ymd_opt(2024, 12, 25).unwrap();
+        assert_eq!(market_day(date), None)
+    }
+
+    #[test]
+    fn market_calendar_skips_closed_days() {
+        let start = NaiveDate::from_ymd_opt(2024, 12, 23).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 12, 30).unwrap();
+        let dates = market_calendar(start..end)
+            .into_iter()
+            .map(|d| d.date.day())
+            .collect::<Vec<_>>();
+        assert_eq!(dates, vec![23, 24, 26, 27])
+    }
+
+    #[test]
+    fn market_clock_during_session() {
+        let now = Utc.with_ymd_and_hms(2024, 7, 3, 15, 0, 0).unwrap();
+        let expected = MarketClock {
+            is_open: true,
+            timestamp: now,
+            next_open: Utc.with_ymd_and_hms(2024, 7, 5, 13, 30, 0).unwrap(),
+            next_close: Utc.with_ymd_and_hms(2024, 7, 3, 17, 0, 0).unwrap(),
+        };
+        assert_eq!(market_clock(now), expected)
+    }
+
+    #[test]
+    fn market_clock_before_open() {
+        let now = Utc.with_ymd_and_hms(2024, 1, 2, 12, 0, 0).unwrap();
+        let expected = MarketClock {
+            is_open: false,
+            timestamp: now,
+            next_open: Utc.with_ymd_and_hms(2024, 1, 2, 14, 30, 0).unwrap(),
+            next_close: Utc.with_ymd_and_hms(2024, 1, 2, 21, 0, 0).unwrap(),
+        };
+        assert_eq!(market_clock(now), expected)
+    }
+
+    #[test]
+    fn market_clock_weekend() {
+        let now = Utc.with_ymd_and_hms(2024, 4, 20, 15, 0, 0).unwrap();
+        let clock = market_clock(now);
+        assert!(!clock.is_open);
+        assert_eq!(
+            clock.next_open,
+            Utc.with_ymd_and_hms(2024, 4, 22, 13, 30, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn market_clock_after_close() {
+        let now = Utc.with_ymd_and_hms(2024, 4, 19, 21, 0, 0).unwrap();
+        let clock = market_clock(now);
+        assert!(!clock.is_open);
+        assert_eq!(
+            clock.next_open,
+            Utc.with_ymd_and_hms(2024, 4, 22, 13, 30, 0).unwrap()
+        );
+    }
 }
@@ -0,0 +1,81 @@
+use crate::date::NaiveDateTimeConvert;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc, Weekday};
+
+/// Converts a US eastern wall clock time on `date` to UTC.
+pub fn eastern_to_utc(date: NaiveDate, time: NaiveTime) -> DateTime<Utc> {
+    (date.and_time(time) - utc_offset(date)).to_utc()
+}
+
+/// The date in the US eastern time zone at `time`.
+pub fn eastern_date(time: DateTime<Utc>) -> NaiveDate {
+    eastern_date_time(time).date()
+}
+
+/// The US eastern wall clock date and time at `time`.
+pub fn eastern_date_time(time: DateTime<Utc>) -> NaiveDateTime {
+    let utc_date = time.date_naive();
+    (time + utc_offset(utc_date)).naive_utc()
+}
+
+/// Daylight saving time runs from the second Sunday in March until the first Sunday in November.
+/// The switch happens overnight so the date is enough to pick the offset during market hours.
+fn utc_offset(date: NaiveDate) -> Duration {
+    let year = date.year();
+    let dst_start = NaiveDate::from_weekday_of_month_opt(year, 3, Weekday::Sun, 2);
+    let dst_end = NaiveDate::from_weekday_of_month_opt(year, 11, Weekday::Sun, 1);
+    let is_dst =
+        matches!((dst_start, dst_end), (Some(start), Some(end)) if date >= start && date < end);
+    if is_dst {
+        Duration::hours(-4)
+    } else {
+        Duration::hours(-5)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn eastern_to_utc_standard_time() {
+        let date = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+        let time = NaiveTime::from_hms_opt(9, 30, 0).unwrap();
+        let expected = Utc.with_ymd_and_hms(2024, 1, 2, 14, 30, 0).unwrap();
+        assert_eq!(eastern_to_utc(date, time), expected)
+    }
+
+    #[test]
+    fn eastern_to_utc_daylight_saving_time() {
+        let date = NaiveDate::from_ymd_opt(2024, 7, 2).unwrap();
+        let time = NaiveTime::from_hms_opt(16, 0, 0).unwrap();
+        let expected = Utc.with_ymd_and_hms(2024, 7, 2, 20, 0, 0).unwrap();
+        assert_eq!(eastern_to_utc(date, time), expected)
+    }
+
+    #[test]
+    fn eastern_to_utc_dst_boundaries() {
+        let time = NaiveTime::from_hms_opt(12, 0, 0).unwrap();
+        let start = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 11, 3).unwrap();
+        assert_eq!(eastern_to_utc(start, time).format("%H").to_string(), "16");
+        assert_eq!(eastern_to_utc(end, time).format("%H").to_string(), "17");
+    }
+
+    #[test]
+    fn eastern_date_time_morning() {
+        let time = Utc.with_ymd_and_hms(2024, 7, 2, 14, 15, 0).unwrap();
+        let expected = NaiveDate::from_ymd_opt(2024, 7, 2)
+            .unwrap()
+            .and_hms_opt(10, 15, 0)
+            .unwrap();
+        assert_eq!(eastern_date_time(time), expected)
+    }
+
+    #[test]
+    fn eastern_date_evening() {
+        let time = Utc.with_ymd_and_hms(2024, 1, 3, 2, 0, 0).unwrap();
+        let expected = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+        assert_eq!(eastern_date(time), expected)
+    }
+}
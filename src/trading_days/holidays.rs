@@ -0,0 +1,244 @@
+use chrono::{Datelike, NaiveDate};
+
+/// Full day NYSE closures from 2020 through 2030 by year, as (month, day). Holidays which fall on a weekend are listed
+/// on the weekday they are observed.
+const HOLIDAYS: &[(i32, &[(u32, u32)])] = &[
+    (
+        2020,
+        &[
+            (1, 1),
+            (1, 20),
+            (2, 17),
+            (4, 10),
+            (5, 25),
+            (7, 3),
+            (9, 7),
+            (11, 26),
+            (12, 25),
+        ],
+    ),
+    (
+        2021,
+        &[
+            (1, 1),
+            (1, 18),
+            (2, 15),
+            (4, 2),
+            (5, 31),
+            (7, 5),
+            (9, 6),
+            (11, 25),
+            (12, 24),
+        ],
+    ),
+    (
+        2022,
+        &[
+            (1, 17),
+            (2, 21),
+            (4, 15),
+            (5, 30),
+            (6, 20),
+            (7, 4),
+            (9, 5),
+            (11, 24),
+            (12, 26),
+        ],
+    ),
+    (
+        2023,
+        &[
+            (1, 2),
+            (1, 16),
+            (2, 20),
+            (4, 7),
+            (5, 29),
+            (6, 19),
+            (7, 4),
+            (9, 4),
+            (11, 23),
+            (12, 25),
+        ],
+    ),
+    (
+        2024,
+        &[
+            (1, 1),
+            (1, 15),
+            (2, 19),
+            (3, 29),
+            (5, 27),
+            (6, 19),
+            (7, 4),
+            (9, 2),
+            (11, 28),
+            (12, 25),
+        ],
+    ),
+    (
+        2025,
+        &[
+            (1, 1),
+            (1, 9),
+            (1, 20),
+            (2, 17),
+            (4, 18),
+            (5, 26),
+            (6, 19),
+            (7, 4),
+            (9, 1),
+            (11, 27),
+            (12, 25),
+        ],
+    ),
+    (
+        2026,
+        &[
+            (1, 1),
+            (1, 19),
+            (2, 16),
+            (4, 3),
+            (5, 25),
+            (6, 19),
+            (7, 3),
+            (9, 7),
+            (11, 26),
+            (12, 25),
+        ],
+    ),
+    (
+        2027,
+        &[
+            (1, 1),
+            (1, 18),
+            (2, 15),
+            (3, 26),
+            (5, 31),
+            (6, 18),
+            (7, 5),
+            (9, 6),
+            (11, 25),
+            (12, 24),
+        ],
+    ),
+    (
+        2028,
+        &[
+            (1, 17),
+            (2, 21),
+            (4, 14),
+            (5, 29),
+            (6, 19),
+            (7, 4),
+            (9, 4),
+            (11, 23),
+            (12, 25),
+        ],
+    ),
+    (
+        2029,
+        &[
+            (1, 1),
+            (1, 15),
+            (2, 19),
+            (3, 30),
+            (5, 28),
+            (6, 19),
+            (7, 4),
+            (9, 3),
+            (11, 22),
+            (12, 25),
+        ],
+    ),
+    (
+        2030,
+        &[
+            (1, 1),
+            (1, 21),
+            (2, 18),
+            (4, 19),
+            (5, 27),
+            (6, 19),
+            (7, 4),
+            (9, 2),
+            (11, 28),
+            (12, 25),
+        ],
+    ),
+];
+
+/// Days the NYSE closes early at 13:00 eastern, as (month, day).
+const EARLY_CLOSES: &[(i32, &[(u32, u32)])] = &[
+    (2020, &[(11, 27), (12, 24)]),
+    (2021, &[(11, 26)]),
+    (2022, &[(11, 25)]),
+    (2023, &[(7, 3), (11, 24)]),
+    (2024, &[(7, 3), (11, 29), (12, 24)]),
+    (2025, &[(7, 3), (11, 28), (12, 24)]),
+    (2026, &[(11, 27), (12, 24)]),
+    (2027, &[(11, 26)]),
+    (2028, &[(7, 3), (11, 24)]),
+    (2029, &[(7, 3), (11, 23), (12, 24)]),
+    (2030, &[(7, 3), (11, 29), (12, 24)]),
+];
+
+pub fn is_holiday(date: NaiveDate) -> bool {
+    contains(HOLIDAYS, date)
+}
+
+pub fn is_early_close(date: NaiveDate) -> bool {
+    contains(EARLY_CLOSES, date)
+}
+
+fn contains(table: &[(i32, &[(u32, u32)])], date: NaiveDate) -> bool {
+    table
+        .iter()
+        .find(|(year, _)| *year == date.year())
+        .map(|(_, days)| days.contains(&(date.month(), date.day())))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_holiday_good_friday() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 29).unwrap();
+        assert!(is_holiday(date))
+    }
+
+    #[test]
+    fn is_holiday_observed() {
+        let date = NaiveDate::from_ymd_opt(2026, 7, 3).unwrap();
+        assert!(is_holiday(date))
+    }
+
+    #[test]
+    fn is_holiday_regular_day() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 28).unwrap();
+        assert!(!is_holiday(date))
+    }
+
+    #[test]
+    fn is_holiday_outside_table() {
+        let date = NaiveDate::from_ymd_opt(2040, 12, 25).unwrap();
+        assert!(!is_holiday(date))
+    }
+
+    #[test]
+    fn is_early_close_black_friday() {
+        let date = NaiveDate::from_ymd_opt(2024, 11, 29).unwrap();
+        assert!(is_early_close(date))
+    }
+
+    #[test]
+    fn tables_cover_every_year() {
+        let years = (2020..=2030).collect::<Vec<_>>();
+        assert_eq!(HOLIDAYS.iter().map(|(y, _)| *y).collect::<Vec<_>>(), years);
+        assert_eq!(
+            EARLY_CLOSES.iter().map(|(y, _)| *y).collect::<Vec<_>>(),
+            years
+        );
+    }
+}